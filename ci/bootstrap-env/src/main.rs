@@ -100,8 +100,16 @@ fn main() -> Result<()> {
                                 denom: DepositToken::VotingModuleToken {},
                                 amount: Uint128::new(1000000000),
                                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                                withdrawal_refund_policy: None,
+                                veto_destination: None,
+                                funding_period: None,
                             }),
                             open_proposal_submission: false,
+                            min_voting_power: None,
+                            max_open_proposals: None,
+                            submission_cooldown: None,
+                            message_restrictions: None,
+                            templates: vec![],
                             extension: Empty::default(),
                         })
                         .unwrap(),
@@ -110,6 +118,14 @@ fn main() -> Result<()> {
                     },
                 },
                 close_proposal_on_execution_failure: false,
+                veto: None,
+                delegation_registry: None,
+                commit_reveal: None,
+                anti_sniping: None,
+                threshold_rules: vec![],
+                voting_delay: None,
+                execution_window: None,
+                no_with_veto_threshold: None,
             })?,
             admin: Some(Admin::CoreModule {}),
             label: "DAO DAO Proposal Module".to_string(),
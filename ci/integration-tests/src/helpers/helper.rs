@@ -69,6 +69,14 @@ pub fn create_dao(
                 allow_revoting: false,
                 only_members_execute: true,
                 close_proposal_on_execution_failure: false,
+                veto: None,
+                delegation_registry: None,
+                commit_reveal: None,
+                anti_sniping: None,
+                threshold_rules: vec![],
+                voting_delay: None,
+                execution_window: None,
+                no_with_veto_threshold: None,
                 pre_propose_info: PreProposeInfo::ModuleMayPropose {
                     info: ModuleInstantiateInfo {
                         code_id: chain.orc.contract_map.code_id("cwd_pre_propose_single")?,
@@ -77,8 +85,16 @@ pub fn create_dao(
                                 denom: DepositToken::VotingModuleToken {},
                                 amount: Uint128::new(1000000000),
                                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                                withdrawal_refund_policy: None,
+                                veto_destination: None,
+                                funding_period: None,
                             }),
                             open_proposal_submission: false,
+                            min_voting_power: None,
+                            max_open_proposals: None,
+                            submission_cooldown: None,
+                            message_restrictions: None,
+                            templates: vec![],
                             extension: Empty::default(),
                         })
                         .unwrap(),
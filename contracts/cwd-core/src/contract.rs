@@ -1,8 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
-    Response, StdError, StdResult, SubMsg,
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Reply, Response, StdError, StdResult, SubMsg, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw_storage_plus::Map;
@@ -18,8 +18,9 @@ use crate::query::{
     GetItemResponse, PauseInfoResponse, SubDao,
 };
 use crate::state::{
-    Config, ProposalModule, ProposalModuleStatus, ACTIVE_PROPOSAL_MODULE_COUNT, ADMIN, CONFIG,
-    CW20_LIST, CW721_LIST, ITEMS, NOMINATED_ADMIN, PAUSED, PROPOSAL_MODULES, SUBDAO_LIST,
+    Config, ProposalModule, ProposalModuleStatus, TimelockedBatch, ACTIVE_PROPOSAL_MODULE_COUNT,
+    ADMIN, CONFIG, CW20_LIST, CW721_LIST, ITEMS, NOMINATED_ADMIN, PAUSED, PROPOSAL_MODULES,
+    SUBDAO_LIST, TIMELOCKED_BATCHES, TIMELOCKED_BATCH_COUNT, TIMELOCK_DELAY,
     TOTAL_PROPOSAL_MODULE_COUNT, VOTING_MODULE,
 };
 
@@ -105,8 +106,8 @@ pub fn execute(
         ExecuteMsg::ExecuteAdminMsgs { msgs } => {
             execute_admin_msgs(deps.as_ref(), info.sender, msgs)
         }
-        ExecuteMsg::ExecuteProposalHook { msgs } => {
-            execute_proposal_hook(deps.as_ref(), info.sender, msgs)
+        ExecuteMsg::ExecuteProposalHook { msgs, proposal_id } => {
+            execute_proposal_hook(deps, env, info.sender, msgs, proposal_id)
         }
         ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
         ExecuteMsg::Receive(_) => execute_receive_cw20(deps, info.sender),
@@ -138,6 +139,15 @@ pub fn execute(
         ExecuteMsg::UpdateSubDaos { to_add, to_remove } => {
             execute_update_sub_daos_list(deps, env, info.sender, to_add, to_remove)
         }
+        ExecuteMsg::UpdateTimelockDelay { delay } => {
+            execute_update_timelock_delay(deps, env, info.sender, delay)
+        }
+        ExecuteMsg::ExecuteTimelockedBatch { id } => {
+            execute_timelocked_batch(deps, env, info.sender, id)
+        }
+        ExecuteMsg::CancelTimelockedBatch { id } => {
+            execute_cancel_timelocked_batch(deps, env, info.sender, id)
+        }
     }
 }
 
@@ -180,9 +190,11 @@ pub fn execute_admin_msgs(
 }
 
 pub fn execute_proposal_hook(
-    deps: Deps,
+    deps: DepsMut,
+    env: Env,
     sender: Addr,
     msgs: Vec<CosmosMsg<Empty>>,
+    proposal_id: Option<u64>,
 ) -> Result<Response, ContractError> {
     let module = PROPOSAL_MODULES
         .may_load(deps.storage, sender.clone())?
@@ -193,9 +205,139 @@ pub fn execute_proposal_hook(
         return Err(ContractError::ModuleDisabledCannotExecute { address: sender });
     }
 
+    // Cancelling queued batches is not itself subject to the
+    // timelock. Were it, a proposal to cancel a batch would always
+    // become executable after the batch it was cancelling.
+    let delay = if only_cancels_timelocked_batches(&env, &msgs) {
+        None
+    } else {
+        TIMELOCK_DELAY.may_load(deps.storage)?
+    };
+
+    match delay {
+        None => Ok(Response::default()
+            .add_attribute("action", "execute_proposal_hook")
+            .add_messages(msgs)),
+        Some(delay) => {
+            let id = TIMELOCKED_BATCH_COUNT
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                + 1;
+            TIMELOCKED_BATCH_COUNT.save(deps.storage, &id)?;
+
+            let batch = TimelockedBatch {
+                id,
+                proposal_module: sender,
+                proposal_id,
+                msgs,
+                executable_at: delay.after(&env.block),
+            };
+            TIMELOCKED_BATCHES.save(deps.storage, id, &batch)?;
+
+            Ok(Response::default()
+                .add_attribute("action", "execute_proposal_hook")
+                .add_attribute("timelocked_batch_id", id.to_string())
+                .add_attribute("proposal_module", batch.proposal_module)
+                .add_attribute(
+                    "proposal_id",
+                    proposal_id
+                        .map(|p| p.to_string())
+                        .unwrap_or_else(|| "None".to_string()),
+                )
+                .add_attribute("executable_at", batch.executable_at.to_string()))
+        }
+    }
+}
+
+/// Returns true if MSGS is non-empty and every message in it is a
+/// `CancelTimelockedBatch` message sent to this contract.
+fn only_cancels_timelocked_batches(env: &Env, msgs: &[CosmosMsg<Empty>]) -> bool {
+    !msgs.is_empty()
+        && msgs.iter().all(|msg| match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                contract_addr.as_str() == env.contract.address.as_str()
+                    && funds.is_empty()
+                    && matches!(
+                        from_binary(msg),
+                        Ok(ExecuteMsg::CancelTimelockedBatch { .. })
+                    )
+            }
+            _ => false,
+        })
+}
+
+pub fn execute_update_timelock_delay(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    delay: Option<Duration>,
+) -> Result<Response, ContractError> {
+    // Only the core contract may call this method.
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match delay {
+        Some(delay) => TIMELOCK_DELAY.save(deps.storage, &delay)?,
+        None => TIMELOCK_DELAY.remove(deps.storage),
+    }
+
     Ok(Response::default()
-        .add_attribute("action", "execute_proposal_hook")
-        .add_messages(msgs))
+        .add_attribute("action", "execute_update_timelock_delay")
+        .add_attribute(
+            "delay",
+            delay
+                .map(|d| format!("{d:?}"))
+                .unwrap_or_else(|| "None".to_string()),
+        ))
+}
+
+pub fn execute_timelocked_batch(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let batch = TIMELOCKED_BATCHES
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NoSuchTimelockedBatch { id })?;
+
+    if !batch.executable_at.is_expired(&env.block) {
+        return Err(ContractError::TimelockNotExpired { id });
+    }
+
+    TIMELOCKED_BATCHES.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_timelocked_batch")
+        .add_attribute("sender", sender)
+        .add_attribute("timelocked_batch_id", id.to_string())
+        .add_messages(batch.msgs))
+}
+
+pub fn execute_cancel_timelocked_batch(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+) -> Result<Response, ContractError> {
+    // Only the core contract may call this method.
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !TIMELOCKED_BATCHES.has(deps.storage, id) {
+        return Err(ContractError::NoSuchTimelockedBatch { id });
+    }
+    TIMELOCKED_BATCHES.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_cancel_timelocked_batch")
+        .add_attribute("timelocked_batch_id", id.to_string()))
 }
 
 pub fn execute_nominate_admin(
@@ -561,6 +703,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             query_list_sub_daos(deps, start_after, limit)
         }
         QueryMsg::DaoURI {} => query_dao_uri(deps),
+        QueryMsg::TimelockDelay {} => query_timelock_delay(deps),
+        QueryMsg::TimelockedBatch { id } => query_timelocked_batch(deps, id),
+        QueryMsg::ListTimelockedBatches { start_after, limit } => {
+            query_list_timelocked_batches(deps, start_after, limit)
+        }
     }
 }
 
@@ -703,6 +850,30 @@ pub fn query_total_power_at_height(deps: Deps, height: Option<u64>) -> StdResult
     to_binary(&total_power)
 }
 
+pub fn query_timelock_delay(deps: Deps) -> StdResult<Binary> {
+    let delay = TIMELOCK_DELAY.may_load(deps.storage)?;
+    to_binary(&delay)
+}
+
+pub fn query_timelocked_batch(deps: Deps, id: u64) -> StdResult<Binary> {
+    let batch = TIMELOCKED_BATCHES.load(deps.storage, id)?;
+    to_binary(&batch)
+}
+
+pub fn query_list_timelocked_batches(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    to_binary(&paginate_map_values(
+        deps,
+        &TIMELOCKED_BATCHES,
+        start_after,
+        limit,
+        Order::Ascending,
+    )?)
+}
+
 pub fn query_get_item(deps: Deps, item: String) -> StdResult<Binary> {
     let item = ITEMS.may_load(deps.storage, item)?;
     to_binary(&GetItemResponse { item })
@@ -50,4 +50,10 @@ pub enum ContractError {
 
     #[error("Proposal module with address is disabled and cannot execute messages.")]
     ModuleDisabledCannotExecute { address: Addr },
+
+    #[error("No timelocked batch with ID ({id}) exists.")]
+    NoSuchTimelockedBatch { id: u64 },
+
+    #[error("Timelocked batch ({id}) may not be executed until its delay has passed.")]
+    TimelockNotExpired { id: u64 },
 }
@@ -60,8 +60,20 @@ pub enum ExecuteMsg {
     /// Executes messages in order.
     ExecuteAdminMsgs { msgs: Vec<CosmosMsg<Empty>> },
     /// Callable by proposal modules. The DAO will execute the
-    /// messages in the hook in order.
-    ExecuteProposalHook { msgs: Vec<CosmosMsg<Empty>> },
+    /// messages in the hook in order. If the DAO has a timelock delay
+    /// configured the messages are instead added to the timelock
+    /// queue and may be executed with `ExecuteTimelockedBatch` once
+    /// the delay has passed.
+    ///
+    /// NOTE: queued messages are not executed in the same
+    /// transaction as the hook, so proposal modules will not learn
+    /// of their execution failing.
+    ExecuteProposalHook {
+        msgs: Vec<CosmosMsg<Empty>>,
+        /// The ID of the proposal the messages belong to. Recorded
+        /// alongside timelocked batches.
+        proposal_id: Option<u64>,
+    },
     /// Pauses the DAO for a set duration.
     /// When paused the DAO is unable to execute proposals
     Pause { duration: Duration },
@@ -131,6 +143,21 @@ pub enum ExecuteMsg {
         to_add: Vec<SubDao>,
         to_remove: Vec<String>,
     },
+    /// Callable by the core contract. Sets the amount of time
+    /// messages from proposal modules must wait in the timelock
+    /// queue before they may be executed. If `None`, proposal
+    /// messages are executed immediately. Batches already in the
+    /// queue keep the delay they were queued with.
+    UpdateTimelockDelay { delay: Option<Duration> },
+    /// Executes a batch of messages in the timelock queue whose delay
+    /// has passed. Callable by anyone.
+    ExecuteTimelockedBatch { id: u64 },
+    /// Callable by the core contract. Removes a batch of messages
+    /// from the timelock queue without executing them. This allows a
+    /// later proposal to cancel a queued one. Proposal hooks that
+    /// only contain these messages are executed immediately rather
+    /// than being queued.
+    CancelTimelockedBatch { id: u64 },
 }
 
 #[voting_query]
@@ -215,6 +242,20 @@ pub enum QueryMsg {
     /// Implements the DAO Star standard: https://daostar.one/EIP
     #[returns(crate::query::DaoURIResponse)]
     DaoURI {},
+    /// Gets the delay proposal messages must wait in the timelock
+    /// queue before they may be executed, if one is set.
+    #[returns(Option<Duration>)]
+    TimelockDelay {},
+    /// Gets a batch of messages in the timelock queue.
+    #[returns(crate::state::TimelockedBatch)]
+    TimelockedBatch { id: u64 },
+    /// Lists the batches of messages in the timelock queue in
+    /// ascending order of ID.
+    #[returns(Vec<crate::state::TimelockedBatch>)]
+    ListTimelockedBatches {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
 
-use cosmwasm_std::{Addr, Empty};
+use cosmwasm_std::{Addr, CosmosMsg, Empty};
 use cw_storage_plus::{Item, Map};
 
 /// Top level config type for core module.
@@ -43,6 +43,23 @@ pub enum ProposalModuleStatus {
     Disabled,
 }
 
+/// A batch of messages sent by a proposal module that is waiting for
+/// the DAO's timelock delay to elapse before it may be executed.
+#[cw_serde]
+pub struct TimelockedBatch {
+    /// The ID of this batch.
+    pub id: u64,
+    /// The proposal module that sent the messages.
+    pub proposal_module: Addr,
+    /// The ID of the proposal in `proposal_module` that the messages
+    /// belong to, if the proposal module provided one.
+    pub proposal_id: Option<u64>,
+    /// The messages that will be executed.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The time after which the batch may be executed.
+    pub executable_at: Expiration,
+}
+
 /// The admin of the contract. Typically a DAO. The contract admin may
 /// unilaterally execute messages on this contract.
 ///
@@ -93,3 +110,17 @@ pub const CW721_LIST: Map<Addr, Empty> = Map::new("cw721s");
 
 /// List of SubDAOs associated to this DAO. Each SubDAO has an optional charter.
 pub const SUBDAO_LIST: Map<&Addr, Option<String>> = Map::new("sub_daos");
+
+/// The amount of time messages from proposal modules must wait in the
+/// timelock queue before they may be executed. If this is not set,
+/// proposal messages are executed immediately.
+pub const TIMELOCK_DELAY: Item<Duration> = Item::new("timelock_delay");
+
+/// Batches of proposal messages waiting in the timelock queue,
+/// keyed by batch ID.
+pub const TIMELOCKED_BATCHES: Map<u64, TimelockedBatch> = Map::new("timelocked_batches");
+
+/// The number of batches that have ever been added to the timelock
+/// queue. Used to assign batch IDs. Not set until the first batch is
+/// queued.
+pub const TIMELOCKED_BATCH_COUNT: Item<u64> = Item::new("timelocked_batch_count");
@@ -16,7 +16,7 @@ use crate::{
         AdminNominationResponse, Cw20BalanceResponse, DaoURIResponse, DumpStateResponse,
         GetItemResponse, PauseInfoResponse, SubDao,
     },
-    state::{Config, ProposalModule, ProposalModuleStatus, PROPOSAL_MODULES},
+    state::{Config, ProposalModule, ProposalModuleStatus, TimelockedBatch, PROPOSAL_MODULES},
     ContractError,
 };
 
@@ -1047,6 +1047,7 @@ fn test_admin_permissions() {
                 funds: vec![],
             }
             .into()],
+            proposal_id: None,
         },
         &[],
     );
@@ -2229,6 +2230,7 @@ fn test_pause() {
                 funds: vec![],
             }
             .into()],
+            proposal_id: None,
         },
         &[],
     )
@@ -2291,6 +2293,7 @@ fn test_pause() {
                     funds: vec![],
                 }
                 .into()],
+                proposal_id: None,
             },
             &[],
         )
@@ -2317,6 +2320,7 @@ fn test_pause() {
                     funds: vec![],
                 }
                 .into()],
+                proposal_id: None,
             },
             &[],
         )
@@ -2353,6 +2357,7 @@ fn test_pause() {
                 funds: vec![],
             }
             .into()],
+            proposal_id: None,
         },
         &[],
     )
@@ -2380,6 +2385,251 @@ fn test_pause() {
     );
 }
 
+#[test]
+fn test_timelock() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = proposal_modules.into_iter().next().unwrap();
+
+    let delay: Option<Duration> = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::TimelockDelay {})
+        .unwrap();
+    assert_eq!(delay, None);
+
+    // Only the DAO may set the delay.
+    let err: ContractError = app
+        .execute_contract(
+            proposal_module.address.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpdateTimelockDelay {
+                delay: Some(Duration::Height(10)),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // No delay is set so this executes immediately.
+    app.execute_contract(
+        proposal_module.address.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: core_addr.to_string(),
+                msg: to_binary(&ExecuteMsg::UpdateTimelockDelay {
+                    delay: Some(Duration::Height(10)),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+            proposal_id: Some(1),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let delay: Option<Duration> = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::TimelockDelay {})
+        .unwrap();
+    assert_eq!(delay, Some(Duration::Height(10)));
+
+    let set_item_msg: CosmosMsg = WasmMsg::Execute {
+        contract_addr: core_addr.to_string(),
+        msg: to_binary(&ExecuteMsg::SetItem {
+            key: "foo".to_string(),
+            addr: "bar".to_string(),
+        })
+        .unwrap(),
+        funds: vec![],
+    }
+    .into();
+
+    let start_height = app.block_info().height;
+    app.execute_contract(
+        proposal_module.address.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![set_item_msg.clone()],
+            proposal_id: Some(2),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Messages were queued rather than executed.
+    let item = get_item(&mut app, core_addr.clone(), "foo".to_string());
+    assert_eq!(item, GetItemResponse { item: None });
+
+    let batch: TimelockedBatch = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::TimelockedBatch { id: 1 })
+        .unwrap();
+    assert_eq!(
+        batch,
+        TimelockedBatch {
+            id: 1,
+            proposal_module: proposal_module.address.clone(),
+            proposal_id: Some(2),
+            msgs: vec![set_item_msg.clone()],
+            executable_at: Expiration::AtHeight(start_height + 10),
+        }
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteTimelockedBatch { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::TimelockNotExpired { id: 1 });
+
+    app.update_block(|block| block.height += 10);
+
+    // Anyone may execute a batch once its delay has passed.
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteTimelockedBatch { id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let item = get_item(&mut app, core_addr.clone(), "foo".to_string());
+    assert_eq!(
+        item,
+        GetItemResponse {
+            item: Some("bar".to_string())
+        }
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteTimelockedBatch { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoSuchTimelockedBatch { id: 1 });
+
+    // Queue a batch that removes the item and then cancel it.
+    app.execute_contract(
+        proposal_module.address.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: core_addr.to_string(),
+                msg: to_binary(&ExecuteMsg::RemoveItem {
+                    key: "foo".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+            proposal_id: Some(3),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let batches: Vec<TimelockedBatch> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListTimelockedBatches {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].id, 2);
+    assert_eq!(batches[0].proposal_id, Some(3));
+
+    let err: ContractError = app
+        .execute_contract(
+            proposal_module.address.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::CancelTimelockedBatch { id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Cancellations are not subject to the timelock.
+    app.execute_contract(
+        proposal_module.address.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: core_addr.to_string(),
+                msg: to_binary(&ExecuteMsg::CancelTimelockedBatch { id: 2 }).unwrap(),
+                funds: vec![],
+            }
+            .into()],
+            proposal_id: Some(4),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let batches: Vec<TimelockedBatch> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListTimelockedBatches {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(batches.is_empty());
+
+    app.update_block(|block| block.height += 10);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteTimelockedBatch { id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoSuchTimelockedBatch { id: 2 });
+
+    let item = get_item(&mut app, core_addr, "foo".to_string());
+    assert_eq!(
+        item,
+        GetItemResponse {
+            item: Some("bar".to_string())
+        }
+    );
+}
+
 #[test]
 fn test_dump_state_proposal_modules() {
     let (core_addr, app) = do_standard_instantiate(false, None);
@@ -2675,6 +2925,7 @@ fn test_execute_stargate_msg() {
                 type_url: "foo_type".to_string(),
                 value: to_binary("foo_bin").unwrap(),
             }],
+            proposal_id: None,
         },
         &[],
     );
@@ -0,0 +1,201 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage,
+};
+use cw2::set_contract_version;
+use cwd_voting::{
+    delegation::{DelegateResponse, DelegatorsResponse},
+    voting::get_voting_power,
+};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{Config, CONFIG, DELEGATES, DELEGATORS};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-delegation-registry";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        dao: info.sender,
+        max_delegators: msg.max_delegators,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", config.dao)
+        .add_attribute("max_delegators", config.max_delegators.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Delegate { delegate } => execute_delegate(deps, env, info, delegate),
+        ExecuteMsg::Undelegate {} => execute_undelegate(deps, env, info),
+        ExecuteMsg::UpdateConfig { max_delegators } => {
+            execute_update_config(deps, info, max_delegators)
+        }
+    }
+}
+
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegation {});
+    }
+
+    // Without this anyone could fill up a delegate's delegator list
+    // and prevent legitimate delegations.
+    let power = get_voting_power(deps.as_ref(), info.sender.clone(), config.dao, None)?;
+    if power.is_zero() {
+        return Err(ContractError::NoVotingPower {});
+    }
+
+    match DELEGATES.may_load(deps.storage, &info.sender)? {
+        Some(current) if current == delegate => return Err(ContractError::AlreadyDelegated {}),
+        Some(current) => remove_delegator(deps.storage, &current, &info.sender, env.block.height)?,
+        None => (),
+    }
+
+    let mut delegators = DELEGATORS
+        .may_load(deps.storage, &delegate)?
+        .unwrap_or_default();
+    if delegators.len() >= config.max_delegators as usize {
+        return Err(ContractError::TooManyDelegators {
+            max: config.max_delegators,
+        });
+    }
+    delegators.push(info.sender.clone());
+    DELEGATORS.save(deps.storage, &delegate, &delegators, env.block.height)?;
+    DELEGATES.save(deps.storage, &info.sender, &delegate, env.block.height)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "delegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let delegate = DELEGATES
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NotDelegated {})?;
+
+    remove_delegator(deps.storage, &delegate, &info.sender, env.block.height)?;
+    DELEGATES.remove(deps.storage, &info.sender, env.block.height)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "undelegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_delegators: u32,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Lowering the maximum does not remove existing delegations, it
+    // only prevents new ones.
+    config.max_delegators = max_delegators;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute("max_delegators", max_delegators.to_string()))
+}
+
+/// Removes DELEGATOR from DELEGATE's list of delegators.
+fn remove_delegator(
+    storage: &mut dyn Storage,
+    delegate: &Addr,
+    delegator: &Addr,
+    height: u64,
+) -> StdResult<()> {
+    let mut delegators = DELEGATORS.load(storage, delegate)?;
+    delegators.retain(|addr| addr != delegator);
+    if delegators.is_empty() {
+        DELEGATORS.remove(storage, delegate, height)
+    } else {
+        DELEGATORS.save(storage, delegate, &delegators, height)
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Delegate { delegator, height } => query_delegate(deps, env, delegator, height),
+        QueryMsg::Delegators { delegate, height } => {
+            query_delegators(deps, env, delegate, height)
+        }
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Info {} => query_info(deps),
+    }
+}
+
+pub fn query_delegate(
+    deps: Deps,
+    env: Env,
+    delegator: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let delegator = deps.api.addr_validate(&delegator)?;
+    let height = height.unwrap_or(env.block.height);
+    let delegate = DELEGATES.may_load_at_height(deps.storage, &delegator, height)?;
+    to_binary(&DelegateResponse { delegate })
+}
+
+pub fn query_delegators(
+    deps: Deps,
+    env: Env,
+    delegate: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    let height = height.unwrap_or(env.block.height);
+    let delegators = DELEGATORS
+        .may_load_at_height(deps.storage, &delegate, height)?
+        .unwrap_or_default();
+    to_binary(&DelegatorsResponse { delegators })
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&cwd_interface::voting::InfoResponse { info })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Set contract to version to latest
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
@@ -0,0 +1,26 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("can not delegate voting power to yourself")]
+    SelfDelegation {},
+
+    #[error("only addresses with voting power may delegate")]
+    NoVotingPower {},
+
+    #[error("voting power is already delegated to this address")]
+    AlreadyDelegated {},
+
+    #[error("voting power is not delegated")]
+    NotDelegated {},
+
+    #[error("delegate may not have more than ({max}) delegators")]
+    TooManyDelegators { max: u32 },
+}
@@ -0,0 +1,48 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cwd_macros::info_query;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The maximum number of delegators that an address may
+    /// have. Proposal modules query the voting power of each of a
+    /// voter's delegators when they vote, so this bounds the gas cost
+    /// of voting.
+    pub max_delegators: u32,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Delegates the sender's voting power to DELEGATE, replacing
+    /// any existing delegation. The delegation applies to proposals
+    /// created after the block this is executed in.
+    Delegate { delegate: String },
+    /// Removes the sender's delegation.
+    Undelegate {},
+    /// Updates the registry's config. Only callable by the DAO.
+    UpdateConfig { max_delegators: u32 },
+}
+
+#[info_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the address that DELEGATOR had delegated their voting
+    /// power to at HEIGHT.
+    #[returns(cwd_voting::delegation::DelegateResponse)]
+    Delegate {
+        delegator: String,
+        height: Option<u64>,
+    },
+    /// Returns the addresses that had delegated their voting power to
+    /// DELEGATE at HEIGHT.
+    #[returns(cwd_voting::delegation::DelegatorsResponse)]
+    Delegators {
+        delegate: String,
+        height: Option<u64>,
+    },
+    #[returns(crate::state::Config)]
+    Config {},
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,31 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, SnapshotMap, Strategy};
+
+#[cw_serde]
+pub struct Config {
+    /// The DAO whose voting power is being delegated.
+    pub dao: Addr,
+    /// The maximum number of delegators that an address may have.
+    pub max_delegators: u32,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Maps delegators to the address they have delegated their voting
+/// power to.
+pub const DELEGATES: SnapshotMap<&Addr, Addr> = SnapshotMap::new(
+    "delegates",
+    "delegates__checkpoints",
+    "delegates__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Maps delegates to the addresses that have delegated their voting
+/// power to them.
+pub const DELEGATORS: SnapshotMap<&Addr, Vec<Addr>> = SnapshotMap::new(
+    "delegators",
+    "delegators__checkpoints",
+    "delegators__changelog",
+    Strategy::EveryBlock,
+);
@@ -0,0 +1,349 @@
+use cosmwasm_std::{to_binary, Addr, Empty};
+use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+use cwd_interface::{Admin, ModuleInstantiateInfo};
+use cwd_voting::delegation::{DelegateResponse, DelegatorsResponse};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::Config,
+    ContractError,
+};
+
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const ADDR3: &str = "addr3";
+const ADDR4: &str = "addr4";
+
+fn registry_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+fn cw4_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw4_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cwd_voting_cw4::contract::execute,
+        cwd_voting_cw4::contract::instantiate,
+        cwd_voting_cw4::contract::query,
+    )
+    .with_reply(cwd_voting_cw4::contract::reply);
+    Box::new(contract)
+}
+
+fn sudo_proposal_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cwd_proposal_sudo::contract::execute,
+        cwd_proposal_sudo::contract::instantiate,
+        cwd_proposal_sudo::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw_core_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cwd_core::contract::execute,
+        cwd_core::contract::instantiate,
+        cwd_core::contract::query,
+    )
+    .with_reply(cwd_core::contract::reply);
+    Box::new(contract)
+}
+
+/// Instantiates a DAO where ADDR1, ADDR2, and ADDR3 have one unit of
+/// voting power and ADDR4 has none, and a delegation registry for
+/// it. Returns the addresses of the DAO and the registry.
+fn setup_test(app: &mut App, max_delegators: u32) -> (Addr, Addr) {
+    let cw4_id = app.store_code(cw4_contract());
+    let voting_id = app.store_code(cw4_voting_contract());
+    let proposal_id = app.store_code(sudo_proposal_contract());
+    let core_id = app.store_code(cw_core_contract());
+    let registry_id = app.store_code(registry_contract());
+
+    let members = vec![
+        cw4::Member {
+            addr: ADDR1.to_string(),
+            weight: 1,
+        },
+        cw4::Member {
+            addr: ADDR2.to_string(),
+            weight: 1,
+        },
+        cw4::Member {
+            addr: ADDR3.to_string(),
+            weight: 1,
+        },
+        cw4::Member {
+            addr: ADDR4.to_string(),
+            weight: 0,
+        },
+    ];
+
+    let core_instantiate = cwd_core::msg::InstantiateMsg {
+        admin: None,
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs.".to_string(),
+        image_url: None,
+        dao_uri: None,
+        automatically_add_cw20s: true,
+        automatically_add_cw721s: true,
+        voting_module_instantiate_info: ModuleInstantiateInfo {
+            code_id: voting_id,
+            msg: to_binary(&cwd_voting_cw4::msg::InstantiateMsg {
+                cw4_group_code_id: cw4_id,
+                initial_members: members,
+            })
+            .unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            label: "voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+            code_id: proposal_id,
+            msg: to_binary(&cwd_proposal_sudo::msg::InstantiateMsg {
+                root: ADDR1.to_string(),
+            })
+            .unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            label: "proposal module".to_string(),
+        }],
+        initial_items: None,
+    };
+    let core = app
+        .instantiate_contract(
+            core_id,
+            Addr::unchecked(ADDR1),
+            &core_instantiate,
+            &[],
+            "DAO DAO",
+            None,
+        )
+        .unwrap();
+
+    let registry = app
+        .instantiate_contract(
+            registry_id,
+            core.clone(),
+            &InstantiateMsg { max_delegators },
+            &[],
+            "delegation registry",
+            None,
+        )
+        .unwrap();
+
+    // Update the block so that voting power appears.
+    app.update_block(next_block);
+
+    (core, registry)
+}
+
+fn delegate(app: &mut App, registry: &Addr, sender: &str, delegate: &str) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        registry.clone(),
+        &ExecuteMsg::Delegate {
+            delegate: delegate.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn delegate_should_fail(
+    app: &mut App,
+    registry: &Addr,
+    sender: &str,
+    delegate: &str,
+) -> ContractError {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        registry.clone(),
+        &ExecuteMsg::Delegate {
+            delegate: delegate.to_string(),
+        },
+        &[],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap()
+}
+
+fn query_delegate(app: &App, registry: &Addr, delegator: &str, height: Option<u64>) -> Option<Addr> {
+    let res: DelegateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            registry,
+            &QueryMsg::Delegate {
+                delegator: delegator.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.delegate
+}
+
+fn query_delegators(app: &App, registry: &Addr, delegate: &str, height: Option<u64>) -> Vec<Addr> {
+    let res: DelegatorsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            registry,
+            &QueryMsg::Delegators {
+                delegate: delegate.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.delegators
+}
+
+#[test]
+fn test_delegate() {
+    let mut app = App::default();
+    let (_, registry) = setup_test(&mut app, 10);
+
+    let start_height = app.block_info().height;
+    delegate(&mut app, &registry, ADDR1, ADDR2);
+    delegate(&mut app, &registry, ADDR3, ADDR2);
+
+    // Delegations are not visible until the next block.
+    assert_eq!(query_delegate(&app, &registry, ADDR1, None), None);
+    assert!(query_delegators(&app, &registry, ADDR2, None).is_empty());
+
+    app.update_block(next_block);
+    assert_eq!(
+        query_delegate(&app, &registry, ADDR1, None),
+        Some(Addr::unchecked(ADDR2))
+    );
+    assert_eq!(
+        query_delegators(&app, &registry, ADDR2, None),
+        vec![Addr::unchecked(ADDR1), Addr::unchecked(ADDR3)]
+    );
+
+    // Redelegating moves the delegation.
+    let delegated_height = app.block_info().height;
+    delegate(&mut app, &registry, ADDR1, ADDR3);
+    app.update_block(next_block);
+    assert_eq!(
+        query_delegate(&app, &registry, ADDR1, None),
+        Some(Addr::unchecked(ADDR3))
+    );
+    assert_eq!(
+        query_delegators(&app, &registry, ADDR2, None),
+        vec![Addr::unchecked(ADDR3)]
+    );
+    assert_eq!(
+        query_delegators(&app, &registry, ADDR3, None),
+        vec![Addr::unchecked(ADDR1)]
+    );
+
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        registry.clone(),
+        &ExecuteMsg::Undelegate {},
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(query_delegate(&app, &registry, ADDR1, None), None);
+    assert!(query_delegators(&app, &registry, ADDR3, None).is_empty());
+
+    // Historical delegations are preserved.
+    assert_eq!(
+        query_delegate(&app, &registry, ADDR1, Some(start_height)),
+        None
+    );
+    assert_eq!(
+        query_delegate(&app, &registry, ADDR1, Some(delegated_height)),
+        Some(Addr::unchecked(ADDR2))
+    );
+    assert_eq!(
+        query_delegators(&app, &registry, ADDR2, Some(delegated_height)),
+        vec![Addr::unchecked(ADDR1), Addr::unchecked(ADDR3)]
+    );
+}
+
+#[test]
+fn test_delegate_errors() {
+    let mut app = App::default();
+    let (_, registry) = setup_test(&mut app, 1);
+
+    let err = delegate_should_fail(&mut app, &registry, ADDR1, ADDR1);
+    assert!(matches!(err, ContractError::SelfDelegation {}));
+
+    let err = delegate_should_fail(&mut app, &registry, ADDR4, ADDR1);
+    assert!(matches!(err, ContractError::NoVotingPower {}));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            registry.clone(),
+            &ExecuteMsg::Undelegate {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotDelegated {}));
+
+    delegate(&mut app, &registry, ADDR1, ADDR2);
+    let err = delegate_should_fail(&mut app, &registry, ADDR1, ADDR2);
+    assert!(matches!(err, ContractError::AlreadyDelegated {}));
+
+    let err = delegate_should_fail(&mut app, &registry, ADDR3, ADDR2);
+    assert!(matches!(err, ContractError::TooManyDelegators { max: 1 }));
+
+    // Addresses with no voting power may receive delegations.
+    delegate(&mut app, &registry, ADDR3, ADDR4);
+}
+
+#[test]
+fn test_update_config() {
+    let mut app = App::default();
+    let (core, registry) = setup_test(&mut app, 1);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            registry.clone(),
+            &ExecuteMsg::UpdateConfig { max_delegators: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        core.clone(),
+        registry.clone(),
+        &ExecuteMsg::UpdateConfig { max_delegators: 2 },
+        &[],
+    )
+    .unwrap();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&registry, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            dao: core,
+            max_delegators: 2
+        }
+    );
+
+    delegate(&mut app, &registry, ADDR1, ADDR3);
+    delegate(&mut app, &registry, ADDR2, ADDR3);
+}
@@ -0,0 +1,466 @@
+use cosmwasm_schema::cw_serde;
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_token_swap::state::CheckedTokenInfo;
+use cw_utils::must_pay;
+use cwd_hooks::HooksResponse;
+use cwd_proposal_hooks::ProposalHookMsg;
+use cwd_voting::status::Status;
+use serde::Deserialize;
+
+use crate::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StatusResponse},
+    state::{CheckedParticipant, Config, CONFIG, EXECUTED, MIRRORS, PARTICIPANTS},
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-joint-proposal";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The queries of participating proposal modules used to link mirror
+/// proposals.
+#[cw_serde]
+enum ProposalModuleQuery {
+    Proposal { proposal_id: u64 },
+    ProposalHooks {},
+}
+
+/// The parts of a proposal module's `Proposal` query response needed
+/// to link a mirror proposal. Other fields are ignored so that any
+/// proposal module may be used.
+#[derive(Deserialize)]
+struct MirrorProposalResponse {
+    proposal: MirrorProposal,
+}
+
+#[derive(Deserialize)]
+struct MirrorProposal {
+    proposer: Addr,
+    start_height: u64,
+    status: Status,
+    /// Proposals without messages, for example multiple choice
+    /// proposals, can not be mirror proposals.
+    #[serde(default)]
+    msgs: Vec<CosmosMsg<Empty>>,
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.participants.is_empty() {
+        return Err(ContractError::NoParticipants {});
+    }
+    let count = msg.participants.len() as u64;
+    let min_approvals = msg.min_approvals.unwrap_or(count);
+    if min_approvals == 0 || min_approvals > count {
+        return Err(ContractError::InvalidMinApprovals {});
+    }
+
+    for participant in msg.participants {
+        let participant = participant.into_checked(deps.as_ref())?;
+        if PARTICIPANTS.has(deps.storage, participant.dao.clone()) {
+            return Err(ContractError::DuplicateParticipant {
+                dao: participant.dao,
+            });
+        }
+        PARTICIPANTS.save(deps.storage, participant.dao.clone(), &participant)?;
+    }
+
+    let config = Config {
+        min_approvals,
+        recipient: deps.api.addr_validate(&msg.recipient)?,
+        msgs: msg.msgs,
+        expiration: msg.expiration,
+        start_height: env.block.height,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    EXECUTED.save(deps.storage, &false)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("participants", count.to_string())
+        .add_attribute("min_approvals", min_approvals.to_string())
+        .add_attribute("recipient", config.recipient))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::LinkProposal { dao, proposal_id } => {
+            execute_link_proposal(deps, env, info, dao, proposal_id)
+        }
+        ExecuteMsg::Approve {} => execute_approve(deps, info),
+        ExecuteMsg::ProposalHook(msg) => execute_proposal_hook(deps, info, msg),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, info.sender, msg),
+        ExecuteMsg::Fund {} => execute_fund(deps, info),
+        ExecuteMsg::Withdraw {} => execute_withdraw(deps, info),
+        ExecuteMsg::Execute {} => execute_execute(deps, env, info),
+    }
+}
+
+pub fn execute_link_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    dao: String,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    if EXECUTED.load(deps.storage)? {
+        return Err(ContractError::Executed {});
+    }
+    let dao = deps.api.addr_validate(&dao)?;
+    let mut participant = PARTICIPANTS
+        .may_load(deps.storage, dao.clone())?
+        .ok_or(ContractError::Unauthorized {})?;
+    if participant.is_approved() {
+        return Err(ContractError::AlreadyApproved { dao });
+    }
+
+    // The proposal is loaded from the DAO's participating proposal
+    // module so it must belong to the DAO.
+    let MirrorProposalResponse { proposal } = deps.querier.query_wasm_smart(
+        &participant.proposal_module,
+        &ProposalModuleQuery::Proposal { proposal_id },
+    )?;
+    if info.sender != dao && info.sender != proposal.proposer {
+        return Err(ContractError::Unauthorized {});
+    }
+    // A proposer may not replace a mirror proposal the DAO is still
+    // voting on.
+    if info.sender != dao
+        && participant.proposal_id.is_some()
+        && matches!(participant.status, Some(Status::Open | Status::Pending))
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    // Only proposals created for the joint action which have not
+    // been decided may be linked. Otherwise, an unrelated proposal,
+    // or one which passed long ago, could record the DAO's approval.
+    if proposal.start_height < CONFIG.load(deps.storage)?.start_height {
+        return Err(ContractError::MirrorTooOld {});
+    }
+    if !matches!(proposal.status, Status::Open | Status::Pending) {
+        return Err(ContractError::MirrorNotOpen {
+            status: proposal.status,
+        });
+    }
+    // Passing the proposal must approve the joint action, so that
+    // an unrelated proposal may not stand in for the DAO's approval.
+    if !proposal
+        .msgs
+        .iter()
+        .any(|msg| is_approval(msg, &env.contract.address))
+    {
+        return Err(ContractError::MirrorMissingApproval {});
+    }
+
+    // Status changes are learned through proposal hooks so this
+    // contract must receive them.
+    let hooks: HooksResponse = deps.querier.query_wasm_smart(
+        &participant.proposal_module,
+        &ProposalModuleQuery::ProposalHooks {},
+    )?;
+    if !hooks.hooks.contains(&env.contract.address.to_string()) {
+        return Err(ContractError::HookNotRegistered {
+            proposal_module: participant.proposal_module,
+        });
+    }
+
+    if let Some(previous) = participant.proposal_id {
+        MIRRORS.remove(
+            deps.storage,
+            (participant.proposal_module.clone(), previous),
+        );
+    }
+    MIRRORS.save(
+        deps.storage,
+        (participant.proposal_module.clone(), proposal_id),
+        &dao,
+    )?;
+    participant.proposal_id = Some(proposal_id);
+    participant.status = Some(proposal.status);
+    PARTICIPANTS.save(deps.storage, dao.clone(), &participant)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "link_proposal")
+        .add_attribute("dao", dao)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Returns true if MSG sends `Approve` to CONTRACT.
+fn is_approval(msg: &CosmosMsg<Empty>, contract: &Addr) -> bool {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => {
+            contract_addr == contract.as_str()
+                && matches!(from_binary(msg), Ok(ExecuteMsg::Approve {}))
+        }
+        _ => false,
+    }
+}
+
+pub fn execute_approve(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    if EXECUTED.load(deps.storage)? {
+        return Err(ContractError::Executed {});
+    }
+    let mut participant = get_participant(deps.as_ref(), &info.sender)?;
+    participant.approved = true;
+    PARTICIPANTS.save(deps.storage, participant.dao.clone(), &participant)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "approve")
+        .add_attribute("dao", participant.dao))
+}
+
+pub fn execute_proposal_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: ProposalHookMsg,
+) -> Result<Response, ContractError> {
+    // Proposal modules remove hook consumers which error so hooks
+    // for proposals which are not mirror proposals are ignored.
+    if let ProposalHookMsg::ProposalStatusChanged { id, new_status, .. } = msg {
+        if let Some(dao) = MIRRORS.may_load(deps.storage, (info.sender, id))? {
+            if !EXECUTED.load(deps.storage)? {
+                let mut participant = PARTICIPANTS.load(deps.storage, dao.clone())?;
+                participant.status = Some(new_status.parse()?);
+                PARTICIPANTS.save(deps.storage, dao.clone(), &participant)?;
+                return Ok(Response::new()
+                    .add_attribute("method", "proposal_hook")
+                    .add_attribute("dao", dao)
+                    .add_attribute("status", new_status));
+            }
+        }
+    }
+    Ok(Response::new().add_attribute("method", "proposal_hook"))
+}
+
+/// Accepts DAO's contribution of PAID tokens.
+///
+/// NOTE: The caller must verify that the denom of PAID is correct.
+fn do_fund(
+    deps: DepsMut,
+    mut participant: CheckedParticipant,
+    paid: Uint128,
+    expected: Uint128,
+) -> Result<Response, ContractError> {
+    if EXECUTED.load(deps.storage)? {
+        return Err(ContractError::Executed {});
+    }
+    if participant.provided {
+        return Err(ContractError::AlreadyProvided {});
+    }
+    if paid != expected {
+        return Err(ContractError::InvalidAmount {
+            expected,
+            actual: paid,
+        });
+    }
+
+    participant.provided = true;
+    PARTICIPANTS.save(deps.storage, participant.dao.clone(), &participant)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "fund")
+        .add_attribute("dao", participant.dao))
+}
+
+/// Loads the participant whose core module is SENDER. Errors if
+/// SENDER is not a participant.
+fn get_participant(deps: Deps, sender: &Addr) -> Result<CheckedParticipant, ContractError> {
+    PARTICIPANTS
+        .may_load(deps.storage, sender.clone())?
+        .ok_or(ContractError::Unauthorized {})
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    token_contract: Addr,
+    msg: cw20::Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_validate(&msg.sender)?;
+    let participant = get_participant(deps.as_ref(), &sender)?;
+
+    let expected = match &participant.contribution {
+        Some(CheckedTokenInfo::Cw20 {
+            contract_addr,
+            amount,
+        }) if *contract_addr == token_contract => *amount,
+        Some(_) => return Err(ContractError::InvalidFunds {}),
+        None => return Err(ContractError::NoContribution { dao: sender }),
+    };
+
+    do_fund(deps, participant, msg.amount, expected)
+}
+
+pub fn execute_fund(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let participant = get_participant(deps.as_ref(), &info.sender)?;
+
+    let (expected, paid) = match &participant.contribution {
+        Some(CheckedTokenInfo::Native { denom, amount }) => {
+            let paid = must_pay(&info, denom).map_err(|_| ContractError::InvalidFunds {})?;
+            (*amount, paid)
+        }
+        Some(_) => return Err(ContractError::InvalidFunds {}),
+        None => return Err(ContractError::NoContribution { dao: info.sender }),
+    };
+
+    do_fund(deps, participant, paid, expected)
+}
+
+pub fn execute_withdraw(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut participant = get_participant(deps.as_ref(), &info.sender)?;
+
+    // Contributions are either used or returned when the joint
+    // action is executed, so none may be withdrawn afterwards.
+    if !participant.provided {
+        return Err(ContractError::NoProvision {});
+    }
+    let message = match participant.contribution.clone() {
+        Some(contribution) => contribution.into_send_message(&participant.dao)?,
+        None => return Err(ContractError::NoProvision {}),
+    };
+
+    participant.provided = false;
+    PARTICIPANTS.save(deps.storage, participant.dao.clone(), &participant)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "withdraw")
+        .add_attribute("dao", participant.dao)
+        .add_message(message))
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if EXECUTED.load(deps.storage)? {
+        return Err(ContractError::Executed {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let participants = load_participants(deps.as_ref())?;
+    check_executable(&config, &participants, &env.block)?;
+
+    // Contributions from DAOs which passed their mirror proposals
+    // are sent to the recipient. Others are returned before the joint
+    // action's messages are executed so that they may not spend
+    // them.
+    let mut messages = vec![];
+    for mut participant in participants {
+        if participant.provided {
+            if let Some(contribution) = participant.contribution.clone() {
+                let to = if participant.is_approved() {
+                    &config.recipient
+                } else {
+                    &participant.dao
+                };
+                messages.push(contribution.into_send_message(to)?);
+            }
+            participant.provided = false;
+            PARTICIPANTS.save(deps.storage, participant.dao.clone(), &participant)?;
+        }
+    }
+    EXECUTED.save(deps.storage, &true)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "execute")
+        .add_attribute("sender", info.sender)
+        .add_messages(messages)
+        .add_messages(config.msgs))
+}
+
+fn load_participants(deps: Deps) -> StdResult<Vec<CheckedParticipant>> {
+    PARTICIPANTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, participant)| participant))
+        .collect()
+}
+
+/// Errors if the joint action may not be executed at BLOCK. It may be
+/// executed before it expires once at least `min_approvals` DAOs,
+/// including every required DAO, have passed their mirror proposals
+/// and every DAO which has passed its mirror proposal has provided
+/// its contribution.
+fn check_executable(
+    config: &Config,
+    participants: &[CheckedParticipant],
+    block: &BlockInfo,
+) -> Result<(), ContractError> {
+    if config.expiration.is_expired(block) {
+        return Err(ContractError::Expired {});
+    }
+    let approvals = count_approvals(participants);
+    if approvals < config.min_approvals {
+        return Err(ContractError::InsufficientApprovals {
+            approvals,
+            min_approvals: config.min_approvals,
+        });
+    }
+    for participant in participants {
+        if participant.is_approved() {
+            if participant.contribution.is_some() && !participant.provided {
+                return Err(ContractError::NotFunded {
+                    dao: participant.dao.clone(),
+                });
+            }
+        } else if participant.required {
+            return Err(ContractError::RequiredNotApproved {
+                dao: participant.dao.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn count_approvals(participants: &[CheckedParticipant]) -> u64 {
+    participants
+        .iter()
+        .filter(|participant| participant.is_approved())
+        .count() as u64
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Status {} => query_status(deps, env),
+    }
+}
+
+pub fn query_status(deps: Deps, env: Env) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let executed = EXECUTED.load(deps.storage)?;
+    let participants = load_participants(deps)?;
+
+    to_binary(&StatusResponse {
+        approvals: count_approvals(&participants),
+        executable: !executed && check_executable(&config, &participants, &env.block).is_ok(),
+        executed,
+        participants,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Set contract to version to latest
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
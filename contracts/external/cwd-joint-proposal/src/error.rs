@@ -0,0 +1,74 @@
+use cosmwasm_std::{Addr, StdError, Uint128};
+use cwd_voting::status::Status;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Escrow(#[from] cw_token_swap::ContractError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("a joint action must have at least one participant")]
+    NoParticipants {},
+
+    #[error("({dao}) may only participate once")]
+    DuplicateParticipant { dao: Addr },
+
+    #[error("({proposal_module}) is not a proposal module of ({dao})")]
+    NotProposalModule { proposal_module: Addr, dao: Addr },
+
+    #[error("min_approvals must be between one and the number of participants")]
+    InvalidMinApprovals {},
+
+    #[error("this contract is not a consumer of ({proposal_module})'s proposal hooks")]
+    HookNotRegistered { proposal_module: Addr },
+
+    #[error("({dao}) has already passed its mirror proposal")]
+    AlreadyApproved { dao: Addr },
+
+    #[error(
+        "mirror proposals must be open or pending when they are linked, proposal is ({status})"
+    )]
+    MirrorNotOpen { status: Status },
+
+    #[error("mirror proposals must be created after the joint action")]
+    MirrorTooOld {},
+
+    #[error("mirror proposals must send `Approve` to the joint action")]
+    MirrorMissingApproval {},
+
+    #[error("the joint action has already been executed")]
+    Executed {},
+
+    #[error("the joint action has expired")]
+    Expired {},
+
+    #[error("({approvals}) DAOs have passed their mirror proposals but ({min_approvals}) must")]
+    InsufficientApprovals { approvals: u64, min_approvals: u64 },
+
+    #[error("({dao}) must pass its mirror proposal")]
+    RequiredNotApproved { dao: Addr },
+
+    #[error("({dao}) has passed its mirror proposal but not provided its contribution")]
+    NotFunded { dao: Addr },
+
+    #[error("({dao}) has not promised a contribution")]
+    NoContribution { dao: Addr },
+
+    #[error("can not provide funds more than once")]
+    AlreadyProvided {},
+
+    #[error("must provide funds before withdrawing")]
+    NoProvision {},
+
+    #[error("provided funds do not match promised funds")]
+    InvalidFunds {},
+
+    #[error("invalid amount. expected ({expected}), got ({actual})")]
+    InvalidAmount { expected: Uint128, actual: Uint128 },
+}
@@ -0,0 +1,106 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Empty};
+use cw_token_swap::msg::TokenInfo;
+use cw_utils::Expiration;
+use cwd_proposal_hooks::ProposalHookMsg;
+
+use crate::state::CheckedParticipant;
+
+/// A DAO participating in the joint action.
+#[cw_serde]
+pub struct Participant {
+    /// The address of the DAO's core module.
+    pub dao: String,
+    /// The proposal module the DAO will create its mirror proposal
+    /// in. Must be one of the DAO's proposal modules.
+    pub proposal_module: String,
+    /// If true, the joint action may not be executed until this DAO
+    /// has passed its mirror proposal, regardless of how many other
+    /// DAOs have.
+    pub required: bool,
+    /// The funds the DAO promises to contribute to the joint action,
+    /// if any.
+    pub contribution: Option<TokenInfo>,
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The DAOs participating in the joint action.
+    pub participants: Vec<Participant>,
+    /// The number of DAOs which must pass their mirror proposals
+    /// before the joint action may be executed. If None, every DAO
+    /// must.
+    pub min_approvals: Option<u64>,
+    /// The address that contributions are sent to when the joint
+    /// action is executed.
+    pub recipient: String,
+    /// Messages executed by this contract when the joint action is
+    /// executed, after contributions have been sent to the recipient
+    /// and returned to DAOs which did not approve.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The joint action may not be executed once it has expired.
+    pub expiration: Expiration,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Links a DAO's mirror proposal to the joint action. Callable by
+    /// the DAO or the proposal's proposer. The proposal must be in
+    /// the DAO's participating proposal module, have been created
+    /// after the joint action, not yet have been decided, and send
+    /// `Approve` to this contract. This contract must be registered
+    /// as a consumer of the proposal module's proposal hooks.
+    /// Replaces the DAO's previously linked proposal unless it has
+    /// passed. Only the DAO may replace a proposal which has not yet
+    /// been decided.
+    LinkProposal { dao: String, proposal_id: u64 },
+    /// Approves the joint action. Must be sent by a participating
+    /// DAO, normally by its mirror proposal.
+    Approve {},
+    /// Receives proposal status changes from participating DAOs'
+    /// proposal modules.
+    ProposalHook(ProposalHookMsg),
+    /// Used to provide cw20 tokens to satisfy a contribution
+    /// promise. Must be sent by the contributing DAO.
+    Receive(cw20::Cw20ReceiveMsg),
+    /// Provides native tokens to satisfy a contribution
+    /// promise. Must be sent by the contributing DAO.
+    Fund {},
+    /// Withdraws the sender's contribution. Allowed until the joint
+    /// action has been executed.
+    Withdraw {},
+    /// Executes the joint action, sending contributions to the
+    /// recipient and executing the joint action's messages. Callable
+    /// by anyone once enough DAOs have passed their mirror proposals
+    /// and every DAO which has passed its mirror proposal has
+    /// provided its contribution. Contributions of DAOs which have
+    /// not approved are returned to them before the joint action's
+    /// messages are executed.
+    Execute {},
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the joint action's config.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Gets the status of each participating DAO and the joint
+    /// action.
+    #[returns(crate::msg::StatusResponse)]
+    Status {},
+}
+
+#[cw_serde]
+pub struct StatusResponse {
+    pub participants: Vec<CheckedParticipant>,
+    /// The number of DAOs which have passed their mirror proposals.
+    pub approvals: u64,
+    /// True if the joint action may be executed.
+    pub executable: bool,
+    /// True if the joint action has been executed.
+    pub executed: bool,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
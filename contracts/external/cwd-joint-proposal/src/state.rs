@@ -0,0 +1,81 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CosmosMsg, Deps, Empty};
+use cw_storage_plus::{Item, Map};
+use cw_token_swap::state::CheckedTokenInfo;
+use cw_utils::Expiration;
+use cwd_voting::status::Status;
+
+use crate::{msg::Participant, ContractError};
+
+#[cw_serde]
+pub struct Config {
+    /// The number of DAOs which must pass their mirror proposals
+    /// before the joint action may be executed.
+    pub min_approvals: u64,
+    pub recipient: Addr,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    pub expiration: Expiration,
+    /// The block height at which the joint action was created. Mirror
+    /// proposals must have been created at or after this height.
+    pub start_height: u64,
+}
+
+#[cw_serde]
+pub struct CheckedParticipant {
+    pub dao: Addr,
+    pub proposal_module: Addr,
+    pub required: bool,
+    pub contribution: Option<CheckedTokenInfo>,
+    /// True if the DAO's contribution is held by this contract.
+    pub provided: bool,
+    /// The DAO's linked mirror proposal, if any.
+    pub proposal_id: Option<u64>,
+    /// The last known status of the DAO's mirror proposal.
+    pub status: Option<Status>,
+    /// True if the DAO has sent `Approve`.
+    pub approved: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const PARTICIPANTS: Map<Addr, CheckedParticipant> = Map::new("participants");
+/// Maps a mirror proposal's proposal module and ID to the DAO it
+/// belongs to.
+pub const MIRRORS: Map<(Addr, u64), Addr> = Map::new("mirrors");
+pub const EXECUTED: Item<bool> = Item::new("executed");
+
+impl Participant {
+    pub fn into_checked(self, deps: Deps) -> Result<CheckedParticipant, ContractError> {
+        let dao = deps.api.addr_validate(&self.dao)?;
+        let proposal_module = deps.api.addr_validate(&self.proposal_module)?;
+        if cwd_core::state::PROPOSAL_MODULES
+            .query(&deps.querier, dao.clone(), proposal_module.clone())?
+            .is_none()
+        {
+            return Err(ContractError::NotProposalModule {
+                proposal_module,
+                dao,
+            });
+        }
+        Ok(CheckedParticipant {
+            dao,
+            proposal_module,
+            required: self.required,
+            contribution: self
+                .contribution
+                .map(|contribution| contribution.into_checked(deps))
+                .transpose()?,
+            provided: false,
+            proposal_id: None,
+            status: None,
+            approved: false,
+        })
+    }
+}
+
+impl CheckedParticipant {
+    /// Returns true if the DAO has passed its mirror proposal or
+    /// approved the joint action directly.
+    pub fn is_approved(&self) -> bool {
+        self.approved || matches!(self.status, Some(Status::Passed | Status::Executed))
+    }
+}
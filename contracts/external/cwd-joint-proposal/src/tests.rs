@@ -0,0 +1,653 @@
+use cosmwasm_std::{coins, to_binary, Addr, BankMsg, CosmosMsg, Empty, Uint128, WasmMsg};
+use cw20::Cw20Coin;
+use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
+use cw_token_swap::msg::TokenInfo;
+use cw_utils::{Duration, Expiration};
+use cwd_testing::{
+    contracts::proposal_single_contract, helpers::instantiate_with_cw4_groups_governance,
+};
+use cwd_voting::{
+    pre_propose::PreProposeInfo,
+    status::Status,
+    threshold::{PercentageThreshold, Threshold},
+    voting::Vote,
+};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, Participant, QueryMsg, StatusResponse},
+    ContractError,
+};
+
+const DENOM: &str = "ujuno";
+const RECIPIENT: &str = "project";
+
+fn joint_proposal_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+/// Instantiates a DAO whose only member is MEMBER and returns the
+/// addresses of its core and proposal modules.
+fn setup_dao(app: &mut App, member: &str) -> (Addr, Addr) {
+    let proposal_single_id = app.store_code(proposal_single_contract());
+    let instantiate = cwd_proposal_single::msg::InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        close_proposal_on_execution_failure: false,
+        veto: None,
+        delegation_registry: None,
+        commit_reveal: None,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        voting_delay: None,
+        execution_window: None,
+        no_with_veto_threshold: None,
+    };
+    let core = instantiate_with_cw4_groups_governance(
+        app,
+        proposal_single_id,
+        to_binary(&instantiate).unwrap(),
+        Some(vec![Cw20Coin {
+            address: member.to_string(),
+            amount: Uint128::new(1),
+        }]),
+    );
+    let modules: Vec<cwd_core::state::ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            &core,
+            &cwd_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    (core, modules[0].address.clone())
+}
+
+/// Creates a proposal in PROPOSAL_MODULE which approves JOINT and
+/// contributes AMOUNT tokens to it when executed. Returns the
+/// proposal's ID.
+fn make_mirror_proposal(
+    app: &mut App,
+    proposal_module: &Addr,
+    proposer: &str,
+    joint: &Addr,
+    amount: u128,
+) -> u64 {
+    let mut msgs = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: joint.to_string(),
+        msg: to_binary(&ExecuteMsg::Approve {}).unwrap(),
+        funds: vec![],
+    })];
+    if amount != 0 {
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: joint.to_string(),
+            msg: to_binary(&ExecuteMsg::Fund {}).unwrap(),
+            funds: coins(amount, DENOM),
+        }));
+    }
+    make_proposal(app, proposal_module, proposer, msgs)
+}
+
+/// Creates a proposal in PROPOSAL_MODULE which executes MSGS. Returns
+/// the proposal's ID.
+fn make_proposal(
+    app: &mut App,
+    proposal_module: &Addr,
+    proposer: &str,
+    msgs: Vec<CosmosMsg>,
+) -> u64 {
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        proposal_module.clone(),
+        &cwd_proposal_single::msg::ExecuteMsg::Propose {
+            title: "co-fund the project".to_string(),
+            description: "contribute to the joint action".to_string(),
+            msgs,
+            proposer: None,
+            dependencies: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    app.wrap()
+        .query_wasm_smart(
+            proposal_module,
+            &cwd_proposal_single::msg::QueryMsg::ProposalCount {},
+        )
+        .unwrap()
+}
+
+fn vote_and_execute(app: &mut App, proposal_module: &Addr, member: &str, proposal_id: u64) {
+    app.execute_contract(
+        Addr::unchecked(member),
+        proposal_module.clone(),
+        &cwd_proposal_single::msg::ExecuteMsg::Vote {
+            proposal_id,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(member),
+        proposal_module.clone(),
+        &cwd_proposal_single::msg::ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .unwrap();
+}
+
+fn add_hook(app: &mut App, core: &Addr, proposal_module: &Addr, joint: &Addr) {
+    app.execute_contract(
+        core.clone(),
+        proposal_module.clone(),
+        &cwd_proposal_single::msg::ExecuteMsg::AddProposalHook {
+            address: joint.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn query_status(app: &App, joint: &Addr) -> StatusResponse {
+    app.wrap()
+        .query_wasm_smart(joint, &QueryMsg::Status {})
+        .unwrap()
+}
+
+#[test]
+fn test_joint_proposal() {
+    let mut app = App::default();
+    let (core_a, module_a) = setup_dao(&mut app, "ekez");
+    let (core_b, module_b) = setup_dao(&mut app, "keze");
+    for core in [&core_a, &core_b] {
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: core.to_string(),
+            amount: coins(100, DENOM),
+        }))
+        .unwrap();
+    }
+
+    let joint_id = app.store_code(joint_proposal_contract());
+    let participant = |core: &Addr, module: &Addr, required, amount| Participant {
+        dao: core.to_string(),
+        proposal_module: module.to_string(),
+        required,
+        contribution: Some(TokenInfo::Native {
+            denom: DENOM.to_string(),
+            amount: Uint128::new(amount),
+        }),
+    };
+    let mut instantiate = InstantiateMsg {
+        participants: vec![
+            participant(&core_a, &module_a, true, 100),
+            participant(&core_b, &module_b, false, 50),
+        ],
+        min_approvals: Some(3),
+        recipient: RECIPIENT.to_string(),
+        msgs: vec![],
+        expiration: Expiration::Never {},
+    };
+
+    let err: ContractError = app
+        .instantiate_contract(
+            joint_id,
+            Addr::unchecked("ekez"),
+            &instantiate,
+            &[],
+            "joint",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidMinApprovals {}));
+
+    // Participants' proposal modules must belong to them.
+    instantiate.min_approvals = None;
+    instantiate.participants[1].proposal_module = module_a.to_string();
+    let err: ContractError = app
+        .instantiate_contract(
+            joint_id,
+            Addr::unchecked("ekez"),
+            &instantiate,
+            &[],
+            "joint",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotProposalModule { .. }));
+
+    instantiate.participants[1].proposal_module = module_b.to_string();
+    let joint = app
+        .instantiate_contract(
+            joint_id,
+            Addr::unchecked("ekez"),
+            &instantiate,
+            &[],
+            "joint",
+            None,
+        )
+        .unwrap();
+
+    // Mirror proposals may not be linked until the joint proposal
+    // receives the proposal module's hooks.
+    let proposal_a = make_mirror_proposal(&mut app, &module_a, "ekez", &joint, 100);
+    let link_a = ExecuteMsg::LinkProposal {
+        dao: core_a.to_string(),
+        proposal_id: proposal_a,
+    };
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked("ekez"), joint.clone(), &link_a, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::HookNotRegistered { .. }));
+
+    add_hook(&mut app, &core_a, &module_a, &joint);
+
+    // Only the proposer or the DAO may link a mirror proposal.
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked("keze"), joint.clone(), &link_a, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    app.execute_contract(Addr::unchecked("ekez"), joint.clone(), &link_a, &[])
+        .unwrap();
+
+    vote_and_execute(&mut app, &module_a, "ekez", proposal_a);
+    let status = query_status(&app, &joint);
+    assert_eq!(status.approvals, 1);
+    assert!(!status.executable);
+    let participant = status
+        .participants
+        .iter()
+        .find(|participant| participant.dao == core_a)
+        .unwrap();
+    assert_eq!(participant.status, Some(Status::Executed));
+    assert!(participant.provided);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            joint.clone(),
+            &ExecuteMsg::Execute {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::InsufficientApprovals {
+            approvals: 1,
+            min_approvals: 2
+        }
+    ));
+
+    // DAO B passes its mirror proposal but has not yet executed it
+    // and provided its contribution.
+    add_hook(&mut app, &core_b, &module_b, &joint);
+    let proposal_b = make_mirror_proposal(&mut app, &module_b, "keze", &joint, 50);
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        joint.clone(),
+        &ExecuteMsg::LinkProposal {
+            dao: core_b.to_string(),
+            proposal_id: proposal_b,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        module_b.clone(),
+        &cwd_proposal_single::msg::ExecuteMsg::Vote {
+            proposal_id: proposal_b,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            joint.clone(),
+            &ExecuteMsg::Execute {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotFunded { dao } if dao == core_b));
+
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        module_b.clone(),
+        &cwd_proposal_single::msg::ExecuteMsg::Execute {
+            proposal_id: proposal_b,
+        },
+        &[],
+    )
+    .unwrap();
+    assert!(query_status(&app, &joint).executable);
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        joint.clone(),
+        &ExecuteMsg::Execute {},
+        &[],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance(RECIPIENT, DENOM).unwrap();
+    assert_eq!(balance.amount, Uint128::new(150));
+    assert!(query_status(&app, &joint).executed);
+
+    // Contributions which were used may not be withdrawn.
+    let err: ContractError = app
+        .execute_contract(core_a, joint, &ExecuteMsg::Withdraw {}, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoProvision {}));
+}
+
+#[test]
+fn test_unused_contribution_returned() {
+    let mut app = App::default();
+    let (core_a, module_a) = setup_dao(&mut app, "ekez");
+    let (core_b, module_b) = setup_dao(&mut app, "keze");
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_b.to_string(),
+        amount: coins(50, DENOM),
+    }))
+    .unwrap();
+
+    // Either DAO passing its mirror proposal is sufficient.
+    let joint_id = app.store_code(joint_proposal_contract());
+    let joint = app
+        .instantiate_contract(
+            joint_id,
+            Addr::unchecked("ekez"),
+            &InstantiateMsg {
+                participants: vec![
+                    Participant {
+                        dao: core_a.to_string(),
+                        proposal_module: module_a.to_string(),
+                        required: false,
+                        contribution: None,
+                    },
+                    Participant {
+                        dao: core_b.to_string(),
+                        proposal_module: module_b.to_string(),
+                        required: false,
+                        contribution: Some(TokenInfo::Native {
+                            denom: DENOM.to_string(),
+                            amount: Uint128::new(50),
+                        }),
+                    },
+                ],
+                min_approvals: Some(1),
+                recipient: RECIPIENT.to_string(),
+                msgs: vec![],
+                expiration: Expiration::Never {},
+            },
+            &[],
+            "joint",
+            None,
+        )
+        .unwrap();
+
+    // DAO B contributes without linking a mirror proposal.
+    app.execute_contract(
+        core_b.clone(),
+        joint.clone(),
+        &ExecuteMsg::Fund {},
+        &coins(50, DENOM),
+    )
+    .unwrap();
+
+    add_hook(&mut app, &core_a, &module_a, &joint);
+    let proposal_a = make_mirror_proposal(&mut app, &module_a, "ekez", &joint, 0);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        joint.clone(),
+        &ExecuteMsg::LinkProposal {
+            dao: core_a.to_string(),
+            proposal_id: proposal_a,
+        },
+        &[],
+    )
+    .unwrap();
+    vote_and_execute(&mut app, &module_a, "ekez", proposal_a);
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        joint.clone(),
+        &ExecuteMsg::Execute {},
+        &[],
+    )
+    .unwrap();
+
+    // DAO B did not pass a mirror proposal so its contribution was
+    // returned.
+    let balance = app.wrap().query_balance(RECIPIENT, DENOM).unwrap();
+    assert_eq!(balance.amount, Uint128::zero());
+    let balance = app.wrap().query_balance(core_b.clone(), DENOM).unwrap();
+    assert_eq!(balance.amount, Uint128::new(50));
+    let err: ContractError = app
+        .execute_contract(core_b, joint, &ExecuteMsg::Withdraw {}, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoProvision {}));
+}
+
+#[test]
+fn test_link_undecided_mirror_proposal() {
+    let mut app = App::default();
+    let (core, module) = setup_dao(&mut app, "ekez");
+    let joint_id = app.store_code(joint_proposal_contract());
+    let instantiate = InstantiateMsg {
+        participants: vec![Participant {
+            dao: core.to_string(),
+            proposal_module: module.to_string(),
+            required: true,
+            contribution: None,
+        }],
+        min_approvals: None,
+        recipient: RECIPIENT.to_string(),
+        msgs: vec![],
+        expiration: Expiration::Never {},
+    };
+
+    // Proposals created before the joint action may not be linked.
+    let joint = Addr::unchecked("joint");
+    let old = make_mirror_proposal(&mut app, &module, "ekez", &joint, 0);
+    app.update_block(next_block);
+    let joint = app
+        .instantiate_contract(
+            joint_id,
+            Addr::unchecked("ekez"),
+            &instantiate,
+            &[],
+            "joint",
+            None,
+        )
+        .unwrap();
+    add_hook(&mut app, &core, &module, &joint);
+
+    let link = |proposal_id| ExecuteMsg::LinkProposal {
+        dao: core.to_string(),
+        proposal_id,
+    };
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked("ekez"), joint.clone(), &link(old), &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::MirrorTooOld {}));
+
+    // Proposals which have already been decided may not be linked.
+    let passed = make_proposal(&mut app, &module, "ekez", vec![]);
+    vote_and_execute(&mut app, &module, "ekez", passed);
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked("ekez"), joint.clone(), &link(passed), &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::MirrorNotOpen {
+            status: Status::Executed
+        }
+    ));
+    assert_eq!(query_status(&app, &joint).approvals, 0);
+}
+
+#[test]
+fn test_mirror_proposal_must_approve() {
+    let mut app = App::default();
+    let (core_a, module_a) = setup_dao(&mut app, "ekez");
+    let (core_b, module_b) = setup_dao(&mut app, "keze");
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_b.to_string(),
+        amount: coins(50, DENOM),
+    }))
+    .unwrap();
+
+    // The joint action's messages try to spend DAO B's escrow.
+    let joint_id = app.store_code(joint_proposal_contract());
+    let joint = app
+        .instantiate_contract(
+            joint_id,
+            Addr::unchecked("ekez"),
+            &InstantiateMsg {
+                participants: vec![
+                    Participant {
+                        dao: core_a.to_string(),
+                        proposal_module: module_a.to_string(),
+                        required: true,
+                        contribution: None,
+                    },
+                    Participant {
+                        dao: core_b.to_string(),
+                        proposal_module: module_b.to_string(),
+                        required: false,
+                        contribution: Some(TokenInfo::Native {
+                            denom: DENOM.to_string(),
+                            amount: Uint128::new(50),
+                        }),
+                    },
+                ],
+                min_approvals: Some(1),
+                recipient: RECIPIENT.to_string(),
+                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "thief".to_string(),
+                    amount: coins(50, DENOM),
+                })],
+                expiration: Expiration::Never {},
+            },
+            &[],
+            "joint",
+            None,
+        )
+        .unwrap();
+    add_hook(&mut app, &core_a, &module_a, &joint);
+    app.execute_contract(
+        core_b.clone(),
+        joint.clone(),
+        &ExecuteMsg::Fund {},
+        &coins(50, DENOM),
+    )
+    .unwrap();
+
+    let link = |proposal_id| ExecuteMsg::LinkProposal {
+        dao: core_a.to_string(),
+        proposal_id,
+    };
+
+    // Proposals which do not approve the joint action may not stand
+    // in for the DAO's approval.
+    let unrelated = make_proposal(&mut app, &module_a, "ekez", vec![]);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            joint.clone(),
+            &link(unrelated),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::MirrorMissingApproval {}));
+
+    // A proposer may not replace a mirror proposal which is still
+    // being voted on, but the DAO may.
+    let mirror = make_mirror_proposal(&mut app, &module_a, "ekez", &joint, 0);
+    app.execute_contract(Addr::unchecked("ekez"), joint.clone(), &link(mirror), &[])
+        .unwrap();
+    let replacement = make_mirror_proposal(&mut app, &module_a, "ekez", &joint, 0);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            joint.clone(),
+            &link(replacement),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    app.execute_contract(core_a.clone(), joint.clone(), &link(replacement), &[])
+        .unwrap();
+
+    // Only participating DAOs may approve.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            joint.clone(),
+            &ExecuteMsg::Approve {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // DAO B has not approved, so its contribution is returned before
+    // the joint action's messages run and they may not spend it.
+    vote_and_execute(&mut app, &module_a, "ekez", replacement);
+    assert!(query_status(&app, &joint).executable);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        joint.clone(),
+        &ExecuteMsg::Execute {},
+        &[],
+    )
+    .unwrap_err();
+    let balance = app.wrap().query_balance(core_b.clone(), DENOM).unwrap();
+    assert_eq!(balance.amount, Uint128::zero());
+    app.execute_contract(core_b.clone(), joint, &ExecuteMsg::Withdraw {}, &[])
+        .unwrap();
+    let balance = app.wrap().query_balance(core_b, DENOM).unwrap();
+    assert_eq!(balance.amount, Uint128::new(50));
+}
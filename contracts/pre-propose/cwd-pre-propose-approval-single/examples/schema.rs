@@ -0,0 +1,10 @@
+use cosmwasm_schema::write_api;
+use cwd_pre_propose_approval_single::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+fn main() {
+    write_api! {
+        instantiate: InstantiateMsg,
+        query: QueryMsg,
+        execute: ExecuteMsg,
+    }
+}
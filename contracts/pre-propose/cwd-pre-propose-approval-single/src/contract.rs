@@ -0,0 +1,368 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
+    Storage, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_paginate::paginate_map_values;
+
+use cwd_pre_propose_base::{
+    error::PreProposeError, msg::ExecuteMsg as ExecuteBase, state::PreProposeContract,
+};
+use cwd_voting::deposit::{CheckedDepositInfo, DepositRefundPolicy};
+
+use crate::{
+    error::ContractError,
+    msg::{
+        ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, ProposeMessage,
+        ProposeMessageInternal, QueryExt, QueryMsg,
+    },
+    state::{PendingProposal, APPROVED, APPROVER, PENDING_PROPOSALS, PENDING_PROPOSAL_COUNT},
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-pre-propose-approval-single";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+type PrePropose = PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposeMessageInternal>;
+type ExecuteInternal = ExecuteBase<ProposeMessageInternal, ExecuteExt>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let approver = deps.api.addr_validate(&msg.extension.approver)?;
+    APPROVER.save(deps.storage, &approver)?;
+
+    let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(resp.add_attribute("approver", approver))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    // Proposals are not sent to the proposal module when they are
+    // submitted. Instead they wait for the approver, so we handle
+    // submission, review, and the creation of approved proposals
+    // here and leave everything else to the base contract.
+    let internalized = match msg {
+        ExecuteMsg::Propose { msg } => return execute_propose(deps, env, info, msg),
+        ExecuteMsg::ProposeDraft { .. }
+        | ExecuteMsg::FundDraft { .. }
+        | ExecuteMsg::ExpireDraft { .. } => return Err(ContractError::DraftsUnsupported {}),
+        ExecuteMsg::Extension { msg } => {
+            return match msg {
+                ExecuteExt::Approve { id } => execute_approve(deps, info, id),
+                ExecuteExt::Reject { id } => execute_reject(deps, info, id),
+                ExecuteExt::WithdrawPending { id } => execute_withdraw_pending(deps, info, id),
+                ExecuteExt::UpdateApprover { address } => {
+                    execute_update_approver(deps, info, address)
+                }
+            }
+        }
+        ExecuteMsg::ProposalCreatedHook {
+            proposal_id,
+            proposer,
+        } => {
+            let resp = PrePropose::default().execute(
+                deps.branch(),
+                env,
+                info,
+                ExecuteInternal::ProposalCreatedHook {
+                    proposal_id,
+                    proposer,
+                },
+            )?;
+            // Approved proposals keep the deposit that was paid when
+            // they were submitted, which may differ from the one
+            // currently configured.
+            return match APPROVED.may_load(deps.storage)? {
+                Some(approved) => {
+                    let pre_propose = PrePropose::default();
+                    APPROVED.remove(deps.storage);
+                    pre_propose.remove_pending_submission(deps.storage, &approved.proposer)?;
+                    pre_propose.deposits.save(
+                        deps.storage,
+                        proposal_id,
+                        &(approved.deposit, approved.proposer),
+                    )?;
+                    Ok(resp.add_attribute("pending_proposal_id", approved.id.to_string()))
+                }
+                None => Ok(resp),
+            };
+        }
+        ExecuteMsg::UpdateProposal {
+            proposal_id,
+            msg:
+                ProposeMessage::Propose {
+                    title,
+                    description,
+                    msgs,
+                    dependencies,
+                },
+        } => {
+            // Dependencies are set when a proposal is created and may
+            // not be updated.
+            if !dependencies.is_empty() {
+                return Err(PreProposeError::InvalidUpdate {}.into());
+            }
+            ExecuteInternal::UpdateProposal {
+                proposal_id,
+                msg: ProposeMessageInternal::UpdateProposal {
+                    proposal_id,
+                    proposer: Some(info.sender.to_string()),
+                    title,
+                    description,
+                    msgs,
+                },
+            }
+        }
+        ExecuteMsg::WithdrawProposal { proposal_id } => {
+            ExecuteInternal::WithdrawProposal { proposal_id }
+        }
+        ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
+        ExecuteMsg::UpdateConfig {
+            deposit_info,
+            open_proposal_submission,
+            min_voting_power,
+            max_open_proposals,
+            submission_cooldown,
+        } => ExecuteInternal::UpdateConfig {
+            deposit_info,
+            open_proposal_submission,
+            min_voting_power,
+            max_open_proposals,
+            submission_cooldown,
+        },
+        ExecuteMsg::UpdateMessageRestrictions { restrictions } => {
+            ExecuteInternal::UpdateMessageRestrictions { restrictions }
+        }
+        ExecuteMsg::UpdateTemplates { to_add, to_remove } => {
+            ExecuteInternal::UpdateTemplates { to_add, to_remove }
+        }
+        ExecuteMsg::ProposalCompletedHook {
+            proposal_id,
+            new_status,
+        } => ExecuteInternal::ProposalCompletedHook {
+            proposal_id,
+            new_status,
+        },
+    };
+
+    Ok(PrePropose::default().execute(deps, env, info, internalized)?)
+}
+
+/// Takes the deposit for a proposal and adds it to the pending
+/// queue.
+pub fn execute_propose(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ProposeMessage,
+) -> Result<Response, ContractError> {
+    let pre_propose = PrePropose::default();
+    let config = pre_propose.config.load(deps.storage)?;
+    pre_propose.check_can_propose(deps.branch(), &env.block, &config, &info.sender)?;
+    let ProposeMessage::Propose { ref msgs, .. } = msg;
+    pre_propose.check_message_restrictions(deps.storage, vec![msgs.iter().collect()])?;
+    pre_propose.start_cooldown(deps.storage, &env.block, &config, &info.sender)?;
+
+    let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
+        deposit_info
+            .check_native_deposit_paid(&info)
+            .map_err(PreProposeError::from)?;
+        deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
+    } else {
+        vec![]
+    };
+
+    let id = PENDING_PROPOSAL_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    PENDING_PROPOSAL_COUNT.save(deps.storage, &id)?;
+    // Proposals awaiting approval count towards their proposer's open
+    // proposals.
+    pre_propose.add_pending_submission(deps.storage, &info.sender)?;
+    PENDING_PROPOSALS.save(
+        deps.storage,
+        id,
+        &PendingProposal {
+            id,
+            proposer: info.sender.clone(),
+            msg,
+            deposit: config.deposit_info,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "execute_propose")
+        .add_attribute("sender", info.sender)
+        .add_attribute("pending_proposal_id", id.to_string())
+        .add_messages(deposit_messages))
+}
+
+pub fn execute_approve(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    check_approver(deps.as_ref(), &info)?;
+    let pending = take_pending_proposal(deps.storage, id)?;
+
+    let ProposeMessage::Propose {
+        title,
+        description,
+        msgs,
+        dependencies,
+    } = pending.msg.clone();
+    let propose_message = WasmMsg::Execute {
+        contract_addr: PrePropose::default()
+            .proposal_module
+            .load(deps.storage)?
+            .into_string(),
+        msg: to_binary(&ProposeMessageInternal::Propose {
+            title,
+            description,
+            msgs,
+            proposer: Some(pending.proposer.to_string()),
+            dependencies,
+        })?,
+        funds: vec![],
+    };
+    APPROVED.save(deps.storage, &pending)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "execute_approve")
+        .add_attribute("pending_proposal_id", id.to_string())
+        .add_message(propose_message))
+}
+
+pub fn execute_reject(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    check_approver(deps.as_ref(), &info)?;
+    let pending = take_pending_proposal(deps.storage, id)?;
+    PrePropose::default().remove_pending_submission(deps.storage, &pending.proposer)?;
+
+    // Rejected proposals are treated like proposals which were
+    // closed.
+    let messages = settle_deposit(deps.as_ref(), &pending, |deposit| {
+        deposit.refund_policy == DepositRefundPolicy::Always
+    })?;
+
+    Ok(Response::default()
+        .add_attribute("method", "execute_reject")
+        .add_attribute("pending_proposal_id", id.to_string())
+        .add_messages(messages))
+}
+
+pub fn execute_withdraw_pending(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NoSuchPendingProposal { id })?;
+    if pending.proposer != info.sender {
+        return Err(ContractError::NotProposer {});
+    }
+    PENDING_PROPOSALS.remove(deps.storage, id);
+    PrePropose::default().remove_pending_submission(deps.storage, &pending.proposer)?;
+
+    let messages = settle_deposit(deps.as_ref(), &pending, |deposit| {
+        deposit.withdrawal_refund_policy() == DepositRefundPolicy::Always
+    })?;
+
+    Ok(Response::default()
+        .add_attribute("method", "execute_withdraw_pending")
+        .add_attribute("pending_proposal_id", id.to_string())
+        .add_messages(messages))
+}
+
+pub fn execute_update_approver(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    if info.sender != dao {
+        check_approver(deps.as_ref(), &info)?;
+    }
+
+    let approver = deps.api.addr_validate(&address)?;
+    APPROVER.save(deps.storage, &approver)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "execute_update_approver")
+        .add_attribute("approver", approver))
+}
+
+fn check_approver(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    if APPROVER.load(deps.storage)? != info.sender {
+        return Err(ContractError::NotApprover {});
+    }
+    Ok(())
+}
+
+fn take_pending_proposal(
+    storage: &mut dyn Storage,
+    id: u64,
+) -> Result<PendingProposal, ContractError> {
+    let pending = PENDING_PROPOSALS
+        .may_load(storage, id)?
+        .ok_or(ContractError::NoSuchPendingProposal { id })?;
+    PENDING_PROPOSALS.remove(storage, id);
+    Ok(pending)
+}
+
+/// Returns the deposit of a proposal which will not be created to
+/// its proposer if SHOULD_REFUND returns true for it, and sends it
+/// to the DAO otherwise.
+fn settle_deposit(
+    deps: Deps,
+    pending: &PendingProposal,
+    should_refund: impl Fn(&CheckedDepositInfo) -> bool,
+) -> StdResult<Vec<CosmosMsg>> {
+    match pending.deposit {
+        Some(ref deposit) if should_refund(deposit) => {
+            deposit.get_return_deposit_message(&pending.proposer)
+        }
+        Some(ref deposit) => {
+            let dao = PrePropose::default().dao.load(deps.storage)?;
+            deposit.get_forfeit_deposit_messages(&pending.proposer, &dao)
+        }
+        None => Ok(vec![]),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::QueryExtension { msg } => match msg {
+            QueryExt::Approver {} => to_binary(&APPROVER.load(deps.storage)?),
+            QueryExt::PendingProposal { id } => {
+                to_binary(&PENDING_PROPOSALS.load(deps.storage, id)?)
+            }
+            QueryExt::PendingProposals { start_after, limit } => to_binary(&paginate_map_values(
+                deps,
+                &PENDING_PROPOSALS,
+                start_after,
+                limit,
+                Order::Ascending,
+            )?),
+        },
+        _ => PrePropose::default().query(deps, env, msg),
+    }
+}
@@ -0,0 +1,25 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+use cwd_pre_propose_base::error::PreProposeError;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    PreProposeBase(#[from] PreProposeError),
+
+    #[error("Message sender is not the approver")]
+    NotApprover {},
+
+    #[error("Only the proposer may withdraw a pending proposal")]
+    NotProposer {},
+
+    #[error("No pending proposal with id ({id})")]
+    NoSuchPendingProposal { id: u64 },
+
+    #[error("Proposals must be approved before they are created. Drafts are not supported")]
+    DraftsUnsupported {},
+}
@@ -0,0 +1,14 @@
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use crate::error::ContractError;
+
+// Exporting these means that contracts interacting with this one don't
+// need an explicit dependency on the base contract to read queries.
+pub use cwd_pre_propose_base::msg::DepositInfoResponse;
+pub use cwd_pre_propose_base::state::Config;
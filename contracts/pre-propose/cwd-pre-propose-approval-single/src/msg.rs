@@ -0,0 +1,100 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{CosmosMsg, Empty};
+use cwd_voting::dependency::UncheckedProposalDependency;
+
+use cwd_pre_propose_base::{
+    msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
+    restrictions::ProposalMessages,
+};
+
+#[cw_serde]
+pub enum ProposeMessage {
+    Propose {
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+        /// Proposals which must be executed before this proposal may
+        /// be executed.
+        #[serde(default)]
+        dependencies: Vec<UncheckedProposalDependency>,
+    },
+}
+
+#[cw_serde]
+pub struct InstantiateExt {
+    /// The address which reviews submitted proposals. For example, a
+    /// moderation subDAO's `cwd-core` contract or a multisig.
+    pub approver: String,
+}
+
+#[cw_serde]
+pub enum ExecuteExt {
+    /// Approves the pending proposal identified by ID, creating it
+    /// in the proposal module. Only the approver may call this.
+    Approve { id: u64 },
+    /// Rejects the pending proposal identified by ID. Its deposit is
+    /// refunded if the deposit's refund policy is `Always` and is
+    /// sent to the DAO otherwise. Only the approver may call this.
+    Reject { id: u64 },
+    /// Withdraws the pending proposal identified by ID before it has
+    /// been reviewed. Its deposit is refunded according to the
+    /// deposit's withdrawal refund policy. Only the proposer may
+    /// call this.
+    WithdrawPending { id: u64 },
+    /// Updates the approver. Only the approver or the DAO may call
+    /// this.
+    UpdateApprover { address: String },
+}
+
+#[cw_serde]
+pub enum QueryExt {
+    /// Gets the approver. Returns `Addr`.
+    Approver {},
+    /// Gets the pending proposal identified by ID. Returns
+    /// `crate::state::PendingProposal`.
+    PendingProposal { id: u64 },
+    /// Lists pending proposals, oldest first. Returns
+    /// `Vec<crate::state::PendingProposal>`.
+    PendingProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+pub type InstantiateMsg = InstantiateBase<InstantiateExt>;
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
+pub type QueryMsg = QueryBase<QueryExt>;
+
+/// Internal version of the propose message that includes the
+/// `proposer` field. The module will fill this in based on the sender
+/// of the external message.
+#[cw_serde]
+pub(crate) enum ProposeMessageInternal {
+    Propose {
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+        proposer: Option<String>,
+        // Omitted when empty so that proposals without dependencies
+        // may be created in proposal modules which do not support
+        // them.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        dependencies: Vec<UncheckedProposalDependency>,
+    },
+    UpdateProposal {
+        proposal_id: u64,
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+        proposer: Option<String>,
+    },
+}
+
+impl ProposalMessages for ProposeMessageInternal {
+    fn cosmos_msgs(&self) -> Vec<Vec<&CosmosMsg>> {
+        match self {
+            ProposeMessageInternal::Propose { msgs, .. }
+            | ProposeMessageInternal::UpdateProposal { msgs, .. } => vec![msgs.iter().collect()],
+        }
+    }
+}
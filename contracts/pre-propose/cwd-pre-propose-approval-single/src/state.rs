@@ -0,0 +1,26 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+
+use cwd_voting::deposit::CheckedDepositInfo;
+
+use crate::msg::ProposeMessage;
+
+/// A proposal which has been submitted but not yet reviewed by the
+/// approver.
+#[cw_serde]
+pub struct PendingProposal {
+    pub id: u64,
+    /// The address that submitted the proposal.
+    pub proposer: Addr,
+    pub msg: ProposeMessage,
+    /// The deposit paid when the proposal was submitted.
+    pub deposit: Option<CheckedDepositInfo>,
+}
+
+pub const APPROVER: Item<Addr> = Item::new("approver");
+pub const PENDING_PROPOSAL_COUNT: Item<u64> = Item::new("pending_proposal_count");
+pub const PENDING_PROPOSALS: Map<u64, PendingProposal> = Map::new("pending_proposals");
+/// The pending proposal being created after its approval. Set when a
+/// proposal is approved and removed by the proposal created hook.
+pub const APPROVED: Item<PendingProposal> = Item::new("approved");
@@ -0,0 +1,652 @@
+use cosmwasm_std::{coins, to_binary, Addr, Coin, Empty, Uint128};
+use cps::query::ProposalResponse;
+use cw_denom::UncheckedDenom;
+use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor};
+use cwd_core::state::ProposalModule;
+use cwd_interface::{Admin, ModuleInstantiateInfo};
+use cwd_pre_propose_base::{error::PreProposeError, msg::DepositInfoResponse};
+use cwd_proposal_single as cps;
+use cwd_testing::helpers::instantiate_with_cw4_groups_governance;
+use cwd_voting::{
+    deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    pre_propose::{PreProposeInfo, ProposalCreationPolicy},
+    status::Status,
+    threshold::{PercentageThreshold, Threshold},
+    voting::Vote,
+};
+
+use crate::{
+    contract::*,
+    msg::{
+        ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, ProposeMessage, QueryExt, QueryMsg,
+    },
+    state::PendingProposal,
+    ContractError,
+};
+
+fn cw_dao_proposal_single_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cps::contract::execute,
+        cps::contract::instantiate,
+        cps::contract::query,
+    )
+    .with_migrate(cps::contract::migrate)
+    .with_reply(cps::contract::reply);
+    Box::new(contract)
+}
+
+fn cw_pre_propose_approval_single() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    Box::new(contract)
+}
+
+fn native_deposit(refund_policy: DepositRefundPolicy) -> UncheckedDepositInfo {
+    UncheckedDepositInfo {
+        denom: DepositToken::Token {
+            denom: UncheckedDenom::Native("ujuno".to_string()),
+        },
+        amount: Uint128::new(10),
+        refund_policy,
+        withdrawal_refund_policy: None,
+        veto_destination: None,
+        funding_period: None,
+    }
+}
+
+struct DefaultTestSetup {
+    core_addr: Addr,
+    proposal_single: Addr,
+    pre_propose: Addr,
+}
+fn setup_default_test(
+    app: &mut App,
+    deposit_info: Option<UncheckedDepositInfo>,
+) -> DefaultTestSetup {
+    let cps_id = app.store_code(cw_dao_proposal_single_contract());
+    let pre_propose_id = app.store_code(cw_pre_propose_approval_single());
+
+    let proposal_module_instantiate = cps::msg::InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: cw_utils::Duration::Time(86400),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        pre_propose_info: PreProposeInfo::ModuleMayPropose {
+            info: ModuleInstantiateInfo {
+                code_id: pre_propose_id,
+                msg: to_binary(&InstantiateMsg {
+                    deposit_info,
+                    open_proposal_submission: false,
+                    min_voting_power: None,
+                    max_open_proposals: None,
+                    submission_cooldown: None,
+                    message_restrictions: None,
+                    templates: vec![],
+                    extension: InstantiateExt {
+                        approver: "approver".to_string(),
+                    },
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                label: "approval pre-propose module".to_string(),
+            },
+        },
+        close_proposal_on_execution_failure: false,
+        veto: None,
+        delegation_registry: None,
+        commit_reveal: None,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        voting_delay: None,
+        execution_window: None,
+        no_with_veto_threshold: None,
+    };
+
+    let core_addr = instantiate_with_cw4_groups_governance(
+        app,
+        cps_id,
+        to_binary(&proposal_module_instantiate).unwrap(),
+        Some(vec![
+            cw20::Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(9),
+            },
+            cw20::Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(8),
+            },
+        ]),
+    );
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &cwd_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
+    let proposal_creation_policy = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &cps::msg::QueryMsg::ProposalCreationPolicy {},
+        )
+        .unwrap();
+
+    let pre_propose = match proposal_creation_policy {
+        ProposalCreationPolicy::Module { addr } => addr,
+        _ => panic!("expected a module for the proposal creation policy"),
+    };
+
+    DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    }
+}
+
+fn mint_natives(app: &mut App, receiver: &str, coins: Vec<Coin>) {
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: receiver.to_string(),
+        amount: coins,
+    }))
+    .unwrap();
+}
+
+fn get_balance_native(app: &App, who: &str, denom: &str) -> Uint128 {
+    let res = app.wrap().query_balance(who, denom).unwrap();
+    res.amount
+}
+
+fn submit_proposal(app: &mut App, pre_propose: Addr, proposer: &str, funds: &[Coin]) -> u64 {
+    let res = app
+        .execute_contract(
+            Addr::unchecked(proposer),
+            pre_propose,
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    dependencies: vec![],
+                },
+            },
+            funds,
+        )
+        .unwrap();
+
+    res.events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "pending_proposal_id")
+        .unwrap()
+        .value
+        .parse()
+        .unwrap()
+}
+
+fn review(
+    app: &mut App,
+    pre_propose: Addr,
+    sender: &str,
+    msg: ExecuteExt,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        pre_propose,
+        &ExecuteMsg::Extension { msg },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn get_pending_proposals(app: &App, pre_propose: Addr) -> Vec<PendingProposal> {
+    app.wrap()
+        .query_wasm_smart(
+            pre_propose,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::PendingProposals {
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        )
+        .unwrap()
+}
+
+fn get_proposal_count(app: &App, proposal_single: Addr) -> u64 {
+    app.wrap()
+        .query_wasm_smart(proposal_single, &cps::msg::QueryMsg::ProposalCount {})
+        .unwrap()
+}
+
+fn get_deposit_info(app: &App, module: Addr, id: u64) -> DepositInfoResponse {
+    app.wrap()
+        .query_wasm_smart(module, &QueryMsg::DepositInfo { proposal_id: id })
+        .unwrap()
+}
+
+fn update_deposit(
+    app: &mut App,
+    core_addr: &Addr,
+    pre_propose: Addr,
+    deposit_info: Option<UncheckedDepositInfo>,
+) {
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose,
+        &ExecuteMsg::UpdateConfig {
+            deposit_info,
+            open_proposal_submission: false,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_approve_proposal() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(native_deposit(DepositRefundPolicy::OnlyPassed)),
+    );
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+
+    // Only members may submit proposals.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("outsider"),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    dependencies: vec![],
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::PreProposeBase(PreProposeError::NotMember {})
+    );
+
+    // Submitting a proposal takes the deposit but does not create
+    // the proposal.
+    let id = submit_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::zero());
+    assert_eq!(get_proposal_count(&app, proposal_single.clone()), 0);
+    let pending = get_pending_proposals(&app, pre_propose.clone());
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, id);
+    assert_eq!(pending[0].proposer, Addr::unchecked("ekez"));
+
+    // Changes to the deposit do not apply to submitted proposals.
+    update_deposit(&mut app, &core_addr, pre_propose.clone(), None);
+
+    let err = review(
+        &mut app,
+        pre_propose.clone(),
+        "ekez",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotApprover {});
+
+    review(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    assert!(get_pending_proposals(&app, pre_propose.clone()).is_empty());
+    assert_eq!(get_proposal_count(&app, proposal_single.clone()), 1);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &cps::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("ekez"));
+    let deposit = get_deposit_info(&app, pre_propose.clone(), 1);
+    assert_eq!(deposit.proposer, Addr::unchecked("ekez"));
+    assert!(matches!(
+        deposit.deposit_info,
+        Some(CheckedDepositInfo {
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            ..
+        })
+    ));
+
+    // Approved proposals may not be approved again.
+    let err = review(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoSuchPendingProposal { id });
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &cps::msg::ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &cps::msg::ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &cps::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+}
+
+#[test]
+fn test_reject_proposal() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, Some(native_deposit(DepositRefundPolicy::Always)));
+    mint_natives(&mut app, "ekez", coins(20, "ujuno"));
+
+    let refunded = submit_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    update_deposit(
+        &mut app,
+        &core_addr,
+        pre_propose.clone(),
+        Some(native_deposit(DepositRefundPolicy::OnlyPassed)),
+    );
+    let forfeited = submit_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    assert_eq!(get_pending_proposals(&app, pre_propose.clone()).len(), 2);
+
+    let err = review(
+        &mut app,
+        pre_propose.clone(),
+        "keze",
+        ExecuteExt::Reject { id: refunded },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotApprover {});
+
+    // Deposits of rejected proposals are refunded if their refund
+    // policy is `Always`, and sent to the DAO otherwise.
+    review(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Reject { id: refunded },
+    )
+    .unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+
+    review(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Reject { id: forfeited },
+    )
+    .unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+    assert_eq!(
+        get_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(10)
+    );
+
+    assert!(get_pending_proposals(&app, pre_propose).is_empty());
+    assert_eq!(get_proposal_count(&app, proposal_single), 0);
+}
+
+#[test]
+fn test_withdraw_pending_proposal() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr: _,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            withdrawal_refund_policy: Some(DepositRefundPolicy::Always),
+            ..native_deposit(DepositRefundPolicy::Never)
+        }),
+    );
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+
+    let id = submit_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+
+    let err = review(
+        &mut app,
+        pre_propose.clone(),
+        "keze",
+        ExecuteExt::WithdrawPending { id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotProposer {});
+
+    review(
+        &mut app,
+        pre_propose.clone(),
+        "ekez",
+        ExecuteExt::WithdrawPending { id },
+    )
+    .unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+
+    // Withdrawn proposals may not be approved.
+    let err = review(
+        &mut app,
+        pre_propose,
+        "approver",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoSuchPendingProposal { id });
+}
+
+#[test]
+fn test_max_open_proposals() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None);
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateConfig {
+            deposit_info: None,
+            open_proposal_submission: false,
+            min_voting_power: None,
+            max_open_proposals: Some(2),
+            submission_cooldown: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let submit_err = |app: &mut App| -> ContractError {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    dependencies: vec![],
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+    };
+    let too_many = ContractError::PreProposeBase(PreProposeError::TooManyOpenProposals { max: 2 });
+
+    // Proposals awaiting approval count towards the limit.
+    let first = submit_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    let second = submit_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    assert_eq!(submit_err(&mut app), too_many);
+
+    review(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Reject { id: first },
+    )
+    .unwrap();
+    let third = submit_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+
+    // Approved proposals count while they are open.
+    review(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Approve { id: second },
+    )
+    .unwrap();
+    assert_eq!(get_proposal_count(&app, proposal_single), 1);
+    assert_eq!(submit_err(&mut app), too_many);
+
+    review(
+        &mut app,
+        pre_propose.clone(),
+        "ekez",
+        ExecuteExt::WithdrawPending { id: third },
+    )
+    .unwrap();
+    submit_proposal(&mut app, pre_propose, "ekez", &[]);
+}
+
+#[test]
+fn test_update_approver() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None);
+
+    let err = review(
+        &mut app,
+        pre_propose.clone(),
+        "ekez",
+        ExecuteExt::UpdateApprover {
+            address: "ekez".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotApprover {});
+
+    // The approver may hand off review, and the DAO may replace the
+    // approver.
+    review(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::UpdateApprover {
+            address: "moderators".to_string(),
+        },
+    )
+    .unwrap();
+    review(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        ExecuteExt::UpdateApprover {
+            address: "keze".to_string(),
+        },
+    )
+    .unwrap();
+    let approver: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Approver {},
+            },
+        )
+        .unwrap();
+    assert_eq!(approver, Addr::unchecked("keze"));
+
+    let id = submit_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    let err = review(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotApprover {});
+    review(
+        &mut app,
+        pre_propose.clone(),
+        "keze",
+        ExecuteExt::Approve { id },
+    )
+    .unwrap();
+    assert_eq!(get_proposal_count(&app, proposal_single), 1);
+
+    // Drafts would skip review and are not supported.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose,
+            &ExecuteMsg::ProposeDraft {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    dependencies: vec![],
+                },
+                amount: Uint128::new(1),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::DraftsUnsupported {});
+}
@@ -1,15 +1,18 @@
 use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+};
 use cw2::set_contract_version;
 
 use cwd_pre_propose_base::{
     error::PreProposeError,
     msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
+    restrictions::{ProposalMessages, TemplateParam},
     state::PreProposeContract,
 };
-use cwd_voting::multiple_choice::MultipleChoiceOptions;
+use cwd_voting::multiple_choice::{MultipleChoiceOption, MultipleChoiceOptions};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-pre-propose-multiple";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -21,6 +24,22 @@ pub enum ProposeMessage {
         description: String,
         choices: MultipleChoiceOptions,
     },
+    /// Creates a proposal whose choices execute the messages of the
+    /// module's templates filled in with their parameters.
+    ProposeFromTemplate {
+        title: String,
+        description: String,
+        choices: Vec<TemplateChoice>,
+    },
+}
+
+/// A choice whose messages are the messages of the module's template
+/// named TEMPLATE filled in with PARAMS.
+#[cw_serde]
+pub struct TemplateChoice {
+    pub description: String,
+    pub template: String,
+    pub params: Vec<TemplateParam>,
 }
 
 pub type InstantiateMsg = InstantiateBase<Empty>;
@@ -38,10 +57,65 @@ enum ProposeMessageInternal {
         choices: MultipleChoiceOptions,
         proposer: Option<String>,
     },
+    UpdateProposal {
+        proposal_id: u64,
+        title: String,
+        description: String,
+        choices: MultipleChoiceOptions,
+        proposer: Option<String>,
+    },
+}
+
+impl ProposalMessages for ProposeMessageInternal {
+    fn cosmos_msgs(&self) -> Vec<Vec<&CosmosMsg>> {
+        match self {
+            ProposeMessageInternal::Propose { choices, .. }
+            | ProposeMessageInternal::UpdateProposal { choices, .. } => choices
+                .options
+                .iter()
+                .map(|option| option.msgs.iter().flatten().collect())
+                .collect(),
+        }
+    }
 }
 
 type PrePropose = PreProposeContract<Empty, Empty, Empty, ProposeMessageInternal>;
 
+/// Fills in the templates of a templated proposal, returning its
+/// title, description, and choices.
+fn fill_templates(
+    deps: Deps,
+    msg: ProposeMessage,
+) -> Result<(String, String, MultipleChoiceOptions), PreProposeError> {
+    match msg {
+        ProposeMessage::Propose {
+            title,
+            description,
+            choices,
+        } => Ok((title, description, choices)),
+        ProposeMessage::ProposeFromTemplate {
+            title,
+            description,
+            choices,
+        } => {
+            let options = choices
+                .into_iter()
+                .map(|choice| {
+                    Ok(MultipleChoiceOption {
+                        description: choice.description,
+                        msgs: Some(PrePropose::default().render_template(
+                            deps.storage,
+                            &choice.template,
+                            &choice.params,
+                        )?),
+                    })
+                })
+                .collect::<Result<Vec<_>, PreProposeError>>()?;
+            Ok((title, description, MultipleChoiceOptions { options }))
+        }
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
@@ -67,30 +141,70 @@ pub fn execute(
     // internal message which sets it.
     type ExecuteInternal = ExecuteBase<ProposeMessageInternal, Empty>;
     let internalized = match msg {
-        ExecuteMsg::Propose {
-            msg:
-                ProposeMessage::Propose {
+        ExecuteMsg::Propose { msg } => {
+            let (title, description, choices) = fill_templates(deps.as_ref(), msg)?;
+            ExecuteInternal::Propose {
+                msg: ProposeMessageInternal::Propose {
+                    proposer: Some(info.sender.to_string()),
                     title,
                     description,
                     choices,
                 },
-        } => ExecuteInternal::Propose {
-            msg: ProposeMessageInternal::Propose {
-                proposer: Some(info.sender.to_string()),
-                title,
-                description,
-                choices,
-            },
-        },
+            }
+        }
+        ExecuteMsg::ProposeDraft { msg, amount } => {
+            let (title, description, choices) = fill_templates(deps.as_ref(), msg)?;
+            ExecuteInternal::ProposeDraft {
+                msg: ProposeMessageInternal::Propose {
+                    proposer: Some(info.sender.to_string()),
+                    title,
+                    description,
+                    choices,
+                },
+                amount,
+            }
+        }
+        ExecuteMsg::FundDraft { draft_id, amount } => {
+            ExecuteInternal::FundDraft { draft_id, amount }
+        }
+        ExecuteMsg::ExpireDraft { draft_id } => ExecuteInternal::ExpireDraft { draft_id },
+        ExecuteMsg::UpdateProposal { proposal_id, msg } => {
+            let (title, description, choices) = fill_templates(deps.as_ref(), msg)?;
+            ExecuteInternal::UpdateProposal {
+                proposal_id,
+                msg: ProposeMessageInternal::UpdateProposal {
+                    proposal_id,
+                    proposer: Some(info.sender.to_string()),
+                    title,
+                    description,
+                    choices,
+                },
+            }
+        }
+        ExecuteMsg::WithdrawProposal { proposal_id } => {
+            ExecuteInternal::WithdrawProposal { proposal_id }
+        }
         ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
         ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
         ExecuteMsg::UpdateConfig {
             deposit_info,
             open_proposal_submission,
+            min_voting_power,
+            max_open_proposals,
+            submission_cooldown,
         } => ExecuteInternal::UpdateConfig {
             deposit_info,
             open_proposal_submission,
+            min_voting_power,
+            max_open_proposals,
+            submission_cooldown,
         },
+        ExecuteMsg::UpdateMessageRestrictions { restrictions } => {
+            ExecuteInternal::UpdateMessageRestrictions { restrictions }
+        }
+        ExecuteMsg::UpdateTemplates { to_add, to_remove } => {
+            ExecuteInternal::UpdateTemplates { to_add, to_remove }
+        }
         ExecuteBase::ProposalCreatedHook {
             proposal_id,
             proposer,
@@ -1,4 +1,6 @@
-use cosmwasm_std::{coins, from_slice, to_binary, Addr, Coin, Decimal, Empty, Uint128};
+use cosmwasm_std::{
+    coins, from_slice, to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Empty, Uint128,
+};
 use cpm::query::ProposalResponse;
 use cw2::ContractVersion;
 use cw20::Cw20Coin;
@@ -7,7 +9,12 @@ use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor};
 use cw_utils::Duration;
 use cwd_core::state::ProposalModule;
 use cwd_interface::{Admin, ModuleInstantiateInfo};
-use cwd_pre_propose_base::{error::PreProposeError, msg::DepositInfoResponse, state::Config};
+use cwd_pre_propose_base::{
+    error::PreProposeError,
+    msg::DepositInfoResponse,
+    restrictions::{Template, TemplateParam, UncheckedMessageRestrictions},
+    state::Config,
+};
 use cwd_proposal_multiple as cpm;
 use cwd_testing::helpers::instantiate_with_cw4_groups_governance;
 use cwd_voting::{
@@ -68,6 +75,11 @@ fn get_default_proposal_module_instantiate(
                 msg: to_binary(&InstantiateMsg {
                     deposit_info,
                     open_proposal_submission,
+                    min_voting_power: None,
+                    max_open_proposals: None,
+                    submission_cooldown: None,
+                    message_restrictions: None,
+                    templates: vec![],
                     extension: Empty::default(),
                 })
                 .unwrap(),
@@ -76,6 +88,9 @@ fn get_default_proposal_module_instantiate(
             },
         },
         close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
     }
 }
 
@@ -306,7 +321,8 @@ fn vote(
         module.clone(),
         &cpm::msg::ExecuteMsg::Vote {
             proposal_id: id,
-            vote: position,
+            vote: position.into(),
+            rationale: None,
         },
         &[],
     )
@@ -357,6 +373,9 @@ fn update_config(
         &ExecuteMsg::UpdateConfig {
             deposit_info,
             open_proposal_submission,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
         },
         &[],
     )
@@ -378,6 +397,9 @@ fn update_config_should_fail(
         &ExecuteMsg::UpdateConfig {
             deposit_info,
             open_proposal_submission,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
         },
         &[],
     )
@@ -461,6 +483,9 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -533,6 +558,9 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -709,6 +737,9 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -791,6 +822,9 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -827,6 +861,9 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false, // no open proposal submission.
     );
@@ -902,6 +939,9 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         true, // yes, open proposal submission.
     );
@@ -1062,8 +1102,16 @@ fn test_instantiate_with_zero_native_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            withdrawal_refund_policy: None,
+                            veto_destination: None,
+                            funding_period: None,
                         }),
                         open_proposal_submission: false,
+                        min_voting_power: None,
+                        max_open_proposals: None,
+                        submission_cooldown: None,
+                        message_restrictions: None,
+                        templates: vec![],
                         extension: Empty::default(),
                     })
                     .unwrap(),
@@ -1072,6 +1120,9 @@ fn test_instantiate_with_zero_native_deposit() {
                 },
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
         }
     };
 
@@ -1123,8 +1174,16 @@ fn test_instantiate_with_zero_cw20_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            withdrawal_refund_policy: None,
+                            veto_destination: None,
+                            funding_period: None,
                         }),
                         open_proposal_submission: false,
+                        min_voting_power: None,
+                        max_open_proposals: None,
+                        submission_cooldown: None,
+                        message_restrictions: None,
+                        templates: vec![],
                         extension: Empty::default(),
                     })
                     .unwrap(),
@@ -1133,6 +1192,9 @@ fn test_instantiate_with_zero_cw20_deposit() {
                 },
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
         }
     };
 
@@ -1168,7 +1230,10 @@ fn test_update_config() {
         config,
         Config {
             deposit_info: None,
-            open_proposal_submission: false
+            open_proposal_submission: false,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
         }
     );
 
@@ -1190,6 +1255,9 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         true,
     );
@@ -1201,9 +1269,16 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
+                staking_contract: None,
             }),
             open_proposal_submission: true,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
         }
     );
 
@@ -1214,6 +1289,7 @@ fn test_update_config() {
         DepositInfoResponse {
             deposit_info: None,
             proposer: Addr::unchecked("ekez"),
+            contributions: vec![],
         }
     );
 
@@ -1233,9 +1309,14 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
+                staking_contract: None,
             }),
             proposer: Addr::unchecked("ekez"),
+            contributions: vec![],
         }
     );
 
@@ -1306,6 +1387,9 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -1350,6 +1434,9 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -1419,3 +1506,101 @@ fn test_withdraw() {
     let balance = get_balance_native(&app, core_addr.as_str(), "ujuno");
     assert_eq!(balance, Uint128::new(30));
 }
+
+#[test]
+fn test_proposal_templates() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None, false);
+
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateTemplates {
+            to_add: vec![Template {
+                name: "pay".to_string(),
+                msgs: r#"[{"bank":{"send":{"to_address":"{{recipient}}","amount":[{"denom":"ujuno","amount":"{{amount}}"}]}}}]"#
+                    .to_string(),
+            }],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let pay = |recipient: &str, amount: &str| TemplateChoice {
+        description: format!("pay {}", recipient),
+        template: "pay".to_string(),
+        params: vec![
+            TemplateParam {
+                name: "recipient".to_string(),
+                value: recipient.to_string(),
+            },
+            TemplateParam {
+                name: "amount".to_string(),
+                value: amount.to_string(),
+            },
+        ],
+    };
+    let propose = ExecuteMsg::Propose {
+        msg: ProposeMessage::ProposeFromTemplate {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            choices: vec![pay("ekez", "10"), pay("keze", "20")],
+        },
+    };
+
+    // Each choice is checked against the message restrictions.
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateMessageRestrictions {
+            restrictions: Some(UncheckedMessageRestrictions {
+                allowed_executes: vec![],
+                bank_send_caps: coins(10, "ujuno"),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    let err: PreProposeError = app
+        .execute_contract(Addr::unchecked("ekez"), pre_propose.clone(), &propose, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::BankSendOverCap {
+            denom: "ujuno".to_string(),
+            cap: Uint128::new(10)
+        }
+    );
+
+    let propose = ExecuteMsg::Propose {
+        msg: ProposeMessage::ProposeFromTemplate {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            choices: vec![pay("ekez", "10"), pay("keze", "5")],
+        },
+    };
+    app.execute_contract(Addr::unchecked("ekez"), pre_propose, &propose, &[])
+        .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &cpm::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.choices[0].description, "pay ekez");
+    assert_eq!(
+        proposal.proposal.choices[1].msgs,
+        Some(vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: "keze".to_string(),
+            amount: coins(5, "ujuno"),
+        })])
+    );
+}
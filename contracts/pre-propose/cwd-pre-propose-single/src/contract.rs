@@ -5,10 +5,12 @@ use cosmwasm_std::{
     Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
 };
 use cw2::set_contract_version;
+use cwd_voting::dependency::UncheckedProposalDependency;
 
 use cwd_pre_propose_base::{
     error::PreProposeError,
     msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
+    restrictions::{ProposalMessages, TemplateParam},
     state::PreProposeContract,
 };
 
@@ -21,6 +23,20 @@ pub enum ProposeMessage {
         title: String,
         description: String,
         msgs: Vec<CosmosMsg<Empty>>,
+        /// Proposals which must be executed before this proposal may
+        /// be executed.
+        #[serde(default)]
+        dependencies: Vec<UncheckedProposalDependency>,
+    },
+    /// Creates a proposal whose messages are the messages of the
+    /// module's template named TEMPLATE filled in with PARAMS.
+    ProposeFromTemplate {
+        title: String,
+        description: String,
+        template: String,
+        params: Vec<TemplateParam>,
+        #[serde(default)]
+        dependencies: Vec<UncheckedProposalDependency>,
     },
 }
 
@@ -38,11 +54,68 @@ enum ProposeMessageInternal {
         description: String,
         msgs: Vec<CosmosMsg<Empty>>,
         proposer: Option<String>,
+        // Omitted when empty so that proposals without dependencies
+        // may be created in proposal modules which do not support
+        // them.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        dependencies: Vec<UncheckedProposalDependency>,
+    },
+    UpdateProposal {
+        proposal_id: u64,
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+        proposer: Option<String>,
     },
 }
 
+impl ProposalMessages for ProposeMessageInternal {
+    fn cosmos_msgs(&self) -> Vec<Vec<&CosmosMsg>> {
+        match self {
+            ProposeMessageInternal::Propose { msgs, .. }
+            | ProposeMessageInternal::UpdateProposal { msgs, .. } => vec![msgs.iter().collect()],
+        }
+    }
+}
+
 type PrePropose = PreProposeContract<Empty, Empty, Empty, ProposeMessageInternal>;
 
+/// The contents of a proposal with its template, if any, filled in.
+struct Proposal {
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+    dependencies: Vec<UncheckedProposalDependency>,
+}
+
+fn fill_template(deps: Deps, msg: ProposeMessage) -> Result<Proposal, PreProposeError> {
+    Ok(match msg {
+        ProposeMessage::Propose {
+            title,
+            description,
+            msgs,
+            dependencies,
+        } => Proposal {
+            title,
+            description,
+            msgs,
+            dependencies,
+        },
+        ProposeMessage::ProposeFromTemplate {
+            title,
+            description,
+            template,
+            params,
+            dependencies,
+        } => Proposal {
+            title,
+            description,
+            msgs: PrePropose::default().render_template(deps.storage, &template, &params)?,
+            dependencies,
+        },
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
@@ -68,31 +141,95 @@ pub fn execute(
     // internal message which sets it.
     type ExecuteInternal = ExecuteBase<ProposeMessageInternal, Empty>;
     let internalized = match msg {
-        ExecuteMsg::Propose {
-            msg:
-                ProposeMessage::Propose {
+        ExecuteMsg::Propose { msg } => {
+            let Proposal {
+                title,
+                description,
+                msgs,
+                dependencies,
+            } = fill_template(deps.as_ref(), msg)?;
+            ExecuteInternal::Propose {
+                msg: ProposeMessageInternal::Propose {
+                    // Fill in proposer based on message sender.
+                    proposer: Some(info.sender.to_string()),
                     title,
                     description,
                     msgs,
+                    dependencies,
                 },
-        } => ExecuteInternal::Propose {
-            msg: ProposeMessageInternal::Propose {
-                // Fill in proposer based on message sender.
-                proposer: Some(info.sender.to_string()),
+            }
+        }
+        ExecuteMsg::ProposeDraft { msg, amount } => {
+            let Proposal {
                 title,
                 description,
                 msgs,
-            },
-        },
+                dependencies,
+            } = fill_template(deps.as_ref(), msg)?;
+            ExecuteInternal::ProposeDraft {
+                msg: ProposeMessageInternal::Propose {
+                    // The proposal is created on behalf of the draft's
+                    // creator once its deposit is funded.
+                    proposer: Some(info.sender.to_string()),
+                    title,
+                    description,
+                    msgs,
+                    dependencies,
+                },
+                amount,
+            }
+        }
+        ExecuteMsg::FundDraft { draft_id, amount } => {
+            ExecuteInternal::FundDraft { draft_id, amount }
+        }
+        ExecuteMsg::ExpireDraft { draft_id } => ExecuteInternal::ExpireDraft { draft_id },
+        ExecuteMsg::UpdateProposal { proposal_id, msg } => {
+            let Proposal {
+                title,
+                description,
+                msgs,
+                dependencies,
+            } = fill_template(deps.as_ref(), msg)?;
+            // Dependencies are set when a proposal is created and may
+            // not be updated.
+            if !dependencies.is_empty() {
+                return Err(PreProposeError::InvalidUpdate {});
+            }
+            ExecuteInternal::UpdateProposal {
+                proposal_id,
+                msg: ProposeMessageInternal::UpdateProposal {
+                    proposal_id,
+                    proposer: Some(info.sender.to_string()),
+                    title,
+                    description,
+                    msgs,
+                },
+            }
+        }
+        ExecuteMsg::WithdrawProposal { proposal_id } => {
+            ExecuteInternal::WithdrawProposal { proposal_id }
+        }
         ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
         ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
         ExecuteMsg::UpdateConfig {
             deposit_info,
             open_proposal_submission,
+            min_voting_power,
+            max_open_proposals,
+            submission_cooldown,
         } => ExecuteInternal::UpdateConfig {
             deposit_info,
             open_proposal_submission,
+            min_voting_power,
+            max_open_proposals,
+            submission_cooldown,
         },
+        ExecuteMsg::UpdateMessageRestrictions { restrictions } => {
+            ExecuteInternal::UpdateMessageRestrictions { restrictions }
+        }
+        ExecuteMsg::UpdateTemplates { to_add, to_remove } => {
+            ExecuteInternal::UpdateTemplates { to_add, to_remove }
+        }
         ExecuteMsg::ProposalCreatedHook {
             proposal_id,
             proposer,
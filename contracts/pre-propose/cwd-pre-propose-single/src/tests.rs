@@ -1,17 +1,29 @@
-use cosmwasm_std::{coins, from_slice, to_binary, Addr, Coin, Empty, Uint128};
+use cosmwasm_std::{
+    coins, from_slice, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Empty, Uint128, WasmMsg,
+};
 use cps::query::ProposalResponse;
 use cw2::ContractVersion;
 use cw20::Cw20Coin;
 use cw_denom::UncheckedDenom;
 use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use cwd_core::state::ProposalModule;
 use cwd_interface::{Admin, ModuleInstantiateInfo};
-use cwd_pre_propose_base::{error::PreProposeError, msg::DepositInfoResponse, state::Config};
+use cwd_pre_propose_base::{
+    error::PreProposeError,
+    msg::DepositInfoResponse,
+    restrictions::{
+        MessageRestrictions, Template, TemplateParam, UncheckedAllowedExecute,
+        UncheckedMessageRestrictions,
+    },
+    state::Config,
+};
 use cwd_proposal_single as cps;
 use cwd_testing::helpers::instantiate_with_cw4_groups_governance;
 use cwd_voting::{
-    deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    deposit::{
+        CheckedDepositInfo, DepositError, DepositRefundPolicy, DepositToken, UncheckedDepositInfo,
+    },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     status::Status,
     threshold::{PercentageThreshold, Threshold},
@@ -66,6 +78,11 @@ fn get_default_proposal_module_instantiate(
                 msg: to_binary(&InstantiateMsg {
                     deposit_info,
                     open_proposal_submission,
+                    min_voting_power: None,
+                    max_open_proposals: None,
+                    submission_cooldown: None,
+                    message_restrictions: None,
+                    templates: vec![],
                     extension: Empty::default(),
                 })
                 .unwrap(),
@@ -74,6 +91,14 @@ fn get_default_proposal_module_instantiate(
             },
         },
         close_proposal_on_execution_failure: false,
+        veto: None,
+        delegation_registry: None,
+        commit_reveal: None,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        voting_delay: None,
+        execution_window: None,
+        no_with_veto_threshold: None,
     }
 }
 
@@ -187,6 +212,7 @@ fn make_proposal(
                     title: "title".to_string(),
                     description: "description".to_string(),
                     msgs: vec![],
+                    dependencies: vec![],
                 },
             },
             funds,
@@ -263,6 +289,7 @@ fn vote(app: &mut App, module: Addr, sender: &str, id: u64, position: Vote) -> S
         &cps::msg::ExecuteMsg::Vote {
             proposal_id: id,
             vote: position,
+            rationale: None,
         },
         &[],
     )
@@ -313,6 +340,9 @@ fn update_config(
         &ExecuteMsg::UpdateConfig {
             deposit_info,
             open_proposal_submission,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
         },
         &[],
     )
@@ -334,6 +364,9 @@ fn update_config_should_fail(
         &ExecuteMsg::UpdateConfig {
             deposit_info,
             open_proposal_submission,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
         },
         &[],
     )
@@ -417,6 +450,9 @@ fn test_native_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -481,6 +517,9 @@ fn test_cw20_permutation(
             },
             amount: Uint128::new(10),
             refund_policy,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -650,6 +689,9 @@ fn test_multiple_open_proposals() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -716,6 +758,970 @@ fn test_multiple_open_proposals() {
     assert_eq!(20, balance.u128());
 }
 
+#[test]
+fn test_withdraw_proposal_refund_policy() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+        }),
+        false,
+    );
+
+    mint_natives(&mut app, "ekez", coins(20, "ujuno"));
+    let id = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &coins(10, "ujuno"),
+    );
+
+    // Without a withdrawal refund policy, withdrawn proposals are
+    // refunded like closed ones, so the DAO receives the deposit.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose.clone(),
+        &ExecuteMsg::WithdrawProposal { proposal_id: id },
+        &[],
+    )
+    .unwrap();
+    let balance = get_balance_native(&app, "ekez", "ujuno");
+    assert_eq!(10, balance.u128());
+    let balance = get_balance_native(&app, core_addr.as_str(), "ujuno");
+    assert_eq!(10, balance.u128());
+
+    update_config(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: Some(DepositRefundPolicy::Always),
+            veto_destination: None,
+            funding_period: None,
+        }),
+        false,
+    );
+    let id = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &coins(10, "ujuno"),
+    );
+
+    // Only the proposer may withdraw the proposal.
+    let err: cps::ContractError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            pre_propose.clone(),
+            &ExecuteMsg::WithdrawProposal { proposal_id: id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, cps::ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose,
+        &ExecuteMsg::WithdrawProposal { proposal_id: id },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &cps::msg::QueryMsg::Proposal { proposal_id: id },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Withdrawn);
+    let balance = get_balance_native(&app, "ekez", "ujuno");
+    assert_eq!(10, balance.u128());
+}
+
+#[test]
+fn test_crowdfunded_deposit() {
+    let mut app = App::default();
+
+    let deposit_info = UncheckedDepositInfo {
+        denom: DepositToken::Token {
+            denom: UncheckedDenom::Native("ujuno".to_string()),
+        },
+        amount: Uint128::new(10),
+        refund_policy: DepositRefundPolicy::Always,
+        withdrawal_refund_policy: None,
+        veto_destination: None,
+        funding_period: Some(Duration::Time(100)),
+    };
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, Some(deposit_info.clone()), false);
+
+    let propose_draft = |amount: u128| ExecuteMsg::ProposeDraft {
+        msg: ProposeMessage::Propose {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs: vec![],
+            dependencies: vec![],
+        },
+        amount: Uint128::new(amount),
+    };
+    mint_natives(&mut app, "ekez", coins(4, "ujuno"));
+    mint_natives(&mut app, "alice", coins(6, "ujuno"));
+    mint_natives(&mut app, "keze", coins(3, "ujuno"));
+
+    // Only members may create drafts, but anyone may fund them.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            pre_propose.clone(),
+            &propose_draft(6),
+            &coins(6, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotMember {});
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose.clone(),
+        &propose_draft(4),
+        &coins(4, "ujuno"),
+    )
+    .unwrap();
+    let draft: cwd_pre_propose_base::msg::DraftResponse = app
+        .wrap()
+        .query_wasm_smart(pre_propose.clone(), &QueryMsg::Draft { draft_id: 1 })
+        .unwrap();
+    assert_eq!(draft.draft.proposer, Addr::unchecked("ekez"));
+    assert_eq!(draft.draft.funded, Uint128::new(4));
+    assert_eq!(draft.contributions.len(), 1);
+
+    let fund = |amount: u128| ExecuteMsg::FundDraft {
+        draft_id: 1,
+        amount: Uint128::new(amount),
+    };
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            pre_propose.clone(),
+            &fund(7),
+            &coins(7, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::Overfunded {
+            remaining: Uint128::new(6)
+        }
+    );
+    let err: PreProposeError = app
+        .execute_contract(Addr::unchecked("alice"), pre_propose.clone(), &fund(0), &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::ZeroContribution {});
+
+    // Fully funding the deposit creates the proposal on behalf of
+    // the draft's creator.
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        pre_propose.clone(),
+        &fund(6),
+        &coins(6, "ujuno"),
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &cps::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("ekez"));
+    let deposit = get_deposit_info(&app, pre_propose.clone(), 1);
+    assert_eq!(
+        deposit.contributions,
+        vec![
+            cwd_pre_propose_base::msg::Contribution {
+                contributor: Addr::unchecked("alice"),
+                amount: Uint128::new(6),
+            },
+            cwd_pre_propose_base::msg::Contribution {
+                contributor: Addr::unchecked("ekez"),
+                amount: Uint128::new(4),
+            },
+        ]
+    );
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            pre_propose.clone(),
+            &fund(1),
+            &coins(1, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NoSuchDraft { id: 1 });
+
+    // Refunds are paid to each contributor.
+    let status = vote(&mut app, proposal_single.clone(), "ekez", 1, Vote::No);
+    assert_eq!(status, Status::Rejected);
+    close_proposal(&mut app, proposal_single, "keze", 1);
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno").u128(), 4);
+    assert_eq!(get_balance_native(&app, "alice", "ujuno").u128(), 6);
+
+    // Drafts which are not funded in time expire and are refunded.
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        pre_propose.clone(),
+        &propose_draft(3),
+        &coins(3, "ujuno"),
+    )
+    .unwrap();
+    let expire = ExecuteMsg::ExpireDraft { draft_id: 2 };
+    let err: PreProposeError = app
+        .execute_contract(Addr::unchecked("alice"), pre_propose.clone(), &expire, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::DraftNotExpired { id: 2 });
+
+    app.update_block(|b| b.time = b.time.plus_seconds(100));
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            pre_propose.clone(),
+            &ExecuteMsg::FundDraft {
+                draft_id: 2,
+                amount: Uint128::new(1),
+            },
+            &coins(1, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::DraftExpired { id: 2 });
+
+    app.execute_contract(Addr::unchecked("alice"), pre_propose.clone(), &expire, &[])
+        .unwrap();
+    assert_eq!(get_balance_native(&app, "keze", "ujuno").u128(), 3);
+    assert_eq!(
+        get_balance_native(&app, pre_propose.as_str(), "ujuno").u128(),
+        0
+    );
+
+    // Drafts may only be created if the deposit has a funding
+    // period.
+    update_config(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        Some(UncheckedDepositInfo {
+            funding_period: None,
+            ..deposit_info
+        }),
+        false,
+    );
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            pre_propose,
+            &propose_draft(3),
+            &coins(3, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::DraftsDisabled {});
+}
+
+fn propose_msgs(
+    app: &mut App,
+    pre_propose: Addr,
+    msgs: Vec<CosmosMsg>,
+) -> Result<(), PreProposeError> {
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose,
+        &ExecuteMsg::Propose {
+            msg: ProposeMessage::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs,
+                dependencies: vec![],
+            },
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn update_message_restrictions(
+    app: &mut App,
+    pre_propose: Addr,
+    sender: &str,
+    restrictions: Option<UncheckedMessageRestrictions>,
+) -> Result<(), PreProposeError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        pre_propose,
+        &ExecuteMsg::UpdateMessageRestrictions { restrictions },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+#[test]
+fn test_message_restrictions() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None, false);
+
+    let restrictions = UncheckedMessageRestrictions {
+        allowed_executes: vec![UncheckedAllowedExecute {
+            contract: "treasury".to_string(),
+            variant: "transfer".to_string(),
+        }],
+        bank_send_caps: coins(100, "ujuno"),
+    };
+    let err = update_message_restrictions(
+        &mut app,
+        pre_propose.clone(),
+        "ekez",
+        Some(restrictions.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::NotDao {});
+    update_message_restrictions(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        Some(restrictions),
+    )
+    .unwrap();
+
+    let execute = |msg: &str, funds: Vec<Coin>| -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: "treasury".to_string(),
+            msg: Binary::from(msg.as_bytes()),
+            funds,
+        }
+        .into()
+    };
+    let send = |amount: Vec<Coin>| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: "ekez".to_string(),
+            amount,
+        }
+        .into()
+    };
+
+    propose_msgs(
+        &mut app,
+        pre_propose.clone(),
+        vec![
+            execute(r#"{"transfer":{"amount":"1"}}"#, vec![]),
+            send(coins(100, "ujuno")),
+        ],
+    )
+    .unwrap();
+
+    // Caps apply to the total sent by all of a proposal's messages.
+    // Sending exactly the cap is allowed.
+    propose_msgs(
+        &mut app,
+        pre_propose.clone(),
+        vec![
+            send(coins(50, "ujuno")),
+            execute(r#"{"transfer":{"amount":"1"}}"#, coins(25, "ujuno")),
+            send(coins(25, "ujuno")),
+        ],
+    )
+    .unwrap();
+    let err = propose_msgs(
+        &mut app,
+        pre_propose.clone(),
+        vec![
+            send(coins(50, "ujuno")),
+            execute(r#"{"transfer":{"amount":"1"}}"#, coins(25, "ujuno")),
+            send(coins(26, "ujuno")),
+        ],
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::BankSendOverCap {
+            denom: "ujuno".to_string(),
+            cap: Uint128::new(100)
+        }
+    );
+
+    let err = propose_msgs(
+        &mut app,
+        pre_propose.clone(),
+        vec![send(vec![]), execute(r#"{"burn":{}}"#, vec![])],
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::MessageNotAllowed { index: 1 });
+
+    let err = propose_msgs(
+        &mut app,
+        pre_propose.clone(),
+        vec![execute(
+            r#"{"transfer":{"amount":"1"}}"#,
+            coins(101, "ujuno"),
+        )],
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::BankSendOverCap {
+            denom: "ujuno".to_string(),
+            cap: Uint128::new(100)
+        }
+    );
+
+    let err =
+        propose_msgs(&mut app, pre_propose.clone(), vec![send(coins(1, "uatom"))]).unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::BankSendOverCap {
+            denom: "uatom".to_string(),
+            cap: Uint128::zero()
+        }
+    );
+
+    let migrate: CosmosMsg = WasmMsg::Migrate {
+        contract_addr: "treasury".to_string(),
+        new_code_id: 1,
+        msg: to_binary(&Empty::default()).unwrap(),
+    }
+    .into();
+    let err = propose_msgs(&mut app, pre_propose.clone(), vec![migrate.clone()]).unwrap_err();
+    assert_eq!(err, PreProposeError::AdminMessage { index: 0 });
+
+    // Restrictions also apply when proposals are updated.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::UpdateProposal {
+                proposal_id: 1,
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![migrate.clone()],
+                    dependencies: vec![],
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::AdminMessage { index: 0 });
+
+    update_message_restrictions(&mut app, pre_propose.clone(), core_addr.as_str(), None).unwrap();
+    let restrictions: Option<MessageRestrictions> = app
+        .wrap()
+        .query_wasm_smart(pre_propose.clone(), &QueryMsg::MessageRestrictions {})
+        .unwrap();
+    assert_eq!(restrictions, None);
+    propose_msgs(&mut app, pre_propose, vec![migrate]).unwrap();
+
+    let count: u64 = app
+        .wrap()
+        .query_wasm_smart(proposal_single, &cps::msg::QueryMsg::ProposalCount {})
+        .unwrap();
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn test_proposal_templates() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None, false);
+
+    let template = Template {
+        name: "pay".to_string(),
+        msgs: r#"[{"bank":{"send":{"to_address":"{{recipient}}","amount":[{"denom":"ujuno","amount":"{{amount}}"}]}}}]"#
+            .to_string(),
+    };
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateTemplates {
+            to_add: vec![template.clone()],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let templates: Vec<Template> = app
+        .wrap()
+        .query_wasm_smart(pre_propose.clone(), &QueryMsg::Templates {})
+        .unwrap();
+    assert_eq!(templates, vec![template]);
+
+    let propose_from_template = |template: &str, params: &[(&str, &str)]| ExecuteMsg::Propose {
+        msg: ProposeMessage::ProposeFromTemplate {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            template: template.to_string(),
+            params: params
+                .iter()
+                .map(|(name, value)| TemplateParam {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                })
+                .collect(),
+            dependencies: vec![],
+        },
+    };
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose.clone(),
+        &propose_from_template("pay", &[("recipient", "keze"), ("amount", "10")]),
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &cps::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("ekez"));
+    assert_eq!(
+        proposal.proposal.msgs,
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: "keze".to_string(),
+            amount: coins(10, "ujuno"),
+        })]
+    );
+
+    let propose_err = |app: &mut App, msg: ExecuteMsg| -> PreProposeError {
+        app.execute_contract(Addr::unchecked("ekez"), pre_propose.clone(), &msg, &[])
+            .unwrap_err()
+            .downcast()
+            .unwrap()
+    };
+
+    let err = propose_err(
+        &mut app,
+        propose_from_template("pay", &[("recipient", "keze")]),
+    );
+    assert_eq!(
+        err,
+        PreProposeError::MissingTemplateParam {
+            name: "amount".to_string()
+        }
+    );
+
+    // Parameter values may not change the shape of the messages.
+    let err = propose_err(
+        &mut app,
+        propose_from_template(
+            "pay",
+            &[("recipient", "keze"), ("amount", r#"10"}],"x":""#)],
+        ),
+    );
+    assert_eq!(
+        err,
+        PreProposeError::InvalidTemplate {
+            name: "pay".to_string()
+        }
+    );
+
+    // Templated proposals are subject to the message restrictions.
+    update_message_restrictions(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        Some(UncheckedMessageRestrictions {
+            allowed_executes: vec![],
+            bank_send_caps: coins(5, "ujuno"),
+        }),
+    )
+    .unwrap();
+    let err = propose_err(
+        &mut app,
+        propose_from_template("pay", &[("recipient", "keze"), ("amount", "10")]),
+    );
+    assert_eq!(
+        err,
+        PreProposeError::BankSendOverCap {
+            denom: "ujuno".to_string(),
+            cap: Uint128::new(5)
+        }
+    );
+
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateTemplates {
+            to_add: vec![],
+            to_remove: vec!["pay".to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+    let err = propose_err(
+        &mut app,
+        propose_from_template("pay", &[("recipient", "keze"), ("amount", "1")]),
+    );
+    assert_eq!(
+        err,
+        PreProposeError::NoSuchTemplate {
+            name: "pay".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_submission_limits() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None, false);
+
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateConfig {
+            deposit_info: None,
+            open_proposal_submission: true,
+            min_voting_power: Some(Uint128::new(9)),
+            max_open_proposals: Some(2),
+            submission_cooldown: Some(Duration::Time(60)),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let propose = |app: &mut App, proposer: &str| -> PreProposeError {
+        app.execute_contract(
+            Addr::unchecked(proposer),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    dependencies: vec![],
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+    };
+
+    // A minimum voting power applies even when submission is open.
+    let err = propose(&mut app, "outsider");
+    assert_eq!(
+        err,
+        PreProposeError::InsufficientVotingPower {
+            min: Uint128::new(9),
+            power: Uint128::zero()
+        }
+    );
+    let err = propose(&mut app, "keze");
+    assert_eq!(
+        err,
+        PreProposeError::InsufficientVotingPower {
+            min: Uint128::new(9),
+            power: Uint128::new(8)
+        }
+    );
+
+    let first = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &[],
+    );
+    let err = propose(&mut app, "ekez");
+    assert_eq!(
+        err,
+        PreProposeError::SubmissionCooldown {
+            expiration: Expiration::AtTime(app.block_info().time.plus_seconds(60))
+        }
+    );
+
+    app.update_block(|b| b.time = b.time.plus_seconds(60));
+    make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &[],
+    );
+    app.update_block(|b| b.time = b.time.plus_seconds(60));
+    let err = propose(&mut app, "ekez");
+    assert_eq!(err, PreProposeError::TooManyOpenProposals { max: 2 });
+
+    // Proposals which are no longer open make room for another, even
+    // if they are never closed.
+    let status = vote(&mut app, proposal_single.clone(), "ekez", first, Vote::No);
+    assert_eq!(status, Status::Rejected);
+    make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &[],
+    );
+    app.update_block(|b| b.time = b.time.plus_seconds(60));
+    let err = propose(&mut app, "ekez");
+    assert_eq!(err, PreProposeError::TooManyOpenProposals { max: 2 });
+
+    // Closing a proposal which no longer counts has no effect.
+    close_proposal(&mut app, proposal_single, "ekez", first);
+    let err = propose(&mut app, "ekez");
+    assert_eq!(err, PreProposeError::TooManyOpenProposals { max: 2 });
+}
+
+#[test]
+fn test_staked_deposit() {
+    let mut app = App::default();
+
+    let staking_id = app.store_code(Box::new(ContractWrapper::new(
+        cwd_voting_native_staked::contract::execute,
+        cwd_voting_native_staked::contract::instantiate,
+        cwd_voting_native_staked::contract::query,
+    )));
+    let staking = app
+        .instantiate_contract(
+            staking_id,
+            Addr::unchecked("owner"),
+            &cwd_voting_native_staked::msg::InstantiateMsg {
+                owner: Some(Admin::Address {
+                    addr: "owner".to_string(),
+                }),
+                manager: None,
+                denom: "ujuno".to_string(),
+                unstaking_duration: None,
+            },
+            &[],
+            "native staking",
+            None,
+        )
+        .unwrap();
+    mint_natives(&mut app, "ekez", coins(200, "ujuno"));
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        staking.clone(),
+        &cwd_voting_native_staked::msg::ExecuteMsg::Stake {},
+        &coins(200, "ujuno"),
+    )
+    .unwrap();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::StakedToken {
+                staking_contract: staking.to_string(),
+            },
+            amount: Uint128::new(60),
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+        }),
+        false,
+    );
+    let deposit_info = get_config(&app, pre_propose.clone()).deposit_info.unwrap();
+    assert_eq!(
+        deposit_info.denom,
+        cw_denom::CheckedDenom::Native("ujuno".to_string())
+    );
+    assert_eq!(deposit_info.staking_contract, Some(staking.clone()));
+
+    // The staking contract must register the module before it may
+    // lock stake.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose.clone(),
+        &ExecuteMsg::Propose {
+            msg: ProposeMessage::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                dependencies: vec![],
+            },
+        },
+        &[],
+    )
+    .unwrap_err();
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        staking.clone(),
+        &cwd_voting_native_staked::msg::ExecuteMsg::AddLocker {
+            addr: pre_propose.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Staked deposits are locked, not paid.
+    mint_natives(&mut app, "ekez", coins(60, "ujuno"));
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    dependencies: vec![],
+                },
+            },
+            &coins(60, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::Deposit(DepositError::Payment(cw_utils::PaymentError::NonPayable {}))
+    );
+
+    let passed = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &[],
+    );
+    let rejected = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &[],
+    );
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(60));
+
+    // Locked stake can't be unstaked or locked for another proposal.
+    let unstake = |app: &mut App, amount: u128| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            staking.clone(),
+            &cwd_voting_native_staked::msg::ExecuteMsg::Unstake {
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )
+    };
+    let err: cwd_voting_native_staked::ContractError =
+        unstake(&mut app, 81).unwrap_err().downcast().unwrap();
+    assert_eq!(
+        err.to_string(),
+        cwd_voting_native_staked::ContractError::StakeLocked {
+            locked: Uint128::new(120)
+        }
+        .to_string()
+    );
+    unstake(&mut app, 40).unwrap();
+    let err: cwd_voting_native_staked::ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    dependencies: vec![],
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        cwd_voting_native_staked::ContractError::InsufficientUnlockedStake {
+            unlocked: Uint128::new(40)
+        }
+        .to_string()
+    );
+
+    // Passed proposals unlock their deposit.
+    let status = vote(&mut app, proposal_single.clone(), "ekez", passed, Vote::Yes);
+    assert_eq!(status, Status::Passed);
+    execute_proposal(&mut app, proposal_single.clone(), "ekez", passed);
+
+    // Under the `OnlyPassed` policy, failed proposals have their
+    // deposit slashed and sent to the DAO.
+    let status = vote(
+        &mut app,
+        proposal_single.clone(),
+        "ekez",
+        rejected,
+        Vote::No,
+    );
+    assert_eq!(status, Status::Rejected);
+    close_proposal(&mut app, proposal_single, "ekez", rejected);
+    assert_eq!(
+        get_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(60)
+    );
+
+    // Nothing remains locked.
+    unstake(&mut app, 100).unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(200));
+}
+
 #[test]
 fn test_set_version() {
     let mut app = App::default();
@@ -732,6 +1738,9 @@ fn test_set_version() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -768,6 +1777,9 @@ fn test_permissions() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false, // no open proposal submission.
     );
@@ -813,6 +1825,7 @@ fn test_permissions() {
                     title: "I would like to join the DAO".to_string(),
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
+                    dependencies: vec![],
                 },
             },
             &[],
@@ -838,6 +1851,9 @@ fn test_propose_open_proposal_submission() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         true, // yes, open proposal submission.
     );
@@ -901,6 +1917,7 @@ fn test_no_deposit_required_members_submission() {
                     title: "I would like to join the DAO".to_string(),
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
+                    dependencies: vec![],
                 },
             },
             &[],
@@ -975,8 +1992,16 @@ fn test_instantiate_with_zero_native_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            withdrawal_refund_policy: None,
+                            veto_destination: None,
+                            funding_period: None,
                         }),
                         open_proposal_submission: false,
+                        min_voting_power: None,
+                        max_open_proposals: None,
+                        submission_cooldown: None,
+                        message_restrictions: None,
+                        templates: vec![],
                         extension: Empty::default(),
                     })
                     .unwrap(),
@@ -985,6 +2010,14 @@ fn test_instantiate_with_zero_native_deposit() {
                 },
             },
             close_proposal_on_execution_failure: false,
+            veto: None,
+            delegation_registry: None,
+            commit_reveal: None,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            voting_delay: None,
+            execution_window: None,
+            no_with_veto_threshold: None,
         }
     };
 
@@ -1036,8 +2069,16 @@ fn test_instantiate_with_zero_cw20_deposit() {
                             },
                             amount: Uint128::zero(),
                             refund_policy: DepositRefundPolicy::OnlyPassed,
+                            withdrawal_refund_policy: None,
+                            veto_destination: None,
+                            funding_period: None,
                         }),
                         open_proposal_submission: false,
+                        min_voting_power: None,
+                        max_open_proposals: None,
+                        submission_cooldown: None,
+                        message_restrictions: None,
+                        templates: vec![],
                         extension: Empty::default(),
                     })
                     .unwrap(),
@@ -1046,6 +2087,14 @@ fn test_instantiate_with_zero_cw20_deposit() {
                 },
             },
             close_proposal_on_execution_failure: false,
+            veto: None,
+            delegation_registry: None,
+            commit_reveal: None,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            voting_delay: None,
+            execution_window: None,
+            no_with_veto_threshold: None,
         }
     };
 
@@ -1081,7 +2130,10 @@ fn test_update_config() {
         config,
         Config {
             deposit_info: None,
-            open_proposal_submission: false
+            open_proposal_submission: false,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
         }
     );
 
@@ -1103,6 +2155,9 @@ fn test_update_config() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Never,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         true,
     );
@@ -1114,9 +2169,16 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
+                staking_contract: None,
             }),
             open_proposal_submission: true,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
         }
     );
 
@@ -1127,6 +2189,7 @@ fn test_update_config() {
         DepositInfoResponse {
             deposit_info: None,
             proposer: Addr::unchecked("ekez"),
+            contributions: vec![],
         }
     );
 
@@ -1146,9 +2209,14 @@ fn test_update_config() {
             deposit_info: Some(CheckedDepositInfo {
                 denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
                 amount: Uint128::new(10),
-                refund_policy: DepositRefundPolicy::Never
+                refund_policy: DepositRefundPolicy::Never,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
+                staking_contract: None,
             }),
             proposer: Addr::unchecked("ekez"),
+            contributions: vec![],
         }
     );
 
@@ -1207,6 +2275,9 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -1251,6 +2322,9 @@ fn test_withdraw() {
             },
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -0,0 +1,783 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Reply, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_denom::UncheckedDenom;
+use cw_storage_plus::Bound;
+use cw_utils::parse_reply_instantiate_data;
+use cwd_hooks::Hooks;
+use cwd_interface::voting::IsActiveResponse;
+use cwd_pre_propose_single::contract::ExecuteMsg as PreProposeMsg;
+use cwd_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
+use cwd_vote_hooks::new_vote_hooks;
+use cwd_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use cwd_voting::proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
+use cwd_voting::reply::{
+    failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
+};
+use cwd_voting::status::Status;
+use cwd_voting::voting::get_voting_power;
+
+use crate::msg::MigrateMsg;
+use crate::proposal::{advance_proposal_id, requested_amount, ConvictionProposal};
+use crate::state::{Config, ALLOCATIONS, CREATION_POLICY, SUPPORT};
+use crate::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
+    state::{CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-proposal-conviction";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    validate_conviction_params(msg.decay, msg.max_ratio, msg.weight)?;
+
+    let dao = info.sender;
+
+    let (initial_policy, pre_propose_messages) = msg
+        .pre_propose_info
+        .into_initial_policy_and_messages(dao.clone())?;
+
+    let config = Config {
+        funding_denom: msg.funding_denom.into_checked(deps.as_ref())?,
+        decay: msg.decay,
+        max_ratio: msg.max_ratio,
+        weight: msg.weight,
+        dao: dao.clone(),
+        close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+    };
+
+    // Initialize proposal count to zero so that queries return zero
+    // instead of None.
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    CONFIG.save(deps.storage, &config)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(pre_propose_messages)
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", dao))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose {
+            title,
+            description,
+            msgs,
+            proposer,
+        } => execute_propose(deps, env, info.sender, title, description, msgs, proposer),
+        ExecuteMsg::Support {
+            proposal_id,
+            amount,
+        } => execute_support(deps, env, info, proposal_id, amount),
+        ExecuteMsg::RefreshSupport { voter } => execute_refresh_support(deps, env, voter),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, info, proposal_id),
+        ExecuteMsg::UpdateConfig {
+            funding_denom,
+            decay,
+            max_ratio,
+            weight,
+            close_proposal_on_execution_failure,
+        } => execute_update_config(
+            deps,
+            info,
+            funding_denom,
+            decay,
+            max_ratio,
+            weight,
+            close_proposal_on_execution_failure,
+        ),
+        ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
+            execute_update_proposal_creation_policy(deps, info, new_info)
+        }
+        ExecuteMsg::AddProposalHook { address } => {
+            execute_add_hook(deps, info, PROPOSAL_HOOKS, "add_proposal_hook", address)
+        }
+        ExecuteMsg::RemoveProposalHook { address } => {
+            execute_remove_hook(deps, info, PROPOSAL_HOOKS, "remove_proposal_hook", address)
+        }
+        ExecuteMsg::AddVoteHook { address } => {
+            execute_add_hook(deps, info, VOTE_HOOKS, "add_vote_hook", address)
+        }
+        ExecuteMsg::RemoveVoteHook { address } => {
+            execute_remove_hook(deps, info, VOTE_HOOKS, "remove_vote_hook", address)
+        }
+    }
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+    proposer: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+
+    // Check that the sender is permitted to create proposals.
+    if !proposal_creation_policy.is_permitted(&sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Determine the appropriate proposer. If this is coming from our
+    // pre-propose module, it must be specified. Otherwise, the
+    // proposer should not be specified.
+    let proposer = match (proposer, &proposal_creation_policy) {
+        (None, ProposalCreationPolicy::Anyone {}) => sender.clone(),
+        // `is_permitted` above checks that an allowed module is
+        // actually sending the propose message.
+        (Some(proposer), ProposalCreationPolicy::Module { .. }) => {
+            deps.api.addr_validate(&proposer)?
+        }
+        _ => return Err(ContractError::InvalidProposer {}),
+    };
+
+    let voting_module: Addr = deps.querier.query_wasm_smart(
+        config.dao.clone(),
+        &cwd_core::msg::QueryMsg::VotingModule {},
+    )?;
+
+    // Voting modules are not required to implement this
+    // query. Lacking an implementation they are active by default.
+    let active_resp: IsActiveResponse = deps
+        .querier
+        .query_wasm_smart(voting_module, &cwd_interface::voting::Query::IsActive {})
+        .unwrap_or(IsActiveResponse { active: true });
+
+    if !active_resp.active {
+        return Err(ContractError::InactiveDao {});
+    }
+
+    let proposal = ConvictionProposal {
+        title,
+        description,
+        proposer: proposer.clone(),
+        start_height: env.block.height,
+        requested_amount: requested_amount(&msgs, &config.funding_denom)?,
+        msgs,
+        status: Status::Open,
+        support: Uint128::zero(),
+        conviction: Uint128::zero(),
+        last_updated: env.block.height,
+    };
+    let id = advance_proposal_id(deps.storage)?;
+
+    // Limit the size of proposals so that they may always be queried.
+    // See `cwd-proposal-single` for how this limit was chosen.
+    let proposal_size = cosmwasm_std::to_vec(&proposal)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+
+    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
+
+    // Add prepropose / deposit module hook which will save deposit info. This
+    // needs to be called after execute_propose because we don't know the
+    // proposal ID beforehand.
+    let hooks = match proposal_creation_policy {
+        ProposalCreationPolicy::Anyone {} => hooks,
+        ProposalCreationPolicy::Module { addr } => {
+            let msg = to_binary(&PreProposeMsg::ProposalCreatedHook {
+                proposal_id: id,
+                proposer: proposer.into_string(),
+            })?;
+            let mut hooks = hooks;
+            hooks.push(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: addr.into_string(),
+                    msg,
+                    funds: vec![],
+                },
+                failed_pre_propose_module_hook_id(),
+            ));
+            hooks
+        }
+    };
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "propose")
+        .add_attribute("sender", sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("requested_amount", proposal.requested_amount)
+        .add_attribute("status", proposal.status.to_string()))
+}
+
+pub fn execute_support(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let current = SUPPORT
+        .may_load(deps.storage, (proposal_id, info.sender.clone()))?
+        .unwrap_or_default();
+    if current == amount {
+        return Err(ContractError::SupportUnchanged { amount });
+    }
+
+    // Support for a proposal is limited by the voting power the
+    // sender had when it was created so that tokens transferred
+    // afterwards may not support it again from another address.
+    let power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        config.dao.clone(),
+        Some(prop.start_height),
+    )?;
+    if amount > power {
+        return Err(ContractError::InsufficientVotingPower {
+            allocated: amount,
+            power,
+        });
+    }
+
+    let allocated = release_completed_support(deps.storage, &info.sender)? - current + amount;
+    let power = get_voting_power(deps.as_ref(), info.sender.clone(), config.dao, None)?;
+    if allocated > power {
+        return Err(ContractError::InsufficientVotingPower { allocated, power });
+    }
+
+    let support = prop.support - current + amount;
+    prop.update_support(env.block.height, config.decay, support)?;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    save_support(deps.storage, proposal_id, &info.sender, amount)?;
+
+    let vote_hooks = new_vote_hooks(
+        VOTE_HOOKS,
+        deps.storage,
+        proposal_id,
+        info.sender.to_string(),
+        amount.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(vote_hooks)
+        .add_attribute("action", "support")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("support", prop.support))
+}
+
+pub fn execute_refresh_support(
+    deps: DepsMut,
+    env: Env,
+    voter: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let voter = deps.api.addr_validate(&voter)?;
+
+    let allocated = release_completed_support(deps.storage, &voter)?;
+    let power = get_voting_power(deps.as_ref(), voter.clone(), config.dao, None)?;
+    if allocated <= power {
+        return Err(ContractError::NothingToRefresh {});
+    }
+
+    let allocations = ALLOCATIONS
+        .prefix(voter.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (proposal_id, current) in allocations {
+        let amount = current.multiply_ratio(power, allocated);
+        let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+        let support = prop.support - current + amount;
+        prop.update_support(env.block.height, config.decay, support)?;
+        PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+        save_support(deps.storage, proposal_id, &voter, amount)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "refresh_support")
+        .add_attribute("voter", voter)
+        .add_attribute("allocated", allocated)
+        .add_attribute("power", power))
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // Passing is checked against the current treasury balance so a
+    // proposal that passed may stop passing if the treasury shrinks.
+    if prop.current_status(deps.as_ref(), &env.block, &config)? != Status::Passed {
+        return Err(ContractError::NotPassed {});
+    }
+
+    let old_status = prop.status;
+    prop.conviction = prop.conviction_at(env.block.height, config.decay)?;
+    prop.last_updated = env.block.height;
+    prop.status = Status::Executed;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let response = {
+        if !prop.msgs.is_empty() {
+            let execute_message = WasmMsg::Execute {
+                contract_addr: config.dao.to_string(),
+                msg: to_binary(&cwd_core::msg::ExecuteMsg::ExecuteProposalHook {
+                    msgs: prop.msgs,
+                    proposal_id: Some(proposal_id),
+                })?,
+                funds: vec![],
+            };
+            match config.close_proposal_on_execution_failure {
+                true => {
+                    let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
+                    Response::default()
+                        .add_submessage(SubMsg::reply_on_error(execute_message, masked_proposal_id))
+                }
+                false => Response::default().add_message(execute_message),
+            }
+        } else {
+            Response::default()
+        }
+    };
+
+    let hooks = completed_hooks(deps.storage, proposal_id, old_status, prop.status)?;
+
+    Ok(response
+        .add_submessages(hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", config.dao))
+}
+
+pub fn execute_close(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // Conviction proposals never expire so they are closed by their
+    // proposer or the DAO instead.
+    if info.sender != prop.proposer && info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let old_status = prop.status;
+    prop.status = Status::Closed;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let hooks = completed_hooks(deps.storage, proposal_id, old_status, prop.status)?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    funding_denom: UncheckedDenom,
+    decay: Decimal,
+    max_ratio: Decimal,
+    weight: Decimal,
+    close_proposal_on_execution_failure: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_conviction_params(decay, max_ratio, weight)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            funding_denom: funding_denom.into_checked(deps.as_ref())?,
+            decay,
+            max_ratio,
+            weight,
+            dao: config.dao,
+            close_proposal_on_execution_failure,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_update_proposal_creation_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_info: PreProposeInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (initial_policy, messages) = new_info.into_initial_policy_and_messages(config.dao)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_attribute("action", "update_proposal_creation_policy")
+        .add_attribute("sender", info.sender)
+        .add_attribute("new_policy", format!("{initial_policy:?}")))
+}
+
+pub fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    hooks: Hooks,
+    action: &str,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+    hooks.add_hook(deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", action)
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    hooks: Hooks,
+    action: &str,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+    hooks.remove_hook(deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", action)
+        .add_attribute("address", address))
+}
+
+fn validate_conviction_params(
+    decay: Decimal,
+    max_ratio: Decimal,
+    weight: Decimal,
+) -> Result<(), ContractError> {
+    if decay.is_zero() || decay >= Decimal::one() {
+        return Err(ContractError::InvalidDecay {});
+    }
+    if max_ratio.is_zero() || max_ratio > Decimal::one() {
+        return Err(ContractError::InvalidMaxRatio {});
+    }
+    if weight.is_zero() {
+        return Err(ContractError::ZeroWeight {});
+    }
+    Ok(())
+}
+
+/// Removes VOTER's support for proposals that are no longer open and
+/// returns the total support they have allocated to open proposals.
+fn release_completed_support(storage: &mut dyn Storage, voter: &Addr) -> StdResult<Uint128> {
+    let allocations = ALLOCATIONS
+        .prefix(voter.clone())
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut allocated = Uint128::zero();
+    for (proposal_id, amount) in allocations {
+        if PROPOSALS.load(storage, proposal_id)?.status == Status::Open {
+            allocated += amount;
+        } else {
+            ALLOCATIONS.remove(storage, (voter.clone(), proposal_id));
+        }
+    }
+    Ok(allocated)
+}
+
+/// Records that VOTER supports PROPOSAL_ID with AMOUNT of their voting
+/// power, removing their support if AMOUNT is zero.
+fn save_support(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    voter: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    if amount.is_zero() {
+        SUPPORT.remove(storage, (proposal_id, voter.clone()));
+        ALLOCATIONS.remove(storage, (voter.clone(), proposal_id));
+        Ok(())
+    } else {
+        SUPPORT.save(storage, (proposal_id, voter.clone()), &amount)?;
+        ALLOCATIONS.save(storage, (voter.clone(), proposal_id), &amount)
+    }
+}
+
+/// Hooks fired when a proposal is executed or closed: proposal status
+/// hooks and, if a pre-propose module is installed, its completion
+/// hook which handles deposit refunds.
+fn completed_hooks(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    old_status: Status,
+    new_status: Status,
+) -> StdResult<Vec<SubMsg>> {
+    let mut hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        storage,
+        proposal_id,
+        old_status.to_string(),
+        new_status.to_string(),
+    )?;
+    if let ProposalCreationPolicy::Module { addr } = CREATION_POLICY.load(storage)? {
+        let msg = to_binary(&PreProposeMsg::ProposalCompletedHook {
+            proposal_id,
+            new_status,
+        })?;
+        hooks.push(SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: addr.into_string(),
+                msg,
+                funds: vec![],
+            },
+            failed_pre_propose_module_hook_id(),
+        ));
+    }
+    Ok(hooks)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Dao {} => to_binary(&CONFIG.load(deps.storage)?.dao),
+        QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
+        QueryMsg::ListProposals { start_after, limit } => {
+            query_list_proposals(deps, env, start_after, limit)
+        }
+        QueryMsg::ReverseProposals {
+            start_before,
+            limit,
+        } => query_reverse_proposals(deps, env, start_before, limit),
+        QueryMsg::ProposalCount {} => to_binary(&PROPOSAL_COUNT.load(deps.storage)?),
+        QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, proposal_id, voter),
+        QueryMsg::ListVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::ProposalCreationPolicy {} => to_binary(&CREATION_POLICY.load(deps.storage)?),
+        QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
+        QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::Info {} => query_info(deps),
+    }
+}
+
+pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_binary(&proposal.into_response(deps, &env.block, &config, id)?)
+}
+
+pub fn query_list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let proposals = PROPOSALS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (id, proposal) = item?;
+            proposal.into_response(deps, &env.block, &config, id)
+        })
+        .collect::<StdResult<Vec<ProposalResponse>>>()?;
+
+    to_binary(&ProposalListResponse { proposals })
+}
+
+pub fn query_reverse_proposals(
+    deps: Deps,
+    env: Env,
+    start_before: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let max = start_before.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let proposals = PROPOSALS
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit as usize)
+        .map(|item| {
+            let (id, proposal) = item?;
+            proposal.into_response(deps, &env.block, &config, id)
+        })
+        .collect::<StdResult<Vec<ProposalResponse>>>()?;
+
+    to_binary(&ProposalListResponse { proposals })
+}
+
+pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let support = SUPPORT.may_load(deps.storage, (proposal_id, voter.clone()))?;
+    let vote = support.map(|support| VoteInfo { voter, support });
+    to_binary(&VoteResponse { vote })
+}
+
+pub fn query_list_votes(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.map(Bound::<Addr>::exclusive);
+
+    let votes = SUPPORT
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (voter, support) = item?;
+            Ok(VoteInfo { voter, support })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&VoteListResponse { votes })
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&cwd_interface::voting::InfoResponse { info })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match msg {
+        MigrateMsg::FromCompatible {} => {
+            set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+            Ok(Response::default()
+                .add_attribute("action", "migrate")
+                .add_attribute("from", "compatible"))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = TaggedReplyId::new(msg.id)?;
+    match repl {
+        TaggedReplyId::FailedProposalExecution(proposal_id) => {
+            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
+                Some(mut prop) => {
+                    prop.status = Status::ExecutionFailed;
+                    Ok(prop)
+                }
+                None => Err(ContractError::NoSuchProposal { id: proposal_id }),
+            })?;
+
+            Ok(Response::new().add_attribute("proposal_execution_failed", proposal_id.to_string()))
+        }
+        TaggedReplyId::FailedProposalHook(idx) => {
+            let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new().add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
+        }
+        TaggedReplyId::FailedVoteHook(idx) => {
+            let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new().add_attribute("removed_vote_hook", format!("{addr}:{idx}")))
+        }
+        TaggedReplyId::PreProposeModuleInstantiation => {
+            let res = parse_reply_instantiate_data(msg)?;
+            let module = deps.api.addr_validate(&res.contract_address)?;
+            CREATION_POLICY.save(
+                deps.storage,
+                &ProposalCreationPolicy::Module { addr: module },
+            )?;
+
+            Ok(Response::new().add_attribute("update_pre_propose_module", res.contract_address))
+        }
+        TaggedReplyId::FailedPreProposeModuleHook => {
+            let addr = match CREATION_POLICY.load(deps.storage)? {
+                ProposalCreationPolicy::Anyone {} => {
+                    // Something is off if we're getting this reply
+                    // and we don't have a pre-propose module
+                    // installed. This should be unreachable.
+                    return Err(ContractError::InvalidReplyID {
+                        id: failed_pre_propose_module_hook_id(),
+                    });
+                }
+                ProposalCreationPolicy::Module { addr } => {
+                    // Our pre-propose module has errored while
+                    // receiving a proposal hook. Remove it.
+                    CREATION_POLICY.save(deps.storage, &ProposalCreationPolicy::Anyone {})?;
+                    addr
+                }
+            };
+            Ok(Response::new().add_attribute("failed_prepropose_hook", format!("{addr}")))
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_denom::DenomError;
+use cw_utils::ParseReplyError;
+use cwd_hooks::HookError;
+use cwd_voting::reply::error::TagError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    ParseReplyError(#[from] ParseReplyError),
+
+    #[error(transparent)]
+    HookError(#[from] HookError),
+
+    #[error(transparent)]
+    DenomError(#[from] DenomError),
+
+    #[error(transparent)]
+    Tag(#[from] TagError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("no such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("proposal is ({size}) bytes, must be <= ({max}) bytes")]
+    ProposalTooLarge { size: u64, max: u64 },
+
+    #[error("proposal is not open ({id})")]
+    NotOpen { id: u64 },
+
+    #[error("proposal is not in 'passed' state")]
+    NotPassed {},
+
+    #[error("the DAO is currently inactive, you cannot create proposals")]
+    InactiveDao {},
+
+    #[error(
+        "pre-propose modules must specify a proposer. lacking one, no proposer should be specified"
+    )]
+    InvalidProposer {},
+
+    #[error("decay must be greater than zero and less than one")]
+    InvalidDecay {},
+
+    #[error("max ratio must be greater than zero and at most one")]
+    InvalidMaxRatio {},
+
+    #[error("weight must be greater than zero")]
+    ZeroWeight {},
+
+    #[error("proposal message ({index}) may move funds other than the funding token")]
+    UnpricedMessage { index: usize },
+
+    #[error("support for this proposal is already ({amount})")]
+    SupportUnchanged { amount: Uint128 },
+
+    #[error("allocating ({allocated}) support exceeds voting power ({power})")]
+    InsufficientVotingPower { allocated: Uint128, power: Uint128 },
+
+    #[error("support allocated by this address does not exceed its voting power")]
+    NothingToRefresh {},
+
+    #[error("received a reply failure with an invalid ID: ({id})")]
+    InvalidReplyID { id: u64 },
+}
@@ -0,0 +1,42 @@
+//! # cwd-proposal-conviction
+//!
+//! A proposal module for continuous funding via conviction
+//! voting. Proposals have no voting period. Instead, members allocate
+//! their voting power as support to any number of open proposals and
+//! each proposal accumulates conviction while it is supported. A
+//! proposal passes once its conviction reaches a threshold which grows
+//! with the share of the DAO's treasury that it requests.
+//!
+//! ## Conviction
+//!
+//! Each block a proposal's conviction decays by the module's `decay`
+//! factor and the support currently allocated to it is added. With
+//! constant support `s` conviction approaches `s / (1 - decay)`.
+//!
+//! The conviction required for a proposal to pass is:
+//!
+//! ```text
+//! weight * total_power / ((1 - decay) * (max_ratio - requested / treasury)^2)
+//! ```
+//!
+//! Proposals requesting `max_ratio` or more of the treasury may not
+//! pass.
+//!
+//! ## Hooks
+//!
+//! This module supports the same proposal and vote hooks as
+//! `cwd-proposal-single`. Vote hooks are fired whenever an address
+//! changes its support for a proposal and carry the new amount of
+//! support as the vote.
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod proposal;
+pub mod query;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use crate::error::ContractError;
@@ -0,0 +1,186 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Decimal, Empty, Uint128};
+use cw_denom::UncheckedDenom;
+use cwd_macros::{info_query, proposal_module_query};
+use cwd_voting::pre_propose::PreProposeInfo;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The token that proposals request from the DAO's treasury. The
+    /// share of the treasury's balance of this token that a proposal
+    /// requests determines its passing threshold.
+    pub funding_denom: UncheckedDenom,
+    /// The fraction of a proposal's conviction that is retained each
+    /// block. Must be greater than zero and less than one. Values
+    /// closer to one cause conviction to build up, and decay, more
+    /// slowly.
+    pub decay: Decimal,
+    /// The maximum share of the treasury a proposal may request. Must
+    /// be greater than zero and at most one.
+    pub max_ratio: Decimal,
+    /// Scales the conviction proposals require to pass. Must be
+    /// greater than zero.
+    pub weight: Decimal,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain open after execution
+    /// failure.
+    pub close_proposal_on_execution_failure: bool,
+    /// Information about what addresses may create proposals.
+    pub pre_propose_info: PreProposeInfo,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a proposal in the module.
+    Propose {
+        /// The title of the proposal.
+        title: String,
+        /// A description of the proposal.
+        description: String,
+        /// The messages that should be executed in response to this
+        /// proposal passing. The amount of the funding token these
+        /// messages spend is the amount the proposal requests. Messages
+        /// that may move funds other than the funding token, such as
+        /// sending another token or executing an arbitrary contract,
+        /// are rejected.
+        msgs: Vec<CosmosMsg<Empty>>,
+        /// The address creating the proposal. If no pre-propose
+        /// module is attached to this module this must always be None
+        /// as the proposer is the sender of the propose message. If a
+        /// pre-propose module is attached, this must be Some and will
+        /// set the proposer of the proposal it creates.
+        proposer: Option<String>,
+    },
+    /// Sets the amount of the sender's voting power allocated to an
+    /// open proposal. Setting this to zero removes the sender's
+    /// support. The support may not exceed the voting power the
+    /// sender had when the proposal was created and the total
+    /// support an address allocates across open proposals may not
+    /// exceed its current voting power.
+    Support {
+        /// The ID of the proposal to support.
+        proposal_id: u64,
+        /// The amount of voting power to allocate.
+        amount: Uint128,
+    },
+    /// Reduces the support allocated by VOTER in proportion to their
+    /// current voting power if it has fallen below the support they
+    /// have allocated. Callable by any address.
+    RefreshSupport { voter: String },
+    /// Causes the messages associated with a passed proposal to be
+    /// executed by the DAO.
+    Execute {
+        /// The ID of the proposal to execute.
+        proposal_id: u64,
+    },
+    /// Closes an open proposal. Only callable by the proposal's
+    /// proposer or the DAO. If applicable this will cause the
+    /// proposal deposit associated with said proposal to be returned.
+    Close {
+        /// The ID of the proposal to close.
+        proposal_id: u64,
+    },
+    /// Updates the governance module's config. Changes apply to all
+    /// open proposals. Only the DAO may call this method.
+    UpdateConfig {
+        funding_denom: UncheckedDenom,
+        decay: Decimal,
+        max_ratio: Decimal,
+        weight: Decimal,
+        close_proposal_on_execution_failure: bool,
+    },
+    /// Update's the proposal creation policy used for this
+    /// module. Only the DAO may call this method.
+    UpdatePreProposeInfo { info: PreProposeInfo },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook { address: String },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook { address: String },
+    /// Adds an address as a consumer of vote hooks. Consumers of vote
+    /// hooks have hook messages executed on them whenever support
+    /// for a proposal changes. If a consumer contract errors when
+    /// handling a hook message it will be removed from the list of
+    /// consumers.
+    AddVoteHook { address: String },
+    /// Removed a consumer of vote hooks.
+    RemoveVoteHook { address: String },
+}
+
+#[proposal_module_query]
+#[info_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the proposal module's config.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Gets information about a proposal.
+    #[returns(crate::query::ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    /// Lists all the proposals that have been cast in this
+    /// module.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposals {
+        /// The proposal ID to start listing proposals after. For
+        /// example, if this is set to 2 proposals with IDs 3 and
+        /// higher will be returned.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists all of the proposals that have been cast in this module
+    /// in decending order of proposal ID.
+    #[returns(crate::query::ProposalListResponse)]
+    ReverseProposals {
+        /// The proposal ID to start listing proposals before. For
+        /// example, if this is set to 6 proposals with IDs 5 and
+        /// lower will be returned.
+        start_before: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Returns the number of proposals that have been created in this
+    /// module.
+    #[returns(u64)]
+    ProposalCount {},
+    /// Returns the support an address has allocated to a proposal.
+    #[returns(crate::query::VoteResponse)]
+    GetVote { proposal_id: u64, voter: String },
+    /// Lists the support allocated to a proposal.
+    #[returns(crate::query::VoteListResponse)]
+    ListVotes {
+        /// The proposal to list the support of.
+        proposal_id: u64,
+        /// The voter to start listing support after. Ordering is
+        /// done alphabetically.
+        start_after: Option<String>,
+        /// The maximum number of entries to return in response to
+        /// this query. If no limit is specified a max of 30 are
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Gets the current proposal creation policy for this
+    /// module.
+    #[returns(cwd_voting::pre_propose::ProposalCreationPolicy)]
+    ProposalCreationPolicy {},
+    /// Lists all of the consumers of proposal hooks for this module.
+    #[returns(cwd_hooks::HooksResponse)]
+    ProposalHooks {},
+    /// Lists all of the consumers of vote hooks for this
+    /// module.
+    #[returns(cwd_hooks::HooksResponse)]
+    VoteHooks {},
+}
+
+#[cw_serde]
+pub enum MigrateMsg {
+    FromCompatible {},
+}
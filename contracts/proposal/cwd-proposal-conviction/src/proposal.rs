@@ -0,0 +1,435 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_binary, Addr, BankMsg, BlockInfo, Coin, CosmosMsg, Decimal, Deps, Empty, StdError,
+    StdResult, Storage, Uint128, Uint256, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use cw_denom::CheckedDenom;
+use cwd_voting::{proposal::Proposal, status::Status, voting::get_total_power};
+
+use crate::{
+    error::ContractError,
+    query::ProposalResponse,
+    state::{Config, PROPOSAL_COUNT},
+};
+
+#[cw_serde]
+pub struct ConvictionProposal {
+    pub title: String,
+    pub description: String,
+    /// The address that created this proposal.
+    pub proposer: Addr,
+    /// The block height at which this proposal was created.
+    pub start_height: u64,
+    /// The messages that will be executed should this proposal pass.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The amount of the funding token that this proposal's messages
+    /// spend from the DAO's treasury as of its creation.
+    pub requested_amount: Uint128,
+    pub status: Status,
+    /// The total support currently allocated to this proposal.
+    pub support: Uint128,
+    /// This proposal's conviction as of `last_updated`.
+    pub conviction: Uint128,
+    /// The block height at which `conviction` was last computed.
+    pub last_updated: u64,
+}
+
+impl Proposal for ConvictionProposal {
+    fn proposer(&self) -> Addr {
+        self.proposer.clone()
+    }
+    fn status(&self) -> Status {
+        self.status
+    }
+}
+
+pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+impl ConvictionProposal {
+    /// Consumes the proposal and returns a version which may be used
+    /// in a query response. Proposal statuses are only updated on
+    /// execute and close events, so this computes whether an open
+    /// proposal has currently passed.
+    pub fn into_response(
+        mut self,
+        deps: Deps,
+        block: &BlockInfo,
+        config: &Config,
+        id: u64,
+    ) -> StdResult<ProposalResponse> {
+        let (conviction, threshold) = if self.status == Status::Open {
+            let (conviction, threshold) = self.conviction_and_threshold(deps, block, config)?;
+            if is_passing(conviction, threshold) {
+                self.status = Status::Passed;
+            }
+            (conviction, threshold)
+        } else {
+            (self.conviction, None)
+        };
+        Ok(ProposalResponse {
+            id,
+            proposal: self,
+            conviction,
+            threshold,
+        })
+    }
+
+    /// Gets the current status of the proposal. Open proposals are
+    /// passed while their conviction is at or above their threshold.
+    pub fn current_status(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        config: &Config,
+    ) -> StdResult<Status> {
+        if self.status != Status::Open {
+            return Ok(self.status);
+        }
+        let (conviction, threshold) = self.conviction_and_threshold(deps, block, config)?;
+        if is_passing(conviction, threshold) {
+            Ok(Status::Passed)
+        } else {
+            Ok(Status::Open)
+        }
+    }
+
+    /// Returns this proposal's conviction at the current block and the
+    /// conviction it must reach to pass given the current treasury
+    /// balance and total voting power.
+    pub fn conviction_and_threshold(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        config: &Config,
+    ) -> StdResult<(Uint128, Option<Uint128>)> {
+        let conviction = self.conviction_at(block.height, config.decay)?;
+        let treasury = config
+            .funding_denom
+            .query_balance(&deps.querier, &config.dao)?;
+        let total_power = get_total_power(deps, config.dao.clone(), None)?;
+        // The funding denom may have changed since this proposal was
+        // created in which case its messages are priced again. A
+        // proposal that may not be priced in the current funding
+        // denom may not pass.
+        let threshold = requested_amount(&self.msgs, &config.funding_denom)
+            .ok()
+            .and_then(|requested| threshold(config, requested, treasury, total_power));
+        Ok((conviction, threshold))
+    }
+
+    /// Returns this proposal's conviction at HEIGHT. Each block conviction is
+    /// multiplied by DECAY and the proposal's support is added, so
+    /// after `t` blocks conviction is:
+    ///
+    /// ```text
+    /// conviction * decay^t + support * (1 - decay^t) / (1 - decay)
+    /// ```
+    pub fn conviction_at(&self, height: u64, decay: Decimal) -> StdResult<Uint128> {
+        let blocks = height.saturating_sub(self.last_updated);
+        if blocks == 0 {
+            return Ok(self.conviction);
+        }
+        let retained = decay
+            .checked_pow(u32::try_from(blocks).unwrap_or(u32::MAX))
+            .map_err(StdError::overflow)?;
+        let accrued = (Decimal::one() - retained)
+            .checked_div(Decimal::one() - decay)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+        mul_decimal(self.conviction, retained)?
+            .checked_add(mul_decimal(self.support, accrued)?)
+            .map_err(StdError::overflow)
+    }
+
+    /// Brings this proposal's conviction up to date at HEIGHT and then
+    /// sets its support to SUPPORT.
+    pub fn update_support(
+        &mut self,
+        height: u64,
+        decay: Decimal,
+        support: Uint128,
+    ) -> StdResult<()> {
+        self.conviction = self.conviction_at(height, decay)?;
+        self.last_updated = height;
+        self.support = support;
+        Ok(())
+    }
+}
+
+/// A proposal with CONVICTION passes if it has a THRESHOLD and its
+/// conviction has reached it.
+fn is_passing(conviction: Uint128, threshold: Option<Uint128>) -> bool {
+    matches!(threshold, Some(threshold) if !conviction.is_zero() && conviction >= threshold)
+}
+
+/// Computes the conviction a proposal requesting REQUESTED from a
+/// treasury holding TREASURY must reach in order to pass:
+///
+/// ```text
+/// weight * total_power / ((1 - decay) * (max_ratio - requested / treasury)^2)
+/// ```
+///
+/// Returns None if the proposal may not pass, either because it
+/// requests `max_ratio` or more of the treasury or because the
+/// threshold is too large to represent.
+pub fn threshold(
+    config: &Config,
+    requested: Uint128,
+    treasury: Uint128,
+    total_power: Uint128,
+) -> Option<Uint128> {
+    if total_power.is_zero() || treasury.is_zero() || requested > treasury {
+        return None;
+    }
+    let share = Decimal::from_ratio(requested, treasury);
+    if share >= config.max_ratio {
+        return None;
+    }
+    let distance = config.max_ratio - share;
+    let denominator = (Decimal::one() - config.decay) * distance * distance;
+    let scale = config.weight.checked_div(denominator).ok()?;
+    mul_decimal(total_power, scale).ok()
+}
+
+/// Computes the amount of DENOM that MSGS spend from the DAO's
+/// treasury. Only messages whose effect on the treasury may be priced
+/// in DENOM are permitted: native tokens sent, burned, or attached to
+/// an instantiation, and cw20 tokens transferred, sent, burned, or
+/// approved for spending. Errors with the index of the first message
+/// that may not be priced, such as one that spends another token or
+/// executes an arbitrary contract.
+pub fn requested_amount(
+    msgs: &[CosmosMsg<Empty>],
+    denom: &CheckedDenom,
+) -> Result<Uint128, ContractError> {
+    msgs.iter()
+        .enumerate()
+        .try_fold(Uint128::zero(), |total, (index, msg)| {
+            let spent = spent_by(msg, denom).ok_or(ContractError::UnpricedMessage { index })?;
+            Ok(total.checked_add(spent).map_err(StdError::overflow)?)
+        })
+}
+
+/// Returns the amount of DENOM spent by MSG or None if MSG may move
+/// funds that are not priced in DENOM.
+fn spent_by(msg: &CosmosMsg<Empty>, denom: &CheckedDenom) -> Option<Uint128> {
+    fn native(coins: &[Coin], denom: &CheckedDenom) -> Option<Uint128> {
+        coins
+            .iter()
+            .try_fold(Uint128::zero(), |total, coin| match denom {
+                CheckedDenom::Native(denom) if &coin.denom == denom => {
+                    total.checked_add(coin.amount).ok()
+                }
+                _ => None,
+            })
+    }
+    match msg {
+        CosmosMsg::Bank(BankMsg::Send { amount, .. })
+        | CosmosMsg::Bank(BankMsg::Burn { amount }) => native(amount, denom),
+        CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => native(funds, denom),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds,
+        }) if funds.is_empty() => match denom {
+            CheckedDenom::Cw20(token) if contract_addr == token.as_str() => {
+                match from_binary(msg).ok()? {
+                    Cw20ExecuteMsg::Transfer { amount, .. }
+                    | Cw20ExecuteMsg::Send { amount, .. }
+                    | Cw20ExecuteMsg::Burn { amount }
+                    | Cw20ExecuteMsg::IncreaseAllowance { amount, .. } => Some(amount),
+                    _ => None,
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Multiplies AMOUNT by FACTOR rounding down. Errors rather than
+/// panicking if the result does not fit in a `Uint128`.
+fn mul_decimal(amount: Uint128, factor: Decimal) -> StdResult<Uint128> {
+    let product = Uint256::from(amount) * Uint256::from(factor.atomics())
+        / Uint256::from(Decimal::one().atomics());
+    Ok(Uint128::try_from(product)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::{coins, to_binary};
+
+    fn config(decay: u64, max_ratio: u64, weight: u64) -> Config {
+        Config {
+            funding_denom: CheckedDenom::Native("ujuno".to_string()),
+            decay: Decimal::percent(decay),
+            max_ratio: Decimal::percent(max_ratio),
+            weight: Decimal::permille(weight),
+            dao: Addr::unchecked("dao"),
+            close_proposal_on_execution_failure: false,
+        }
+    }
+
+    fn proposal(support: u128) -> ConvictionProposal {
+        ConvictionProposal {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            proposer: Addr::unchecked("proposer"),
+            start_height: 10,
+            msgs: vec![],
+            requested_amount: Uint128::zero(),
+            status: Status::Open,
+            support: Uint128::new(support),
+            conviction: Uint128::zero(),
+            last_updated: 10,
+        }
+    }
+
+    #[test]
+    fn test_conviction_growth() {
+        let decay = Decimal::percent(90);
+        let mut prop = proposal(100);
+
+        assert_eq!(prop.conviction_at(10, decay).unwrap(), Uint128::zero());
+        assert_eq!(prop.conviction_at(11, decay).unwrap(), Uint128::new(100));
+        assert_eq!(prop.conviction_at(12, decay).unwrap(), Uint128::new(190));
+        assert_eq!(prop.conviction_at(13, decay).unwrap(), Uint128::new(271));
+        // Conviction approaches support / (1 - decay).
+        assert_eq!(
+            prop.conviction_at(10_000, decay).unwrap(),
+            Uint128::new(1000)
+        );
+
+        // Removing support decays conviction.
+        prop.update_support(12, decay, Uint128::zero()).unwrap();
+        assert_eq!(prop.conviction, Uint128::new(190));
+        assert_eq!(prop.conviction_at(13, decay).unwrap(), Uint128::new(171));
+        assert_eq!(prop.conviction_at(10_000, decay).unwrap(), Uint128::zero());
+    }
+
+    #[test]
+    fn test_threshold() {
+        // weight * total_power / ((1 - decay) * (max_ratio - share)^2)
+        let config = config(90, 50, 10);
+        let total_power = Uint128::new(100);
+
+        // 0.01 * 100 / (0.1 * 0.5^2) = 40
+        assert_eq!(
+            threshold(&config, Uint128::zero(), Uint128::new(1000), total_power),
+            Some(Uint128::new(40))
+        );
+        // 0.01 * 100 / (0.1 * 0.4^2) = 62.5
+        assert_eq!(
+            threshold(&config, Uint128::new(100), Uint128::new(1000), total_power),
+            Some(Uint128::new(62))
+        );
+        // 0.01 * 100 / (0.1 * 0.1^2) = 1000
+        assert_eq!(
+            threshold(&config, Uint128::new(400), Uint128::new(1000), total_power),
+            Some(Uint128::new(1000))
+        );
+        assert_eq!(
+            threshold(&config, Uint128::new(500), Uint128::new(1000), total_power),
+            None
+        );
+        assert_eq!(
+            threshold(&config, Uint128::new(1), Uint128::zero(), total_power),
+            None
+        );
+        assert_eq!(
+            threshold(
+                &config,
+                Uint128::zero(),
+                Uint128::new(1000),
+                Uint128::zero()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_requested_amount() {
+        let native = CheckedDenom::Native("ujuno".to_string());
+        let cw20 = CheckedDenom::Cw20(Addr::unchecked("token"));
+        let cw20_transfer = |token: &str, amount: u128| -> CosmosMsg<Empty> {
+            WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "grantee".to_string(),
+                    amount: Uint128::new(amount),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()
+        };
+
+        let msgs: Vec<CosmosMsg<Empty>> = vec![
+            BankMsg::Send {
+                to_address: "grantee".to_string(),
+                amount: coins(10, "ujuno"),
+            }
+            .into(),
+            BankMsg::Burn {
+                amount: coins(3, "ujuno"),
+            }
+            .into(),
+            WasmMsg::Instantiate {
+                admin: None,
+                code_id: 1,
+                msg: to_binary(&Empty {}).unwrap(),
+                funds: coins(5, "ujuno"),
+                label: "grant".to_string(),
+            }
+            .into(),
+        ];
+        assert_eq!(requested_amount(&msgs, &native).unwrap(), Uint128::new(18));
+        assert_eq!(
+            requested_amount(&[cw20_transfer("token", 7)], &cw20).unwrap(),
+            Uint128::new(7)
+        );
+        assert_eq!(requested_amount(&[], &native).unwrap(), Uint128::zero());
+
+        // Messages that may move funds other than the funding token
+        // may not be priced.
+        let unpriced: Vec<CosmosMsg<Empty>> = vec![
+            BankMsg::Send {
+                to_address: "grantee".to_string(),
+                amount: coins(10, "uatom"),
+            }
+            .into(),
+            WasmMsg::Execute {
+                contract_addr: "contract".to_string(),
+                msg: to_binary(&Empty {}).unwrap(),
+                funds: coins(5, "ujuno"),
+            }
+            .into(),
+            WasmMsg::UpdateAdmin {
+                contract_addr: "contract".to_string(),
+                admin: "grantee".to_string(),
+            }
+            .into(),
+        ];
+        for msg in unpriced {
+            let msgs = vec![msgs[0].clone(), msg];
+            assert!(matches!(
+                requested_amount(&msgs, &native).unwrap_err(),
+                ContractError::UnpricedMessage { index: 1 }
+            ));
+        }
+        assert!(matches!(
+            requested_amount(&[cw20_transfer("other", 100)], &cw20).unwrap_err(),
+            ContractError::UnpricedMessage { index: 0 }
+        ));
+        assert!(matches!(
+            requested_amount(&msgs, &cw20).unwrap_err(),
+            ContractError::UnpricedMessage { index: 0 }
+        ));
+    }
+}
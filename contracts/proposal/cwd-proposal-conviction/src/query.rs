@@ -0,0 +1,47 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+
+use crate::proposal::ConvictionProposal;
+
+/// Information about a proposal returned by proposal queries.
+#[cw_serde]
+pub struct ProposalResponse {
+    /// The ID of the proposal being returned.
+    pub id: u64,
+    pub proposal: ConvictionProposal,
+    /// The proposal's conviction at the current block.
+    pub conviction: Uint128,
+    /// The conviction the proposal must reach to pass. None if the
+    /// proposal requests too large a share of the treasury to pass.
+    pub threshold: Option<Uint128>,
+}
+
+/// Support an address has allocated to a proposal.
+#[cw_serde]
+pub struct VoteInfo {
+    /// The address supporting the proposal.
+    pub voter: Addr,
+    /// The voting power the address has allocated to the proposal.
+    pub support: Uint128,
+}
+
+/// Information about a vote.
+#[cw_serde]
+pub struct VoteResponse {
+    /// None if the address does not support the proposal, Some
+    /// otherwise.
+    pub vote: Option<VoteInfo>,
+}
+
+/// Information about the support for a proposal.
+#[cw_serde]
+pub struct VoteListResponse {
+    pub votes: Vec<VoteInfo>,
+}
+
+/// A list of proposals returned by `ListProposals` and
+/// `ReverseProposals`.
+#[cw_serde]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
@@ -0,0 +1,48 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_denom::CheckedDenom;
+use cw_storage_plus::{Item, Map};
+use cwd_hooks::Hooks;
+use cwd_voting::pre_propose::ProposalCreationPolicy;
+
+use crate::proposal::ConvictionProposal;
+
+/// The governance module's configuration.
+#[cw_serde]
+pub struct Config {
+    /// The token that proposals request from the DAO's treasury. The
+    /// share of the treasury's balance of this token that a proposal
+    /// requests determines its passing threshold.
+    pub funding_denom: CheckedDenom,
+    /// The fraction of a proposal's conviction that is retained each
+    /// block.
+    pub decay: Decimal,
+    /// The maximum share of the treasury a proposal may request.
+    pub max_ratio: Decimal,
+    /// Scales the conviction proposals require to pass.
+    pub weight: Decimal,
+    /// The address of the DAO that this governance module is
+    /// associated with.
+    pub dao: Addr,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain open after execution
+    /// failure.
+    pub close_proposal_on_execution_failure: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<u64, ConvictionProposal> = Map::new("proposals");
+/// Support allocated to a proposal, keyed by proposal and supporter.
+pub const SUPPORT: Map<(u64, Addr), Uint128> = Map::new("support");
+/// The same support as `SUPPORT` keyed by supporter and proposal so
+/// that an address's allocations may be listed.
+pub const ALLOCATIONS: Map<(Addr, u64), Uint128> = Map::new("allocations");
+/// Consumers of proposal state change hooks.
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+/// Consumers of vote hooks.
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+/// The address of the pre-propose module associated with this
+/// proposal module (if any).
+pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
@@ -0,0 +1,637 @@
+use cosmwasm_std::{coins, to_binary, Addr, BankMsg, CosmosMsg, Decimal, Empty, Uint128};
+use cw_denom::UncheckedDenom;
+use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
+use cwd_interface::{Admin, ModuleInstantiateInfo};
+use cwd_voting::{pre_propose::PreProposeInfo, status::Status};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::{ProposalResponse, VoteListResponse, VoteResponse},
+    ContractError,
+};
+
+const CREATOR_ADDR: &str = "creator";
+const DENOM: &str = "ujuno";
+
+fn conviction_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply)
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+fn cw4_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw_core_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cwd_core::contract::execute,
+        cwd_core::contract::instantiate,
+        cwd_core::contract::query,
+    )
+    .with_reply(cwd_core::contract::reply);
+    Box::new(contract)
+}
+
+fn cw4_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cwd_voting_cw4::contract::execute,
+        cwd_voting_cw4::contract::instantiate,
+        cwd_voting_cw4::contract::query,
+    )
+    .with_reply(cwd_voting_cw4::contract::reply);
+    Box::new(contract)
+}
+
+fn get_instantiate() -> InstantiateMsg {
+    InstantiateMsg {
+        funding_denom: UncheckedDenom::Native(DENOM.to_string()),
+        decay: Decimal::percent(90),
+        max_ratio: Decimal::percent(50),
+        weight: Decimal::permille(10),
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+    }
+}
+
+struct Setup {
+    app: App,
+    dao: Addr,
+    module: Addr,
+    group: Addr,
+}
+
+/// Creates a DAO with members "a", "b" and "c" holding 50, 30 and 20
+/// voting power and a treasury of 1000 ujuno.
+fn setup(instantiate: InstantiateMsg) -> Setup {
+    let mut app = App::default();
+    let module_id = app.store_code(conviction_contract());
+    let cw4_id = app.store_code(cw4_contract());
+    let core_id = app.store_code(cw_core_contract());
+    let votemod_id = app.store_code(cw4_voting_contract());
+
+    let initial_members = [("a", 50), ("b", 30), ("c", 20)]
+        .into_iter()
+        .map(|(addr, weight)| cw4::Member {
+            addr: addr.to_string(),
+            weight,
+        })
+        .collect();
+
+    let governance_instantiate = cwd_core::msg::InstantiateMsg {
+        admin: None,
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs".to_string(),
+        dao_uri: None,
+        image_url: None,
+        automatically_add_cw20s: true,
+        automatically_add_cw721s: true,
+        voting_module_instantiate_info: ModuleInstantiateInfo {
+            code_id: votemod_id,
+            msg: to_binary(&cwd_voting_cw4::msg::InstantiateMsg {
+                cw4_group_code_id: cw4_id,
+                initial_members,
+            })
+            .unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            label: "DAO DAO voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+            code_id: module_id,
+            msg: to_binary(&instantiate).unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            label: "DAO DAO governance module".to_string(),
+        }],
+        initial_items: None,
+    };
+
+    let dao = app
+        .instantiate_contract(
+            core_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &governance_instantiate,
+            &[],
+            "DAO DAO",
+            None,
+        )
+        .unwrap();
+
+    let modules: Vec<cwd_core::state::ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            &dao,
+            &cwd_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let module = modules.into_iter().next().unwrap().address;
+
+    let voting: Addr = app
+        .wrap()
+        .query_wasm_smart(&dao, &cwd_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let group: Addr = app
+        .wrap()
+        .query_wasm_smart(voting, &cwd_voting_cw4::msg::QueryMsg::GroupContract {})
+        .unwrap();
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: dao.to_string(),
+        amount: coins(1000, DENOM),
+    }))
+    .unwrap();
+
+    // Update the block so that weights appear.
+    app.update_block(|block| block.height += 1);
+
+    Setup {
+        app,
+        dao,
+        module,
+        group,
+    }
+}
+
+fn propose_grant(app: &mut App, module: &Addr, proposer: &str, amount: u128) -> u64 {
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        module.clone(),
+        &ExecuteMsg::Propose {
+            title: "grant".to_string(),
+            description: "fund some work".to_string(),
+            msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "grantee".to_string(),
+                amount: coins(amount, DENOM),
+            })],
+            proposer: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.wrap()
+        .query_wasm_smart(module, &QueryMsg::ProposalCount {})
+        .unwrap()
+}
+
+fn support(
+    app: &mut App,
+    module: &Addr,
+    voter: &str,
+    proposal_id: u64,
+    amount: u128,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(voter),
+        module.clone(),
+        &ExecuteMsg::Support {
+            proposal_id,
+            amount: Uint128::new(amount),
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn execute(app: &mut App, module: &Addr, proposal_id: u64) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        module.clone(),
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn close(
+    app: &mut App,
+    module: &Addr,
+    sender: &str,
+    proposal_id: u64,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module.clone(),
+        &ExecuteMsg::Close { proposal_id },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn query_proposal(app: &App, module: &Addr, proposal_id: u64) -> ProposalResponse {
+    app.wrap()
+        .query_wasm_smart(module, &QueryMsg::Proposal { proposal_id })
+        .unwrap()
+}
+
+fn query_support(app: &App, module: &Addr, proposal_id: u64, voter: &str) -> Option<Uint128> {
+    let res: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            module,
+            &QueryMsg::GetVote {
+                proposal_id,
+                voter: voter.to_string(),
+            },
+        )
+        .unwrap();
+    res.vote.map(|vote| vote.support)
+}
+
+#[test]
+fn test_conviction_passes_and_executes() {
+    let Setup {
+        mut app,
+        module,
+        dao,
+        ..
+    } = setup(get_instantiate());
+
+    // 100 of a 1000 treasury with 100 total power:
+    // 0.01 * 100 / (0.1 * (0.5 - 0.1)^2) = 62.5
+    let id = propose_grant(&mut app, &module, "c", 100);
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.proposal.requested_amount, Uint128::new(100));
+    assert_eq!(proposal.threshold, Some(Uint128::new(62)));
+    assert_eq!(proposal.conviction, Uint128::zero());
+
+    support(&mut app, &module, "a", id, 50).unwrap();
+
+    app.update_block(|block| block.height += 1);
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.conviction, Uint128::new(50));
+    assert_eq!(proposal.proposal.status, Status::Open);
+    let err = execute(&mut app, &module, id).unwrap_err();
+    assert!(matches!(err, ContractError::NotPassed {}));
+
+    app.update_block(|block| block.height += 1);
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.conviction, Uint128::new(95));
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    execute(&mut app, &module, id).unwrap();
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(proposal.threshold, None);
+
+    let grantee = app.wrap().query_balance("grantee", DENOM).unwrap();
+    assert_eq!(grantee.amount, Uint128::new(100));
+    let treasury = app.wrap().query_balance(dao, DENOM).unwrap();
+    assert_eq!(treasury.amount, Uint128::new(900));
+
+    // Support is not changed once a proposal has closed.
+    let err = support(&mut app, &module, "a", id, 10).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { id: err_id } if err_id == id));
+    let err = execute(&mut app, &module, id).unwrap_err();
+    assert!(matches!(err, ContractError::NotPassed {}));
+}
+
+#[test]
+fn test_max_ratio() {
+    let Setup {
+        mut app, module, ..
+    } = setup(get_instantiate());
+
+    let id = propose_grant(&mut app, &module, "a", 500);
+    support(&mut app, &module, "a", id, 50).unwrap();
+    support(&mut app, &module, "b", id, 30).unwrap();
+    support(&mut app, &module, "c", id, 20).unwrap();
+
+    app.update_block(|block| block.height += 100);
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.threshold, None);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    let err = execute(&mut app, &module, id).unwrap_err();
+    assert!(matches!(err, ContractError::NotPassed {}));
+}
+
+#[test]
+fn test_unpriced_proposal() {
+    let Setup {
+        mut app, module, ..
+    } = setup(get_instantiate());
+
+    // Sending another token may not be priced in the funding token so
+    // it may not be proposed with the threshold of a zero request.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a"),
+            module.clone(),
+            &ExecuteMsg::Propose {
+                title: "grant".to_string(),
+                description: "fund some work".to_string(),
+                msgs: vec![
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: "grantee".to_string(),
+                        amount: coins(10, DENOM),
+                    }),
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: "grantee".to_string(),
+                        amount: coins(10, "uatom"),
+                    }),
+                ],
+                proposer: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::UnpricedMessage { index: 1 }));
+}
+
+#[test]
+fn test_support_limited_by_voting_power() {
+    let Setup {
+        mut app, module, ..
+    } = setup(get_instantiate());
+
+    let first = propose_grant(&mut app, &module, "a", 100);
+    let second = propose_grant(&mut app, &module, "a", 10);
+
+    let err = support(&mut app, &module, "b", first, 31).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InsufficientVotingPower { allocated, power }
+            if allocated == Uint128::new(31) && power == Uint128::new(30)
+    ));
+
+    support(&mut app, &module, "b", first, 20).unwrap();
+    let err = support(&mut app, &module, "b", second, 11).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InsufficientVotingPower { allocated, power }
+            if allocated == Uint128::new(31) && power == Uint128::new(30)
+    ));
+    support(&mut app, &module, "b", second, 10).unwrap();
+
+    let err = support(&mut app, &module, "b", second, 10).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::SupportUnchanged { amount } if amount == Uint128::new(10)
+    ));
+
+    // Moving support between proposals is allowed.
+    support(&mut app, &module, "b", first, 5).unwrap();
+    support(&mut app, &module, "b", second, 25).unwrap();
+
+    // Support for closed proposals is released.
+    close(&mut app, &module, "a", second).unwrap();
+    support(&mut app, &module, "b", first, 30).unwrap();
+
+    // Removing support removes the vote.
+    support(&mut app, &module, "b", first, 0).unwrap();
+    assert_eq!(query_support(&app, &module, first, "b"), None);
+
+    let err = support(&mut app, &module, "b", 3, 1).unwrap_err();
+    assert!(matches!(err, ContractError::NoSuchProposal { id: 3 }));
+}
+
+#[test]
+fn test_refresh_support() {
+    let Setup {
+        mut app,
+        module,
+        dao,
+        group,
+    } = setup(get_instantiate());
+
+    let first = propose_grant(&mut app, &module, "a", 100);
+    let second = propose_grant(&mut app, &module, "a", 10);
+    support(&mut app, &module, "a", first, 30).unwrap();
+    support(&mut app, &module, "a", second, 20).unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("b"),
+            module.clone(),
+            &ExecuteMsg::RefreshSupport {
+                voter: "a".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NothingToRefresh {}));
+
+    // Halve a's voting power.
+    app.execute_contract(
+        dao,
+        group,
+        &cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: vec![],
+            add: vec![cw4::Member {
+                addr: "a".to_string(),
+                weight: 25,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 1);
+
+    app.execute_contract(
+        Addr::unchecked("b"),
+        module.clone(),
+        &ExecuteMsg::RefreshSupport {
+            voter: "a".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_support(&app, &module, first, "a"),
+        Some(Uint128::new(15))
+    );
+    assert_eq!(
+        query_support(&app, &module, second, "a"),
+        Some(Uint128::new(10))
+    );
+    assert_eq!(
+        query_proposal(&app, &module, first).proposal.support,
+        Uint128::new(15)
+    );
+
+    let votes: VoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &module,
+            &QueryMsg::ListVotes {
+                proposal_id: second,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(votes.votes.len(), 1);
+    assert_eq!(votes.votes[0].voter, Addr::unchecked("a"));
+}
+
+#[test]
+fn test_support_limited_by_power_at_creation() {
+    let Setup {
+        mut app,
+        module,
+        dao,
+        group,
+    } = setup(get_instantiate());
+
+    let id = propose_grant(&mut app, &module, "c", 100);
+    support(&mut app, &module, "a", id, 50).unwrap();
+
+    // a's voting power moves to c who tries to support with it again.
+    app.execute_contract(
+        dao,
+        group,
+        &cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: vec!["a".to_string()],
+            add: vec![cw4::Member {
+                addr: "c".to_string(),
+                weight: 70,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 1);
+
+    let err = support(&mut app, &module, "c", id, 50).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InsufficientVotingPower { allocated, power }
+            if allocated == Uint128::new(50) && power == Uint128::new(20)
+    ));
+    support(&mut app, &module, "c", id, 20).unwrap();
+
+    app.update_block(|block| block.height += 1);
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.proposal.support, Uint128::new(70));
+    // 50 from a for one block, then 50 * 0.9 + 70.
+    assert_eq!(proposal.conviction, Uint128::new(115));
+
+    // c's new voting power may support proposals created after it
+    // was received.
+    let second = propose_grant(&mut app, &module, "c", 10);
+    support(&mut app, &module, "c", id, 0).unwrap();
+    support(&mut app, &module, "c", second, 70).unwrap();
+}
+
+#[test]
+fn test_close() {
+    let Setup {
+        mut app,
+        module,
+        dao,
+        ..
+    } = setup(get_instantiate());
+
+    let id = propose_grant(&mut app, &module, "a", 100);
+    let err = close(&mut app, &module, "b", id).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    close(&mut app, &module, dao.as_str(), id).unwrap();
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.proposal.status, Status::Closed);
+
+    let err = close(&mut app, &module, "a", id).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { id: err_id } if err_id == id));
+}
+
+#[test]
+fn test_invalid_config() {
+    let mut app = App::default();
+    let code_id = app.store_code(conviction_contract());
+
+    let instantiate = |app: &mut App, msg: InstantiateMsg| -> ContractError {
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &msg,
+            &[],
+            "conviction",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+    };
+
+    let err = instantiate(
+        &mut app,
+        InstantiateMsg {
+            decay: Decimal::one(),
+            ..get_instantiate()
+        },
+    );
+    assert!(matches!(err, ContractError::InvalidDecay {}));
+
+    let err = instantiate(
+        &mut app,
+        InstantiateMsg {
+            max_ratio: Decimal::zero(),
+            ..get_instantiate()
+        },
+    );
+    assert!(matches!(err, ContractError::InvalidMaxRatio {}));
+
+    let err = instantiate(
+        &mut app,
+        InstantiateMsg {
+            weight: Decimal::zero(),
+            ..get_instantiate()
+        },
+    );
+    assert!(matches!(err, ContractError::ZeroWeight {}));
+}
+
+#[test]
+fn test_only_dao_updates_config() {
+    let Setup {
+        mut app,
+        module,
+        dao,
+        ..
+    } = setup(get_instantiate());
+
+    let update = ExecuteMsg::UpdateConfig {
+        funding_denom: UncheckedDenom::Native(DENOM.to_string()),
+        decay: Decimal::percent(99),
+        max_ratio: Decimal::percent(20),
+        weight: Decimal::permille(1),
+        close_proposal_on_execution_failure: false,
+    };
+
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked("a"), module.clone(), &update, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // The DAO updates the config via a proposal in practice, here it
+    // is sent directly.
+    app.execute_contract(dao.clone(), module.clone(), &update, &[])
+        .unwrap();
+    let config: crate::state::Config = app
+        .wrap()
+        .query_wasm_smart(&module, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.decay, Decimal::percent(99));
+    assert_eq!(config.dao, dao);
+}
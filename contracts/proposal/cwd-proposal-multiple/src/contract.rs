@@ -1,38 +1,47 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdResult,
-    Storage, SubMsg, WasmMsg,
+    to_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
+    Response, StdError, StdResult, Storage, SubMsg, Uint128, Uint64, WasmMsg,
 };
 
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
-use cw_utils::{parse_reply_instantiate_data, Duration};
+use cw_utils::{parse_reply_instantiate_data, Duration, Expiration};
 use cwd_hooks::Hooks;
 use cwd_interface::voting::IsActiveResponse;
 use cwd_pre_propose_multiple::contract::ExecuteMsg as PreProposeMsg;
 use cwd_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
 use cwd_vote_hooks::new_vote_hooks;
 use cwd_voting::{
+    anti_sniping::{AntiSnipingConfig, AntiSnipingState},
     multiple_choice::{
-        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
+        MultipleChoiceBallot, MultipleChoiceOptions, MultipleChoiceVotes, VotingStrategy,
     },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
     reply::{
         failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
     },
+    signed_ballot::SignedBallot,
     status::Status,
-    voting::{get_total_power, get_voting_power, validate_voting_period},
+    voting::{
+        get_total_power, get_voting_power, validate_execution_window, validate_voting_delay,
+        validate_voting_period,
+    },
 };
 
 use crate::{msg::MigrateMsg, state::CREATION_POLICY};
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     proposal::{MultipleChoiceProposal, VoteResult},
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
+    query::{
+        ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse,
+        VoterVoteInfo, VoterVoteListResponse,
+    },
     state::{
-        Ballot, Config, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS,
+        ballots, proposals, Ballot, Config, IndexProgress, BALLOT_NONCES, CONFIG, INDEX_PROGRESS,
+        PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS,
     },
     ContractError,
 };
@@ -56,6 +65,13 @@ pub fn instantiate(
     let (min_voting_period, max_voting_period) =
         validate_voting_period(msg.min_voting_period, msg.max_voting_period)?;
 
+    if let Some(anti_sniping) = &msg.anti_sniping {
+        anti_sniping.validate(&max_voting_period)?;
+    }
+
+    let voting_delay = validate_voting_delay(msg.voting_delay, &max_voting_period)?;
+    let execution_window = validate_execution_window(msg.execution_window, &max_voting_period)?;
+
     let (initial_policy, pre_propose_messages) = msg
         .pre_propose_info
         .into_initial_policy_and_messages(dao.clone())?;
@@ -68,6 +84,9 @@ pub fn instantiate(
         allow_revoting: msg.allow_revoting,
         dao,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        anti_sniping: msg.anti_sniping,
+        voting_delay,
+        execution_window,
     };
 
     // Initialize proposal count to zero so that queries return zero
@@ -104,7 +123,36 @@ pub fn execute(
             choices,
             proposer,
         ),
-        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::UpdateProposal {
+            proposal_id,
+            title,
+            description,
+            choices,
+            proposer,
+        } => execute_update_proposal(
+            deps,
+            env,
+            info,
+            proposal_id,
+            title,
+            description,
+            choices,
+            proposer,
+        ),
+        ExecuteMsg::WithdrawProposal {
+            proposal_id,
+            proposer,
+        } => execute_withdraw_proposal(deps, env, info, proposal_id, proposer),
+        ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale,
+        } => execute_vote(deps, env, info, proposal_id, vote, rationale),
+        ExecuteMsg::RelayVotes { ballots } => execute_relay_votes(deps, env, info, ballots),
+        ExecuteMsg::UpdateRationale {
+            proposal_id,
+            rationale,
+        } => execute_update_rationale(deps, info, proposal_id, rationale),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
         ExecuteMsg::UpdateConfig {
@@ -115,6 +163,9 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            anti_sniping,
+            voting_delay,
+            execution_window,
         } => execute_update_config(
             deps,
             info,
@@ -125,6 +176,9 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            anti_sniping,
+            voting_delay,
+            execution_window,
         ),
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
@@ -139,6 +193,7 @@ pub fn execute(
         ExecuteMsg::RemoveVoteHook { address } => {
             execute_remove_vote_hook(deps, env, info, address)
         }
+        ExecuteMsg::IndexExisting { limit } => execute_index_existing(deps, limit),
     }
 }
 
@@ -191,7 +246,23 @@ pub fn execute_propose(
     // Validate options.
     let checked_multiple_choice_options = options.into_checked()?.options;
 
-    let expiration = config.max_voting_period.after(&env.block);
+    // Proposals with a voting delay are pending until it has elapsed
+    // and their voting period begins once they open.
+    let voting_start = config.voting_delay.map(|delay| delay.after(&env.block));
+    // Voting delays are measured in blocks so the height voting power
+    // is snapshotted at is fixed when the proposal is created.
+    let start_height = match voting_start {
+        Some(Expiration::AtHeight(height)) => height,
+        _ => env.block.height,
+    };
+    let after_start = |period: Duration| -> StdResult<Expiration> {
+        match voting_start {
+            Some(start) => start + period,
+            None => Ok(period.after(&env.block)),
+        }
+    };
+    let expiration = after_start(config.max_voting_period)?;
+    let min_voting_period = config.min_voting_period.map(after_start).transpose()?;
     let total_power = get_total_power(deps.as_ref(), config.dao, None)?;
 
     let proposal = {
@@ -200,15 +271,23 @@ pub fn execute_propose(
             title,
             description,
             proposer: proposer.clone(),
-            start_height: env.block.height,
-            min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
+            start_height,
+            min_voting_period,
             expiration,
             voting_strategy: config.voting_strategy,
             total_power,
-            status: Status::Open,
+            status: match voting_start {
+                Some(_) => Status::Pending,
+                None => Status::Open,
+            },
             votes: MultipleChoiceVotes::zero(checked_multiple_choice_options.len()),
+            turnout: Uint128::zero(),
+            rankings: vec![],
             allow_revoting: config.allow_revoting,
             choices: checked_multiple_choice_options,
+            anti_sniping: config.anti_sniping.map(AntiSnipingState::new),
+            voting_start,
+            execution_window: config.execution_window,
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -239,7 +318,7 @@ pub fn execute_propose(
         });
     }
 
-    PROPOSALS.save(deps.storage, id, &proposal)?;
+    proposals().save(deps.storage, id, &proposal)?;
 
     let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
     // Add prepropose / deposit module hook which will save deposit info. This
@@ -273,40 +352,293 @@ pub fn execute_propose(
         .add_attribute("status", proposal.status.to_string()))
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    title: String,
+    description: String,
+    options: MultipleChoiceOptions,
+    proposer: Option<String>,
+) -> Result<Response<Empty>, ContractError> {
+    let mut prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    check_amendable(
+        deps.as_ref(),
+        &env,
+        &info.sender,
+        proposer,
+        proposal_id,
+        &prop,
+    )?;
+
+    let checked_multiple_choice_options = options.into_checked()?.options;
+
+    prop.title = title;
+    prop.description = description;
+    // No votes have been cast so the tally may be reset for the new
+    // choices.
+    prop.votes = MultipleChoiceVotes::zero(checked_multiple_choice_options.len());
+    prop.choices = checked_multiple_choice_options;
+
+    // See `execute_propose` for why proposal size is limited.
+    let proposal_size = cosmwasm_std::to_vec(&prop)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    proposals().save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_withdraw_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    proposer: Option<String>,
+) -> Result<Response<Empty>, ContractError> {
+    let mut prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    check_amendable(
+        deps.as_ref(),
+        &env,
+        &info.sender,
+        proposer,
+        proposal_id,
+        &prop,
+    )?;
+
+    let old_status = prop.status;
+    prop.status = Status::Withdrawn;
+    proposals().save(deps.storage, proposal_id, &prop)?;
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let hooks = match proposal_creation_policy {
+        ProposalCreationPolicy::Anyone {} => hooks,
+        ProposalCreationPolicy::Module { addr } => {
+            let msg = to_binary(&PreProposeMsg::ProposalCompletedHook {
+                proposal_id,
+                new_status: prop.status,
+            })?;
+            let mut hooks = hooks;
+            hooks.push(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: addr.into_string(),
+                    msg,
+                    funds: vec![],
+                },
+                failed_pre_propose_module_hook_id(),
+            ));
+            hooks
+        }
+    };
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "withdraw_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("status", prop.status.to_string()))
+}
+
+/// Checks that SENDER may update or withdraw PROP and that it has
+/// not yet been voted on. As with proposal creation, if a
+/// pre-propose module is attached it must send the message on behalf
+/// of the proposer. Otherwise, the proposer must send it themselves.
+fn check_amendable(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    proposer: Option<String>,
+    proposal_id: u64,
+    prop: &MultipleChoiceProposal,
+) -> Result<(), ContractError> {
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    if !proposal_creation_policy.is_permitted(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let proposer = match (proposer, &proposal_creation_policy) {
+        (None, ProposalCreationPolicy::Anyone {}) => sender.clone(),
+        (Some(proposer), ProposalCreationPolicy::Module { .. }) => {
+            deps.api.addr_validate(&proposer)?
+        }
+        _ => return Err(ContractError::InvalidProposer {}),
+    };
+    if proposer != prop.proposer {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !matches!(
+        prop.current_status(&env.block)?,
+        Status::Open | Status::Pending
+    ) {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+    if ballots()
+        .prefix(proposal_id)
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+    {
+        return Err(ContractError::VotingStarted { id: proposal_id });
+    }
+    Ok(())
+}
+
 pub fn execute_vote(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
-    vote: MultipleChoiceVote,
+    vote: MultipleChoiceBallot,
+    rationale: Option<String>,
+) -> Result<Response<Empty>, ContractError> {
+    cast_vote(deps, env, info.sender, proposal_id, vote, rationale, "vote")
+}
+
+pub fn execute_relay_votes(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ballots: Vec<SignedBallot<MultipleChoiceBallot>>,
+) -> Result<Response<Empty>, ContractError> {
+    if ballots.is_empty() {
+        return Err(ContractError::NoBallots {});
+    }
+
+    let mut response = Response::default()
+        .add_attribute("action", "relay_votes")
+        .add_attribute("relayer", info.sender);
+
+    for ballot in ballots {
+        let voter = ballot.verify(deps.api, &env.block.chain_id, &env.contract.address)?;
+        use_ballot_nonce(deps.storage, &voter, ballot.nonce)?;
+
+        let res = cast_vote(
+            deps.branch(),
+            env.clone(),
+            voter,
+            ballot.proposal_id,
+            ballot.vote,
+            None,
+            "relayed_vote",
+        )?;
+        response = response
+            .add_submessages(res.messages)
+            .add_attributes(res.attributes);
+    }
+
+    Ok(response)
+}
+
+/// Opens a pending proposal whose voting delay has elapsed,
+/// snapshotting its total voting power at its start height, the
+/// height its voting delay ended.
+fn open_pending_proposal(
+    deps: Deps,
+    dao: &Addr,
+    prop: &mut MultipleChoiceProposal,
+    block: &BlockInfo,
+) -> StdResult<()> {
+    if prop.status != Status::Pending || prop.is_pending(block) {
+        return Ok(());
+    }
+    prop.total_power = get_total_power(deps, dao.clone(), Some(prop.start_height))?;
+    prop.status = Status::Open;
+    Ok(())
+}
+
+/// Errors if NONCE is lower than VOTER's next ballot nonce. Otherwise,
+/// sets their next nonce to NONCE + 1 so that the ballot may not be
+/// replayed.
+fn use_ballot_nonce(
+    storage: &mut dyn Storage,
+    voter: &Addr,
+    nonce: u64,
+) -> Result<(), ContractError> {
+    let next = BALLOT_NONCES
+        .may_load(storage, voter.clone())?
+        .unwrap_or_default();
+    if nonce < next {
+        return Err(ContractError::InvalidNonce { next });
+    }
+    let next = Uint64::new(nonce)
+        .checked_add(Uint64::one())
+        .map_err(StdError::overflow)?;
+    BALLOT_NONCES.save(storage, voter.clone(), &next.u64())?;
+    Ok(())
+}
+
+/// Counts VOTER's vote on a proposal. ACTION is used as the
+/// response's action attribute.
+fn cast_vote(
+    deps: DepsMut,
+    env: Env,
+    voter: Addr,
+    proposal_id: u64,
+    vote: MultipleChoiceBallot,
+    rationale: Option<String>,
+    action: &str,
 ) -> Result<Response<Empty>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut prop = PROPOSALS
+    let mut prop = proposals()
         .may_load(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
     // Check that this is a valid vote.
-    if vote.option_id as usize >= prop.choices.len() {
-        return Err(ContractError::InvalidVote {});
-    }
+    validate_ballot(&prop, &vote)?;
 
     if prop.current_status(&env.block)? != Status::Open {
         return Err(ContractError::NotOpen { id: proposal_id });
     }
 
+    let old_status = prop.status;
+    open_pending_proposal(deps.as_ref(), &config.dao, &mut prop, &env.block)?;
+    let was_passing = prop.passing_choice()?;
+
     let vote_power = get_voting_power(
         deps.as_ref(),
-        info.sender.clone(),
-        config.dao,
+        voter.clone(),
+        config.dao.clone(),
         Some(prop.start_height),
     )?;
     if vote_power.is_zero() {
         return Err(ContractError::NotRegistered {});
     }
+    if let MultipleChoiceBallot::Quadratic { allocations } = &vote {
+        let credits = allocations
+            .iter()
+            .try_fold(Uint128::zero(), |total, a| total.checked_add(a.credits))
+            .map_err(StdError::overflow)?;
+        if credits > vote_power {
+            return Err(ContractError::InsufficientCredits {});
+        }
+    }
 
-    BALLOTS.update(
+    ballots().update(
         deps.storage,
-        (proposal_id, info.sender.clone()),
+        (proposal_id, voter.clone()),
         |bal| match bal {
             Some(current_ballot) => {
                 if prop.allow_revoting {
@@ -317,11 +649,11 @@ pub fn execute_vote(
                         Err(ContractError::AlreadyCast {})
                     } else {
                         // Remove the old vote if this is a re-vote.
-                        prop.votes
-                            .remove_vote(current_ballot.vote, current_ballot.power)?;
+                        prop.remove_ballot(&current_ballot.vote, current_ballot.power)?;
                         Ok(Ballot {
                             power: vote_power,
-                            vote,
+                            vote: vote.clone(),
+                            rationale,
                         })
                     }
                 } else {
@@ -329,17 +661,32 @@ pub fn execute_vote(
                 }
             }
             None => Ok(Ballot {
-                vote,
+                vote: vote.clone(),
                 power: vote_power,
+                rationale,
             }),
         },
     )?;
 
-    let old_status = prop.status;
-
-    prop.votes.add_vote(vote, vote_power)?;
+    prop.add_ballot(&vote, vote_power)?;
     prop.update_status(&env.block)?;
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    // If this vote changed the option the proposal would pass with
+    // shortly before it expires, extend its voting period so that
+    // others may respond. Proposals which are no longer open have an
+    // outcome that no future vote can change and are not extended.
+    let outcome_changed = was_passing != prop.passing_choice()?;
+    let extended = match &mut prop.anti_sniping {
+        Some(anti_sniping) if outcome_changed && prop.status == Status::Open => {
+            anti_sniping.extend(prop.expiration, &env.block)?
+        }
+        _ => None,
+    };
+    if let Some(expiration) = extended {
+        prop.expiration = expiration;
+    }
+
+    proposals().save(deps.storage, proposal_id, &prop)?;
     let new_status = prop.status;
     let change_hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
@@ -352,17 +699,76 @@ pub fn execute_vote(
         VOTE_HOOKS,
         deps.storage,
         proposal_id,
-        info.sender.to_string(),
+        voter.to_string(),
         vote.to_string(),
     )?;
     Ok(Response::default()
         .add_submessages(change_hooks)
         .add_submessages(vote_hooks)
-        .add_attribute("action", "vote")
-        .add_attribute("sender", info.sender)
+        .add_attribute("action", action)
+        .add_attribute("sender", voter)
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("position", vote.to_string())
-        .add_attribute("status", prop.status.to_string()))
+        .add_attribute("status", prop.status.to_string())
+        .add_attributes(extended.map(|expiration| ("extended_expiration", expiration.to_string()))))
+}
+
+/// Checks that BALLOT matches PROP's voting strategy and that every
+/// option it references exists and is referenced only once.
+fn validate_ballot(
+    prop: &MultipleChoiceProposal,
+    ballot: &MultipleChoiceBallot,
+) -> Result<(), ContractError> {
+    if !ballot.matches_strategy(&prop.voting_strategy) {
+        return Err(ContractError::WrongBallotType {});
+    }
+    let option_ids = ballot.option_ids();
+    if option_ids.is_empty() {
+        return Err(ContractError::InvalidVote {});
+    }
+    let mut seen = vec![false; prop.choices.len()];
+    for option_id in option_ids {
+        match seen.get_mut(option_id as usize) {
+            Some(seen) if !*seen => *seen = true,
+            _ => return Err(ContractError::InvalidVote {}),
+        }
+    }
+    if let MultipleChoiceBallot::Quadratic { allocations } = ballot {
+        if allocations.iter().any(|a| a.credits.is_zero()) {
+            return Err(ContractError::InvalidVote {});
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_update_rationale(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+    rationale: Option<String>,
+) -> Result<Response<Empty>, ContractError> {
+    // Rationales may be updated at any time, including after the
+    // proposal has closed and when revoting is disabled, as they do
+    // not affect the outcome of the proposal.
+    ballots().update(
+        deps.storage,
+        (proposal_id, info.sender.clone()),
+        |ballot| match ballot {
+            Some(ballot) => Ok(Ballot {
+                rationale,
+                ..ballot
+            }),
+            None => Err(ContractError::NoSuchVote {
+                id: proposal_id,
+                voter: info.sender.to_string(),
+            }),
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_rationale")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
 pub fn execute_execute(
@@ -384,7 +790,7 @@ pub fn execute_execute(
         }
     }
 
-    let mut prop = PROPOSALS
+    let mut prop = proposals()
         .may_load(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
@@ -393,13 +799,16 @@ pub fn execute_execute(
     // voting period.
     prop.update_status(&env.block)?;
     let old_status = prop.status;
+    if prop.status == Status::ExecutionExpired {
+        return Err(ContractError::ExecutionWindowExpired {});
+    }
     if prop.status != Status::Passed {
         return Err(ContractError::NotPassed {});
     }
 
     prop.status = Status::Executed;
 
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    proposals().save(deps.storage, proposal_id, &prop)?;
 
     let vote_result = prop.calculate_vote_result()?;
     match vote_result {
@@ -412,6 +821,7 @@ pub fn execute_execute(
                             contract_addr: config.dao.to_string(),
                             msg: to_binary(&cwd_core::msg::ExecuteMsg::ExecuteProposalHook {
                                 msgs,
+                                proposal_id: Some(proposal_id),
                             })?,
                             funds: vec![],
                         };
@@ -479,18 +889,23 @@ pub fn execute_close(
     info: MessageInfo,
     proposal_id: u64,
 ) -> Result<Response<Empty>, ContractError> {
-    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    let mut prop = proposals().load(deps.storage, proposal_id)?;
+    let stored_status = prop.status;
 
     prop.update_status(&env.block)?;
-    if prop.status != Status::Rejected {
-        return Err(ContractError::WrongCloseStatus {});
-    }
-
-    let old_status = prop.status;
-
-    prop.status = Status::Closed;
+    let old_status = match prop.status {
+        Status::Rejected => {
+            prop.status = Status::Closed;
+            Status::Rejected
+        }
+        // Passed proposals whose execution window has ended keep
+        // their status when closed. Closing them saves it and settles
+        // their deposit.
+        Status::ExecutionExpired if stored_status != Status::ExecutionExpired => Status::Passed,
+        _ => return Err(ContractError::WrongCloseStatus {}),
+    };
 
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    proposals().save(deps.storage, proposal_id, &prop)?;
 
     let hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
@@ -539,6 +954,9 @@ pub fn execute_update_config(
     allow_revoting: bool,
     dao: String,
     close_proposal_on_execution_failure: bool,
+    anti_sniping: Option<AntiSnipingConfig>,
+    voting_delay: Option<Duration>,
+    execution_window: Option<Duration>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -554,6 +972,13 @@ pub fn execute_update_config(
     let (min_voting_period, max_voting_period) =
         validate_voting_period(min_voting_period, max_voting_period)?;
 
+    if let Some(anti_sniping) = &anti_sniping {
+        anti_sniping.validate(&max_voting_period)?;
+    }
+
+    let voting_delay = validate_voting_delay(voting_delay, &max_voting_period)?;
+    let execution_window = validate_execution_window(execution_window, &max_voting_period)?;
+
     CONFIG.save(
         deps.storage,
         &Config {
@@ -564,6 +989,9 @@ pub fn execute_update_config(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            anti_sniping,
+            voting_delay,
+            execution_window,
         },
     )?;
 
@@ -704,6 +1132,77 @@ pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
     Ok(id)
 }
 
+/// Re-saves up to LIMIT of the proposals, and then the ballots, that
+/// have not been indexed since migrating so that they are present in
+/// their indexes.
+pub fn execute_index_existing(
+    deps: DepsMut,
+    limit: Option<u64>,
+) -> Result<Response, ContractError> {
+    let progress = INDEX_PROGRESS
+        .may_load(deps.storage)?
+        .ok_or(ContractError::IndexingComplete {})?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+
+    let (indexed, next) = match progress {
+        IndexProgress::Proposals { start_after } => {
+            let mut props = proposals()
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (id, prop) in props.iter_mut() {
+                // Backfill the turnout of proposals saved before it
+                // was tracked.
+                prop.turnout = prop.turnout();
+                proposals().save(deps.storage, *id, prop)?;
+            }
+            let next = match props.last() {
+                Some((id, _)) if props.len() == limit => IndexProgress::Proposals {
+                    start_after: Some(*id),
+                },
+                _ => IndexProgress::Ballots { start_after: None },
+            };
+            (props.len(), Some(next))
+        }
+        IndexProgress::Ballots { start_after } => {
+            let all = ballots()
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (key, ballot) in &all {
+                ballots().save(deps.storage, key.clone(), ballot)?;
+            }
+            let next = match all.last() {
+                Some((key, _)) if all.len() == limit => Some(IndexProgress::Ballots {
+                    start_after: Some(key.clone()),
+                }),
+                _ => None,
+            };
+            (all.len(), next)
+        }
+    };
+
+    match &next {
+        Some(next) => INDEX_PROGRESS.save(deps.storage, next)?,
+        None => INDEX_PROGRESS.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "index_existing")
+        .add_attribute("indexed", indexed.to_string())
+        .add_attribute("complete", next.is_none().to_string()))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -712,13 +1211,29 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListProposals { start_after, limit } => {
             query_list_proposals(deps, env, start_after, limit)
         }
+        QueryMsg::ListProposalsByStatus {
+            status,
+            start_after,
+            limit,
+        } => query_list_proposals_by_status(deps, env, status, start_after, limit),
+        QueryMsg::ListProposalsByProposer {
+            proposer,
+            start_after,
+            limit,
+        } => query_list_proposals_by_proposer(deps, env, proposer, start_after, limit),
         QueryMsg::ProposalCount {} => query_proposal_count(deps),
         QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, proposal_id, voter),
+        QueryMsg::ListVotesByVoter {
+            voter,
+            start_after,
+            limit,
+        } => query_list_votes_by_voter(deps, voter, start_after, limit),
         QueryMsg::ListVotes {
             proposal_id,
             start_after,
             limit,
         } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::BallotNonce { address } => query_ballot_nonce(deps, address),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ReverseProposals {
             start_before,
@@ -727,6 +1242,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
         QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::IndexProgress {} => to_binary(&INDEX_PROGRESS.may_load(deps.storage)?),
         QueryMsg::Dao {} => query_dao(deps),
     }
 }
@@ -742,7 +1258,7 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 }
 
 pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
-    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let proposal = proposals().load(deps.storage, id)?;
     to_binary(&proposal.into_response(&env.block, id)?)
 }
 
@@ -759,7 +1275,7 @@ pub fn query_list_proposals(
 ) -> StdResult<Binary> {
     let min = start_after.map(Bound::exclusive);
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
-    let props: Vec<ProposalResponse> = PROPOSALS
+    let props: Vec<ProposalResponse> = proposals()
         .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
         .take(limit as usize)
         .collect::<Result<Vec<(u64, MultipleChoiceProposal)>, _>>()?
@@ -778,7 +1294,7 @@ pub fn query_reverse_proposals(
 ) -> StdResult<Binary> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
     let max = start_before.map(Bound::exclusive);
-    let props: Vec<ProposalResponse> = PROPOSALS
+    let props: Vec<ProposalResponse> = proposals()
         .range(deps.storage, None, max, cosmwasm_std::Order::Descending)
         .take(limit as usize)
         .collect::<Result<Vec<(u64, MultipleChoiceProposal)>, _>>()?
@@ -789,6 +1305,68 @@ pub fn query_reverse_proposals(
     to_binary(&ProposalListResponse { proposals: props })
 }
 
+pub fn query_list_proposals_by_status(
+    deps: Deps,
+    env: Env,
+    status: Status,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+
+    // Stored statuses may be out of date so check every stored status
+    // that could currently be STATUS. Each contributes at most LIMIT
+    // proposals so the first LIMIT proposals of their union are the
+    // first LIMIT proposals whose current status is STATUS.
+    let mut props = vec![];
+    for stored_status in status.possible_stored_statuses() {
+        let matching = proposals()
+            .idx
+            .status
+            .prefix(stored_status.to_string())
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                cosmwasm_std::Order::Ascending,
+            )
+            .map(|item| item.and_then(|(id, proposal)| proposal.into_response(&env.block, id)))
+            .filter(|item| {
+                item.as_ref()
+                    .map_or(true, |response| response.proposal.status == status)
+            })
+            .take(limit)
+            .collect::<StdResult<Vec<ProposalResponse>>>()?;
+        props.extend(matching);
+    }
+    props.sort_by_key(|response| response.id);
+    props.truncate(limit);
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_list_proposals_by_proposer(
+    deps: Deps,
+    env: Env,
+    proposer: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let proposer = deps.api.addr_validate(&proposer)?;
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let props: Vec<ProposalResponse> = proposals()
+        .idx
+        .proposer
+        .prefix(proposer)
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| item.and_then(|(id, proposal)| proposal.into_response(&env.block, id)))
+        .collect::<StdResult<_>>()?;
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
 pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
     let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
     to_binary(&proposal_count)
@@ -796,11 +1374,12 @@ pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
 
 pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Binary> {
     let voter = deps.api.addr_validate(&voter)?;
-    let ballot = BALLOTS.may_load(deps.storage, (proposal_id, voter.clone()))?;
+    let ballot = ballots().may_load(deps.storage, (proposal_id, voter.clone()))?;
     let vote = ballot.map(|ballot| VoteInfo {
         voter,
         vote: ballot.vote,
         power: ballot.power,
+        rationale: ballot.rationale,
     });
     to_binary(&VoteResponse { vote })
 }
@@ -817,7 +1396,7 @@ pub fn query_list_votes(
         .transpose()?;
     let min = start_after.map(Bound::<Addr>::exclusive);
 
-    let votes = BALLOTS
+    let votes = ballots()
         .prefix(proposal_id)
         .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
         .take(limit as usize)
@@ -827,6 +1406,7 @@ pub fn query_list_votes(
                 voter,
                 vote: ballot.vote,
                 power: ballot.power,
+                rationale: ballot.rationale,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -834,6 +1414,44 @@ pub fn query_list_votes(
     to_binary(&VoteListResponse { votes })
 }
 
+pub fn query_list_votes_by_voter(
+    deps: Deps,
+    voter: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let min = start_after.map(|proposal_id| Bound::exclusive((proposal_id, voter.clone())));
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    let votes = ballots()
+        .idx
+        .voter
+        .prefix(voter)
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let ((proposal_id, _), ballot) = item?;
+            Ok(VoterVoteInfo {
+                proposal_id,
+                vote: ballot.vote,
+                power: ballot.power,
+                rationale: ballot.rationale,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&VoterVoteListResponse { votes })
+}
+
+pub fn query_ballot_nonce(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let nonce = BALLOT_NONCES
+        .may_load(deps.storage, address)?
+        .unwrap_or_default();
+    to_binary(&nonce)
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&cwd_interface::voting::InfoResponse { info })
@@ -844,7 +1462,7 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
-            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
+            proposals().update(deps.storage, proposal_id, |prop| match prop {
                 Some(mut prop) => {
                     prop.status = Status::ExecutionFailed;
                     Ok(prop)
@@ -898,5 +1516,14 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Proposals and ballots saved by earlier versions of this module
+    // have not been indexed. They are indexed in batches by
+    // `IndexExisting`.
+    INDEX_PROGRESS.save(
+        deps.storage,
+        &IndexProgress::Proposals { start_after: None },
+    )?;
+
     Ok(Response::default())
 }
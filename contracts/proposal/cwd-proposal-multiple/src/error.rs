@@ -3,7 +3,10 @@ use std::u64;
 use cosmwasm_std::StdError;
 use cw_utils::ParseReplyError;
 use cwd_hooks::HookError;
-use cwd_voting::{reply::error::TagError, threshold::ThresholdError};
+use cwd_voting::{
+    anti_sniping::AntiSnipingError, reply::error::TagError, signed_ballot::SignedBallotError,
+    threshold::ThresholdError,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -32,6 +35,9 @@ pub enum ContractError {
     #[error("No such proposal ({id})")]
     NoSuchProposal { id: u64 },
 
+    #[error("No vote exists for proposal ({id}) and voter ({voter})")]
+    NoSuchVote { id: u64, voter: String },
+
     #[error("Proposal is ({size}) bytes, must be <= ({max}) bytes")]
     ProposalTooLarge { size: u64, max: u64 },
 
@@ -59,7 +65,7 @@ pub enum ContractError {
     #[error("Proposal is not expired.")]
     NotExpired {},
 
-    #[error("Only rejected proposals may be closed.")]
+    #[error("Only rejected proposals and passed proposals whose execution window has ended may be closed.")]
     WrongCloseStatus {},
 
     #[error("The DAO is currently inactive, you cannot create proposals.")]
@@ -77,6 +83,12 @@ pub enum ContractError {
     #[error("Invalid vote selected.")]
     InvalidVote {},
 
+    #[error("Ballot type does not match the proposal's voting strategy.")]
+    WrongBallotType {},
+
+    #[error("Allocated credits exceed voting power.")]
+    InsufficientCredits {},
+
     #[error("Must have voting power to propose.")]
     MustHaveVotingPower {},
 
@@ -98,4 +110,25 @@ pub enum ContractError {
 
     #[error("received a reply failure with an invalid ID: ({id})")]
     InvalidReplyID { id: u64 },
+
+    #[error(transparent)]
+    SignedBallotError(#[from] SignedBallotError),
+
+    #[error("no ballots to relay")]
+    NoBallots {},
+
+    #[error("ballot nonce is too low. the signer's next nonce is ({next})")]
+    InvalidNonce { next: u64 },
+
+    #[error(transparent)]
+    AntiSnipingError(#[from] AntiSnipingError),
+
+    #[error("The execution window for this proposal has ended.")]
+    ExecutionWindowExpired {},
+
+    #[error("Proposal ({id}) has been voted on and may no longer be updated or withdrawn.")]
+    VotingStarted { id: u64 },
+
+    #[error("All proposals and ballots have been indexed.")]
+    IndexingComplete {},
 }
@@ -2,8 +2,11 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cw_utils::Duration;
 use cwd_macros::{info_query, proposal_module_query};
 use cwd_voting::{
-    multiple_choice::{MultipleChoiceOptions, MultipleChoiceVote, VotingStrategy},
+    anti_sniping::AntiSnipingConfig,
+    multiple_choice::{MultipleChoiceBallot, MultipleChoiceOptions, VotingStrategy},
     pre_propose::PreProposeInfo,
+    signed_ballot::SignedBallot,
+    status::Status,
 };
 
 #[cw_serde]
@@ -37,6 +40,21 @@ pub struct InstantiateMsg {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// If set, a vote cast within `window` of a proposal's
+    /// expiration which changes its winning option extends its
+    /// voting period by `extension`, at most `max_extensions` times.
+    pub anti_sniping: Option<AntiSnipingConfig>,
+    /// If set, proposals are pending for this amount of time after
+    /// they are created and may not be voted on. This gives members
+    /// time to read proposals and to stake or unstake before voting
+    /// power is snapshotted when voting opens. Must be measured in
+    /// blocks, as must `max_voting_period`.
+    pub voting_delay: Option<Duration>,
+    /// If set, passed proposals which are not executed within this
+    /// amount of time after their voting period ends move to the
+    /// `execution_expired` status and may no longer be
+    /// executed. Must have the same units as `max_voting_period`.
+    pub execution_window: Option<Duration>,
 }
 
 #[cw_serde]
@@ -56,13 +74,63 @@ pub enum ExecuteMsg {
         /// set the proposer of the proposal it creates.
         proposer: Option<String>,
     },
+    /// Replaces the title, description, and choices of a
+    /// proposal. Only the proposal's proposer may update it, and only
+    /// before any votes have been cast on it.
+    UpdateProposal {
+        /// The ID of the proposal to update.
+        proposal_id: u64,
+        /// The new title of the proposal.
+        title: String,
+        /// The new description of the proposal.
+        description: String,
+        /// The new choices of the proposal.
+        choices: MultipleChoiceOptions,
+        /// The proposer of the proposal. As with `Propose`, this must
+        /// be None unless a pre-propose module is attached to this
+        /// module, in which case it must be Some and the update must
+        /// be sent by the pre-propose module.
+        proposer: Option<String>,
+    },
+    /// Withdraws a proposal, moving it to the `withdrawn` status. Only
+    /// the proposal's proposer may withdraw it, and only before any
+    /// votes have been cast on it. If applicable, the proposal's
+    /// deposit is refunded according to its withdrawal refund
+    /// policy.
+    WithdrawProposal {
+        /// The ID of the proposal to withdraw.
+        proposal_id: u64,
+        /// The proposer of the proposal. See `UpdateProposal`.
+        proposer: Option<String>,
+    },
     /// Votes on a proposal. Voting power is determined by the DAO's
     /// voting power module.
     Vote {
         /// The ID of the proposal to vote on.
         proposal_id: u64,
-        /// The senders position on the proposal.
-        vote: MultipleChoiceVote,
+        /// The senders position on the proposal. This must match
+        /// the proposal's voting strategy.
+        vote: MultipleChoiceBallot,
+        /// An optional rationale for why this vote was cast. This
+        /// can be updated, set, or removed later with
+        /// `UpdateRationale`.
+        rationale: Option<String>,
+    },
+    /// Casts votes signed off chain by voters on their behalf. This
+    /// allows voters without gas tokens to vote. Each ballot is
+    /// counted as if its signer had cast it with `Vote`. If any
+    /// ballot is invalid no ballots are counted.
+    RelayVotes {
+        /// The signed ballots. See
+        /// `cwd_voting::signed_ballot::BallotSignDoc` for what voters
+        /// sign.
+        ballots: Vec<SignedBallot<MultipleChoiceBallot>>,
+    },
+    /// Updates the sender's rationale for their vote on the specified
+    /// proposal. Errors if no vote has been cast.
+    UpdateRationale {
+        proposal_id: u64,
+        rationale: Option<String>,
     },
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
@@ -112,6 +180,19 @@ pub enum ExecuteMsg {
         /// remain open until the DAO's treasury was large enough for it to be
         /// executed.
         close_proposal_on_execution_failure: bool,
+        /// Optional configuration for extending the voting period of
+        /// proposals whose outcome is changed shortly before they
+        /// expire. This will only apply to proposals created after
+        /// the config update.
+        anti_sniping: Option<AntiSnipingConfig>,
+        /// Optional amount of time proposals are pending after they
+        /// are created before they may be voted on. This will only
+        /// apply to proposals created after the config update.
+        voting_delay: Option<Duration>,
+        /// Optional amount of time passed proposals may be executed
+        /// for after their voting period ends. This will only apply
+        /// to proposals created after the config update.
+        execution_window: Option<Duration>,
     },
     /// Update's the proposal creation policy used for this
     /// module. Only the DAO may call this method.
@@ -130,6 +211,17 @@ pub enum ExecuteMsg {
     RemoveVoteHook {
         address: String,
     },
+    /// Indexes up to LIMIT proposals or ballots saved by an earlier
+    /// version of this module. Migrating from an earlier version
+    /// starts indexing, which is done in batches so that large
+    /// modules do not exceed the gas limit. Until it completes,
+    /// queries by status, proposer, or voter may omit older proposals
+    /// and ballots. Callable by any address.
+    IndexExisting {
+        /// The maximum number of proposals or ballots to index. If
+        /// no limit is specified 30 are indexed.
+        limit: Option<u64>,
+    },
 }
 
 #[proposal_module_query]
@@ -157,6 +249,22 @@ pub enum QueryMsg {
         start_before: Option<u64>,
         limit: Option<u64>,
     },
+    /// Lists the proposals whose current status is STATUS in
+    /// ascending order of proposal ID.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposalsByStatus {
+        status: Status,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+    /// Lists the proposals created by PROPOSER in ascending order of
+    /// proposal ID.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposalsByProposer {
+        proposer: String,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
     /// Returns the number of proposals that have been created in this
     /// module./// Returns a voters position on a propsal.
     #[returns(u64)]
@@ -172,6 +280,18 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u64>,
     },
+    /// Lists the votes cast by VOTER across all proposals in
+    /// ascending order of proposal ID.
+    #[returns(crate::query::VoterVoteListResponse)]
+    ListVotesByVoter {
+        voter: String,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+    /// Returns the lowest nonce that will be accepted for the next
+    /// ballot signed by ADDRESS.
+    #[returns(u64)]
+    BallotNonce { address: String },
     /// Gets the current proposal creation policy for this
     /// module.
     #[returns(cwd_voting::pre_propose::ProposalCreationPolicy)]
@@ -183,12 +303,17 @@ pub enum QueryMsg {
     /// module.
     #[returns(cwd_hooks::HooksResponse)]
     VoteHooks {},
+    /// Gets the progress of indexing proposals and ballots saved
+    /// by an earlier version of this module, or None if indexing is
+    /// complete.
+    #[returns(Option<crate::state::IndexProgress>)]
+    IndexProgress {},
 }
 
 #[cw_serde]
 pub struct VoteMsg {
     pub proposal_id: u64,
-    pub vote: MultipleChoiceVote,
+    pub vote: MultipleChoiceBallot,
 }
 
 #[cw_serde]
@@ -1,9 +1,11 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, BlockInfo, StdError, StdResult, Uint128};
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
 use cwd_voting::{
+    anti_sniping::AntiSnipingState,
     multiple_choice::{
-        CheckedMultipleChoiceOption, MultipleChoiceOptionType, MultipleChoiceVotes, VotingStrategy,
+        quadratic_weight, CheckedMultipleChoiceOption, MultipleChoiceBallot,
+        MultipleChoiceOptionType, MultipleChoiceVote, MultipleChoiceVotes, Ranking, VotingStrategy,
     },
     proposal::Proposal,
     status::Status,
@@ -18,9 +20,10 @@ pub struct MultipleChoiceProposal {
     pub description: String,
     /// The address that created this proposal.
     pub proposer: Addr,
-    /// The block height at which this proposal was created. Voting
-    /// power queries should query for voting power at this block
-    /// height.
+    /// The block height at which this proposal was created, or if it
+    /// has a voting delay, the block height at which its voting delay
+    /// ends. Voting power queries should query for voting power at
+    /// this block height.
     pub start_height: u64,
     /// The minimum amount of time this proposal must remain open for
     /// voting. The proposal may not pass unless this is expired or
@@ -37,13 +40,34 @@ pub struct MultipleChoiceProposal {
     pub voting_strategy: VotingStrategy,
     /// The total power when the proposal started (used to calculate percentages)
     pub total_power: Uint128,
-    /// The vote tally.
+    /// The vote tally. For ranked choice proposals this holds the
+    /// first preferences of each ballot and for quadratic proposals
+    /// the sum of the square roots of the credits allocated to each
+    /// option.
     pub votes: MultipleChoiceVotes,
+    /// The total voting power of the addresses that have voted. Zero
+    /// for proposals created before it was tracked, see `turnout()`.
+    #[serde(default)]
+    pub turnout: Uint128,
+    /// The rankings cast on a ranked choice proposal. Empty for all
+    /// other voting strategies.
+    #[serde(default)]
+    pub rankings: Vec<Ranking>,
     /// Whether DAO members are allowed to change their votes.
     /// When disabled, proposals can be executed as soon as they pass.
     /// When enabled, proposals can only be executed after the voting
     /// perid has ended and the proposal passed.
     pub allow_revoting: bool,
+    /// The anti-sniping configuration of this proposal and the
+    /// number of times its voting period has been extended, if
+    /// any. Extensions move `expiration` later.
+    pub anti_sniping: Option<AntiSnipingState>,
+    /// If this proposal has a voting delay, the time at which voting
+    /// opens. Its voting period begins then.
+    pub voting_start: Option<Expiration>,
+    /// If set, the amount of time this proposal may be executed for
+    /// once its voting period has ended.
+    pub execution_window: Option<Duration>,
 }
 
 pub enum VoteResult {
@@ -70,22 +94,67 @@ impl MultipleChoiceProposal {
     /// information.
     pub fn into_response(mut self, block: &BlockInfo, id: u64) -> StdResult<ProposalResponse> {
         self.update_status(block)?;
+        self.turnout = self.turnout();
         Ok(ProposalResponse { id, proposal: self })
     }
 
+    /// Returns the total voting power of the addresses that have
+    /// voted. Proposals created before turnout was tracked only
+    /// accepted single choice ballots, so their turnout is the sum of
+    /// their votes. Any other proposal with zero turnout has no votes.
+    /// Turnout is backfilled when existing proposals are indexed after
+    /// a migration.
+    pub fn turnout(&self) -> Uint128 {
+        if self.turnout.is_zero() {
+            self.votes.total()
+        } else {
+            self.turnout
+        }
+    }
+
     /// Gets the current status of the proposal.
     pub fn current_status(&self, block: &BlockInfo) -> StdResult<Status> {
-        if self.status == Status::Open && self.is_passed(block)? {
-            Ok(Status::Passed)
-        } else if self.status == Status::Open
+        // Pending proposals open once their voting delay has elapsed.
+        let status = match self.status {
+            Status::Pending if self.is_pending(block) => return Ok(Status::Pending),
+            Status::Pending => Status::Open,
+            status => status,
+        };
+        let status = if status == Status::Open && self.is_passed(block)? {
+            Status::Passed
+        } else if status == Status::Open
             && (self.expiration.is_expired(block) || self.is_rejected(block)?)
         {
-            Ok(Status::Rejected)
+            Status::Rejected
         } else {
-            Ok(self.status)
+            status
+        };
+        // Passed proposals which were not executed during their
+        // execution window may no longer be executed.
+        match self.execution_deadline()? {
+            Some(deadline) if status == Status::Passed && deadline.is_expired(block) => {
+                Ok(Status::ExecutionExpired)
+            }
+            _ => Ok(status),
         }
     }
 
+    /// Returns the time after which this proposal may no longer be
+    /// executed, if it has an execution window. The window begins
+    /// once the proposal's voting period has ended.
+    pub fn execution_deadline(&self) -> StdResult<Option<Expiration>> {
+        self.execution_window
+            .map(|window| self.expiration + window)
+            .transpose()
+    }
+
+    /// Returns true if this proposal's voting delay has not yet
+    /// elapsed. This does not check the proposal's status.
+    pub fn is_pending(&self, block: &BlockInfo) -> bool {
+        self.voting_start
+            .map_or(false, |start| !start.is_expired(block))
+    }
+
     /// Sets a proposals status to its current status.
     pub fn update_status(&mut self, block: &BlockInfo) -> StdResult<()> {
         let new_status = self.current_status(block)?;
@@ -118,7 +187,7 @@ impl MultipleChoiceProposal {
 
         // Proposal can only pass if quorum has been met.
         if does_vote_count_pass(
-            self.votes.total(),
+            self.turnout(),
             self.total_power,
             self.voting_strategy.get_quorum(),
         ) {
@@ -157,13 +226,13 @@ impl MultipleChoiceProposal {
             // there is no voting power left.
             VoteResult::Tie => {
                 let rejected =
-                    self.expiration.is_expired(block) || self.total_power == self.votes.total();
+                    self.expiration.is_expired(block) || self.total_power == self.turnout();
                 Ok(rejected)
             }
             VoteResult::SingleWinner(winning_choice) => {
                 match (
                     does_vote_count_pass(
-                        self.votes.total(),
+                        self.turnout(),
                         self.total_power,
                         self.voting_strategy.get_quorum(),
                     ),
@@ -193,10 +262,138 @@ impl MultipleChoiceProposal {
         }
     }
 
-    /// Find the option with the highest vote weight, and note if there is a tie.
+    /// Adds a ballot cast with POWER to the proposal's tally.
+    pub fn add_ballot(&mut self, ballot: &MultipleChoiceBallot, power: Uint128) -> StdResult<()> {
+        // Read before the votes change as legacy turnout is derived
+        // from them.
+        let turnout = self.turnout();
+        match ballot {
+            MultipleChoiceBallot::Single(vote) => self.votes.add_vote(*vote, power)?,
+            MultipleChoiceBallot::Approval { option_ids } => {
+                for &option_id in option_ids {
+                    self.votes
+                        .add_vote(MultipleChoiceVote { option_id }, power)?;
+                }
+            }
+            MultipleChoiceBallot::Ranked { option_ids } => {
+                if let Some(&option_id) = option_ids.first() {
+                    self.votes
+                        .add_vote(MultipleChoiceVote { option_id }, power)?;
+                }
+                match self
+                    .rankings
+                    .iter_mut()
+                    .find(|ranking| &ranking.option_ids == option_ids)
+                {
+                    Some(ranking) => {
+                        ranking.power = ranking
+                            .power
+                            .checked_add(power)
+                            .map_err(StdError::overflow)?
+                    }
+                    None => self.rankings.push(Ranking {
+                        option_ids: option_ids.clone(),
+                        power,
+                    }),
+                }
+            }
+            MultipleChoiceBallot::Quadratic { allocations } => {
+                for allocation in allocations {
+                    self.votes.add_vote(
+                        MultipleChoiceVote {
+                            option_id: allocation.option_id,
+                        },
+                        quadratic_weight(allocation.credits),
+                    )?;
+                }
+            }
+        }
+        self.turnout = turnout.checked_add(power).map_err(StdError::overflow)?;
+        Ok(())
+    }
+
+    /// Removes a ballot previously added with `add_ballot` from the
+    /// proposal's tally.
+    pub fn remove_ballot(
+        &mut self,
+        ballot: &MultipleChoiceBallot,
+        power: Uint128,
+    ) -> StdResult<()> {
+        let turnout = self.turnout();
+        match ballot {
+            MultipleChoiceBallot::Single(vote) => self.votes.remove_vote(*vote, power)?,
+            MultipleChoiceBallot::Approval { option_ids } => {
+                for &option_id in option_ids {
+                    self.votes
+                        .remove_vote(MultipleChoiceVote { option_id }, power)?;
+                }
+            }
+            MultipleChoiceBallot::Ranked { option_ids } => {
+                if let Some(&option_id) = option_ids.first() {
+                    self.votes
+                        .remove_vote(MultipleChoiceVote { option_id }, power)?;
+                }
+                let index = self
+                    .rankings
+                    .iter()
+                    .position(|ranking| &ranking.option_ids == option_ids)
+                    .ok_or_else(|| StdError::not_found("ranking"))?;
+                let remaining = self.rankings[index]
+                    .power
+                    .checked_sub(power)
+                    .map_err(StdError::overflow)?;
+                if remaining.is_zero() {
+                    self.rankings.remove(index);
+                } else {
+                    self.rankings[index].power = remaining;
+                }
+            }
+            MultipleChoiceBallot::Quadratic { allocations } => {
+                for allocation in allocations {
+                    self.votes.remove_vote(
+                        MultipleChoiceVote {
+                            option_id: allocation.option_id,
+                        },
+                        quadratic_weight(allocation.credits),
+                    )?;
+                }
+            }
+        }
+        self.turnout = turnout.checked_sub(power).map_err(StdError::overflow)?;
+        Ok(())
+    }
+
+    /// Returns the index of the option this proposal would pass with
+    /// were it to expire with its current votes, or None if it would
+    /// be rejected. This ignores the minimum voting period and is
+    /// used to determine if a vote changed the outcome of the
+    /// proposal.
+    pub fn passing_choice(&self) -> StdResult<Option<u32>> {
+        if !does_vote_count_pass(
+            self.turnout(),
+            self.total_power,
+            self.voting_strategy.get_quorum(),
+        ) {
+            return Ok(None);
+        }
+        match self.calculate_vote_result()? {
+            VoteResult::SingleWinner(choice)
+                if choice.option_type != MultipleChoiceOptionType::None =>
+            {
+                Ok(Some(choice.index))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Find the winning option according to the proposal's voting
+    /// strategy, and note if there is a tie.
     pub fn calculate_vote_result(&self) -> StdResult<VoteResult> {
         match self.voting_strategy {
-            VotingStrategy::SingleChoice { quorum: _ } => {
+            VotingStrategy::RankedChoice { .. } => Ok(self.instant_runoff()),
+            VotingStrategy::SingleChoice { .. }
+            | VotingStrategy::Approval { .. }
+            | VotingStrategy::Quadratic { .. } => {
                 // We expect to have at least 3 vote weights
                 if let Some(max_weight) = self.votes.vote_weights.iter().max_by(|&a, &b| a.cmp(b)) {
                     let top_choices: Vec<(usize, &Uint128)> = self
@@ -228,12 +425,67 @@ impl MultipleChoiceProposal {
         }
     }
 
+    /// Runs an instant runoff over the proposal's rankings. Each
+    /// round every ranking counts towards its most preferred option
+    /// that has not been eliminated. An option wins once it holds a
+    /// majority of the rankings still counting towards some option,
+    /// otherwise every option tied for the fewest votes is
+    /// eliminated. If all remaining options are tied the result is a
+    /// tie.
+    fn instant_runoff(&self) -> VoteResult {
+        let mut eliminated = vec![false; self.choices.len()];
+        loop {
+            let mut tally = vec![Uint128::zero(); self.choices.len()];
+            for ranking in &self.rankings {
+                if let Some(&option_id) = ranking
+                    .option_ids
+                    .iter()
+                    .find(|&&option_id| !eliminated[option_id as usize])
+                {
+                    tally[option_id as usize] += ranking.power;
+                }
+            }
+            let continuing: Uint128 = tally.iter().sum();
+            let remaining: Vec<usize> = (0..self.choices.len())
+                .filter(|&index| !eliminated[index])
+                .collect();
+
+            if let Some(&winner) = remaining
+                .iter()
+                .find(|&&index| tally[index] > continuing - tally[index])
+            {
+                return VoteResult::SingleWinner(self.choices[winner].clone());
+            }
+
+            let fewest = remaining
+                .iter()
+                .map(|&index| tally[index])
+                .min()
+                .unwrap_or_default();
+            let lowest: Vec<usize> = remaining
+                .iter()
+                .copied()
+                .filter(|&index| tally[index] == fewest)
+                .collect();
+            if lowest.len() == remaining.len() {
+                return VoteResult::Tie;
+            }
+            for index in lowest {
+                eliminated[index] = true;
+            }
+        }
+    }
+
     /// Ensure that with the remaining vote power, the choice with the second highest votes
     /// cannot overtake the first choice.
     fn is_choice_unbeatable(
         &self,
         winning_choice: &CheckedMultipleChoiceOption,
     ) -> StdResult<bool> {
+        if let VotingStrategy::RankedChoice { .. } = self.voting_strategy {
+            return Ok(self.is_ranked_choice_unbeatable(winning_choice));
+        }
+
         let winning_choice_power = self.votes.vote_weights[winning_choice.index as usize];
         if let Some(second_choice_power) = self
             .votes
@@ -243,7 +495,10 @@ impl MultipleChoiceProposal {
             .max_by(|&a, &b| a.cmp(b))
         {
             // Check if the remaining vote power can be used to overtake the current winning choice.
-            let remaining_vote_power = self.total_power - self.votes.total();
+            // Under approval voting each remaining voter may add their power to every option.
+            // Under quadratic voting each remaining voter adds at most the square root of their
+            // power to an option, which is never more than their power.
+            let remaining_vote_power = self.total_power - self.turnout();
             match winning_choice.option_type {
                 MultipleChoiceOptionType::Standard => {
                     if winning_choice_power > *second_choice_power + remaining_vote_power {
@@ -264,6 +519,22 @@ impl MultipleChoiceProposal {
         }
         Ok(false)
     }
+
+    /// Instant runoff results are not monotonic in the votes cast, so
+    /// a ranked choice winner is only known to be unbeatable once it
+    /// holds a majority of first preferences out of all possible
+    /// voting power. No future ballots can then prevent it winning
+    /// the first round.
+    fn is_ranked_choice_unbeatable(&self, winning_choice: &CheckedMultipleChoiceOption) -> bool {
+        let first_preferences = self.votes.vote_weights[winning_choice.index as usize];
+        let others = self.total_power - first_preferences;
+        match winning_choice.option_type {
+            MultipleChoiceOptionType::Standard => first_preferences > others,
+            // As above, a tie with "None of the above" fails the
+            // proposal.
+            MultipleChoiceOptionType::None => first_preferences >= others,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -271,7 +542,9 @@ mod tests {
     use super::*;
 
     use cosmwasm_std::testing::mock_env;
-    use cwd_voting::multiple_choice::{MultipleChoiceOption, MultipleChoiceOptions};
+    use cwd_voting::multiple_choice::{
+        MultipleChoiceOption, MultipleChoiceOptions, QuadraticAllocation,
+    };
 
     fn create_proposal(
         block: &BlockInfo,
@@ -311,8 +584,13 @@ mod tests {
             status: Status::Open,
             voting_strategy,
             total_power,
+            turnout: votes.total(),
             votes,
+            rankings: vec![],
             allow_revoting,
+            anti_sniping: None,
+            voting_start: None,
+            execution_window: None,
             min_voting_period: None,
         }
     }
@@ -853,4 +1131,292 @@ mod tests {
         // No quorum reached & proposal has expired => rejection
         assert!(prop.is_rejected(&env.block).unwrap());
     }
+
+    fn ranked(option_ids: Vec<u32>) -> MultipleChoiceBallot {
+        MultipleChoiceBallot::Ranked { option_ids }
+    }
+
+    #[test]
+    fn test_ranked_choice_instant_runoff() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::RankedChoice {
+            quorum: cwd_voting::threshold::PercentageThreshold::Majority {},
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(100),
+            false,
+            false,
+        );
+
+        // Option 1 has the fewest first preferences and is
+        // eliminated. Its ballot transfers to option 0 which then has
+        // a majority.
+        prop.add_ballot(&ranked(vec![0]), Uint128::new(40)).unwrap();
+        prop.add_ballot(&ranked(vec![1, 0]), Uint128::new(20))
+            .unwrap();
+        prop.add_ballot(&ranked(vec![2, 1]), Uint128::new(30))
+            .unwrap();
+        assert!(matches!(
+            prop.calculate_vote_result().unwrap(),
+            VoteResult::SingleWinner(CheckedMultipleChoiceOption { index: 0, .. })
+        ));
+        assert_eq!(prop.turnout, Uint128::new(90));
+        assert_eq!(
+            prop.votes.vote_weights,
+            vec![Uint128::new(40), Uint128::new(20), Uint128::new(30)]
+        );
+
+        // The winner does not hold a majority of first preferences
+        // so the remaining voting power could still change the
+        // result.
+        assert!(!prop.is_passed(&env.block).unwrap());
+
+        // Identical rankings are combined.
+        prop.add_ballot(&ranked(vec![0]), Uint128::new(10)).unwrap();
+        assert_eq!(prop.rankings.len(), 3);
+        assert_eq!(prop.rankings[0].power, Uint128::new(50));
+        assert!(!prop.is_passed(&env.block).unwrap());
+
+        prop.remove_ballot(&ranked(vec![1, 0]), Uint128::new(20))
+            .unwrap();
+        assert_eq!(prop.rankings.len(), 2);
+        prop.add_ballot(&ranked(vec![0, 1]), Uint128::new(20))
+            .unwrap();
+        // 70 of 100 first preferences can not be beaten.
+        assert!(prop.is_passed(&env.block).unwrap());
+    }
+
+    #[test]
+    fn test_ranked_choice_tie() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::RankedChoice {
+            quorum: cwd_voting::threshold::PercentageThreshold::Majority {},
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(100),
+            false,
+            false,
+        );
+        assert!(matches!(
+            prop.calculate_vote_result().unwrap(),
+            VoteResult::Tie
+        ));
+
+        prop.add_ballot(&ranked(vec![0]), Uint128::new(50)).unwrap();
+        prop.add_ballot(&ranked(vec![1]), Uint128::new(50)).unwrap();
+        assert!(matches!(
+            prop.calculate_vote_result().unwrap(),
+            VoteResult::Tie
+        ));
+        // All voting power has been cast so the tie is final.
+        assert!(prop.is_rejected(&env.block).unwrap());
+    }
+
+    #[test]
+    fn test_ranked_choice_unbeatable_none_option() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::RankedChoice {
+            quorum: cwd_voting::threshold::PercentageThreshold::Majority {},
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(100),
+            false,
+            false,
+        );
+
+        // At best the remaining voting power ties "None of the
+        // above" which also rejects the proposal.
+        prop.add_ballot(&ranked(vec![2]), Uint128::new(50)).unwrap();
+        prop.add_ballot(&ranked(vec![0, 1]), Uint128::new(10))
+            .unwrap();
+        assert!(prop.is_rejected(&env.block).unwrap());
+    }
+
+    #[test]
+    fn test_turnout() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::Approval {
+            quorum: cwd_voting::threshold::PercentageThreshold::Majority {},
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(100),
+            false,
+            false,
+        );
+
+        // Ballots count their voter's power once however many
+        // options they approve.
+        let ballot = MultipleChoiceBallot::Approval {
+            option_ids: vec![0, 1],
+        };
+        prop.add_ballot(&ballot, Uint128::new(40)).unwrap();
+        assert_eq!(prop.turnout, Uint128::new(40));
+        assert!(!prop.is_passed(&env.block).unwrap());
+
+        // A single voter changing their vote.
+        prop.remove_ballot(&ballot, Uint128::new(40)).unwrap();
+        assert_eq!(prop.turnout, Uint128::zero());
+        let ballot = MultipleChoiceBallot::Approval {
+            option_ids: vec![0, 1, 2],
+        };
+        prop.add_ballot(&ballot, Uint128::new(40)).unwrap();
+        assert_eq!(prop.turnout, Uint128::new(40));
+        prop.remove_ballot(&ballot, Uint128::new(40)).unwrap();
+        prop.add_ballot(
+            &MultipleChoiceBallot::Approval {
+                option_ids: vec![1],
+            },
+            Uint128::new(40),
+        )
+        .unwrap();
+        assert_eq!(prop.turnout, Uint128::new(40));
+    }
+
+    #[test]
+    fn test_approval_unbeatable() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::Approval {
+            quorum: cwd_voting::threshold::PercentageThreshold::Majority {},
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(100),
+            false,
+            false,
+        );
+
+        prop.add_ballot(
+            &MultipleChoiceBallot::Approval {
+                option_ids: vec![0, 1],
+            },
+            Uint128::new(60),
+        )
+        .unwrap();
+        assert!(matches!(
+            prop.calculate_vote_result().unwrap(),
+            VoteResult::Tie
+        ));
+        assert!(!prop.is_rejected(&env.block).unwrap());
+
+        prop.add_ballot(
+            &MultipleChoiceBallot::Approval {
+                option_ids: vec![0],
+            },
+            Uint128::new(20),
+        )
+        .unwrap();
+        // The remaining 20 voting power could approve option 1 and
+        // tie.
+        assert!(!prop.is_passed(&env.block).unwrap());
+
+        prop.add_ballot(
+            &MultipleChoiceBallot::Approval {
+                option_ids: vec![0],
+            },
+            Uint128::new(10),
+        )
+        .unwrap();
+        assert_eq!(prop.turnout, Uint128::new(90));
+        assert!(prop.is_passed(&env.block).unwrap());
+    }
+
+    #[test]
+    fn test_quadratic_tally() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::Quadratic {
+            quorum: cwd_voting::threshold::PercentageThreshold::Majority {},
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes::zero(3),
+            Uint128::new(200),
+            false,
+            false,
+        );
+
+        let ballot = MultipleChoiceBallot::Quadratic {
+            allocations: vec![
+                QuadraticAllocation {
+                    option_id: 0,
+                    credits: Uint128::new(81),
+                },
+                QuadraticAllocation {
+                    option_id: 1,
+                    credits: Uint128::new(19),
+                },
+            ],
+        };
+        prop.add_ballot(&ballot, Uint128::new(100)).unwrap();
+        assert_eq!(
+            prop.votes.vote_weights,
+            vec![Uint128::new(9), Uint128::new(4), Uint128::zero()]
+        );
+        assert_eq!(prop.turnout, Uint128::new(100));
+        // 100 voting power remains which bounds the weight any option
+        // may still receive.
+        assert!(!prop.is_passed(&env.block).unwrap());
+
+        prop.remove_ballot(&ballot, Uint128::new(100)).unwrap();
+        assert_eq!(prop.votes, MultipleChoiceVotes::zero(3));
+        assert_eq!(prop.turnout, Uint128::zero());
+    }
+    #[test]
+    fn test_legacy_proposal() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::SingleChoice {
+            quorum: cwd_voting::threshold::PercentageThreshold::Majority {},
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            MultipleChoiceVotes {
+                vote_weights: vec![Uint128::new(60), Uint128::zero(), Uint128::zero()],
+            },
+            Uint128::new(100),
+            false,
+            false,
+        );
+
+        // Proposals saved before turnout and rankings were added.
+        let encoded = String::from_utf8(cosmwasm_std::to_vec(&prop).unwrap())
+            .unwrap()
+            .replace(r#""turnout":"60","rankings":[],"#, "");
+        let legacy: MultipleChoiceProposal = cosmwasm_std::from_slice(encoded.as_bytes()).unwrap();
+        assert_eq!(legacy.turnout, Uint128::zero());
+        assert_eq!(legacy.rankings, vec![]);
+        assert_eq!(legacy.turnout(), Uint128::new(60));
+        assert!(legacy.is_passed(&env.block).unwrap());
+
+        prop = legacy;
+        prop.add_ballot(
+            &MultipleChoiceBallot::Single(MultipleChoiceVote { option_id: 1 }),
+            Uint128::new(10),
+        )
+        .unwrap();
+        assert_eq!(prop.turnout, Uint128::new(70));
+
+        // Revoting on a legacy proposal removes its voter's power once.
+        let mut prop: MultipleChoiceProposal =
+            cosmwasm_std::from_slice(encoded.as_bytes()).unwrap();
+        let ballot = MultipleChoiceBallot::Single(MultipleChoiceVote { option_id: 0 });
+        prop.remove_ballot(&ballot, Uint128::new(60)).unwrap();
+        assert_eq!(prop.turnout, Uint128::zero());
+        prop.add_ballot(&ballot, Uint128::new(60)).unwrap();
+        assert_eq!(prop.turnout, Uint128::new(60));
+    }
 }
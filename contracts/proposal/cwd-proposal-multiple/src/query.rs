@@ -2,7 +2,7 @@ use crate::{proposal::MultipleChoiceProposal, state::Config};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 
-use cwd_voting::multiple_choice::MultipleChoiceVote;
+use cwd_voting::multiple_choice::MultipleChoiceBallot;
 
 #[cw_serde]
 pub struct ProposalListResponse {
@@ -22,9 +22,11 @@ pub struct VoteInfo {
     /// The address that voted.
     pub voter: Addr,
     /// Position on the vote.
-    pub vote: MultipleChoiceVote,
+    pub vote: MultipleChoiceBallot,
     /// The voting power behind the vote.
     pub power: Uint128,
+    /// Address-specified rationale for the vote.
+    pub rationale: Option<String>,
 }
 
 #[cw_serde]
@@ -37,6 +39,24 @@ pub struct VoteListResponse {
     pub votes: Vec<VoteInfo>,
 }
 
+/// A vote cast by a voter on a proposal.
+#[cw_serde]
+pub struct VoterVoteInfo {
+    /// The proposal that was voted on.
+    pub proposal_id: u64,
+    /// Position on the vote.
+    pub vote: MultipleChoiceBallot,
+    /// The voting power behind the vote.
+    pub power: Uint128,
+    /// Address-specified rationale for the vote.
+    pub rationale: Option<String>,
+}
+
+#[cw_serde]
+pub struct VoterVoteListResponse {
+    pub votes: Vec<VoterVoteInfo>,
+}
+
 #[cw_serde]
 pub struct VoterResponse {
     pub weight: Option<Uint128>,
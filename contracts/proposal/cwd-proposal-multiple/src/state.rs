@@ -1,11 +1,12 @@
 use crate::proposal::MultipleChoiceProposal;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, KeyDeserialize, Map, MultiIndex};
 use cw_utils::Duration;
 use cwd_hooks::Hooks;
 use cwd_voting::{
-    multiple_choice::{MultipleChoiceVote, VotingStrategy},
+    anti_sniping::AntiSnipingConfig,
+    multiple_choice::{MultipleChoiceBallot, VotingStrategy},
     pre_propose::ProposalCreationPolicy,
 };
 
@@ -43,6 +44,17 @@ pub struct Config {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// If set, votes cast shortly before a proposal expires which
+    /// change its outcome extend its voting period.
+    pub anti_sniping: Option<AntiSnipingConfig>,
+    /// If set, proposals are pending for this amount of time after
+    /// they are created before they may be voted on. Voting power is
+    /// snapshotted when voting opens.
+    pub voting_delay: Option<Duration>,
+    /// If set, passed proposals which are not executed within this
+    /// amount of time after their voting period ends may no longer
+    /// be executed.
+    pub execution_window: Option<Duration>,
 }
 
 // we cast a ballot with our chosen vote and a given weight
@@ -52,14 +64,100 @@ pub struct Ballot {
     /// The amount of voting power behind the vote.
     pub power: Uint128,
     /// The position.
-    pub vote: MultipleChoiceVote,
+    pub vote: MultipleChoiceBallot,
+    /// An optional rationale for why this vote was cast.
+    pub rationale: Option<String>,
 }
 
 /// The current top level config for the module.
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
-pub const PROPOSALS: Map<u64, MultipleChoiceProposal> = Map::new("proposals");
-pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
+
+/// Secondary indexes over proposals.
+pub struct ProposalIndexes<'a> {
+    /// Proposals by their stored status. A proposal's stored status
+    /// is only updated when it is acted on, so an open proposal may
+    /// have since passed or been rejected. See
+    /// `Status::possible_stored_statuses`.
+    pub status: MultiIndex<'a, String, MultipleChoiceProposal, u64>,
+    /// Proposals by their proposer.
+    pub proposer: MultiIndex<'a, Addr, MultipleChoiceProposal, u64>,
+}
+
+impl<'a> IndexList<MultipleChoiceProposal> for ProposalIndexes<'a> {
+    fn get_indexes(
+        &'_ self,
+    ) -> Box<dyn Iterator<Item = &'_ dyn Index<MultipleChoiceProposal>> + '_> {
+        let v: Vec<&dyn Index<MultipleChoiceProposal>> = vec![&self.status, &self.proposer];
+        Box::new(v.into_iter())
+    }
+}
+
+/// The module's proposals indexed by status and proposer.
+pub fn proposals<'a>() -> IndexedMap<'a, u64, MultipleChoiceProposal, ProposalIndexes<'a>> {
+    let indexes = ProposalIndexes {
+        status: MultiIndex::new(
+            |_, proposal| proposal.status.to_string(),
+            "proposals",
+            "proposals__status",
+        ),
+        proposer: MultiIndex::new(
+            |_, proposal| proposal.proposer.clone(),
+            "proposals",
+            "proposals__proposer",
+        ),
+    };
+    IndexedMap::new("proposals", indexes)
+}
+
+/// Progress re-saving proposals and ballots stored by earlier
+/// versions of this module so that they are present in their
+/// indexes.
+#[cw_serde]
+pub enum IndexProgress {
+    /// Proposals after this ID have not been indexed.
+    Proposals { start_after: Option<u64> },
+    /// Ballots after this key have not been indexed.
+    Ballots { start_after: Option<(u64, Addr)> },
+}
+
+/// Indexing progress after a migration. Removed once every proposal
+/// and ballot has been indexed.
+pub const INDEX_PROGRESS: Item<IndexProgress> = Item::new("index_progress");
+
+/// Secondary indexes over ballots.
+pub struct BallotIndexes<'a> {
+    /// Ballots by the address that cast them.
+    pub voter: MultiIndex<'a, Addr, Ballot, (u64, Addr)>,
+}
+
+impl<'a> IndexList<Ballot> for BallotIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Ballot>> + '_> {
+        let v: Vec<&dyn Index<Ballot>> = vec![&self.voter];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Ballots keyed by proposal ID and voter, indexed by voter.
+pub fn ballots<'a>() -> IndexedMap<'a, (u64, Addr), Ballot, BallotIndexes<'a>> {
+    let indexes = BallotIndexes {
+        voter: MultiIndex::new(
+            // Ballots do not store their voter so it is read from
+            // the ballot's (proposal_id, voter) key.
+            |pk, _| {
+                <(u64, Addr)>::from_slice(pk)
+                    .expect("ballot keys are (proposal_id, voter)")
+                    .1
+            },
+            "ballots",
+            "ballots__voter",
+        ),
+    };
+    IndexedMap::new("ballots", indexes)
+}
+/// The lowest nonce that will be accepted for the next signed ballot
+/// from an address. Prevents relayed ballots from being replayed.
+pub const BALLOT_NONCES: Map<Addr, u64> = Map::new("ballot_nonces");
 /// Consumers of proposal state change hooks.
 pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
 /// Consumers of vote hooks.
@@ -129,6 +129,9 @@ where
         allow_revoting: false,
         voting_strategy,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         pre_propose_info,
     };
 
@@ -230,7 +233,8 @@ where
             govmod.clone(),
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: position,
+                vote: position.into(),
+                rationale: None,
             },
             &[],
         );
@@ -254,7 +258,7 @@ where
                 let expected = VoteResponse {
                     vote: Some(VoteInfo {
                         voter: Addr::unchecked(&voter),
-                        vote: position,
+                        vote: position.into(),
                         power: match deposit_config.deposit_info {
                             Some(CheckedDepositInfo {
                                 amount,
@@ -271,6 +275,7 @@ where
                             // expected voting power.
                             _ => weight,
                         },
+                        rationale: None,
                     }),
                 };
                 assert_eq!(vote, expected)
@@ -39,6 +39,10 @@ pub fn make_proposal(
                     denom,
                     amount,
                     refund_policy: _,
+                    withdrawal_refund_policy: _,
+                    veto_destination: _,
+                    funding_period: _,
+                    staking_contract: None,
                 }) => match denom {
                     CheckedDenom::Native(denom) => coins(amount.u128(), denom),
                     CheckedDenom::Cw20(addr) => {
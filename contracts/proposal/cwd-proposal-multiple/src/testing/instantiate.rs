@@ -36,6 +36,11 @@ fn get_pre_propose_info(
             msg: to_binary(&cppm::InstantiateMsg {
                 deposit_info,
                 open_proposal_submission,
+                min_voting_power: None,
+                max_open_proposals: None,
+                submission_cooldown: None,
+                message_restrictions: None,
+                templates: vec![],
                 extension: Empty::default(),
             })
             .unwrap(),
@@ -61,10 +66,16 @@ fn _get_default_token_dao_proposal_module_instantiate(app: &mut App) -> Instanti
                 denom: cwd_voting::deposit::DepositToken::VotingModuleToken {},
                 amount: Uint128::new(10_000_000),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
             }),
             false,
         ),
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
     }
 }
 
@@ -81,6 +92,9 @@ fn _get_default_non_token_dao_proposal_module_instantiate(app: &mut App) -> Inst
         allow_revoting: false,
         pre_propose_info: get_pre_propose_info(app, None, false),
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
     }
 }
 
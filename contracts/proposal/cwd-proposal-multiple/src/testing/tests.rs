@@ -2,16 +2,18 @@ use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Decimal, Empty, Timestamp,
 use cw20::Cw20Coin;
 use cw_denom::{CheckedDenom, UncheckedDenom};
 use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use cwd_core::state::ProposalModule;
 use cwd_hooks::HooksResponse;
 use cwd_interface::{Admin, ModuleInstantiateInfo};
 use cwd_voting::{
+    anti_sniping::{AntiSnipingConfig, AntiSnipingError},
     deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    error::VotingError,
     multiple_choice::{
-        CheckedMultipleChoiceOption, MultipleChoiceOption, MultipleChoiceOptionType,
-        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
-        MAX_NUM_CHOICES,
+        CheckedMultipleChoiceOption, MultipleChoiceBallot, MultipleChoiceOption,
+        MultipleChoiceOptionType, MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes,
+        QuadraticAllocation, VotingStrategy, MAX_NUM_CHOICES,
     },
     pre_propose::PreProposeInfo,
     status::Status,
@@ -23,7 +25,10 @@ use std::panic;
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     proposal::MultipleChoiceProposal,
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse},
+    query::{
+        ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse,
+        VoterVoteInfo, VoterVoteListResponse,
+    },
     state::Config,
     testing::{
         do_votes::do_test_votes_cw20_balances,
@@ -45,6 +50,7 @@ use cwd_pre_propose_multiple as cppm;
 
 use cwd_testing::{
     contracts::{cw20_balances_voting_contract, cw20_contract},
+    signing::{signer_app, TestSigner},
     ShouldExecute,
 };
 
@@ -92,6 +98,11 @@ pub fn get_pre_propose_info(
             msg: to_binary(&cppm::InstantiateMsg {
                 deposit_info,
                 open_proposal_submission,
+                min_voting_power: None,
+                max_open_proposals: None,
+                submission_cooldown: None,
+                message_restrictions: None,
+                templates: vec![],
                 extension: Empty::default(),
             })
             .unwrap(),
@@ -118,6 +129,9 @@ fn test_propose() {
         voting_strategy: voting_strategy.clone(),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
 
@@ -134,6 +148,9 @@ fn test_propose() {
         voting_strategy: voting_strategy.clone(),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
     };
     assert_eq!(config, expected);
 
@@ -170,7 +187,12 @@ fn test_propose() {
         votes: MultipleChoiceVotes {
             vote_weights: vec![Uint128::zero(); 3],
         },
+        turnout: Uint128::zero(),
+        rankings: vec![],
         allow_revoting: false,
+        anti_sniping: None,
+        voting_start: None,
+        execution_window: None,
         min_voting_period: None,
     };
 
@@ -191,6 +213,9 @@ fn test_propose_wrong_num_choices() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -206,6 +231,9 @@ fn test_propose_wrong_num_choices() {
     let expected = Config {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -268,6 +296,9 @@ fn test_proposal_count_initialized_to_zero() {
         max_voting_period: Duration::Height(10),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         only_members_execute: true,
         allow_revoting: false,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
@@ -304,6 +335,9 @@ fn test_no_early_pass_with_min_duration() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
 
@@ -364,7 +398,8 @@ fn test_no_early_pass_with_min_duration() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -393,6 +428,9 @@ fn test_propose_with_messages() {
         max_voting_period: Duration::Height(10),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         only_members_execute: true,
         allow_revoting: false,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
@@ -428,6 +466,9 @@ fn test_propose_with_messages() {
         },
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period: cw_utils::Duration::Height(20),
         only_members_execute: false,
         allow_revoting: false,
@@ -471,7 +512,8 @@ fn test_propose_with_messages() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -511,6 +553,9 @@ fn test_min_duration_units_missmatch() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
     instantiate_with_staked_balances_governance(
@@ -543,6 +588,9 @@ fn test_min_duration_larger_than_proposal_duration() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
     instantiate_with_staked_balances_governance(
@@ -574,6 +622,9 @@ fn test_min_duration_same_as_proposal_duration() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
 
@@ -634,7 +685,8 @@ fn test_min_duration_same_as_proposal_duration() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -650,7 +702,8 @@ fn test_min_duration_same_as_proposal_duration() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 2 },
+            vote: MultipleChoiceVote { option_id: 2 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -678,6 +731,9 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -688,6 +744,9 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
                 denom: DepositToken::VotingModuleToken {},
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
             }),
             false,
         ),
@@ -712,7 +771,11 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
         Some(CheckedDepositInfo {
             denom: CheckedDenom::Cw20(token),
             amount: Uint128::new(1),
-            refund_policy: DepositRefundPolicy::OnlyPassed
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         })
     )
 }
@@ -748,6 +811,9 @@ fn test_different_token_proposal_deposit() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -760,6 +826,9 @@ fn test_different_token_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
             }),
             false,
         ),
@@ -809,6 +878,9 @@ fn test_bad_token_proposal_deposit() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -821,6 +893,9 @@ fn test_bad_token_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
             }),
             false,
         ),
@@ -841,6 +916,9 @@ fn test_take_proposal_deposit() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -851,6 +929,9 @@ fn test_take_proposal_deposit() {
                 denom: DepositToken::VotingModuleToken {},
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
             }),
             false,
         ),
@@ -946,6 +1027,9 @@ fn test_native_proposal_deposit() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         pre_propose_info: get_pre_propose_info(
             &mut app,
             Some(UncheckedDepositInfo {
@@ -954,6 +1038,9 @@ fn test_native_proposal_deposit() {
                 },
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::Always,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
             }),
             false,
         ),
@@ -1042,7 +1129,8 @@ fn test_native_proposal_deposit() {
             govmod.clone(),
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 1 },
+                vote: MultipleChoiceVote { option_id: 1 }.into(),
+                rationale: None,
             },
             &[],
         );
@@ -1091,6 +1179,9 @@ fn test_deposit_return_on_execute() {
             denom: DepositToken::VotingModuleToken {},
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         true,
     );
@@ -1228,13 +1319,15 @@ fn test_query_list_votes() {
     let expected = vec![
         VoteInfo {
             voter: Addr::unchecked("blue"),
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
             power: Uint128::new(10),
+            rationale: None,
         },
         VoteInfo {
             voter: Addr::unchecked("note"),
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote { option_id: 1 }.into(),
             power: Uint128::new(20),
+            rationale: None,
         },
     ];
 
@@ -1304,7 +1397,8 @@ fn test_cant_vote_executed_or_closed() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -1342,7 +1436,8 @@ fn test_cant_vote_executed_or_closed() {
         govmod,
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -1359,6 +1454,9 @@ fn test_cant_propose_zero_power() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1369,6 +1467,9 @@ fn test_cant_propose_zero_power() {
                 denom: DepositToken::VotingModuleToken {},
                 amount: Uint128::new(1),
                 refund_policy: DepositRefundPolicy::Always,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
             }),
             false,
         ),
@@ -1482,6 +1583,9 @@ fn test_cant_vote_not_registered() {
             denom: DepositToken::VotingModuleToken {},
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -1502,7 +1606,8 @@ fn test_cant_vote_not_registered() {
             govmod,
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 0 },
+                vote: MultipleChoiceVote { option_id: 0 }.into(),
+                rationale: None,
             },
             &[],
         )
@@ -1528,6 +1633,9 @@ fn test_cant_execute_not_member() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: true,
         allow_revoting: false,
@@ -1578,7 +1686,8 @@ fn test_cant_execute_not_member() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -1617,6 +1726,9 @@ fn test_open_proposal_submission() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         pre_propose_info: get_pre_propose_info(&mut app, None, true),
     };
     let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
@@ -1650,6 +1762,9 @@ fn test_open_proposal_submission() {
         expiration: max_voting_period.after(&current_block),
         min_voting_period: None,
         allow_revoting: false,
+        anti_sniping: None,
+        voting_start: None,
+        execution_window: None,
         total_power: Uint128::new(100_000_000),
         status: Status::Open,
         voting_strategy: VotingStrategy::SingleChoice {
@@ -1681,6 +1796,8 @@ fn test_open_proposal_submission() {
         votes: MultipleChoiceVotes {
             vote_weights: vec![Uint128::zero(); 3],
         },
+        turnout: Uint128::zero(),
+        rankings: vec![],
     };
 
     assert_eq!(created.proposal, expected);
@@ -1705,6 +1822,9 @@ fn test_close_open_proposal() {
             denom: DepositToken::VotingModuleToken {},
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -1773,6 +1893,9 @@ fn test_no_refund_failed_proposal() {
             denom: DepositToken::VotingModuleToken {},
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -1851,6 +1974,9 @@ fn test_deposit_return_on_close() {
             denom: DepositToken::VotingModuleToken {},
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -1901,6 +2027,9 @@ fn test_execute_expired_proposal() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1963,7 +2092,8 @@ fn test_execute_expired_proposal() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -2061,6 +2191,9 @@ fn test_update_config() {
             },
             min_voting_period: None,
             close_proposal_on_execution_failure: true,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
             max_voting_period: cw_utils::Duration::Height(10),
             only_members_execute: false,
             allow_revoting: false,
@@ -2080,6 +2213,9 @@ fn test_update_config() {
             },
             min_voting_period: None,
             close_proposal_on_execution_failure: true,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
             max_voting_period: cw_utils::Duration::Height(10),
             only_members_execute: false,
             allow_revoting: false,
@@ -2097,6 +2233,9 @@ fn test_update_config() {
         },
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period: cw_utils::Duration::Height(10),
         only_members_execute: false,
         allow_revoting: false,
@@ -2115,6 +2254,9 @@ fn test_update_config() {
             },
             min_voting_period: None,
             close_proposal_on_execution_failure: true,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
             max_voting_period: cw_utils::Duration::Height(10),
             only_members_execute: false,
             allow_revoting: false,
@@ -2143,6 +2285,9 @@ fn test_no_return_if_no_refunds() {
             denom: DepositToken::VotingModuleToken {},
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         true,
     );
@@ -2189,6 +2334,9 @@ fn test_query_list_proposals() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -2270,7 +2418,12 @@ fn test_query_list_proposals() {
             votes: MultipleChoiceVotes {
                 vote_weights: vec![Uint128::zero(); 3],
             },
+            turnout: Uint128::zero(),
+            rankings: vec![],
             allow_revoting: false,
+            anti_sniping: None,
+            voting_start: None,
+            execution_window: None,
             min_voting_period: None,
         },
     };
@@ -2298,7 +2451,12 @@ fn test_query_list_proposals() {
             votes: MultipleChoiceVotes {
                 vote_weights: vec![Uint128::zero(); 3],
             },
+            turnout: Uint128::zero(),
+            rankings: vec![],
             allow_revoting: false,
+            anti_sniping: None,
+            voting_start: None,
+            execution_window: None,
             min_voting_period: None,
         },
     };
@@ -2320,6 +2478,9 @@ fn test_hooks() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -2446,6 +2607,9 @@ fn test_active_threshold_absolute() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -2571,6 +2735,9 @@ fn test_active_threshold_percent() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -2697,6 +2864,9 @@ fn test_active_threshold_none() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -2809,6 +2979,9 @@ fn test_revoting() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
         },
         Some(vec![
@@ -2857,7 +3030,8 @@ fn test_revoting() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -2869,7 +3043,8 @@ fn test_revoting() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote { option_id: 1 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -2900,7 +3075,8 @@ fn test_revoting() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -2936,6 +3112,9 @@ fn test_allow_revoting_config_changes() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
         },
         Some(vec![
@@ -2992,6 +3171,9 @@ fn test_allow_revoting_config_changes() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
         },
         &[],
     )
@@ -3006,7 +3188,8 @@ fn test_allow_revoting_config_changes() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -3016,7 +3199,8 @@ fn test_allow_revoting_config_changes() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote { option_id: 1 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -3041,7 +3225,8 @@ fn test_allow_revoting_config_changes() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 2,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -3053,7 +3238,8 @@ fn test_allow_revoting_config_changes() {
             proposal_module,
             &ExecuteMsg::Vote {
                 proposal_id: 2,
-                vote: MultipleChoiceVote { option_id: 1 },
+                vote: MultipleChoiceVote { option_id: 1 }.into(),
+                rationale: None,
             },
             &[],
         )
@@ -3081,6 +3267,9 @@ fn test_revoting_same_vote_twice() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
         },
         Some(vec![
@@ -3129,7 +3318,8 @@ fn test_revoting_same_vote_twice() {
         proprosal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -3142,7 +3332,8 @@ fn test_revoting_same_vote_twice() {
             proprosal_module,
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 0 },
+                vote: MultipleChoiceVote { option_id: 0 }.into(),
+                rationale: None,
             },
             &[],
         )
@@ -3171,6 +3362,9 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
         },
         Some(vec![
@@ -3219,7 +3413,8 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -3231,7 +3426,8 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote { option_id: 1 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -3262,7 +3458,8 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
             proposal_module,
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 99 },
+                vote: MultipleChoiceVote { option_id: 99 }.into(),
+                rationale: None,
             },
             &[],
         )
@@ -3299,6 +3496,9 @@ fn test_return_deposit_to_dao_on_proposal_failure() {
             denom: DepositToken::VotingModuleToken {},
             amount: Uint128::new(1),
             refund_policy: DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -3354,6 +3554,9 @@ fn test_close_failed_proposal() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
 
@@ -3439,7 +3642,8 @@ fn test_close_failed_proposal() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -3486,6 +3690,9 @@ fn test_close_failed_proposal() {
                                     allow_revoting: false,
                                     dao: original.dao.to_string(),
                                     close_proposal_on_execution_failure: false,
+                                    anti_sniping: None,
+                                    voting_delay: None,
+                                    execution_window: None,
                                 })
                                 .unwrap(),
                                 funds: vec![],
@@ -3510,7 +3717,8 @@ fn test_close_failed_proposal() {
             govmod.clone(),
             &ExecuteMsg::Vote {
                 proposal_id: 2,
-                vote: MultipleChoiceVote { option_id: 0 },
+                vote: MultipleChoiceVote { option_id: 0 }.into(),
+                rationale: None,
             },
             &[],
         )
@@ -3546,7 +3754,8 @@ fn test_close_failed_proposal() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 3,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -3584,6 +3793,9 @@ fn test_no_double_refund_on_execute_fail_and_close() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
         pre_propose_info: get_pre_propose_info(
             &mut app,
             Some(UncheckedDepositInfo {
@@ -3593,6 +3805,9 @@ fn test_no_double_refund_on_execute_fail_and_close() {
                 // that we don't get a second refund on close. Refunds on
                 // close only happen if this is true.
                 refund_policy: DepositRefundPolicy::Always,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
             }),
             false,
         ),
@@ -3710,7 +3925,8 @@ fn test_no_double_refund_on_execute_fail_and_close() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
         },
         &[],
     )
@@ -3755,3 +3971,1205 @@ fn test_no_double_refund_on_execute_fail_and_close() {
     let balance = query_balance_cw20(&app, token_contract.to_string(), CREATOR_ADDR);
     assert_eq!(balance, Uint128::new(1));
 }
+
+#[test]
+fn test_vote_rationale() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: false,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+        ]),
+    );
+    let proposal_module = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "A simple text proposal".to_string(),
+            choices: MultipleChoiceOptions { options },
+            proposer: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: Some("to the moon".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let query_rationale = |app: &App| {
+        let vote: VoteResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::GetVote {
+                    proposal_id: 1,
+                    voter: "a-1".to_string(),
+                },
+            )
+            .unwrap();
+        vote.vote.unwrap().rationale
+    };
+    assert_eq!(query_rationale(&app), Some("to the moon".to_string()));
+
+    // Rationales may be updated when revoting is disabled.
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateRationale {
+            proposal_id: 1,
+            rationale: Some("to mars".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_rationale(&app), Some("to mars".to_string()));
+
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateRationale {
+            proposal_id: 1,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_rationale(&app), None);
+
+    // Addresses that have not voted have no rationale to update.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a-2"),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateRationale {
+                proposal_id: 1,
+                rationale: Some("to the moon".to_string()),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoSuchVote { .. }));
+}
+
+/// Instantiates a DAO using VOTING_STRATEGY with VOTERS as its
+/// members and creates a proposal with three options. Returns the
+/// address of the proposal module.
+fn setup_voting_strategy_test(
+    app: &mut App,
+    voting_strategy: VotingStrategy,
+    voters: &[(&str, u128)],
+) -> Addr {
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: false,
+            voting_strategy,
+            close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            voting_delay: None,
+            execution_window: None,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        },
+        Some(
+            voters
+                .iter()
+                .map(|(address, amount)| Cw20Coin {
+                    address: address.to_string(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+        ),
+    );
+    let proposal_module = query_multiple_proposal_module(app, &core_addr);
+
+    let options = (1..=3)
+        .map(|i| MultipleChoiceOption {
+            description: format!("multiple choice option {}", i),
+            msgs: None,
+        })
+        .collect();
+    app.execute_contract(
+        Addr::unchecked(voters[0].0),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "A simple text proposal".to_string(),
+            choices: MultipleChoiceOptions { options },
+            proposer: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    proposal_module
+}
+
+fn cast_ballot(
+    app: &mut App,
+    proposal_module: &Addr,
+    voter: &str,
+    vote: MultipleChoiceBallot,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(voter),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote,
+            rationale: None,
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+#[test]
+fn test_approval_voting() {
+    let mut app = App::default();
+    let proposal_module = setup_voting_strategy_test(
+        &mut app,
+        VotingStrategy::Approval {
+            quorum: PercentageThreshold::Majority {},
+        },
+        &[("a-1", 40), ("a-2", 30), ("a-3", 30)],
+    );
+
+    let err = cast_ballot(
+        &mut app,
+        &proposal_module,
+        "a-1",
+        MultipleChoiceVote { option_id: 0 }.into(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::WrongBallotType {}));
+    for option_ids in [vec![], vec![0, 0], vec![4]] {
+        let err = cast_ballot(
+            &mut app,
+            &proposal_module,
+            "a-1",
+            MultipleChoiceBallot::Approval { option_ids },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidVote {}));
+    }
+
+    cast_ballot(
+        &mut app,
+        &proposal_module,
+        "a-1",
+        MultipleChoiceBallot::Approval {
+            option_ids: vec![0, 1],
+        },
+    )
+    .unwrap();
+    cast_ballot(
+        &mut app,
+        &proposal_module,
+        "a-2",
+        MultipleChoiceBallot::Approval {
+            option_ids: vec![1],
+        },
+    )
+    .unwrap();
+
+    // The remaining voter could still approve option 0 and tie.
+    let proposal = query_proposal(&app, &proposal_module, 1);
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    cast_ballot(
+        &mut app,
+        &proposal_module,
+        "a-3",
+        MultipleChoiceBallot::Approval {
+            option_ids: vec![2],
+        },
+    )
+    .unwrap();
+
+    let proposal = query_proposal(&app, &proposal_module, 1);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(proposal.proposal.turnout, Uint128::new(100));
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![
+            Uint128::new(40),
+            Uint128::new(70),
+            Uint128::new(30),
+            Uint128::zero()
+        ]
+    );
+}
+
+#[test]
+fn test_ranked_choice_voting() {
+    let mut app = App::default();
+    let proposal_module = setup_voting_strategy_test(
+        &mut app,
+        VotingStrategy::RankedChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        &[("a-1", 40), ("a-2", 35), ("a-3", 25)],
+    );
+
+    let err = cast_ballot(
+        &mut app,
+        &proposal_module,
+        "a-1",
+        MultipleChoiceBallot::Ranked {
+            option_ids: vec![0, 4],
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidVote {}));
+
+    cast_ballot(
+        &mut app,
+        &proposal_module,
+        "a-1",
+        MultipleChoiceBallot::Ranked {
+            option_ids: vec![0],
+        },
+    )
+    .unwrap();
+    cast_ballot(
+        &mut app,
+        &proposal_module,
+        "a-2",
+        MultipleChoiceBallot::Ranked {
+            option_ids: vec![1],
+        },
+    )
+    .unwrap();
+    cast_ballot(
+        &mut app,
+        &proposal_module,
+        "a-3",
+        MultipleChoiceBallot::Ranked {
+            option_ids: vec![2, 1],
+        },
+    )
+    .unwrap();
+
+    // Option 0 has the most first preferences but option 1 wins
+    // once option 2 is eliminated. No option has a majority of
+    // first preferences so the result is only final once the
+    // proposal expires.
+    let proposal = query_proposal(&app, &proposal_module, 1);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.rankings.len(), 3);
+
+    app.update_block(|block| block.height += 6);
+    let proposal = query_proposal(&app, &proposal_module, 1);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        proposal_module.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_quadratic_voting() {
+    let mut app = App::default();
+    let proposal_module = setup_voting_strategy_test(
+        &mut app,
+        VotingStrategy::Quadratic {
+            quorum: PercentageThreshold::Majority {},
+        },
+        &[("a-1", 100), ("a-2", 36), ("a-3", 64)],
+    );
+
+    let allocate = |allocations: &[(u32, u128)]| MultipleChoiceBallot::Quadratic {
+        allocations: allocations
+            .iter()
+            .map(|(option_id, credits)| QuadraticAllocation {
+                option_id: *option_id,
+                credits: Uint128::new(*credits),
+            })
+            .collect(),
+    };
+
+    let err = cast_ballot(&mut app, &proposal_module, "a-2", allocate(&[(1, 37)])).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientCredits {}));
+    let err = cast_ballot(&mut app, &proposal_module, "a-2", allocate(&[(1, 0)])).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidVote {}));
+
+    cast_ballot(&mut app, &proposal_module, "a-1", allocate(&[(0, 100)])).unwrap();
+    cast_ballot(&mut app, &proposal_module, "a-2", allocate(&[(1, 36)])).unwrap();
+    cast_ballot(
+        &mut app,
+        &proposal_module,
+        "a-3",
+        allocate(&[(1, 49), (2, 15)]),
+    )
+    .unwrap();
+
+    // Option 1 wins despite a-1 having more voting power than a-2
+    // and a-3 combined.
+    let proposal = query_proposal(&app, &proposal_module, 1);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(proposal.proposal.turnout, Uint128::new(200));
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![
+            Uint128::new(10),
+            Uint128::new(13),
+            Uint128::new(3),
+            Uint128::zero()
+        ]
+    );
+}
+
+#[test]
+fn test_relay_votes() {
+    let mut app = signer_app();
+    let alice = TestSigner::secp256k1(1);
+    let bob = TestSigner::ed25519(2);
+
+    let code_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
+        max_voting_period: Duration::Time(604800),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+    };
+    let core_addr = cwd_testing::helpers::instantiate_with_cw4_groups_governance(
+        &mut app,
+        code_id,
+        to_binary(&instantiate).unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: alice.address(),
+                amount: Uint128::new(6),
+            },
+            Cw20Coin {
+                address: bob.address(),
+                amount: Uint128::new(4),
+            },
+        ]),
+    );
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            &core_addr,
+            &cwd_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod = modules.into_iter().next().unwrap().address;
+
+    app.execute_contract(
+        Addr::unchecked("proposer"),
+        govmod.clone(),
+        &ExecuteMsg::Propose {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            choices: MultipleChoiceOptions {
+                options: vec![
+                    MultipleChoiceOption {
+                        description: "multiple choice option 1".to_string(),
+                        msgs: None,
+                    },
+                    MultipleChoiceOption {
+                        description: "multiple choice option 2".to_string(),
+                        msgs: None,
+                    },
+                ],
+            },
+            proposer: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let chain_id = app.block_info().chain_id;
+    let alice_ballot = alice.sign(
+        &chain_id,
+        &govmod,
+        1,
+        MultipleChoiceBallot::Single(MultipleChoiceVote { option_id: 0 }),
+        0,
+    );
+    app.execute_contract(
+        Addr::unchecked("relayer"),
+        govmod.clone(),
+        &ExecuteMsg::RelayVotes {
+            ballots: vec![
+                alice_ballot.clone(),
+                bob.sign(
+                    &chain_id,
+                    &govmod,
+                    1,
+                    MultipleChoiceBallot::Single(MultipleChoiceVote { option_id: 1 }),
+                    3,
+                ),
+            ],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let vote: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: bob.address(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vote.vote,
+        Some(VoteInfo {
+            voter: Addr::unchecked(bob.address()),
+            vote: MultipleChoiceVote { option_id: 1 }.into(),
+            power: Uint128::new(4),
+            rationale: None,
+        })
+    );
+    let nonce: u64 = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::BallotNonce {
+                address: bob.address(),
+            },
+        )
+        .unwrap();
+    assert_eq!(nonce, 4);
+
+    // Ballots may not be replayed.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("relayer"),
+            govmod.clone(),
+            &ExecuteMsg::RelayVotes {
+                ballots: vec![alice_ballot],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidNonce { next: 1 }));
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(&govmod, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![Uint128::new(6), Uint128::new(4), Uint128::zero()]
+    );
+}
+
+#[test]
+fn test_indexed_queries() {
+    let mut app = App::default();
+    let code_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        voting_delay: None,
+        execution_window: None,
+        max_voting_period: Duration::Time(604800),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+    };
+    let core_addr = cwd_testing::helpers::instantiate_with_cw4_groups_governance(
+        &mut app,
+        code_id,
+        to_binary(&instantiate).unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(6),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(4),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let propose = |app: &mut App, proposer: &str| {
+        app.execute_contract(
+            Addr::unchecked(proposer),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: MultipleChoiceOptions {
+                    options: vec![
+                        MultipleChoiceOption {
+                            description: "multiple choice option 1".to_string(),
+                            msgs: None,
+                        },
+                        MultipleChoiceOption {
+                            description: "multiple choice option 2".to_string(),
+                            msgs: None,
+                        },
+                    ],
+                },
+                proposer: None,
+            },
+            &[],
+        )
+        .unwrap();
+        let count: u64 = app
+            .wrap()
+            .query_wasm_smart(&govmod, &QueryMsg::ProposalCount {})
+            .unwrap();
+        count
+    };
+    let list = |app: &App, query: QueryMsg| {
+        let res: ProposalListResponse = app.wrap().query_wasm_smart(&govmod, &query).unwrap();
+        res.proposals.into_iter().map(|p| p.id).collect::<Vec<_>>()
+    };
+    let by_status = |status| QueryMsg::ListProposalsByStatus {
+        status,
+        start_after: None,
+        limit: None,
+    };
+
+    // Passes as soon as alice votes.
+    let passed = propose(&mut app, "alice");
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: passed,
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    // Rejected once it expires, though its stored status remains
+    // open.
+    let rejected = propose(&mut app, "bob");
+
+    app.update_block(|block| block.time = block.time.plus_seconds(604800));
+    let open = propose(&mut app, "bob");
+
+    assert_eq!(list(&app, by_status(Status::Passed)), vec![passed]);
+    assert_eq!(list(&app, by_status(Status::Rejected)), vec![rejected]);
+    assert_eq!(list(&app, by_status(Status::Open)), vec![open]);
+    assert!(list(&app, by_status(Status::Executed)).is_empty());
+
+    assert_eq!(
+        list(
+            &app,
+            QueryMsg::ListProposalsByProposer {
+                proposer: "bob".to_string(),
+                start_after: None,
+                limit: None,
+            }
+        ),
+        vec![rejected, open]
+    );
+    assert_eq!(
+        list(
+            &app,
+            QueryMsg::ListProposalsByProposer {
+                proposer: "bob".to_string(),
+                start_after: Some(rejected),
+                limit: None,
+            }
+        ),
+        vec![open]
+    );
+
+    let votes: VoterVoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::ListVotesByVoter {
+                voter: "alice".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        votes.votes,
+        vec![VoterVoteInfo {
+            proposal_id: passed,
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            power: Uint128::new(6),
+            rationale: None,
+        }]
+    );
+}
+
+#[test]
+fn test_anti_sniping() {
+    let mut app = App::default();
+    let code_id = app.store_code(proposal_multiple_contract());
+    let mut instantiate = InstantiateMsg {
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        anti_sniping: Some(AntiSnipingConfig {
+            window: Duration::Height(0),
+            extension: Duration::Height(3),
+            max_extensions: 2,
+        }),
+        max_voting_period: Duration::Height(10),
+        only_members_execute: false,
+        allow_revoting: true,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Percent(Decimal::percent(20)),
+        },
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_delay: None,
+        execution_window: None,
+    };
+    let err: ContractError = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("dao"),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::AntiSnipingError(AntiSnipingError::ZeroWindow {})
+    ));
+
+    instantiate.anti_sniping = Some(AntiSnipingConfig {
+        window: Duration::Height(3),
+        extension: Duration::Height(3),
+        max_extensions: 2,
+    });
+    let core_addr = cwd_testing::helpers::instantiate_with_cw4_groups_governance(
+        &mut app,
+        code_id,
+        to_binary(&instantiate).unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "carol".to_string(),
+                amount: Uint128::new(3),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+    let proposal_id = make_proposal(
+        &mut app,
+        &govmod,
+        "alice",
+        MultipleChoiceOptions { options },
+    );
+    let start = app.block_info().height;
+
+    let vote = |app: &mut App, voter: &str, height: u64, option_id: u32| {
+        app.update_block(|b| b.height = start + height);
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote { option_id }.into(),
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+        query_proposal(app, &govmod, proposal_id).proposal
+    };
+
+    // Votes before the window which change the outcome do not extend
+    // the voting period.
+    let proposal = vote(&mut app, "alice", 1, 0);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 10));
+
+    // A vote within the window which ties the proposal extends it.
+    let proposal = vote(&mut app, "bob", 8, 1);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 13));
+
+    // A vote within the window which changes the winning option
+    // extends it again, after its original expiration.
+    let proposal = vote(&mut app, "carol", 11, 1);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 16));
+    assert_eq!(proposal.status, Status::Open);
+    assert_eq!(proposal.anti_sniping.unwrap().extensions, 2);
+
+    // Once the maximum number of extensions have been made the
+    // voting period is not extended.
+    let proposal = vote(&mut app, "carol", 14, 0);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 16));
+
+    app.update_block(|b| b.height = start + 16);
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_voting_delay() {
+    let mut app = App::default();
+    let code_id = app.store_code(proposal_multiple_contract());
+    let mut instantiate = InstantiateMsg {
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        max_voting_period: Duration::Time(100),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_delay: Some(Duration::Height(10)),
+        execution_window: None,
+    };
+
+    // The voting delay must be measured in the same units as the
+    // voting period.
+    let err: ContractError = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("dao"),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::VotingDelayUnitsConflict {})
+    ));
+
+    // The voting delay must be measured in blocks so that the height
+    // voting power is snapshotted at is known when proposals are
+    // created.
+    instantiate.voting_delay = Some(Duration::Time(10));
+    let err: ContractError = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("dao"),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::TimeVotingDelay {})
+    ));
+
+    instantiate.max_voting_period = Duration::Height(100);
+    instantiate.voting_delay = Some(Duration::Height(10));
+    let core_addr = cwd_testing::helpers::instantiate_with_cw4_groups_governance(
+        &mut app,
+        code_id,
+        to_binary(&instantiate).unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(3),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+    let proposal_id = make_proposal(
+        &mut app,
+        &govmod,
+        "alice",
+        MultipleChoiceOptions { options },
+    );
+    let start = app.block_info().height;
+
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Pending);
+    assert_eq!(
+        proposal.voting_start,
+        Some(Expiration::AtHeight(start + 10))
+    );
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 110));
+    assert_eq!(proposal.start_height, start + 10);
+
+    let vote = |app: &mut App, voter: &str| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote { option_id: 0 }.into(),
+                rationale: None,
+            },
+            &[],
+        )
+    };
+
+    // Pending proposals may not be voted on.
+    let err: ContractError = vote(&mut app, "alice").unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::NotOpen { .. }));
+
+    // Voting power is snapshotted at the height voting opened, not
+    // at the height of the first vote.
+    app.update_block(|b| b.height = start + 14);
+    vote(&mut app, "alice").unwrap();
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.start_height, start + 10);
+    assert_eq!(proposal.total_power, Uint128::new(6));
+    assert_eq!(proposal.status, Status::Open);
+
+    vote(&mut app, "bob").unwrap();
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_execution_window() {
+    let mut app = App::default();
+    let code_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        max_voting_period: Duration::Height(10),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_delay: None,
+        execution_window: Some(Duration::Height(5)),
+    };
+    let core_addr = cwd_testing::helpers::instantiate_with_cw4_groups_governance(
+        &mut app,
+        code_id,
+        to_binary(&instantiate).unwrap(),
+        Some(vec![Cw20Coin {
+            address: "alice".to_string(),
+            amount: Uint128::new(3),
+        }]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+    let proposal_id = make_proposal(
+        &mut app,
+        &govmod,
+        "alice",
+        MultipleChoiceOptions { options },
+    );
+    let start = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Passed proposals may be executed until five blocks after their
+    // voting period ends.
+    app.update_block(|b| b.height = start + 14);
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+
+    app.update_block(|b| b.height = start + 15);
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::ExecutionExpired);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            govmod.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ExecutionWindowExpired {}));
+
+    // Closing the proposal saves its status.
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        govmod.clone(),
+        &ExecuteMsg::Close { proposal_id },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            govmod.clone(),
+            &ExecuteMsg::Close { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::ExecutionExpired);
+}
+
+#[test]
+fn test_update_and_withdraw_proposal() {
+    let mut app = App::default();
+    let code_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        max_voting_period: Duration::Height(10),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_delay: None,
+        execution_window: None,
+    };
+    let core_addr = cwd_testing::helpers::instantiate_with_cw4_groups_governance(
+        &mut app,
+        code_id,
+        to_binary(&instantiate).unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(3),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    let options = |count: usize| MultipleChoiceOptions {
+        options: (0..count)
+            .map(|i| MultipleChoiceOption {
+                description: format!("multiple choice option {}", i),
+                msgs: None,
+            })
+            .collect(),
+    };
+    let update = |proposer: Option<String>| ExecuteMsg::UpdateProposal {
+        proposal_id: 1,
+        title: "updated title".to_string(),
+        description: "updated".to_string(),
+        choices: options(3),
+        proposer,
+    };
+    let proposal_id = make_proposal(&mut app, &govmod, "alice", options(2));
+
+    // Only the proposer may update the proposal.
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked("bob"), govmod.clone(), &update(None), &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Without a pre-propose module the proposer may not be specified.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            govmod.clone(),
+            &update(Some("alice".to_string())),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidProposer {}));
+
+    app.execute_contract(Addr::unchecked("alice"), govmod.clone(), &update(None), &[])
+        .unwrap();
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.title, "updated title");
+    assert_eq!(proposal.description, "updated");
+    // Three options and "none of the above."
+    assert_eq!(proposal.choices.len(), 4);
+    assert_eq!(proposal.votes.vote_weights.len(), 4);
+
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        govmod.clone(),
+        &ExecuteMsg::WithdrawProposal {
+            proposal_id,
+            proposer: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Withdrawn);
+
+    // Withdrawn proposals may not be voted on.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("bob"),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote { option_id: 0 }.into(),
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotOpen { .. }));
+
+    // Proposals may not be updated or withdrawn once they have been
+    // voted on. Alice's vote alone does not complete the proposal.
+    let proposal_id = make_proposal(&mut app, &govmod, "alice", options(2));
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote: MultipleChoiceVote { option_id: 0 }.into(),
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            govmod,
+            &ExecuteMsg::WithdrawProposal {
+                proposal_id,
+                proposer: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::VotingStarted { id } if id == proposal_id));
+}
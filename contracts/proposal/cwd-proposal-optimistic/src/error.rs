@@ -0,0 +1,58 @@
+use cosmwasm_std::StdError;
+use cw_utils::ParseReplyError;
+use cwd_hooks::HookError;
+use cwd_voting::{reply::error::TagError, threshold::ThresholdError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    ParseReplyError(#[from] ParseReplyError),
+
+    #[error(transparent)]
+    HookError(#[from] HookError),
+
+    #[error(transparent)]
+    ThresholdError(#[from] ThresholdError),
+
+    #[error(transparent)]
+    Tag(#[from] TagError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("no such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("proposal is ({size}) bytes, must be <= ({max}) bytes")]
+    ProposalTooLarge { size: u64, max: u64 },
+
+    #[error("proposal is not open ({id})")]
+    NotOpen { id: u64 },
+
+    #[error("proposal is not in 'passed' state")]
+    NotPassed {},
+
+    #[error("only rejected proposals may be closed")]
+    WrongCloseStatus {},
+
+    #[error("the DAO is currently inactive, you cannot create proposals")]
+    InactiveDao {},
+
+    #[error(
+        "pre-propose modules must specify a proposer. lacking one, no proposer should be specified"
+    )]
+    InvalidProposer {},
+
+    #[error("must have voting power to object")]
+    NotRegistered {},
+
+    #[error("already objected to this proposal")]
+    AlreadyObjected {},
+
+    #[error("received a reply failure with an invalid ID: ({id})")]
+    InvalidReplyID { id: u64 },
+}
@@ -0,0 +1,31 @@
+//! # cwd-proposal-optimistic
+//!
+//! A proposal module where proposals pass unless they are objected
+//! to. Proposals may only be created by addresses permitted by the
+//! module's proposal creation policy. Once created, a proposal passes
+//! at the end of its voting period unless objections from a
+//! configurable share of the voting power at the proposal's start
+//! height are raised against it, in which case it is rejected.
+//!
+//! This is intended for routine operations, for example recurring
+//! spends, where requiring members to vote on every proposal leads to
+//! voting fatigue while token holders should retain the ability to
+//! stop a proposal they disagree with.
+//!
+//! ## Hooks
+//!
+//! This module supports the same proposal and vote hooks as
+//! `cwd-proposal-single`. Vote hooks are fired when an objection is
+//! raised with `"object"` as the vote.
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod proposal;
+pub mod query;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub use crate::error::ContractError;
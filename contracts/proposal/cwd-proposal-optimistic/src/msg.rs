@@ -0,0 +1,175 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Empty};
+use cw_utils::Duration;
+use cwd_macros::{info_query, proposal_module_query};
+use cwd_voting::{pre_propose::PreProposeInfo, threshold::PercentageThreshold};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The share of the total voting power at a proposal's start
+    /// height that must object to the proposal for it to be
+    /// rejected.
+    pub objection_threshold: PercentageThreshold,
+    /// The amount of time objections may be raised against a
+    /// proposal. Proposals which have not been rejected pass once
+    /// this has elapsed.
+    pub voting_period: Duration,
+    /// If set to true only members may execute passed
+    /// proposals. Otherwise, any address may execute a passed
+    /// proposal.
+    pub only_members_execute: bool,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain open after execution
+    /// failure.
+    pub close_proposal_on_execution_failure: bool,
+    /// Information about what addresses may create proposals. As
+    /// proposals pass by default this should usually be restricted
+    /// to a pre-propose module which only accepts proposals from
+    /// trusted proposers.
+    pub pre_propose_info: PreProposeInfo,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a proposal in the module.
+    Propose {
+        /// The title of the proposal.
+        title: String,
+        /// A description of the proposal.
+        description: String,
+        /// The messages that should be executed in response to this
+        /// proposal passing.
+        msgs: Vec<CosmosMsg<Empty>>,
+        /// The address creating the proposal. If no pre-propose
+        /// module is attached to this module this must always be None
+        /// as the proposer is the sender of the propose message. If a
+        /// pre-propose module is attached, this must be Some and will
+        /// set the proposer of the proposal it creates.
+        proposer: Option<String>,
+    },
+    /// Objects to an open proposal with the sender's voting power at
+    /// the proposal's start height. Objections may not be withdrawn.
+    Object {
+        /// The ID of the proposal to object to.
+        proposal_id: u64,
+        /// An optional rationale for why this objection was raised.
+        rationale: Option<String>,
+    },
+    /// Causes the messages associated with a passed proposal to be
+    /// executed by the DAO.
+    Execute {
+        /// The ID of the proposal to execute.
+        proposal_id: u64,
+    },
+    /// Closes a proposal that has been rejected. If applicable this
+    /// will cause the proposal deposit associated with said proposal
+    /// to be returned.
+    Close {
+        /// The ID of the proposal to close.
+        proposal_id: u64,
+    },
+    /// Updates the governance module's config. Only the DAO may call
+    /// this method. Changes only apply to proposals created after
+    /// the update.
+    UpdateConfig {
+        objection_threshold: PercentageThreshold,
+        voting_period: Duration,
+        only_members_execute: bool,
+        close_proposal_on_execution_failure: bool,
+    },
+    /// Update's the proposal creation policy used for this
+    /// module. Only the DAO may call this method.
+    UpdatePreProposeInfo { info: PreProposeInfo },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook { address: String },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook { address: String },
+    /// Adds an address as a consumer of vote hooks. Consumers of vote
+    /// hooks have hook messages executed on them whenever an
+    /// objection is raised. If a consumer contract errors when
+    /// handling a hook message it will be removed from the list of
+    /// consumers.
+    AddVoteHook { address: String },
+    /// Removed a consumer of vote hooks.
+    RemoveVoteHook { address: String },
+}
+
+#[proposal_module_query]
+#[info_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the proposal module's config.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Gets information about a proposal.
+    #[returns(crate::query::ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    /// Lists all the proposals that have been cast in this
+    /// module.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposals {
+        /// The proposal ID to start listing proposals after. For
+        /// example, if this is set to 2 proposals with IDs 3 and
+        /// higher will be returned.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists all of the proposals that have been cast in this module
+    /// in decending order of proposal ID.
+    #[returns(crate::query::ProposalListResponse)]
+    ReverseProposals {
+        /// The proposal ID to start listing proposals before. For
+        /// example, if this is set to 6 proposals with IDs 5 and
+        /// lower will be returned.
+        start_before: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Returns the number of proposals that have been created in this
+    /// module.
+    #[returns(u64)]
+    ProposalCount {},
+    /// Returns the objection an address raised against a proposal,
+    /// if any.
+    #[returns(crate::query::VoteResponse)]
+    GetVote { proposal_id: u64, voter: String },
+    /// Lists the objections raised against a proposal.
+    #[returns(crate::query::VoteListResponse)]
+    ListVotes {
+        /// The proposal to list the objections of.
+        proposal_id: u64,
+        /// The voter to start listing objections after. Ordering is
+        /// done alphabetically.
+        start_after: Option<String>,
+        /// The maximum number of objections to return in response to
+        /// this query. If no limit is specified a max of 30 are
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Gets the current proposal creation policy for this
+    /// module.
+    #[returns(cwd_voting::pre_propose::ProposalCreationPolicy)]
+    ProposalCreationPolicy {},
+    /// Lists all of the consumers of proposal hooks for this module.
+    #[returns(cwd_hooks::HooksResponse)]
+    ProposalHooks {},
+    /// Lists all of the consumers of vote hooks for this
+    /// module.
+    #[returns(cwd_hooks::HooksResponse)]
+    VoteHooks {},
+}
+
+#[cw_serde]
+pub enum MigrateMsg {
+    FromCompatible {},
+}
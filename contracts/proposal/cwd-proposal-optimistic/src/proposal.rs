@@ -0,0 +1,139 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Empty, StdResult, Storage, Uint128};
+use cw_utils::Expiration;
+use cwd_voting::{
+    proposal::Proposal, status::Status, threshold::PercentageThreshold,
+    voting::does_vote_count_pass,
+};
+
+use crate::{query::ProposalResponse, state::PROPOSAL_COUNT};
+
+#[cw_serde]
+pub struct OptimisticProposal {
+    pub title: String,
+    pub description: String,
+    /// The address that created this proposal.
+    pub proposer: Addr,
+    /// The block height at which this proposal was created. Voting
+    /// power queries should query for voting power at this block
+    /// height.
+    pub start_height: u64,
+    /// The the time at which this proposal will pass if it has not
+    /// been rejected.
+    pub expiration: Expiration,
+    /// The share of `total_power` that must object to this proposal
+    /// for it to be rejected.
+    pub objection_threshold: PercentageThreshold,
+    /// The total amount of voting power at the time of this
+    /// proposal's creation.
+    pub total_power: Uint128,
+    /// The messages that will be executed should this proposal pass.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    pub status: Status,
+    /// The total voting power that has objected to this proposal.
+    pub objections: Uint128,
+}
+
+impl Proposal for OptimisticProposal {
+    fn proposer(&self) -> Addr {
+        self.proposer.clone()
+    }
+    fn status(&self) -> Status {
+        self.status
+    }
+}
+
+pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+impl OptimisticProposal {
+    /// Consumes the proposal and returns a version which may be used
+    /// in a query response. Proposal statuses are only updated on
+    /// objection, execute, and close events so this recomputes the
+    /// status in case the proposal has since expired.
+    pub fn into_response(mut self, block: &BlockInfo, id: u64) -> ProposalResponse {
+        self.update_status(block);
+        ProposalResponse { id, proposal: self }
+    }
+
+    /// Gets the current status of the proposal. Open proposals are
+    /// rejected as soon as enough objections are raised and pass
+    /// once they expire.
+    pub fn current_status(&self, block: &BlockInfo) -> Status {
+        if self.status == Status::Open && self.is_rejected() {
+            Status::Rejected
+        } else if self.status == Status::Open && self.expiration.is_expired(block) {
+            Status::Passed
+        } else {
+            self.status
+        }
+    }
+
+    /// Sets a proposals status to its current status.
+    pub fn update_status(&mut self, block: &BlockInfo) {
+        self.status = self.current_status(block);
+    }
+
+    /// Returns true if the objections raised against this proposal
+    /// have reached its objection threshold.
+    pub fn is_rejected(&self) -> bool {
+        does_vote_count_pass(self.objections, self.total_power, self.objection_threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{testing::mock_env, Decimal};
+
+    fn proposal(objection_threshold: PercentageThreshold, objections: u128) -> OptimisticProposal {
+        let env = mock_env();
+        OptimisticProposal {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            proposer: Addr::unchecked("proposer"),
+            start_height: env.block.height,
+            expiration: Expiration::AtHeight(env.block.height + 10),
+            objection_threshold,
+            total_power: Uint128::new(100),
+            msgs: vec![],
+            status: Status::Open,
+            objections: Uint128::new(objections),
+        }
+    }
+
+    #[test]
+    fn test_passes_without_objections() {
+        let mut block = mock_env().block;
+        let prop = proposal(PercentageThreshold::Percent(Decimal::percent(10)), 0);
+        assert_eq!(prop.current_status(&block), Status::Open);
+
+        block.height += 10;
+        assert_eq!(prop.current_status(&block), Status::Passed);
+    }
+
+    #[test]
+    fn test_objection_threshold() {
+        let mut block = mock_env().block;
+        let threshold = PercentageThreshold::Percent(Decimal::percent(10));
+
+        let prop = proposal(threshold, 9);
+        assert_eq!(prop.current_status(&block), Status::Open);
+        block.height += 10;
+        assert_eq!(prop.current_status(&block), Status::Passed);
+
+        // Rejection takes effect immediately and does not wait for
+        // the proposal to expire.
+        let block = mock_env().block;
+        let prop = proposal(threshold, 10);
+        assert_eq!(prop.current_status(&block), Status::Rejected);
+
+        let prop = proposal(PercentageThreshold::Majority {}, 50);
+        assert_eq!(prop.current_status(&block), Status::Open);
+        let prop = proposal(PercentageThreshold::Majority {}, 51);
+        assert_eq!(prop.current_status(&block), Status::Rejected);
+    }
+}
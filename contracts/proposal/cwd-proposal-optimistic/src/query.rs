@@ -0,0 +1,43 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+
+use crate::proposal::OptimisticProposal;
+
+/// Information about a proposal returned by proposal queries.
+#[cw_serde]
+pub struct ProposalResponse {
+    /// The ID of the proposal being returned.
+    pub id: u64,
+    pub proposal: OptimisticProposal,
+}
+
+/// Information about an objection.
+#[cw_serde]
+pub struct VoteInfo {
+    /// The address that objected to the proposal.
+    pub voter: Addr,
+    /// The voting power behind the objection.
+    pub power: Uint128,
+    /// The rationale given for the objection, if any.
+    pub rationale: Option<String>,
+}
+
+/// Information about an objection that was raised.
+#[cw_serde]
+pub struct VoteResponse {
+    /// None if no such objection, Some otherwise.
+    pub vote: Option<VoteInfo>,
+}
+
+/// Information about the objections raised against a proposal.
+#[cw_serde]
+pub struct VoteListResponse {
+    pub votes: Vec<VoteInfo>,
+}
+
+/// A list of proposals returned by `ListProposals` and
+/// `ReverseProposals`.
+#[cw_serde]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
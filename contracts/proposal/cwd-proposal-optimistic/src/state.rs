@@ -0,0 +1,53 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use cw_utils::Duration;
+use cwd_hooks::Hooks;
+use cwd_voting::{pre_propose::ProposalCreationPolicy, threshold::PercentageThreshold};
+
+use crate::proposal::OptimisticProposal;
+
+/// An objection raised against a proposal.
+#[cw_serde]
+pub struct Objection {
+    /// The amount of voting power behind the objection.
+    pub power: Uint128,
+    /// An optional rationale for why this objection was raised.
+    pub rationale: Option<String>,
+}
+
+/// The governance module's configuration.
+#[cw_serde]
+pub struct Config {
+    /// The share of the total voting power at a proposal's start
+    /// height that must object to the proposal for it to be rejected.
+    pub objection_threshold: PercentageThreshold,
+    /// The amount of time objections may be raised against a
+    /// proposal. Proposals which have not been rejected pass once
+    /// this has elapsed.
+    pub voting_period: Duration,
+    /// If set to true only members may execute passed
+    /// proposals. Otherwise, any address may execute a passed
+    /// proposal.
+    pub only_members_execute: bool,
+    /// The address of the DAO that this governance module is
+    /// associated with.
+    pub dao: Addr,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain open after execution
+    /// failure.
+    pub close_proposal_on_execution_failure: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<u64, OptimisticProposal> = Map::new("proposals");
+pub const OBJECTIONS: Map<(u64, Addr), Objection> = Map::new("objections");
+/// Consumers of proposal state change hooks.
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+/// Consumers of vote hooks.
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+/// The address of the pre-propose module associated with this
+/// proposal module (if any).
+pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
@@ -0,0 +1,539 @@
+use cosmwasm_std::{coins, to_binary, Addr, BankMsg, CosmosMsg, Decimal, Empty, Uint128};
+use cw_denom::UncheckedDenom;
+use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
+use cw_utils::Duration;
+use cwd_interface::{Admin, ModuleInstantiateInfo};
+use cwd_pre_propose_single as cppbps;
+use cwd_voting::{
+    deposit::{DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    pre_propose::{PreProposeInfo, ProposalCreationPolicy},
+    status::Status,
+    threshold::{PercentageThreshold, ThresholdError},
+};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::{ProposalResponse, VoteListResponse, VoteResponse},
+    ContractError,
+};
+
+const CREATOR_ADDR: &str = "creator";
+const DENOM: &str = "ujuno";
+const DEPOSIT: u128 = 10;
+
+fn optimistic_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply)
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+fn pre_propose_single_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cppbps::contract::execute,
+        cppbps::contract::instantiate,
+        cppbps::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw4_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw_core_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cwd_core::contract::execute,
+        cwd_core::contract::instantiate,
+        cwd_core::contract::query,
+    )
+    .with_reply(cwd_core::contract::reply);
+    Box::new(contract)
+}
+
+fn cw4_voting_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cwd_voting_cw4::contract::execute,
+        cwd_voting_cw4::contract::instantiate,
+        cwd_voting_cw4::contract::query,
+    )
+    .with_reply(cwd_voting_cw4::contract::reply);
+    Box::new(contract)
+}
+
+/// Proposals may only be created by members of the DAO via a
+/// pre-propose module which takes a deposit that is only refunded if
+/// the proposal passes.
+fn get_instantiate(app: &mut App) -> InstantiateMsg {
+    let pre_propose_id = app.store_code(pre_propose_single_contract());
+    InstantiateMsg {
+        objection_threshold: PercentageThreshold::Percent(Decimal::percent(25)),
+        voting_period: Duration::Height(10),
+        only_members_execute: true,
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::ModuleMayPropose {
+            info: ModuleInstantiateInfo {
+                code_id: pre_propose_id,
+                msg: to_binary(&cppbps::InstantiateMsg {
+                    deposit_info: Some(UncheckedDepositInfo {
+                        denom: DepositToken::Token {
+                            denom: UncheckedDenom::Native(DENOM.to_string()),
+                        },
+                        amount: Uint128::new(DEPOSIT),
+                        refund_policy: DepositRefundPolicy::OnlyPassed,
+                        withdrawal_refund_policy: None,
+                        veto_destination: None,
+                        funding_period: None,
+                    }),
+                    open_proposal_submission: false,
+                    min_voting_power: None,
+                    max_open_proposals: None,
+                    submission_cooldown: None,
+                    message_restrictions: None,
+                    templates: vec![],
+                    extension: Empty::default(),
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                label: "pre_propose_contract".to_string(),
+            },
+        },
+    }
+}
+
+struct Setup {
+    app: App,
+    dao: Addr,
+    module: Addr,
+    pre_propose: Addr,
+    group: Addr,
+}
+
+/// Creates a DAO with members "a", "b" and "c" holding 50, 30 and 20
+/// voting power and a treasury of 1000 ujuno.
+fn setup() -> Setup {
+    let mut app = App::default();
+    let instantiate = get_instantiate(&mut app);
+    let module_id = app.store_code(optimistic_contract());
+    let cw4_id = app.store_code(cw4_contract());
+    let core_id = app.store_code(cw_core_contract());
+    let votemod_id = app.store_code(cw4_voting_contract());
+
+    let initial_members = [("a", 50), ("b", 30), ("c", 20)]
+        .into_iter()
+        .map(|(addr, weight)| cw4::Member {
+            addr: addr.to_string(),
+            weight,
+        })
+        .collect();
+
+    let governance_instantiate = cwd_core::msg::InstantiateMsg {
+        admin: None,
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs".to_string(),
+        dao_uri: None,
+        image_url: None,
+        automatically_add_cw20s: true,
+        automatically_add_cw721s: true,
+        voting_module_instantiate_info: ModuleInstantiateInfo {
+            code_id: votemod_id,
+            msg: to_binary(&cwd_voting_cw4::msg::InstantiateMsg {
+                cw4_group_code_id: cw4_id,
+                initial_members,
+            })
+            .unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            label: "DAO DAO voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+            code_id: module_id,
+            msg: to_binary(&instantiate).unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            label: "DAO DAO governance module".to_string(),
+        }],
+        initial_items: None,
+    };
+
+    let dao = app
+        .instantiate_contract(
+            core_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &governance_instantiate,
+            &[],
+            "DAO DAO",
+            None,
+        )
+        .unwrap();
+
+    let modules: Vec<cwd_core::state::ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            &dao,
+            &cwd_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let module = modules.into_iter().next().unwrap().address;
+
+    let policy: ProposalCreationPolicy = app
+        .wrap()
+        .query_wasm_smart(&module, &QueryMsg::ProposalCreationPolicy {})
+        .unwrap();
+    let pre_propose = match policy {
+        ProposalCreationPolicy::Module { addr } => addr,
+        ProposalCreationPolicy::Anyone {} => panic!("expected a pre-propose module"),
+    };
+
+    let voting: Addr = app
+        .wrap()
+        .query_wasm_smart(&dao, &cwd_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let group: Addr = app
+        .wrap()
+        .query_wasm_smart(voting, &cwd_voting_cw4::msg::QueryMsg::GroupContract {})
+        .unwrap();
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: dao.to_string(),
+        amount: coins(1000, DENOM),
+    }))
+    .unwrap();
+
+    // Update the block so that weights appear.
+    app.update_block(|block| block.height += 1);
+
+    Setup {
+        app,
+        dao,
+        module,
+        pre_propose,
+        group,
+    }
+}
+
+/// Creates a proposal to send 100 ujuno to "grantee" via the
+/// pre-propose module, minting the deposit to the proposer first.
+fn propose(app: &mut App, module: &Addr, pre_propose: &Addr, proposer: &str) -> u64 {
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: proposer.to_string(),
+        amount: coins(DEPOSIT, DENOM),
+    }))
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        pre_propose.clone(),
+        &cppbps::ExecuteMsg::Propose {
+            msg: cppbps::ProposeMessage::Propose {
+                title: "routine spend".to_string(),
+                description: "pay for the servers".to_string(),
+                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "grantee".to_string(),
+                    amount: coins(100, DENOM),
+                })],
+                dependencies: vec![],
+            },
+        },
+        &coins(DEPOSIT, DENOM),
+    )
+    .unwrap();
+    app.wrap()
+        .query_wasm_smart(module, &QueryMsg::ProposalCount {})
+        .unwrap()
+}
+
+fn object(
+    app: &mut App,
+    module: &Addr,
+    voter: &str,
+    proposal_id: u64,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(voter),
+        module.clone(),
+        &ExecuteMsg::Object {
+            proposal_id,
+            rationale: Some("too expensive".to_string()),
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn execute(
+    app: &mut App,
+    module: &Addr,
+    sender: &str,
+    proposal_id: u64,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module.clone(),
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn close(app: &mut App, module: &Addr, proposal_id: u64) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        module.clone(),
+        &ExecuteMsg::Close { proposal_id },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+fn query_proposal(app: &App, module: &Addr, proposal_id: u64) -> ProposalResponse {
+    app.wrap()
+        .query_wasm_smart(module, &QueryMsg::Proposal { proposal_id })
+        .unwrap()
+}
+
+fn query_balance(app: &App, address: impl Into<String>) -> Uint128 {
+    app.wrap().query_balance(address, DENOM).unwrap().amount
+}
+
+#[test]
+fn test_passes_without_enough_objections() {
+    let Setup {
+        mut app,
+        module,
+        pre_propose,
+        dao,
+        ..
+    } = setup();
+
+    let id = propose(&mut app, &module, &pre_propose, "a");
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("a"));
+    assert_eq!(proposal.proposal.total_power, Uint128::new(100));
+    assert_eq!(query_balance(&app, "a"), Uint128::zero());
+
+    // 20% of voting power objecting is below the 25% threshold.
+    object(&mut app, &module, "c", id).unwrap();
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.objections, Uint128::new(20));
+
+    let err = execute(&mut app, &module, "a", id).unwrap_err();
+    assert!(matches!(err, ContractError::NotPassed {}));
+
+    app.update_block(|block| block.height += 10);
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    // Objections may not be raised once the voting period is over.
+    let err = object(&mut app, &module, "b", id).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { id: err_id } if err_id == id));
+
+    let err = execute(&mut app, &module, "grantee", id).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    execute(&mut app, &module, "b", id).unwrap();
+
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(query_balance(&app, "grantee"), Uint128::new(100));
+    assert_eq!(query_balance(&app, dao), Uint128::new(900));
+    // The deposit is refunded as the proposal passed.
+    assert_eq!(query_balance(&app, "a"), Uint128::new(DEPOSIT));
+}
+
+#[test]
+fn test_objections_reject() {
+    let Setup {
+        mut app,
+        module,
+        pre_propose,
+        dao,
+        ..
+    } = setup();
+
+    let id = propose(&mut app, &module, &pre_propose, "a");
+
+    let err = close(&mut app, &module, id).unwrap_err();
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+
+    // 30% of voting power objecting rejects the proposal without
+    // waiting for the voting period to end.
+    object(&mut app, &module, "b", id).unwrap();
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+
+    let err = object(&mut app, &module, "c", id).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { id: err_id } if err_id == id));
+
+    app.update_block(|block| block.height += 10);
+    let err = execute(&mut app, &module, "a", id).unwrap_err();
+    assert!(matches!(err, ContractError::NotPassed {}));
+
+    close(&mut app, &module, id).unwrap();
+    let proposal = query_proposal(&app, &module, id);
+    assert_eq!(proposal.proposal.status, Status::Closed);
+
+    // The deposit is sent to the DAO as the proposal did not pass.
+    assert_eq!(query_balance(&app, "a"), Uint128::zero());
+    assert_eq!(query_balance(&app, dao), Uint128::new(1000 + DEPOSIT));
+    assert_eq!(query_balance(&app, "grantee"), Uint128::zero());
+
+    let votes: VoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &module,
+            &QueryMsg::ListVotes {
+                proposal_id: id,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(votes.votes.len(), 1);
+    assert_eq!(votes.votes[0].voter, Addr::unchecked("b"));
+    assert_eq!(votes.votes[0].power, Uint128::new(30));
+    assert_eq!(votes.votes[0].rationale, Some("too expensive".to_string()));
+}
+
+#[test]
+fn test_objection_power() {
+    let Setup {
+        mut app,
+        module,
+        pre_propose,
+        dao,
+        group,
+    } = setup();
+
+    let id = propose(&mut app, &module, &pre_propose, "a");
+
+    object(&mut app, &module, "c", id).unwrap();
+    let err = object(&mut app, &module, "c", id).unwrap_err();
+    assert!(matches!(err, ContractError::AlreadyObjected {}));
+
+    let err = object(&mut app, &module, "stranger", id).unwrap_err();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    // Voting power gained after a proposal is created may not be used
+    // to object to it.
+    app.execute_contract(
+        dao,
+        group,
+        &cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: vec![],
+            add: vec![cw4::Member {
+                addr: "d".to_string(),
+                weight: 100,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 1);
+
+    let err = object(&mut app, &module, "d", id).unwrap_err();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    let vote: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &module,
+            &QueryMsg::GetVote {
+                proposal_id: id,
+                voter: "c".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(vote.vote.unwrap().power, Uint128::new(20));
+}
+
+#[test]
+fn test_only_authorized_proposers() {
+    let Setup {
+        mut app,
+        module,
+        pre_propose,
+        ..
+    } = setup();
+
+    // Only members may propose via the pre-propose module.
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: "stranger".to_string(),
+        amount: coins(DEPOSIT, DENOM),
+    }))
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("stranger"),
+        pre_propose,
+        &cppbps::ExecuteMsg::Propose {
+            msg: cppbps::ProposeMessage::Propose {
+                title: "routine spend".to_string(),
+                description: "pay for the servers".to_string(),
+                msgs: vec![],
+                dependencies: vec![],
+            },
+        },
+        &coins(DEPOSIT, DENOM),
+    )
+    .unwrap_err();
+
+    // Proposals may not bypass the pre-propose module.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a"),
+            module,
+            &ExecuteMsg::Propose {
+                title: "routine spend".to_string(),
+                description: "pay for the servers".to_string(),
+                msgs: vec![],
+                proposer: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+}
+
+#[test]
+fn test_invalid_objection_threshold() {
+    let mut app = App::default();
+    let code_id = app.store_code(optimistic_contract());
+
+    let err: ContractError = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &InstantiateMsg {
+                objection_threshold: PercentageThreshold::Percent(Decimal::zero()),
+                voting_period: Duration::Height(10),
+                only_members_execute: true,
+                close_proposal_on_execution_failure: true,
+                pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            },
+            &[],
+            "optimistic",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::ThresholdError(ThresholdError::ZeroThreshold {})
+    ));
+}
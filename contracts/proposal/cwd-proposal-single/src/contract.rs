@@ -1,30 +1,42 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
-    Response, StdResult, Storage, SubMsg, WasmMsg,
+    to_binary, Addr, Binary, BlockInfo, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint64, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw_proposal_single_v1 as v1;
 use cw_storage_plus::Bound;
-use cw_utils::{parse_reply_instantiate_data, Duration};
+use cw_utils::{parse_reply_instantiate_data, Duration, Expiration};
 use cwd_hooks::Hooks;
 use cwd_interface::voting::IsActiveResponse;
 use cwd_pre_propose_single::contract::ExecuteMsg as PreProposeMsg;
 use cwd_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
 use cwd_vote_hooks::new_vote_hooks;
+use cwd_voting::anti_sniping::{AntiSnipingConfig, AntiSnipingState};
+use cwd_voting::commit_reveal::{commitment_hash, CommitRevealConfig};
+use cwd_voting::delegation::{get_delegate, get_delegations};
+use cwd_voting::dependency::{DependencyError, ProposalDependency, UncheckedProposalDependency};
 use cwd_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
 use cwd_voting::proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
 use cwd_voting::reply::{
     failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
 };
+use cwd_voting::signed_ballot::SignedBallot;
 use cwd_voting::status::Status;
 use cwd_voting::threshold::Threshold;
-use cwd_voting::voting::{get_total_power, get_voting_power, validate_voting_period, Vote, Votes};
+use cwd_voting::veto::UncheckedVetoConfig;
+use cwd_voting::voting::{
+    get_total_power, get_voting_power, validate_execution_window, validate_no_with_veto_threshold,
+    validate_voting_delay, validate_voting_period, Vote, Votes,
+};
 
 use crate::msg::MigrateMsg;
 use crate::proposal::SingleChoiceProposal;
-use crate::state::{Config, CREATION_POLICY};
+use crate::rules::{select_rule, UncheckedThresholdRule};
+use crate::state::{
+    Config, IndexProgress, BALLOT_NONCES, COMMITMENTS, CREATION_POLICY, DEPENDENTS, INDEX_PROGRESS,
+};
 
 use crate::v1_state::{
     v1_duration_to_v2, v1_expiration_to_v2, v1_status_to_v2, v1_threshold_to_v2, v1_votes_to_v2,
@@ -33,9 +45,13 @@ use crate::{
     error::ContractError,
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     proposal::advance_proposal_id,
-    query::ProposalListResponse,
-    query::{ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
-    state::{Ballot, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
+    query::{CommitmentInfo, CommitmentListResponse, ProposalListResponse},
+    query::{DependenciesResponse, DependencyInfo, DependentListResponse},
+    query::{
+        ProposalResponse, VoteInfo, VoteListResponse, VoteResponse, VoterVoteInfo,
+        VoterVoteListResponse,
+    },
+    state::{ballots, proposals, Ballot, CONFIG, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-proposal-single";
@@ -57,6 +73,34 @@ pub fn instantiate(
     let (min_voting_period, max_voting_period) =
         validate_voting_period(msg.min_voting_period, msg.max_voting_period)?;
 
+    let veto = msg
+        .veto
+        .map(|veto| veto.into_checked(&deps.as_ref(), &max_voting_period))
+        .transpose()?;
+
+    if let Some(commit_reveal) = &msg.commit_reveal {
+        commit_reveal.validate(&max_voting_period)?;
+    }
+
+    if let Some(anti_sniping) = &msg.anti_sniping {
+        anti_sniping.validate(&max_voting_period)?;
+    }
+
+    let voting_delay = validate_voting_delay(msg.voting_delay, &max_voting_period)?;
+    let execution_window = validate_execution_window(msg.execution_window, &max_voting_period)?;
+    let no_with_veto_threshold = validate_no_with_veto_threshold(msg.no_with_veto_threshold)?;
+
+    let threshold_rules = msg
+        .threshold_rules
+        .into_iter()
+        .map(|rule| rule.into_checked(deps.as_ref(), min_voting_period, &max_voting_period))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let delegation_registry = msg
+        .delegation_registry
+        .map(|registry| deps.api.addr_validate(&registry))
+        .transpose()?;
+
     let (initial_policy, pre_propose_messages) = msg
         .pre_propose_info
         .into_initial_policy_and_messages(dao.clone())?;
@@ -69,6 +113,14 @@ pub fn instantiate(
         dao: dao.clone(),
         allow_revoting: msg.allow_revoting,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        veto,
+        delegation_registry,
+        commit_reveal: msg.commit_reveal,
+        anti_sniping: msg.anti_sniping,
+        threshold_rules,
+        voting_delay,
+        execution_window,
+        no_with_veto_threshold,
     };
 
     // Initialize proposal count to zero so that queries return zero
@@ -96,8 +148,57 @@ pub fn execute(
             description,
             msgs,
             proposer,
-        } => execute_propose(deps, env, info.sender, title, description, msgs, proposer),
-        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+            dependencies,
+        } => execute_propose(
+            deps,
+            env,
+            info.sender,
+            title,
+            description,
+            msgs,
+            proposer,
+            dependencies,
+        ),
+        ExecuteMsg::UpdateProposal {
+            proposal_id,
+            title,
+            description,
+            msgs,
+            proposer,
+        } => execute_update_proposal(
+            deps,
+            env,
+            info,
+            proposal_id,
+            title,
+            description,
+            msgs,
+            proposer,
+        ),
+        ExecuteMsg::WithdrawProposal {
+            proposal_id,
+            proposer,
+        } => execute_withdraw_proposal(deps, env, info, proposal_id, proposer),
+        ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale,
+        } => execute_vote(deps, env, info, proposal_id, vote, rationale),
+        ExecuteMsg::CommitVote {
+            proposal_id,
+            commitment,
+        } => execute_commit_vote(deps, env, info, proposal_id, commitment),
+        ExecuteMsg::RevealVote {
+            proposal_id,
+            vote,
+            salt,
+            rationale,
+        } => execute_reveal_vote(deps, env, info, proposal_id, vote, salt, rationale),
+        ExecuteMsg::RelayVotes { ballots } => execute_relay_votes(deps, env, info, ballots),
+        ExecuteMsg::UpdateRationale {
+            proposal_id,
+            rationale,
+        } => execute_update_rationale(deps, info, proposal_id, rationale),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
         ExecuteMsg::UpdateConfig {
@@ -108,6 +209,14 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            veto,
+            delegation_registry,
+            commit_reveal,
+            anti_sniping,
+            threshold_rules,
+            voting_delay,
+            execution_window,
+            no_with_veto_threshold,
         } => execute_update_config(
             deps,
             info,
@@ -118,7 +227,16 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            veto,
+            delegation_registry,
+            commit_reveal,
+            anti_sniping,
+            threshold_rules,
+            voting_delay,
+            execution_window,
+            no_with_veto_threshold,
         ),
+        ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
         }
@@ -132,9 +250,11 @@ pub fn execute(
         ExecuteMsg::RemoveVoteHook { address } => {
             execute_remove_vote_hook(deps, env, info, address)
         }
+        ExecuteMsg::IndexExisting { limit } => execute_index_existing(deps, limit),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_propose(
     deps: DepsMut,
     env: Env,
@@ -143,6 +263,7 @@ pub fn execute_propose(
     description: String,
     msgs: Vec<CosmosMsg<Empty>>,
     proposer: Option<String>,
+    dependencies: Vec<UncheckedProposalDependency>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
@@ -181,7 +302,59 @@ pub fn execute_propose(
         return Err(ContractError::InactiveDao {});
     }
 
-    let expiration = config.max_voting_period.after(&env.block);
+    // Dependencies must be existing proposals in one of the DAO's
+    // proposal modules. As proposals may only depend on proposals
+    // created before them, dependencies never form a cycle.
+    let dependencies = dependencies
+        .into_iter()
+        .map(|dependency| {
+            let dependency = dependency.into_checked(deps.as_ref(), &config.dao)?;
+            dependency_status(deps.as_ref(), &env, &dependency)?;
+            Ok(dependency)
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
+    // Proposals which meet a threshold rule use its threshold and
+    // voting period in place of the defaults.
+    let rule = select_rule(&config.threshold_rules, &msgs)?.cloned();
+    let (threshold, max_voting_period) = match &rule {
+        Some(rule) => (rule.threshold.clone(), rule.max_voting_period),
+        None => (config.threshold, config.max_voting_period),
+    };
+
+    // Proposals with a voting delay are pending until it has elapsed
+    // and their voting period begins once they open.
+    let voting_start = config.voting_delay.map(|delay| delay.after(&env.block));
+    // Voting delays are measured in blocks so the height voting power
+    // is snapshotted at is fixed when the proposal is created.
+    let start_height = match voting_start {
+        Some(Expiration::AtHeight(height)) => height,
+        _ => env.block.height,
+    };
+    let after_start = |period: Duration| -> StdResult<Expiration> {
+        match voting_start {
+            Some(start) => start + period,
+            None => Ok(period.after(&env.block)),
+        }
+    };
+    let expiration = after_start(max_voting_period)?;
+    let min_voting_period = config.min_voting_period.map(after_start).transpose()?;
+
+    // With commit-reveal voting the voting period is the commit phase
+    // and the proposal expires once the reveal phase that follows it
+    // has ended.
+    let (commit_expiration, expiration) = match &config.commit_reveal {
+        Some(commit_reveal) => (Some(expiration), commit_reveal.reveal_end(expiration)?),
+        None => (None, expiration),
+    };
+
+    // Votes are hidden until the voting period has ended on
+    // commit-reveal proposals so there is nothing to respond to and
+    // their voting period is never extended.
+    let anti_sniping = match &config.commit_reveal {
+        Some(_) => None,
+        None => config.anti_sniping.map(AntiSnipingState::new),
+    };
 
     let total_power = get_total_power(deps.as_ref(), config.dao, Some(env.block.height))?;
 
@@ -191,15 +364,27 @@ pub fn execute_propose(
             title,
             description,
             proposer: proposer.clone(),
-            start_height: env.block.height,
-            min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
+            start_height,
+            min_voting_period,
             expiration,
-            threshold: config.threshold,
+            threshold,
             total_power,
             msgs,
-            status: Status::Open,
+            status: match voting_start {
+                Some(_) => Status::Pending,
+                None => Status::Open,
+            },
             votes: Votes::zero(),
             allow_revoting: config.allow_revoting,
+            veto: config.veto,
+            delegation_registry: config.delegation_registry,
+            commit_expiration,
+            anti_sniping,
+            rule,
+            voting_start,
+            execution_window: config.execution_window,
+            dependencies,
+            no_with_veto_threshold: config.no_with_veto_threshold,
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -230,7 +415,18 @@ pub fn execute_propose(
         });
     }
 
-    PROPOSALS.save(deps.storage, id, &proposal)?;
+    proposals().save(deps.storage, id, &proposal)?;
+    for dependency in &proposal.dependencies {
+        DEPENDENTS.save(
+            deps.storage,
+            (
+                dependency.proposal_module.clone(),
+                dependency.proposal_id,
+                id,
+            ),
+            &Empty {},
+        )?;
+    }
 
     let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
 
@@ -265,6 +461,171 @@ pub fn execute_propose(
         .add_attribute("status", proposal.status.to_string()))
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+    proposer: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    check_amendable(
+        deps.as_ref(),
+        &env,
+        &info.sender,
+        proposer,
+        proposal_id,
+        &prop,
+    )?;
+
+    // The proposal's threshold and voting period were selected by
+    // its messages when it was created. Allowing updates to select
+    // another rule would let a proposal be created under a lower
+    // threshold than its messages require.
+    if select_rule(&config.threshold_rules, &msgs)? != prop.rule.as_ref() {
+        return Err(ContractError::ThresholdRuleChanged {});
+    }
+
+    prop.title = title;
+    prop.description = description;
+    prop.msgs = msgs;
+
+    // See `execute_propose` for why proposal size is limited.
+    let proposal_size = cosmwasm_std::to_vec(&prop)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    proposals().save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_withdraw_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    proposer: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    check_amendable(
+        deps.as_ref(),
+        &env,
+        &info.sender,
+        proposer,
+        proposal_id,
+        &prop,
+    )?;
+
+    let old_status = prop.status;
+    prop.status = Status::Withdrawn;
+    proposals().save(deps.storage, proposal_id, &prop)?;
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let hooks = match proposal_creation_policy {
+        ProposalCreationPolicy::Anyone {} => hooks,
+        ProposalCreationPolicy::Module { addr } => {
+            let msg = to_binary(&PreProposeMsg::ProposalCompletedHook {
+                proposal_id,
+                new_status: prop.status,
+            })?;
+            let mut hooks = hooks;
+            hooks.push(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: addr.into_string(),
+                    msg,
+                    funds: vec![],
+                },
+                failed_pre_propose_module_hook_id(),
+            ));
+            hooks
+        }
+    };
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "withdraw_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("status", prop.status.to_string()))
+}
+
+/// Checks that SENDER may update or withdraw PROP and that it has
+/// not yet been voted on. As with proposal creation, if a
+/// pre-propose module is attached it must send the message on behalf
+/// of the proposer. Otherwise, the proposer must send it themselves.
+fn check_amendable(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    proposer: Option<String>,
+    proposal_id: u64,
+    prop: &SingleChoiceProposal,
+) -> Result<(), ContractError> {
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    if !proposal_creation_policy.is_permitted(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let proposer = match (proposer, &proposal_creation_policy) {
+        (None, ProposalCreationPolicy::Anyone {}) => sender.clone(),
+        (Some(proposer), ProposalCreationPolicy::Module { .. }) => {
+            deps.api.addr_validate(&proposer)?
+        }
+        _ => return Err(ContractError::InvalidProposer {}),
+    };
+    if proposer != prop.proposer {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !matches!(
+        prop.current_status(&env.block),
+        Status::Open | Status::Pending
+    ) {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+    // Votes on commit-reveal proposals are not counted until they
+    // are revealed so commitments count as votes here.
+    let voted = ballots()
+        .prefix(proposal_id)
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+        || COMMITMENTS
+            .prefix(proposal_id)
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+    if voted {
+        return Err(ContractError::VotingStarted { id: proposal_id });
+    }
+    Ok(())
+}
+
 pub fn execute_execute(
     deps: DepsMut,
     env: Env,
@@ -272,29 +633,54 @@ pub fn execute_execute(
     proposal_id: u64,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    if config.only_members_execute {
+    let mut prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // The vetoer may execute proposals during their veto window if
+    // early execution is enabled. They need not be a member of the
+    // DAO to do so.
+    let is_early_executing_vetoer = prop
+        .veto
+        .as_ref()
+        .map(|veto| veto.early_execute && veto.is_vetoer(&info.sender))
+        .unwrap_or(false);
+
+    if config.only_members_execute && !is_early_executing_vetoer {
         let power = get_voting_power(deps.as_ref(), info.sender.clone(), config.dao.clone(), None)?;
         if power.is_zero() {
             return Err(ContractError::Unauthorized {});
         }
     }
 
-    let mut prop = PROPOSALS
-        .may_load(deps.storage, proposal_id)?
-        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
-
     // Check here that the proposal is passed. Allow it to be executed
     // even if it is expired so long as it passed during its voting
     // period.
     let old_status = prop.status;
     prop.update_status(&env.block);
+    if prop.status == Status::ExecutionExpired {
+        return Err(ContractError::ExecutionWindowExpired {});
+    }
     if prop.status != Status::Passed {
         return Err(ContractError::NotPassed {});
     }
 
+    if prop.is_veto_window_active(&env.block)? && !is_early_executing_vetoer {
+        return Err(ContractError::VetoWindowActive {});
+    }
+
+    for dependency in &prop.dependencies {
+        if dependency_status(deps.as_ref(), &env, dependency)? != Status::Executed {
+            return Err(ContractError::DependencyNotExecuted {
+                proposal_module: dependency.proposal_module.clone(),
+                proposal_id: dependency.proposal_id,
+            });
+        }
+    }
+
     prop.status = Status::Executed;
 
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    proposals().save(deps.storage, proposal_id, &prop)?;
 
     let response = {
         if !prop.msgs.is_empty() {
@@ -302,6 +688,7 @@ pub fn execute_execute(
                 contract_addr: config.dao.to_string(),
                 msg: to_binary(&cwd_core::msg::ExecuteMsg::ExecuteProposalHook {
                     msgs: prop.msgs,
+                    proposal_id: Some(proposal_id),
                 })?,
                 funds: vec![],
             };
@@ -362,28 +749,327 @@ pub fn execute_vote(
     info: MessageInfo,
     proposal_id: u64,
     vote: Vote,
+    rationale: Option<String>,
+) -> Result<Response, ContractError> {
+    vote.validate()?;
+    let prop = load_votable_proposal(deps.storage, &env, proposal_id)?;
+
+    cast_vote(
+        deps,
+        env,
+        info.sender,
+        proposal_id,
+        prop,
+        vote,
+        rationale,
+        "vote",
+    )
+}
+
+pub fn execute_relay_votes(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ballots: Vec<SignedBallot<Vote>>,
+) -> Result<Response, ContractError> {
+    if ballots.is_empty() {
+        return Err(ContractError::NoBallots {});
+    }
+
+    let mut response = Response::default()
+        .add_attribute("action", "relay_votes")
+        .add_attribute("relayer", info.sender);
+
+    for ballot in ballots {
+        let voter = ballot.verify(deps.api, &env.block.chain_id, &env.contract.address)?;
+        use_ballot_nonce(deps.storage, &voter, ballot.nonce)?;
+
+        ballot.vote.validate()?;
+        let prop = load_votable_proposal(deps.storage, &env, ballot.proposal_id)?;
+
+        let res = cast_vote(
+            deps.branch(),
+            env.clone(),
+            voter,
+            ballot.proposal_id,
+            prop,
+            ballot.vote,
+            None,
+            "relayed_vote",
+        )?;
+        response = response
+            .add_submessages(res.messages)
+            .add_attributes(res.attributes);
+    }
+
+    Ok(response)
+}
+
+/// Loads a proposal which may be voted on directly, as opposed to
+/// with a commitment.
+fn load_votable_proposal(
+    storage: &dyn Storage,
+    env: &Env,
+    proposal_id: u64,
+) -> Result<SingleChoiceProposal, ContractError> {
+    let prop = proposals()
+        .may_load(storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.current_status(&env.block) != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+    if prop.commit_expiration.is_some() {
+        return Err(ContractError::CommitRevealEnabled {});
+    }
+    Ok(prop)
+}
+
+/// Opens a pending proposal whose voting delay has elapsed,
+/// snapshotting its total voting power at its start height, the
+/// height its voting delay ended. Returns true if the proposal was
+/// opened.
+fn open_pending_proposal(
+    deps: Deps,
+    dao: &Addr,
+    prop: &mut SingleChoiceProposal,
+    block: &BlockInfo,
+) -> StdResult<bool> {
+    if prop.status != Status::Pending || prop.is_pending(block) {
+        return Ok(false);
+    }
+    prop.total_power = get_total_power(deps, dao.clone(), Some(prop.start_height))?;
+    prop.status = Status::Open;
+    Ok(true)
+}
+
+/// Errors if NONCE is lower than VOTER's next ballot nonce. Otherwise,
+/// sets their next nonce to NONCE + 1 so that the ballot may not be
+/// replayed.
+fn use_ballot_nonce(
+    storage: &mut dyn Storage,
+    voter: &Addr,
+    nonce: u64,
+) -> Result<(), ContractError> {
+    let next = BALLOT_NONCES
+        .may_load(storage, voter.clone())?
+        .unwrap_or_default();
+    if nonce < next {
+        return Err(ContractError::InvalidNonce { next });
+    }
+    let next = Uint64::new(nonce)
+        .checked_add(Uint64::one())
+        .map_err(StdError::overflow)?;
+    BALLOT_NONCES.save(storage, voter.clone(), &next.u64())?;
+    Ok(())
+}
+
+/// Gets the current status of DEPENDENCY. Proposals in this module
+/// are loaded from storage and proposals in other modules are
+/// queried.
+fn dependency_status(
+    deps: Deps,
+    env: &Env,
+    dependency: &ProposalDependency,
+) -> Result<Status, DependencyError> {
+    if dependency.proposal_module == env.contract.address {
+        let prop = proposals()
+            .may_load(deps.storage, dependency.proposal_id)?
+            .ok_or_else(|| DependencyError::NoSuchDependency {
+                proposal_module: dependency.proposal_module.clone(),
+                proposal_id: dependency.proposal_id,
+            })?;
+        Ok(prop.current_status(&env.block))
+    } else {
+        dependency.query_status(deps)
+    }
+}
+
+pub fn execute_commit_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    commitment: Binary,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut prop = PROPOSALS
+    let mut prop = proposals()
         .may_load(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let commit_expiration = prop
+        .commit_expiration
+        .ok_or(ContractError::CommitRevealDisabled {})?;
     if prop.current_status(&env.block) != Status::Open {
         return Err(ContractError::NotOpen { id: proposal_id });
     }
+    if commit_expiration.is_expired(&env.block) {
+        return Err(ContractError::NotCommitPhase { id: proposal_id });
+    }
+
+    // Voting power is snapshotted when the first commitment is made
+    // on a proposal with a voting delay.
+    let change_hooks = if open_pending_proposal(deps.as_ref(), &config.dao, &mut prop, &env.block)?
+    {
+        proposals().save(deps.storage, proposal_id, &prop)?;
+        proposal_status_changed_hooks(
+            PROPOSAL_HOOKS,
+            deps.storage,
+            proposal_id,
+            Status::Pending.to_string(),
+            prop.status.to_string(),
+        )?
+    } else {
+        vec![]
+    };
+
+    // Voters without voting power of their own may still have
+    // voting power delegated to them, so this can only be checked
+    // without a delegation registry. Otherwise, it is checked when
+    // the vote is revealed.
+    if prop.delegation_registry.is_none() {
+        let power = get_voting_power(
+            deps.as_ref(),
+            info.sender.clone(),
+            config.dao,
+            Some(prop.start_height),
+        )?;
+        if power.is_zero() {
+            return Err(ContractError::NotRegistered {});
+        }
+    }
+
+    // Committing again during the commit phase replaces the previous
+    // commitment.
+    COMMITMENTS.save(
+        deps.storage,
+        (proposal_id, info.sender.clone()),
+        &commitment,
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(change_hooks)
+        .add_attribute("action", "commit_vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("commitment", commitment.to_base64()))
+}
+
+pub fn execute_reveal_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: Vote,
+    salt: String,
+    rationale: Option<String>,
+) -> Result<Response, ContractError> {
+    vote.validate()?;
+
+    let prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let commit_expiration = prop
+        .commit_expiration
+        .ok_or(ContractError::CommitRevealDisabled {})?;
+    if !commit_expiration.is_expired(&env.block) || prop.current_status(&env.block) != Status::Open
+    {
+        return Err(ContractError::NotRevealPhase { id: proposal_id });
+    }
+
+    let commitment = COMMITMENTS
+        .may_load(deps.storage, (proposal_id, info.sender.clone()))?
+        .ok_or(ContractError::NoCommitment {})?;
+    if commitment_hash(&info.sender, &vote, &salt)? != commitment {
+        return Err(ContractError::CommitmentMismatch {});
+    }
+    COMMITMENTS.remove(deps.storage, (proposal_id, info.sender.clone()));
+
+    cast_vote(
+        deps,
+        env,
+        info.sender,
+        proposal_id,
+        prop,
+        vote,
+        rationale,
+        "reveal_vote",
+    )
+}
+
+/// Counts VOTER's vote on a proposal which is known to be open for
+/// voting. ACTION is used as the response's action attribute.
+#[allow(clippy::too_many_arguments)]
+fn cast_vote(
+    deps: DepsMut,
+    env: Env,
+    voter: Addr,
+    proposal_id: u64,
+    mut prop: SingleChoiceProposal,
+    vote: Vote,
+    rationale: Option<String>,
+    action: &str,
+) -> Result<Response, ContractError> {
+    if vote == Vote::NoWithVeto && prop.no_with_veto_threshold.is_none() {
+        return Err(ContractError::NoWithVetoDisabled {});
+    }
 
-    let vote_power = get_voting_power(
+    let config = CONFIG.load(deps.storage)?;
+    let old_status = prop.status;
+    open_pending_proposal(deps.as_ref(), &config.dao, &mut prop, &env.block)?;
+    let was_passing = prop.is_passing();
+
+    let own_power = get_voting_power(
         deps.as_ref(),
-        info.sender.clone(),
-        config.dao,
+        voter.clone(),
+        config.dao.clone(),
         Some(prop.start_height),
     )?;
+
+    // Voting power delegated to the voter counts towards their vote
+    // unless the delegator has voted themselves.
+    let mut vote_power = own_power;
+    if let Some(registry) = &prop.delegation_registry {
+        for delegation in get_delegations(
+            deps.as_ref(),
+            registry,
+            &config.dao,
+            &voter,
+            Some(prop.start_height),
+        )? {
+            if !ballots().has(deps.storage, (proposal_id, delegation.delegator)) {
+                vote_power += delegation.power;
+            }
+        }
+    }
     if vote_power.is_zero() {
         return Err(ContractError::NotRegistered {});
     }
 
-    BALLOTS.update(
+    // If this is the voter's first vote, and they had delegated their
+    // voting power, their voting power was counted towards their
+    // delegate's vote if the delegate has voted. Voting overrides
+    // the delegate's vote so remove it from the delegate's ballot.
+    if let Some(registry) = &prop.delegation_registry {
+        if !ballots().has(deps.storage, (proposal_id, voter.clone())) {
+            let delegate = get_delegate(deps.as_ref(), registry, &voter, Some(prop.start_height))?;
+            if let Some(delegate) = delegate {
+                if let Some(mut ballot) =
+                    ballots().may_load(deps.storage, (proposal_id, delegate.clone()))?
+                {
+                    // Split votes are divided according to the ballot's
+                    // total power so recount the whole ballot rather
+                    // than removing only the overridden power.
+                    prop.votes.remove_vote(ballot.vote, ballot.power);
+                    ballot.power -= own_power;
+                    prop.votes.add_vote(ballot.vote, ballot.power);
+                    ballots().save(deps.storage, (proposal_id, delegate), &ballot)?;
+                }
+            }
+        }
+    }
+
+    ballots().update(
         deps.storage,
-        (proposal_id, info.sender.clone()),
+        (proposal_id, voter.clone()),
         |bal| match bal {
             Some(current_ballot) => {
                 if prop.allow_revoting {
@@ -399,6 +1085,7 @@ pub fn execute_vote(
                         Ok(Ballot {
                             power: vote_power,
                             vote,
+                            rationale,
                         })
                     }
                 } else {
@@ -408,16 +1095,30 @@ pub fn execute_vote(
             None => Ok(Ballot {
                 power: vote_power,
                 vote,
+                rationale,
             }),
         },
     )?;
 
-    let old_status = prop.status;
-
     prop.votes.add_vote(vote, vote_power);
     prop.update_status(&env.block);
 
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    // If this vote flipped the outcome of the proposal shortly before
+    // it expires, extend its voting period so that others may
+    // respond. Proposals which are no longer open have an outcome
+    // that no future vote can change and are not extended.
+    let outcome_changed = was_passing != prop.is_passing();
+    let extended = match &mut prop.anti_sniping {
+        Some(anti_sniping) if outcome_changed && prop.status == Status::Open => {
+            anti_sniping.extend(prop.expiration, &env.block)?
+        }
+        _ => None,
+    };
+    if let Some(expiration) = extended {
+        prop.expiration = expiration;
+    }
+
+    proposals().save(deps.storage, proposal_id, &prop)?;
 
     let new_status = prop.status;
     let change_hooks = proposal_status_changed_hooks(
@@ -432,18 +1133,49 @@ pub fn execute_vote(
         VOTE_HOOKS,
         deps.storage,
         proposal_id,
-        info.sender.to_string(),
+        voter.to_string(),
         vote.to_string(),
     )?;
 
     Ok(Response::default()
         .add_submessages(change_hooks)
         .add_submessages(vote_hooks)
-        .add_attribute("action", "vote")
-        .add_attribute("sender", info.sender)
+        .add_attribute("action", action)
+        .add_attribute("sender", voter)
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("position", vote.to_string())
-        .add_attribute("status", prop.status.to_string()))
+        .add_attribute("status", prop.status.to_string())
+        .add_attributes(extended.map(|expiration| ("extended_expiration", expiration.to_string()))))
+}
+
+pub fn execute_update_rationale(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+    rationale: Option<String>,
+) -> Result<Response, ContractError> {
+    // Rationales may be updated at any time, including after the
+    // proposal has closed and when revoting is disabled, as they do
+    // not affect the outcome of the proposal.
+    ballots().update(
+        deps.storage,
+        (proposal_id, info.sender.clone()),
+        |ballot| match ballot {
+            Some(ballot) => Ok(Ballot {
+                rationale,
+                ..ballot
+            }),
+            None => Err(ContractError::NoSuchVote {
+                id: proposal_id,
+                voter: info.sender.to_string(),
+            }),
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_rationale")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
 pub fn execute_close(
@@ -452,19 +1184,95 @@ pub fn execute_close(
     info: MessageInfo,
     proposal_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    let mut prop = proposals().load(deps.storage, proposal_id)?;
+    let stored_status = prop.status;
 
     // Update status to ensure that proposals which were open and have
     // expired are moved to "rejected."
     prop.update_status(&env.block);
-    if prop.status != Status::Rejected {
-        return Err(ContractError::WrongCloseStatus {});
+    let old_status = match prop.status {
+        // Proposals rejected by NoWithVeto votes have their deposits
+        // sent to the pre-propose module's veto destination.
+        Status::Rejected if prop.is_vetoed_by_votes(&env.block) => {
+            prop.status = Status::ClosedWithVeto;
+            Status::Rejected
+        }
+        Status::Rejected => {
+            prop.status = Status::Closed;
+            Status::Rejected
+        }
+        // Passed proposals whose execution window has ended keep
+        // their status when closed. Closing them saves it and settles
+        // their deposit.
+        Status::ExecutionExpired if stored_status != Status::ExecutionExpired => Status::Passed,
+        _ => return Err(ContractError::WrongCloseStatus {}),
+    };
+
+    proposals().save(deps.storage, proposal_id, &prop)?;
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let hooks = match proposal_creation_policy {
+        ProposalCreationPolicy::Anyone {} => hooks,
+        ProposalCreationPolicy::Module { addr } => {
+            let msg = to_binary(&PreProposeMsg::ProposalCompletedHook {
+                proposal_id,
+                new_status: prop.status,
+            })?;
+            let mut hooks = hooks;
+            hooks.push(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: addr.into_string(),
+                    msg,
+                    funds: vec![],
+                },
+                failed_pre_propose_module_hook_id(),
+            ));
+            hooks
+        }
+    };
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_veto(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    match &prop.veto {
+        Some(veto) if veto.is_vetoer(&info.sender) => (),
+        _ => return Err(ContractError::Unauthorized {}),
     }
 
     let old_status = prop.status;
+    prop.update_status(&env.block);
+    if prop.status != Status::Passed {
+        return Err(ContractError::NotPassed {});
+    }
+    if !prop.is_veto_window_active(&env.block)? {
+        return Err(ContractError::VetoWindowElapsed {});
+    }
 
-    prop.status = Status::Closed;
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    prop.status = Status::Vetoed;
+    proposals().save(deps.storage, proposal_id, &prop)?;
 
     let hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
@@ -498,7 +1306,7 @@ pub fn execute_close(
 
     Ok(Response::default()
         .add_submessages(hooks)
-        .add_attribute("action", "close")
+        .add_attribute("action", "veto")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string()))
 }
@@ -514,6 +1322,14 @@ pub fn execute_update_config(
     allow_revoting: bool,
     dao: String,
     close_proposal_on_execution_failure: bool,
+    veto: Option<UncheckedVetoConfig>,
+    delegation_registry: Option<String>,
+    commit_reveal: Option<CommitRevealConfig>,
+    anti_sniping: Option<AntiSnipingConfig>,
+    threshold_rules: Vec<UncheckedThresholdRule>,
+    voting_delay: Option<Duration>,
+    execution_window: Option<Duration>,
+    no_with_veto_threshold: Option<Decimal>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -528,6 +1344,31 @@ pub fn execute_update_config(
     let (min_voting_period, max_voting_period) =
         validate_voting_period(min_voting_period, max_voting_period)?;
 
+    let veto = veto
+        .map(|veto| veto.into_checked(&deps.as_ref(), &max_voting_period))
+        .transpose()?;
+
+    if let Some(commit_reveal) = &commit_reveal {
+        commit_reveal.validate(&max_voting_period)?;
+    }
+
+    if let Some(anti_sniping) = &anti_sniping {
+        anti_sniping.validate(&max_voting_period)?;
+    }
+
+    let voting_delay = validate_voting_delay(voting_delay, &max_voting_period)?;
+    let execution_window = validate_execution_window(execution_window, &max_voting_period)?;
+    let no_with_veto_threshold = validate_no_with_veto_threshold(no_with_veto_threshold)?;
+
+    let threshold_rules = threshold_rules
+        .into_iter()
+        .map(|rule| rule.into_checked(deps.as_ref(), min_voting_period, &max_voting_period))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let delegation_registry = delegation_registry
+        .map(|registry| deps.api.addr_validate(&registry))
+        .transpose()?;
+
     CONFIG.save(
         deps.storage,
         &Config {
@@ -538,6 +1379,14 @@ pub fn execute_update_config(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            veto,
+            delegation_registry,
+            commit_reveal,
+            anti_sniping,
+            threshold_rules,
+            voting_delay,
+            execution_window,
+            no_with_veto_threshold,
         },
     )?;
 
@@ -672,6 +1521,74 @@ pub fn execute_remove_vote_hook(
         .add_attribute("address", address))
 }
 
+/// Re-saves up to LIMIT of the proposals, and then the ballots, that
+/// have not been indexed since migrating so that they are present in
+/// their indexes.
+pub fn execute_index_existing(
+    deps: DepsMut,
+    limit: Option<u64>,
+) -> Result<Response, ContractError> {
+    let progress = INDEX_PROGRESS
+        .may_load(deps.storage)?
+        .ok_or(ContractError::IndexingComplete {})?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+
+    let (indexed, next) = match progress {
+        IndexProgress::Proposals { start_after } => {
+            let props = proposals()
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (id, prop) in &props {
+                proposals().save(deps.storage, *id, prop)?;
+            }
+            let next = match props.last() {
+                Some((id, _)) if props.len() == limit => IndexProgress::Proposals {
+                    start_after: Some(*id),
+                },
+                _ => IndexProgress::Ballots { start_after: None },
+            };
+            (props.len(), Some(next))
+        }
+        IndexProgress::Ballots { start_after } => {
+            let all = ballots()
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (key, ballot) in &all {
+                ballots().save(deps.storage, key.clone(), ballot)?;
+            }
+            let next = match all.last() {
+                Some((key, _)) if all.len() == limit => Some(IndexProgress::Ballots {
+                    start_after: Some(key.clone()),
+                }),
+                _ => None,
+            };
+            (all.len(), next)
+        }
+    };
+
+    match &next {
+        Some(next) => INDEX_PROGRESS.save(deps.storage, next)?,
+        None => INDEX_PROGRESS.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "index_existing")
+        .add_attribute("indexed", indexed.to_string())
+        .add_attribute("complete", next.is_none().to_string()))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -681,13 +1598,43 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListProposals { start_after, limit } => {
             query_list_proposals(deps, env, start_after, limit)
         }
+        QueryMsg::ListProposalsByStatus {
+            status,
+            start_after,
+            limit,
+        } => query_list_proposals_by_status(deps, env, status, start_after, limit),
+        QueryMsg::ListProposalsByProposer {
+            proposer,
+            start_after,
+            limit,
+        } => query_list_proposals_by_proposer(deps, env, proposer, start_after, limit),
         QueryMsg::ProposalCount {} => query_proposal_count(deps),
         QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, proposal_id, voter),
+        QueryMsg::ListVotesByVoter {
+            voter,
+            start_after,
+            limit,
+        } => query_list_votes_by_voter(deps, voter, start_after, limit),
         QueryMsg::ListVotes {
             proposal_id,
             start_after,
             limit,
         } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::ListCommitments {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_commitments(deps, proposal_id, start_after, limit),
+        QueryMsg::ProposalDependencies { proposal_id } => {
+            query_proposal_dependencies(deps, env, proposal_id)
+        }
+        QueryMsg::ListDependents {
+            proposal_module,
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_dependents(deps, proposal_module, proposal_id, start_after, limit),
+        QueryMsg::BallotNonce { address } => query_ballot_nonce(deps, address),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ReverseProposals {
             start_before,
@@ -696,6 +1643,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
         QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::IndexProgress {} => to_binary(&INDEX_PROGRESS.may_load(deps.storage)?),
     }
 }
 
@@ -710,7 +1658,7 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 }
 
 pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
-    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let proposal = proposals().load(deps.storage, id)?;
     to_binary(&proposal.into_response(&env.block, id))
 }
 
@@ -727,7 +1675,7 @@ pub fn query_list_proposals(
 ) -> StdResult<Binary> {
     let min = start_after.map(Bound::exclusive);
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
-    let props: Vec<ProposalResponse> = PROPOSALS
+    let props: Vec<ProposalResponse> = proposals()
         .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
         .take(limit as usize)
         .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
@@ -746,7 +1694,7 @@ pub fn query_reverse_proposals(
 ) -> StdResult<Binary> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
     let max = start_before.map(Bound::exclusive);
-    let props: Vec<ProposalResponse> = PROPOSALS
+    let props: Vec<ProposalResponse> = proposals()
         .range(deps.storage, None, max, cosmwasm_std::Order::Descending)
         .take(limit as usize)
         .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
@@ -757,6 +1705,68 @@ pub fn query_reverse_proposals(
     to_binary(&ProposalListResponse { proposals: props })
 }
 
+pub fn query_list_proposals_by_status(
+    deps: Deps,
+    env: Env,
+    status: Status,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+
+    // Stored statuses may be out of date so check every stored status
+    // that could currently be STATUS. Each contributes at most LIMIT
+    // proposals so the first LIMIT proposals of their union are the
+    // first LIMIT proposals whose current status is STATUS.
+    let mut props = vec![];
+    for stored_status in status.possible_stored_statuses() {
+        let matching = proposals()
+            .idx
+            .status
+            .prefix(stored_status.to_string())
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .map(|item| item.map(|(id, proposal)| proposal.into_response(&env.block, id)))
+            .filter(|item| {
+                item.as_ref()
+                    .map_or(true, |response| response.proposal.status == status)
+            })
+            .take(limit)
+            .collect::<StdResult<Vec<ProposalResponse>>>()?;
+        props.extend(matching);
+    }
+    props.sort_by_key(|response| response.id);
+    props.truncate(limit);
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_list_proposals_by_proposer(
+    deps: Deps,
+    env: Env,
+    proposer: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let proposer = deps.api.addr_validate(&proposer)?;
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let props: Vec<ProposalResponse> = proposals()
+        .idx
+        .proposer
+        .prefix(proposer)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| item.map(|(id, proposal)| proposal.into_response(&env.block, id)))
+        .collect::<StdResult<_>>()?;
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
 pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
     let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
     to_binary(&proposal_count)
@@ -764,11 +1774,12 @@ pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
 
 pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Binary> {
     let voter = deps.api.addr_validate(&voter)?;
-    let ballot = BALLOTS.may_load(deps.storage, (proposal_id, voter.clone()))?;
+    let ballot = ballots().may_load(deps.storage, (proposal_id, voter.clone()))?;
     let vote = ballot.map(|ballot| VoteInfo {
         voter,
         vote: ballot.vote,
         power: ballot.power,
+        rationale: ballot.rationale,
     });
     to_binary(&VoteResponse { vote })
 }
@@ -785,7 +1796,7 @@ pub fn query_list_votes(
         .transpose()?;
     let min = start_after.map(Bound::<Addr>::exclusive);
 
-    let votes = BALLOTS
+    let votes = ballots()
         .prefix(proposal_id)
         .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
         .take(limit as usize)
@@ -795,6 +1806,7 @@ pub fn query_list_votes(
                 voter,
                 vote: ballot.vote,
                 power: ballot.power,
+                rationale: ballot.rationale,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -802,6 +1814,112 @@ pub fn query_list_votes(
     to_binary(&VoteListResponse { votes })
 }
 
+pub fn query_list_votes_by_voter(
+    deps: Deps,
+    voter: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let min = start_after.map(|proposal_id| Bound::exclusive((proposal_id, voter.clone())));
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    let votes = ballots()
+        .idx
+        .voter
+        .prefix(voter)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let ((proposal_id, _), ballot) = item?;
+            Ok(VoterVoteInfo {
+                proposal_id,
+                vote: ballot.vote,
+                power: ballot.power,
+                rationale: ballot.rationale,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&VoterVoteListResponse { votes })
+}
+
+pub fn query_list_commitments(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.map(Bound::<Addr>::exclusive);
+
+    let commitments = COMMITMENTS
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (voter, commitment) = item?;
+            Ok(CommitmentInfo { voter, commitment })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&CommitmentListResponse { commitments })
+}
+
+pub fn query_proposal_dependencies(deps: Deps, env: Env, proposal_id: u64) -> StdResult<Binary> {
+    let proposal = proposals().load(deps.storage, proposal_id)?;
+    let dependencies = proposal
+        .dependencies
+        .into_iter()
+        .map(|dependency| {
+            let status = dependency_status(deps, &env, &dependency)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            Ok(DependencyInfo {
+                proposal_module: dependency.proposal_module,
+                proposal_id: dependency.proposal_id,
+                status,
+                met: status == Status::Executed,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&DependenciesResponse {
+        met: dependencies.iter().all(|dependency| dependency.met),
+        dependencies,
+    })
+}
+
+pub fn query_list_dependents(
+    deps: Deps,
+    proposal_module: String,
+    proposal_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let proposal_module = deps.api.addr_validate(&proposal_module)?;
+    let min = start_after.map(Bound::<u64>::exclusive);
+
+    let dependents = DEPENDENTS
+        .prefix((proposal_module, proposal_id))
+        .keys(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&DependentListResponse { dependents })
+}
+
+pub fn query_ballot_nonce(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let nonce = BALLOT_NONCES
+        .may_load(deps.storage, address)?
+        .unwrap_or_default();
+    to_binary(&nonce)
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&cwd_interface::voting::InfoResponse { info })
@@ -830,6 +1948,14 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                     allow_revoting: current_config.allow_revoting,
                     dao: current_config.dao.clone(),
                     close_proposal_on_execution_failure,
+                    veto: None,
+                    delegation_registry: None,
+                    commit_reveal: None,
+                    anti_sniping: None,
+                    threshold_rules: vec![],
+                    voting_delay: None,
+                    execution_window: None,
+                    no_with_veto_threshold: None,
                 },
             )?;
 
@@ -874,22 +2000,46 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                         status: v1_status_to_v2(prop.status),
                         votes: v1_votes_to_v2(prop.votes),
                         allow_revoting: prop.allow_revoting,
+                        veto: None,
+                        delegation_registry: None,
+                        commit_expiration: None,
+                        anti_sniping: None,
+                        rule: None,
+                        voting_start: None,
+                        execution_window: None,
+                        dependencies: vec![],
+                        no_with_veto_threshold: None,
                     };
 
-                    PROPOSALS
+                    proposals()
                         .save(deps.storage, id, &migrated_proposal)
                         .map_err(|e| e.into())
                 })?;
 
+            // v1 ballots are stored under the same keys as v2
+            // ballots but have not been indexed. Proposals were
+            // indexed as they were migrated above.
+            INDEX_PROGRESS.save(deps.storage, &IndexProgress::Ballots { start_after: None })?;
+
             Ok(Response::default()
                 .add_attribute("action", "migrate")
                 .add_attribute("from", "v1")
                 .add_submessages(pre_propose_messages))
         }
 
-        MigrateMsg::FromCompatible {} => Ok(Response::default()
-            .add_attribute("action", "migrate")
-            .add_attribute("from", "compatible")),
+        MigrateMsg::FromCompatible {} => {
+            // Proposals and ballots saved by earlier versions of this
+            // module have not been indexed. They are indexed in
+            // batches by `IndexExisting`.
+            INDEX_PROGRESS.save(
+                deps.storage,
+                &IndexProgress::Proposals { start_after: None },
+            )?;
+
+            Ok(Response::default()
+                .add_attribute("action", "migrate")
+                .add_attribute("from", "compatible"))
+        }
     }
 }
 
@@ -898,7 +2048,7 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
-            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
+            proposals().update(deps.storage, proposal_id, |prop| match prop {
                 Some(mut prop) => {
                     prop.status = Status::ExecutionFailed;
 
@@ -1,9 +1,13 @@
 use std::u64;
 
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Addr, StdError};
+use cw_denom::DenomError;
 use cw_utils::ParseReplyError;
 use cwd_hooks::HookError;
-use cwd_voting::reply::error::TagError;
+use cwd_voting::{
+    anti_sniping::AntiSnipingError, commit_reveal::CommitRevealError, dependency::DependencyError,
+    reply::error::TagError, signed_ballot::SignedBallotError, veto::VetoError,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -29,6 +33,9 @@ pub enum ContractError {
     #[error("no such proposal ({id})")]
     NoSuchProposal { id: u64 },
 
+    #[error("no vote exists for proposal ({id}) and voter ({voter})")]
+    NoSuchVote { id: u64, voter: String },
+
     #[error("proposal is ({size}) bytes, must be <= ({max}) bytes")]
     ProposalTooLarge { size: u64, max: u64 },
 
@@ -56,7 +63,7 @@ pub enum ContractError {
     #[error("proposal is closed")]
     Closed {},
 
-    #[error("only rejected proposals may be closed")]
+    #[error("only rejected proposals and passed proposals whose execution window has ended may be closed")]
     WrongCloseStatus {},
 
     #[error("the DAO is currently inactive, you cannot create proposals")]
@@ -86,4 +93,76 @@ pub enum ContractError {
 
     #[error("received a reply failure with an invalid ID: ({id})")]
     InvalidReplyID { id: u64 },
+
+    #[error(transparent)]
+    VetoError(#[from] VetoError),
+
+    #[error("proposal may not be executed until its veto window has ended")]
+    VetoWindowActive {},
+
+    #[error("the veto window for this proposal has ended")]
+    VetoWindowElapsed {},
+
+    #[error(transparent)]
+    CommitRevealError(#[from] CommitRevealError),
+
+    #[error("votes on this proposal must be committed and then revealed")]
+    CommitRevealEnabled {},
+
+    #[error("this proposal does not use commit-reveal voting")]
+    CommitRevealDisabled {},
+
+    #[error("this proposal does not allow no with veto votes")]
+    NoWithVetoDisabled {},
+
+    #[error("the commit phase of proposal ({id}) has ended")]
+    NotCommitPhase { id: u64 },
+
+    #[error("proposal ({id}) is not in its reveal phase")]
+    NotRevealPhase { id: u64 },
+
+    #[error("no vote commitment to reveal")]
+    NoCommitment {},
+
+    #[error("revealed vote does not match commitment")]
+    CommitmentMismatch {},
+
+    #[error(transparent)]
+    SignedBallotError(#[from] SignedBallotError),
+
+    #[error("no ballots to relay")]
+    NoBallots {},
+
+    #[error("ballot nonce is too low. the signer's next nonce is ({next})")]
+    InvalidNonce { next: u64 },
+
+    #[error(transparent)]
+    AntiSnipingError(#[from] AntiSnipingError),
+
+    #[error(transparent)]
+    DenomError(#[from] DenomError),
+
+    #[error("the execution window for this proposal has ended")]
+    ExecutionWindowExpired {},
+
+    #[error("threshold rule voting periods must have the same units (height or time) as max_voting_period")]
+    RuleDurationUnitsConflict {},
+
+    #[error(transparent)]
+    DependencyError(#[from] DependencyError),
+
+    #[error("proposal {proposal_id} in ({proposal_module}) must be executed before this proposal may be executed")]
+    DependencyNotExecuted {
+        proposal_module: Addr,
+        proposal_id: u64,
+    },
+
+    #[error("proposal ({id}) has been voted on and may no longer be updated or withdrawn")]
+    VotingStarted { id: u64 },
+
+    #[error("updated proposal messages must select the same threshold rule as the proposal's current messages")]
+    ThresholdRuleChanged {},
+
+    #[error("all proposals and ballots have been indexed")]
+    IndexingComplete {},
 }
@@ -47,6 +47,7 @@ mod error;
 pub mod msg;
 pub mod proposal;
 pub mod query;
+pub mod rules;
 
 #[cfg(test)]
 mod testing;
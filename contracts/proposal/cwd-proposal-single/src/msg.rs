@@ -1,8 +1,15 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, Empty};
+use cosmwasm_std::{Binary, CosmosMsg, Decimal, Empty};
 use cw_utils::Duration;
 use cwd_macros::{info_query, proposal_module_query};
-use cwd_voting::{pre_propose::PreProposeInfo, threshold::Threshold, voting::Vote};
+use cwd_voting::{
+    anti_sniping::AntiSnipingConfig, commit_reveal::CommitRevealConfig,
+    dependency::UncheckedProposalDependency, pre_propose::PreProposeInfo,
+    signed_ballot::SignedBallot, status::Status, threshold::Threshold, veto::UncheckedVetoConfig,
+    voting::Vote,
+};
+
+use crate::rules::UncheckedThresholdRule;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -36,6 +43,48 @@ pub struct InstantiateMsg {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// Optional configuration for an address that may veto passed
+    /// proposals before they are executed.
+    pub veto: Option<UncheckedVetoConfig>,
+    /// An optional delegation registry. If set, voting power
+    /// delegated to a voter is counted towards their vote unless the
+    /// delegator votes themselves.
+    pub delegation_registry: Option<String>,
+    /// If set, votes are committed as hashes during the voting
+    /// period and revealed during a reveal period which follows
+    /// it. Proposals may not pass or be rejected early and running
+    /// tallies remain hidden until votes are revealed.
+    pub commit_reveal: Option<CommitRevealConfig>,
+    /// If set, a vote cast within `window` of a proposal's
+    /// expiration which flips its outcome extends its voting period
+    /// by `extension`, at most `max_extensions` times. Has no effect
+    /// on proposals which use commit-reveal voting.
+    pub anti_sniping: Option<AntiSnipingConfig>,
+    /// Rules which select the threshold and voting period of a
+    /// proposal based on the messages it executes. The first rule
+    /// whose condition is met applies. Proposals which meet no rule
+    /// use `threshold` and `max_voting_period`. Omitting this means
+    /// there are no rules.
+    #[serde(default)]
+    pub threshold_rules: Vec<UncheckedThresholdRule>,
+    /// If set, proposals are pending for this amount of time after
+    /// they are created and may not be voted on. This gives members
+    /// time to read proposals and to stake or unstake before voting
+    /// power is snapshotted when voting opens. Must be measured in
+    /// blocks, as must `max_voting_period`.
+    pub voting_delay: Option<Duration>,
+    /// If set, passed proposals which are not executed within this
+    /// amount of time after their voting period, and veto window if
+    /// any, ends move to the `execution_expired` status and may no
+    /// longer be executed. Must have the same units as
+    /// `max_voting_period`.
+    pub execution_window: Option<Duration>,
+    /// If set, members may vote NoWithVeto, which counts as a no
+    /// vote. Proposals where more than this portion of the votes
+    /// cast are NoWithVeto votes are rejected and their deposits
+    /// are sent to the pre-propose module's veto destination rather
+    /// than refunded. Must be greater than zero and at most one.
+    pub no_with_veto_threshold: Option<Decimal>,
 }
 
 #[cw_serde]
@@ -55,14 +104,95 @@ pub enum ExecuteMsg {
         /// pre-propose module is attached, this must be Some and will
         /// set the proposer of the proposal it creates.
         proposer: Option<String>,
+        /// Proposals, possibly in other proposal modules of the DAO,
+        /// which must be executed before this proposal may be
+        /// executed.
+        #[serde(default)]
+        dependencies: Vec<UncheckedProposalDependency>,
+    },
+    /// Replaces the title, description, and messages of a
+    /// proposal. Only the proposal's proposer may update it, and only
+    /// before any votes have been cast on it. The updated messages
+    /// must select the same threshold rule as the proposal's current
+    /// ones.
+    UpdateProposal {
+        /// The ID of the proposal to update.
+        proposal_id: u64,
+        /// The new title of the proposal.
+        title: String,
+        /// The new description of the proposal.
+        description: String,
+        /// The messages that should be executed in response to this
+        /// proposal passing.
+        msgs: Vec<CosmosMsg<Empty>>,
+        /// The proposer of the proposal. As with `Propose`, this must
+        /// be None unless a pre-propose module is attached to this
+        /// module, in which case it must be Some and the update must
+        /// be sent by the pre-propose module.
+        proposer: Option<String>,
+    },
+    /// Withdraws a proposal, moving it to the `withdrawn` status. Only
+    /// the proposal's proposer may withdraw it, and only before any
+    /// votes have been cast on it. If applicable, the proposal's
+    /// deposit is refunded according to its withdrawal refund
+    /// policy.
+    WithdrawProposal {
+        /// The ID of the proposal to withdraw.
+        proposal_id: u64,
+        /// The proposer of the proposal. See `UpdateProposal`.
+        proposer: Option<String>,
     },
     /// Votes on a proposal. Voting power is determined by the DAO's
     /// voting power module.
     Vote {
         /// The ID of the proposal to vote on.
         proposal_id: u64,
-        /// The senders position on the proposal.
+        /// The senders position on the proposal. A split vote
+        /// divides the sender's voting power between positions.
+        vote: Vote,
+        /// An optional rationale for why this vote was cast. This
+        /// can be updated, set, or removed later with
+        /// `UpdateRationale`.
+        rationale: Option<String>,
+    },
+    /// Commits to a vote on a commit-reveal proposal during its
+    /// commit phase. Committing again replaces the previous
+    /// commitment. See `cwd_voting::commit_reveal::commitment_hash`
+    /// for how commitments are computed.
+    CommitVote {
+        /// The ID of the proposal to commit a vote on.
+        proposal_id: u64,
+        /// The hash of the sender's address, vote, and salt.
+        commitment: Binary,
+    },
+    /// Reveals a committed vote on a commit-reveal proposal during
+    /// its reveal phase. The vote is counted as if it were cast with
+    /// `Vote`.
+    RevealVote {
+        /// The ID of the proposal to reveal a vote on.
+        proposal_id: u64,
+        /// The vote that was committed to.
         vote: Vote,
+        /// The salt that was used when committing.
+        salt: String,
+        /// An optional rationale for why this vote was cast.
+        rationale: Option<String>,
+    },
+    /// Casts votes signed off chain by voters on their behalf. This
+    /// allows voters without gas tokens to vote. Each ballot is
+    /// counted as if its signer had cast it with `Vote`. If any
+    /// ballot is invalid no ballots are counted.
+    RelayVotes {
+        /// The signed ballots. See
+        /// `cwd_voting::signed_ballot::BallotSignDoc` for what voters
+        /// sign.
+        ballots: Vec<SignedBallot<Vote>>,
+    },
+    /// Updates the sender's rationale for their vote on the specified
+    /// proposal. Errors if no vote has been cast.
+    UpdateRationale {
+        proposal_id: u64,
+        rationale: Option<String>,
     },
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
@@ -112,6 +242,48 @@ pub enum ExecuteMsg {
         /// remain open until the DAO's treasury was large enough for it to be
         /// executed.
         close_proposal_on_execution_failure: bool,
+        /// Optional configuration for an address that may veto passed
+        /// proposals before they are executed. This will only apply
+        /// to proposals created after the config update.
+        veto: Option<UncheckedVetoConfig>,
+        /// An optional delegation registry. If set, voting power
+        /// delegated to a voter is counted towards their vote unless
+        /// the delegator votes themselves. This will only apply to
+        /// proposals created after the config update.
+        delegation_registry: Option<String>,
+        /// Optional configuration for commit-reveal voting. This will
+        /// only apply to proposals created after the config update.
+        commit_reveal: Option<CommitRevealConfig>,
+        /// Optional configuration for extending the voting period of
+        /// proposals whose outcome is changed shortly before they
+        /// expire. This will only apply to proposals created after
+        /// the config update.
+        anti_sniping: Option<AntiSnipingConfig>,
+        /// Rules which select the threshold and voting period of
+        /// proposals based on the messages they execute. Omitting
+        /// this means there are no rules. This will only apply to
+        /// proposals created after the config update.
+        #[serde(default)]
+        threshold_rules: Vec<UncheckedThresholdRule>,
+        /// Optional amount of time proposals are pending after they
+        /// are created before they may be voted on. This will only
+        /// apply to proposals created after the config update.
+        voting_delay: Option<Duration>,
+        /// Optional amount of time passed proposals may be executed
+        /// for after their voting period ends. This will only apply
+        /// to proposals created after the config update.
+        execution_window: Option<Duration>,
+        /// Optional portion of the votes cast which must be
+        /// NoWithVeto votes to reject a proposal. This will only
+        /// apply to proposals created after the config update.
+        no_with_veto_threshold: Option<Decimal>,
+    },
+    /// Vetoes a passed proposal during its veto window, preventing it
+    /// from being executed. Only callable by the vetoer configured
+    /// for the proposal.
+    Veto {
+        /// The ID of the proposal to veto.
+        proposal_id: u64,
     },
     /// Update's the proposal creation policy used for this
     /// module. Only the DAO may call this method.
@@ -131,6 +303,17 @@ pub enum ExecuteMsg {
     AddVoteHook { address: String },
     /// Removed a consumer of vote hooks.
     RemoveVoteHook { address: String },
+    /// Indexes up to LIMIT proposals or ballots saved by an earlier
+    /// version of this module. Migrating from an earlier version
+    /// starts indexing, which is done in batches so that large
+    /// modules do not exceed the gas limit. Until it completes,
+    /// queries by status, proposer, or voter may omit older proposals
+    /// and ballots. Callable by any address.
+    IndexExisting {
+        /// The maximum number of proposals or ballots to index. If
+        /// no limit is specified 30 are indexed.
+        limit: Option<u64>,
+    },
 }
 
 #[proposal_module_query]
@@ -170,6 +353,32 @@ pub enum QueryMsg {
         /// returned.
         limit: Option<u64>,
     },
+    /// Lists the proposals whose current status is STATUS in
+    /// ascending order of proposal ID.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposalsByStatus {
+        /// The status of the proposals to list.
+        status: Status,
+        /// The proposal ID to start listing proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists the proposals created by PROPOSER in ascending order of
+    /// proposal ID.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposalsByProposer {
+        /// The address whose proposals should be listed.
+        proposer: String,
+        /// The proposal ID to start listing proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
     /// Returns the number of proposals that have been created in this
     /// module.
     #[returns(u64)]
@@ -190,6 +399,54 @@ pub enum QueryMsg {
         /// query. If no limit is specified a max of 30 are returned.
         limit: Option<u64>,
     },
+    /// Lists the votes cast by VOTER across all proposals in
+    /// ascending order of proposal ID.
+    #[returns(crate::query::VoterVoteListResponse)]
+    ListVotesByVoter {
+        /// The address whose votes should be listed.
+        voter: String,
+        /// The proposal ID to start listing votes after.
+        start_after: Option<u64>,
+        /// The maximum number of votes to return in response to this
+        /// query. If no limit is specified a max of 30 are returned.
+        limit: Option<u64>,
+    },
+    /// Lists the vote commitments on a commit-reveal proposal which
+    /// have not yet been revealed.
+    #[returns(crate::query::CommitmentListResponse)]
+    ListCommitments {
+        /// The proposal to list the commitments of.
+        proposal_id: u64,
+        /// The voter to start listing commitments after. Ordering is
+        /// done alphabetically.
+        start_after: Option<String>,
+        /// The maximum number of commitments to return in response
+        /// to this query. If no limit is specified a max of 30 are
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists the proposals a proposal depends on, their current
+    /// statuses, and whether they have been executed.
+    #[returns(crate::query::DependenciesResponse)]
+    ProposalDependencies { proposal_id: u64 },
+    /// Lists the IDs of the proposals in this module which depend on
+    /// a proposal in ascending order.
+    #[returns(crate::query::DependentListResponse)]
+    ListDependents {
+        /// The proposal module the proposal was created in.
+        proposal_module: String,
+        /// The ID of the proposal to list the dependents of.
+        proposal_id: u64,
+        /// The proposal ID to start listing dependents after.
+        start_after: Option<u64>,
+        /// The maximum number of dependents to return. If no limit is
+        /// set a max of 30 are returned.
+        limit: Option<u64>,
+    },
+    /// Returns the lowest nonce that will be accepted for the next
+    /// ballot signed by ADDRESS.
+    #[returns(u64)]
+    BallotNonce { address: String },
     /// Gets the current proposal creation policy for this
     /// module.
     #[returns(cwd_voting::pre_propose::ProposalCreationPolicy)]
@@ -201,6 +458,11 @@ pub enum QueryMsg {
     /// module.
     #[returns(cwd_hooks::HooksResponse)]
     VoteHooks {},
+    /// Gets the progress of indexing proposals and ballots saved
+    /// by an earlier version of this module, or None if indexing is
+    /// complete.
+    #[returns(Option<crate::state::IndexProgress>)]
+    IndexProgress {},
 }
 
 #[cw_serde]
@@ -1,11 +1,18 @@
 use crate::query::ProposalResponse;
+use crate::rules::ThresholdRule;
 use crate::state::PROPOSAL_COUNT;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Decimal, Empty, StdResult, Storage, Uint128};
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
+use cwd_voting::anti_sniping::AntiSnipingState;
+use cwd_voting::dependency::ProposalDependency;
 use cwd_voting::status::Status;
 use cwd_voting::threshold::{PercentageThreshold, Threshold};
-use cwd_voting::voting::{does_vote_count_fail, does_vote_count_pass, Votes};
+use cwd_voting::veto::VetoConfig;
+use cwd_voting::voting::{
+    compare_vote_count, does_adaptive_vote_count_pass, does_vote_count_fail, does_vote_count_pass,
+    VoteCmp, Votes,
+};
 
 #[cw_serde]
 pub struct SingleChoiceProposal {
@@ -13,9 +20,10 @@ pub struct SingleChoiceProposal {
     pub description: String,
     /// The address that created this proposal.
     pub proposer: Addr,
-    /// The block height at which this proposal was created. Voting
-    /// power queries should query for voting power at this block
-    /// height.
+    /// The block height at which this proposal was created, or if it
+    /// has a voting delay, the block height at which its voting delay
+    /// ends. Voting power queries should query for voting power at
+    /// this block height.
     pub start_height: u64,
     /// The minimum amount of time this proposal must remain open for
     /// voting. The proposal may not pass unless this is expired or
@@ -34,6 +42,36 @@ pub struct SingleChoiceProposal {
     pub status: Status,
     pub votes: Votes,
     pub allow_revoting: bool,
+    /// The address that may veto this proposal once it has passed,
+    /// if any.
+    pub veto: Option<VetoConfig>,
+    /// The delegation registry used to count delegated voting power
+    /// on this proposal, if any.
+    pub delegation_registry: Option<Addr>,
+    /// If this proposal uses commit-reveal voting, the time at which
+    /// its commit phase ends. Votes are revealed between then and
+    /// `expiration`.
+    pub commit_expiration: Option<Expiration>,
+    /// The anti-sniping configuration of this proposal and the
+    /// number of times its voting period has been extended, if
+    /// any. Extensions move `expiration` later.
+    pub anti_sniping: Option<AntiSnipingState>,
+    /// The threshold rule which selected this proposal's threshold
+    /// and voting period, if any.
+    pub rule: Option<ThresholdRule>,
+    /// If this proposal has a voting delay, the time at which voting
+    /// opens. Its voting period begins then.
+    pub voting_start: Option<Expiration>,
+    /// If set, the amount of time this proposal may be executed for
+    /// once its voting period, and veto window if any, has ended.
+    pub execution_window: Option<Duration>,
+    /// Proposals which must be executed before this proposal may be
+    /// executed.
+    #[serde(default)]
+    pub dependencies: Vec<ProposalDependency>,
+    /// If set, this proposal is rejected if more than this portion of
+    /// the votes cast on it are NoWithVeto votes.
+    pub no_with_veto_threshold: Option<Decimal>,
 }
 
 pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
@@ -57,30 +95,176 @@ impl SingleChoiceProposal {
 
     /// Gets the current status of the proposal.
     pub fn current_status(&self, block: &BlockInfo) -> Status {
-        if self.status == Status::Open && self.is_passed(block) {
+        // Pending proposals open once their voting delay has elapsed.
+        let status = match self.status {
+            Status::Pending if self.is_pending(block) => return Status::Pending,
+            Status::Pending => Status::Open,
+            status => status,
+        };
+        let status = if status == Status::Open && self.is_passed(block) {
             Status::Passed
-        } else if self.status == Status::Open
+        } else if status == Status::Open
             && (self.expiration.is_expired(block) || self.is_rejected(block))
         {
             Status::Rejected
         } else {
-            self.status
+            status
+        };
+        // Passed proposals which were not executed during their
+        // execution window may no longer be executed.
+        if status == Status::Passed && self.is_execution_window_expired(block) {
+            Status::ExecutionExpired
+        } else {
+            status
         }
     }
 
+    /// Returns the time after which this proposal may no longer be
+    /// executed, if it has an execution window. The window begins
+    /// once the proposal's voting period, and veto window if any, has
+    /// ended.
+    pub fn execution_deadline(&self) -> StdResult<Option<Expiration>> {
+        self.execution_window
+            .map(|window| {
+                let start = match &self.veto {
+                    Some(veto) => veto.window_end(self.expiration)?,
+                    None => self.expiration,
+                };
+                start + window
+            })
+            .transpose()
+    }
+
+    /// Returns true if this proposal has an execution window which has
+    /// ended. This does not check the proposal's status.
+    fn is_execution_window_expired(&self, block: &BlockInfo) -> bool {
+        // Execution windows are validated to have the same units as
+        // the voting period so computing the deadline does not fail.
+        self.execution_deadline()
+            .ok()
+            .flatten()
+            .map_or(false, |deadline| deadline.is_expired(block))
+    }
+
+    /// Returns true if this proposal's voting delay has not yet
+    /// elapsed. This does not check the proposal's status.
+    pub fn is_pending(&self, block: &BlockInfo) -> bool {
+        self.voting_start
+            .map_or(false, |start| !start.is_expired(block))
+    }
+
     /// Sets a proposals status to its current status.
     pub fn update_status(&mut self, block: &BlockInfo) {
         let new_status = self.current_status(block);
         self.status = new_status
     }
 
+    /// Returns true if this proposal has a vetoer and its veto window
+    /// has not yet ended. This does not check the proposal's status.
+    pub fn is_veto_window_active(&self, block: &BlockInfo) -> StdResult<bool> {
+        match &self.veto {
+            Some(veto) => Ok(!veto.window_end(self.expiration)?.is_expired(block)),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns true if this proposal would pass were it to expire
+    /// with its current votes. This ignores the minimum voting period
+    /// and is used to determine if a vote changed the outcome of the
+    /// proposal.
+    pub fn is_passing(&self) -> bool {
+        if self.exceeds_no_with_veto_threshold(self.votes.total()) {
+            return false;
+        }
+        match self.threshold {
+            Threshold::AbsolutePercentage { percentage } => {
+                let options = self.total_power - self.votes.abstain;
+                does_vote_count_pass(self.votes.yes, options, percentage)
+            }
+            Threshold::ThresholdQuorum { threshold, quorum } => {
+                let options = self.votes.total() - self.votes.abstain;
+                does_vote_count_pass(self.votes.total(), self.total_power, quorum)
+                    && does_vote_count_pass(self.votes.yes, options, threshold)
+            }
+            Threshold::AbsoluteCount { threshold } => self.votes.yes >= threshold,
+            Threshold::AdaptiveQuorum { .. } => self.does_adaptive_threshold_pass(),
+        }
+    }
+
+    /// Returns true if this proposal's NoWithVeto votes are more than
+    /// its veto threshold's portion of POWER. Returns false if the
+    /// proposal has no veto threshold.
+    fn exceeds_no_with_veto_threshold(&self, power: Uint128) -> bool {
+        self.no_with_veto_threshold.map_or(false, |threshold| {
+            compare_vote_count(self.votes.no_with_veto, VoteCmp::Greater, power, threshold)
+        })
+    }
+
+    /// Returns true if this proposal is sure to be rejected by its
+    /// NoWithVeto votes. Once the proposal has expired these are
+    /// compared to the votes cast on it. Before then it is only
+    /// known once they exceed the veto threshold's portion of all
+    /// voting power.
+    pub fn is_vetoed_by_votes(&self, block: &BlockInfo) -> bool {
+        if self.expiration.is_expired(block) {
+            self.exceeds_no_with_veto_threshold(self.votes.total())
+        } else if self.allow_revoting || self.commit_expiration.is_some() {
+            false
+        } else {
+            self.exceeds_no_with_veto_threshold(self.total_power)
+        }
+    }
+
+    /// Returns true if this proposal has been, or may yet be,
+    /// rejected by NoWithVeto votes. Before the proposal expires this
+    /// is the case until the remaining voting power voting NoWithVeto
+    /// could not exceed the veto threshold.
+    fn may_be_vetoed_by_votes(&self, block: &BlockInfo) -> bool {
+        match self.no_with_veto_threshold {
+            None => false,
+            Some(_) if self.expiration.is_expired(block) => {
+                self.exceeds_no_with_veto_threshold(self.votes.total())
+            }
+            Some(threshold) => compare_vote_count(
+                self.votes.no_with_veto + (self.total_power - self.votes.total()),
+                VoteCmp::Greater,
+                self.total_power,
+                threshold,
+            ),
+        }
+    }
+
+    /// Returns true if this proposal's votes meet its adaptive quorum
+    /// threshold were it to expire with its current votes. Returns
+    /// false if the proposal does not use an adaptive quorum.
+    fn does_adaptive_threshold_pass(&self) -> bool {
+        match self.threshold {
+            Threshold::AdaptiveQuorum {
+                quorum,
+                threshold_at_quorum,
+                threshold_at_full_turnout,
+            } => does_adaptive_vote_count_pass(
+                self.votes.yes,
+                self.votes.total() - self.votes.abstain,
+                self.votes.total(),
+                self.total_power,
+                quorum,
+                threshold_at_quorum,
+                threshold_at_full_turnout,
+            ),
+            _ => false,
+        }
+    }
+
     /// Returns true iff this proposal is sure to pass (even before
     /// expiration if no future sequence of possible votes can cause
     /// it to fail).
     pub fn is_passed(&self, block: &BlockInfo) -> bool {
-        // If re-voting is allowed nothing is known until the proposal
-        // has expired.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+        // If re-voting is allowed, or votes are hidden until they are
+        // revealed, nothing is known until the proposal has expired.
+        if (self.allow_revoting || self.commit_expiration.is_some())
+            && !self.expiration.is_expired(block)
+        {
             return false;
         }
         // If the min voting period is set and not expired the
@@ -93,6 +277,11 @@ impl SingleChoiceProposal {
                 return false;
             }
         }
+        // Proposals may not pass while NoWithVeto votes could still
+        // reject them.
+        if self.may_be_vetoed_by_votes(block) {
+            return false;
+        }
 
         match self.threshold {
             Threshold::AbsolutePercentage { percentage } => {
@@ -117,18 +306,49 @@ impl SingleChoiceProposal {
                 }
             }
             Threshold::AbsoluteCount { threshold } => self.votes.yes >= threshold,
+            Threshold::AdaptiveQuorum {
+                quorum,
+                threshold_at_quorum,
+                ..
+            } => {
+                if self.expiration.is_expired(block) {
+                    self.does_adaptive_threshold_pass()
+                } else {
+                    // The required threshold is never higher than
+                    // `threshold_at_quorum`, so if quorum has been met
+                    // and the yes votes meet it with all outstanding
+                    // votes counted as no the proposal is sure to
+                    // pass.
+                    does_adaptive_vote_count_pass(
+                        self.votes.yes,
+                        self.total_power - self.votes.abstain,
+                        self.votes.total(),
+                        self.total_power,
+                        quorum,
+                        threshold_at_quorum,
+                        threshold_at_quorum,
+                    )
+                }
+            }
         }
     }
 
     /// As above for the passed check, used to check if a proposal is
     /// already rejected.
     pub fn is_rejected(&self, block: &BlockInfo) -> bool {
-        // If re-voting is allowed and the proposal is not expired no
-        // information is known.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+        // If re-voting is allowed, or votes are hidden until they are
+        // revealed, and the proposal is not expired no information is
+        // known.
+        if (self.allow_revoting || self.commit_expiration.is_some())
+            && !self.expiration.is_expired(block)
+        {
             return false;
         }
 
+        if self.is_vetoed_by_votes(block) {
+            return true;
+        }
+
         match self.threshold {
             Threshold::AbsolutePercentage {
                 percentage: percentage_needed,
@@ -230,6 +450,29 @@ impl SingleChoiceProposal {
                 let outstanding_votes = self.total_power - self.votes.total();
                 self.votes.yes + outstanding_votes < threshold
             }
+            Threshold::AdaptiveQuorum {
+                threshold_at_full_turnout,
+                ..
+            } => {
+                if self.expiration.is_expired(block) {
+                    !self.does_adaptive_threshold_pass()
+                } else {
+                    // The required threshold is never lower than
+                    // `threshold_at_full_turnout`, so if all the
+                    // outstanding votes voting yes would not meet it
+                    // the proposal is sure to be rejected.
+                    let options = self.total_power - self.votes.abstain;
+                    !does_adaptive_vote_count_pass(
+                        options - self.votes.no,
+                        options,
+                        self.total_power,
+                        self.total_power,
+                        Decimal::zero(),
+                        threshold_at_full_turnout,
+                        threshold_at_full_turnout,
+                    )
+                }
+            }
         }
     }
 }
@@ -270,6 +513,15 @@ mod test {
             threshold,
             total_power,
             votes,
+            veto: None,
+            delegation_registry: None,
+            commit_expiration: None,
+            anti_sniping: None,
+            rule: None,
+            voting_start: None,
+            execution_window: None,
+            dependencies: vec![],
+            no_with_veto_threshold: None,
         };
         (prop, block)
     }
@@ -321,6 +573,7 @@ mod test {
             yes: Uint128::new(7),
             no: Uint128::new(4),
             abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
         };
 
         // 15 total votes. 7 yes and 2 abstain. Majority threshold. This
@@ -363,6 +616,7 @@ mod test {
             yes: Uint128::new(7),
             no: Uint128::new(4),
             abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
         };
 
         // Does not pass if min voting period is not expired.
@@ -404,6 +658,7 @@ mod test {
             yes: Uint128::new(4),
             no: Uint128::new(7),
             abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
         };
 
         // Proposal has not passed.
@@ -438,6 +693,7 @@ mod test {
             yes: Uint128::new(7),
             no: Uint128::new(4),
             abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
         };
 
         // 15 total votes. 7 yes and 2 abstain. Majority threshold. This
@@ -473,6 +729,7 @@ mod test {
             yes: Uint128::new(4),
             no: Uint128::new(7),
             abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
         };
 
         // Not expired, revoting allowed => no rejection.
@@ -510,6 +767,7 @@ mod test {
                 yes: Uint128::new(10),
                 no: Uint128::zero(),
                 abstain: Uint128::zero(),
+                no_with_veto: Uint128::zero(),
             },
             Uint128::new(100),
             false,
@@ -523,6 +781,7 @@ mod test {
                 yes: Uint128::new(9),
                 no: Uint128::new(1),
                 abstain: Uint128::zero()
+                no_with_veto: Uint128::zero(),
             },
             Uint128::new(10),
             false,
@@ -536,6 +795,7 @@ mod test {
                 yes: Uint128::new(9),
                 no: Uint128::new(1),
                 abstain: Uint128::zero()
+                no_with_veto: Uint128::zero(),
             },
             Uint128::new(11),
             false,
@@ -549,6 +809,7 @@ mod test {
                 yes: Uint128::new(9),
                 no: Uint128::new(1),
                 abstain: Uint128::zero()
+                no_with_veto: Uint128::zero(),
             },
             Uint128::new(11),
             false,
@@ -571,6 +832,7 @@ mod test {
                 yes: Uint128::new(10),
                 no: Uint128::zero(),
                 abstain: Uint128::zero(),
+                no_with_veto: Uint128::zero(),
             },
             Uint128::new(100),
             false,
@@ -583,6 +845,7 @@ mod test {
                 yes: Uint128::new(10),
                 no: Uint128::zero(),
                 abstain: Uint128::zero(),
+                no_with_veto: Uint128::zero(),
             },
             Uint128::new(100),
             true,
@@ -596,6 +859,7 @@ mod test {
                 yes: Uint128::new(9),
                 no: Uint128::new(1),
                 abstain: Uint128::zero()
+                no_with_veto: Uint128::zero(),
             },
             Uint128::new(10),
             false,
@@ -608,6 +872,7 @@ mod test {
                 yes: Uint128::new(9),
                 no: Uint128::new(1),
                 abstain: Uint128::zero()
+                no_with_veto: Uint128::zero(),
             },
             Uint128::new(10),
             true,
@@ -625,6 +890,7 @@ mod test {
             yes: Uint128::new(7),
             no: Uint128::new(6),
             abstain: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
         };
         assert!(check_is_passed(
             threshold,
@@ -645,6 +911,7 @@ mod test {
             yes: Uint128::new(6),
             no: Uint128::new(7),
             abstain: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
         };
         assert!(check_is_passed(
             threshold.clone(),
@@ -673,6 +940,7 @@ mod test {
             yes: Uint128::new(7),
             no: Uint128::new(6),
             abstain: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
         };
         assert!(check_is_passed(
             threshold.clone(),
@@ -703,6 +971,7 @@ mod test {
             yes: Uint128::new(4),
             no: Uint128::new(7),
             abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
         };
 
         // 15 total voting power
@@ -774,18 +1043,21 @@ mod test {
             yes: Uint128::new(7),
             no: Uint128::new(3),
             abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
         };
         // abstain votes are not counted for threshold => yes / (yes + no + veto)
         let passes_ignoring_abstain = Votes {
             yes: Uint128::new(6),
             no: Uint128::new(6),
             abstain: Uint128::new(5),
+            no_with_veto: Uint128::zero(),
         };
         // fails any way you look at it
         let failing = Votes {
             yes: Uint128::new(6),
             no: Uint128::new(7),
             abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
         };
 
         // first, expired (voting period over)
@@ -886,18 +1158,21 @@ mod test {
             yes: Uint128::new(3),
             no: Uint128::new(8),
             abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
         };
         // abstain votes are not counted for threshold => yes / (yes + no)
         let rejected_ignoring_abstain = Votes {
             yes: Uint128::new(4),
             no: Uint128::new(8),
             abstain: Uint128::new(5),
+            no_with_veto: Uint128::zero(),
         };
         // fails any way you look at it
         let failing = Votes {
             yes: Uint128::new(5),
             no: Uint128::new(8),
             abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
         };
 
         // first, expired (voting period over)
@@ -1019,6 +1294,7 @@ mod test {
             yes: Uint128::new(9),
             no: Uint128::new(1),
             abstain: Uint128::new(0),
+            no_with_veto: Uint128::zero(),
         };
         assert!(!check_is_passed(
             quorum.clone(),
@@ -1042,6 +1318,7 @@ mod test {
             yes: Uint128::new(8),
             no: Uint128::new(4),
             abstain: Uint128::new(0),
+            no_with_veto: Uint128::zero(),
         };
         assert!(!check_is_passed(
             quorum.clone(),
@@ -1065,6 +1342,7 @@ mod test {
             yes: Uint128::new(9),
             no: Uint128::new(3),
             abstain: Uint128::new(0),
+            no_with_veto: Uint128::zero(),
         };
         assert!(check_is_passed(
             quorum.clone(),
@@ -1083,4 +1361,167 @@ mod test {
             false
         ));
     }
+
+    #[test]
+    fn test_adaptive_quorum_threshold() {
+        let threshold = Threshold::AdaptiveQuorum {
+            quorum: Decimal::percent(20),
+            threshold_at_quorum: Decimal::percent(67),
+            threshold_at_full_turnout: Decimal::percent(50),
+        };
+        let votes = |yes: u128, no: u128, abstain: u128| Votes {
+            yes: Uint128::new(yes),
+            no: Uint128::new(no),
+            abstain: Uint128::new(abstain),
+            no_with_veto: Uint128::zero(),
+        };
+        let total_power = Uint128::new(1000);
+
+        // At 60% turnout 58.5% of the yes and no votes must be yes.
+        assert!(check_is_passed(
+            threshold.clone(),
+            votes(351, 249, 0),
+            total_power,
+            true,
+            true,
+            false
+        ));
+        assert!(!check_is_rejected(
+            threshold.clone(),
+            votes(351, 249, 0),
+            total_power,
+            true,
+            true,
+            false
+        ));
+        assert!(!check_is_passed(
+            threshold.clone(),
+            votes(350, 250, 0),
+            total_power,
+            true,
+            true,
+            false
+        ));
+        assert!(check_is_rejected(
+            threshold.clone(),
+            votes(350, 250, 0),
+            total_power,
+            true,
+            true,
+            false
+        ));
+
+        // Below quorum proposals are rejected once expired.
+        assert!(check_is_rejected(
+            threshold.clone(),
+            votes(199, 0, 0),
+            total_power,
+            true,
+            true,
+            false
+        ));
+
+        // Before expiration proposals pass if the yes votes meet the
+        // threshold at quorum with all outstanding votes counted as
+        // no.
+        assert!(check_is_passed(
+            threshold.clone(),
+            votes(670, 0, 0),
+            total_power,
+            false,
+            true,
+            false
+        ));
+        assert!(!check_is_passed(
+            threshold.clone(),
+            votes(669, 0, 0),
+            total_power,
+            false,
+            true,
+            false
+        ));
+        assert!(!check_is_passed(
+            threshold.clone(),
+            votes(400, 0, 300),
+            total_power,
+            false,
+            true,
+            false
+        ));
+
+        // Before expiration proposals are rejected if all outstanding
+        // votes voting yes would not meet the threshold at full
+        // turnout.
+        assert!(check_is_rejected(
+            threshold.clone(),
+            votes(0, 501, 0),
+            total_power,
+            false,
+            true,
+            false
+        ));
+        assert!(!check_is_rejected(
+            threshold,
+            votes(0, 500, 0),
+            total_power,
+            false,
+            true,
+            false
+        ));
+    }
+
+    #[test]
+    fn test_no_with_veto_threshold() {
+        let threshold = Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        };
+        let check = |yes: u128, no: u128, no_with_veto: u128, is_expired: bool| {
+            let (mut prop, block) = setup_prop(
+                threshold.clone(),
+                Votes {
+                    yes: Uint128::new(yes),
+                    no: Uint128::new(no),
+                    abstain: Uint128::zero(),
+                    no_with_veto: Uint128::new(no_with_veto),
+                },
+                Uint128::new(100),
+                is_expired,
+                true,
+                false,
+            );
+            prop.no_with_veto_threshold = Some(Decimal::percent(33));
+            (prop.is_passed(&block), prop.is_rejected(&block))
+        };
+
+        // Proposals may not pass early while the outstanding votes
+        // could still veto them.
+        assert_eq!(check(60, 30, 30, false), (false, false));
+        assert_eq!(check(70, 30, 20, false), (true, false));
+
+        // Proposals are rejected early once vetoed by enough of all
+        // voting power.
+        assert_eq!(check(60, 34, 34, false), (false, true));
+
+        // Once expired veto votes are compared to the votes cast.
+        assert_eq!(check(60, 35, 32, true), (false, true));
+        assert_eq!(check(70, 30, 30, true), (true, false));
+
+        // Vetoed proposals are not passing.
+        let (mut prop, _) = setup_prop(
+            threshold,
+            Votes {
+                yes: Uint128::new(60),
+                no: Uint128::new(40),
+                abstain: Uint128::zero(),
+                no_with_veto: Uint128::new(40),
+            },
+            Uint128::new(100),
+            false,
+            true,
+            false,
+        );
+        assert!(prop.is_passing());
+        prop.no_with_veto_threshold = Some(Decimal::percent(33));
+        assert!(!prop.is_passing());
+    }
 }
@@ -1,7 +1,7 @@
 use crate::proposal::SingleChoiceProposal;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use cwd_voting::voting::Vote;
+use cosmwasm_std::{Addr, Binary, Uint128};
+use cwd_voting::{status::Status, voting::Vote};
 
 /// Information about a proposal returned by proposal queries.
 #[cw_serde]
@@ -20,6 +20,8 @@ pub struct VoteInfo {
     pub vote: Vote,
     /// The voting power behind the vote.
     pub power: Uint128,
+    /// Address-specified rationale for the vote.
+    pub rationale: Option<String>,
 }
 
 /// Information about a vote.
@@ -35,9 +37,72 @@ pub struct VoteListResponse {
     pub votes: Vec<VoteInfo>,
 }
 
-/// A list of proposals returned by `ListProposals` and
-/// `ReverseProposals`.
+/// A vote cast by a voter on a proposal.
+#[cw_serde]
+pub struct VoterVoteInfo {
+    /// The proposal that was voted on.
+    pub proposal_id: u64,
+    /// Position on the vote.
+    pub vote: Vote,
+    /// The voting power behind the vote.
+    pub power: Uint128,
+    /// Address-specified rationale for the vote.
+    pub rationale: Option<String>,
+}
+
+/// Information about the votes cast by a voter.
+#[cw_serde]
+pub struct VoterVoteListResponse {
+    pub votes: Vec<VoterVoteInfo>,
+}
+
+/// A vote commitment that has not yet been revealed.
+#[cw_serde]
+pub struct CommitmentInfo {
+    /// The address that committed to a vote.
+    pub voter: Addr,
+    /// The hash of the committed vote.
+    pub commitment: Binary,
+}
+
+/// Information about the unrevealed commitments on a proposal.
+#[cw_serde]
+pub struct CommitmentListResponse {
+    pub commitments: Vec<CommitmentInfo>,
+}
+
+/// A list of proposals returned by `ListProposals`,
+/// `ReverseProposals`, `ListProposalsByStatus`, and
+/// `ListProposalsByProposer`.
 #[cw_serde]
 pub struct ProposalListResponse {
     pub proposals: Vec<ProposalResponse>,
 }
+
+/// A proposal which must be executed before another proposal may be
+/// executed.
+#[cw_serde]
+pub struct DependencyInfo {
+    /// The proposal module the proposal was created in.
+    pub proposal_module: Addr,
+    pub proposal_id: u64,
+    /// The proposal's current status.
+    pub status: Status,
+    /// True if the proposal has been executed.
+    pub met: bool,
+}
+
+/// Information about the dependencies of a proposal.
+#[cw_serde]
+pub struct DependenciesResponse {
+    pub dependencies: Vec<DependencyInfo>,
+    /// True if every dependency has been executed and the proposal
+    /// may be executed once it has passed.
+    pub met: bool,
+}
+
+/// The IDs of the proposals which depend on a proposal.
+#[cw_serde]
+pub struct DependentListResponse {
+    pub dependents: Vec<u64>,
+}
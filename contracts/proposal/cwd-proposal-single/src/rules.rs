@@ -0,0 +1,360 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_binary, Addr, BankMsg, Coin, CosmosMsg, Deps, Empty, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use cw_denom::{CheckedDenom, UncheckedDenom};
+use cw_utils::Duration;
+use cwd_voting::{threshold::Threshold, voting::validate_voting_period};
+
+use crate::ContractError;
+
+/// A kind of message a proposal may execute.
+#[cw_serde]
+pub enum MessageKind {
+    /// A `BankMsg::Send` or `BankMsg::Burn`.
+    Bank,
+    /// A `WasmMsg::Execute`.
+    WasmExecute,
+    /// A `WasmMsg::Migrate`.
+    WasmMigrate,
+    /// A `WasmMsg::UpdateAdmin`.
+    WasmUpdateAdmin,
+    /// A `WasmMsg::ClearAdmin`.
+    WasmClearAdmin,
+}
+
+/// A condition on a proposal's messages which determines if a
+/// threshold rule applies to it.
+#[cw_serde]
+pub enum UncheckedRuleCondition {
+    /// Matches proposals with at least one message of `kind`. If
+    /// `target` is set the message must also be sent to that
+    /// contract, or for bank sends, that recipient.
+    Message {
+        kind: MessageKind,
+        target: Option<String>,
+    },
+    /// Matches proposals which in total send, burn, or attach as
+    /// funds more than `amount` of `denom`. For cw20 denoms the
+    /// `Transfer`, `Send`, `Burn`, and `IncreaseAllowance` messages
+    /// count towards the total.
+    Spend {
+        denom: UncheckedDenom,
+        amount: Uint128,
+    },
+}
+
+/// A threshold and voting period for proposals meeting a condition.
+#[cw_serde]
+pub struct UncheckedThresholdRule {
+    /// A name for the rule. Shown on proposals the rule applies to.
+    pub name: String,
+    /// The condition a proposal must meet for the rule to apply.
+    pub condition: UncheckedRuleCondition,
+    /// The threshold at which proposals the rule applies to pass.
+    pub threshold: Threshold,
+    /// The amount of time proposals the rule applies to may be
+    /// voted on before expiring. Must have the same units as the
+    /// module's `max_voting_period`.
+    pub max_voting_period: Duration,
+}
+
+/// Counterpart to `UncheckedRuleCondition` whose addresses and
+/// denoms have been validated.
+#[cw_serde]
+pub enum RuleCondition {
+    Message {
+        kind: MessageKind,
+        target: Option<Addr>,
+    },
+    Spend {
+        denom: CheckedDenom,
+        amount: Uint128,
+    },
+}
+
+/// Counterpart to `UncheckedThresholdRule` which has been
+/// validated. This type should always be built by calling
+/// `into_checked` on an `UncheckedThresholdRule`.
+#[cw_serde]
+pub struct ThresholdRule {
+    pub name: String,
+    pub condition: RuleCondition,
+    pub threshold: Threshold,
+    pub max_voting_period: Duration,
+}
+
+impl UncheckedThresholdRule {
+    /// Validates the rule for a module whose default voting periods
+    /// are MIN_VOTING_PERIOD and MAX_VOTING_PERIOD.
+    pub fn into_checked(
+        self,
+        deps: Deps,
+        min_voting_period: Option<Duration>,
+        max_voting_period: &Duration,
+    ) -> Result<ThresholdRule, ContractError> {
+        self.threshold.validate()?;
+        validate_voting_period(min_voting_period, self.max_voting_period)?;
+        match (self.max_voting_period, max_voting_period) {
+            (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_)) => {
+            }
+            _ => return Err(ContractError::RuleDurationUnitsConflict {}),
+        }
+
+        let condition = match self.condition {
+            UncheckedRuleCondition::Message { kind, target } => RuleCondition::Message {
+                kind,
+                target: target
+                    .map(|target| deps.api.addr_validate(&target))
+                    .transpose()?,
+            },
+            UncheckedRuleCondition::Spend { denom, amount } => RuleCondition::Spend {
+                denom: denom.into_checked(deps)?,
+                amount,
+            },
+        };
+
+        Ok(ThresholdRule {
+            name: self.name,
+            condition,
+            threshold: self.threshold,
+            max_voting_period: self.max_voting_period,
+        })
+    }
+}
+
+impl RuleCondition {
+    /// Returns true if a proposal executing MSGS meets this
+    /// condition.
+    pub fn matches(&self, msgs: &[CosmosMsg<Empty>]) -> StdResult<bool> {
+        match self {
+            RuleCondition::Message { kind, target } => Ok(msgs
+                .iter()
+                .any(|msg| message_matches(msg, kind, target.as_ref()))),
+            RuleCondition::Spend { denom, amount } => Ok(total_spend(msgs, denom)? > *amount),
+        }
+    }
+}
+
+/// Selects the rule that applies to a proposal executing MSGS. Rules
+/// are checked in order and the first whose condition is met is
+/// selected. Returns None if no rule applies, in which case the
+/// module's default threshold and voting period are used.
+pub fn select_rule<'a>(
+    rules: &'a [ThresholdRule],
+    msgs: &[CosmosMsg<Empty>],
+) -> StdResult<Option<&'a ThresholdRule>> {
+    for rule in rules {
+        if rule.condition.matches(msgs)? {
+            return Ok(Some(rule));
+        }
+    }
+    Ok(None)
+}
+
+fn message_matches(msg: &CosmosMsg<Empty>, kind: &MessageKind, target: Option<&Addr>) -> bool {
+    let (msg_kind, msg_target) = match msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, .. }) => {
+            (MessageKind::Bank, Some(to_address.as_str()))
+        }
+        CosmosMsg::Bank(BankMsg::Burn { .. }) => (MessageKind::Bank, None),
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            (MessageKind::WasmExecute, Some(contract_addr.as_str()))
+        }
+        CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. }) => {
+            (MessageKind::WasmMigrate, Some(contract_addr.as_str()))
+        }
+        CosmosMsg::Wasm(WasmMsg::UpdateAdmin { contract_addr, .. }) => {
+            (MessageKind::WasmUpdateAdmin, Some(contract_addr.as_str()))
+        }
+        CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+            (MessageKind::WasmClearAdmin, Some(contract_addr.as_str()))
+        }
+        _ => return false,
+    };
+    msg_kind == *kind
+        && match target {
+            Some(target) => msg_target == Some(target.as_str()),
+            None => true,
+        }
+}
+
+/// Sums the amount of DENOM sent, burned, or attached as funds by
+/// MSGS.
+fn total_spend(msgs: &[CosmosMsg<Empty>], denom: &CheckedDenom) -> StdResult<Uint128> {
+    let native_amount = |coins: &[Coin]| -> Uint128 {
+        match denom {
+            CheckedDenom::Native(denom) => coins
+                .iter()
+                .filter(|coin| &coin.denom == denom)
+                .map(|coin| coin.amount)
+                .sum(),
+            CheckedDenom::Cw20(_) => Uint128::zero(),
+        }
+    };
+
+    msgs.iter().try_fold(Uint128::zero(), |total, msg| {
+        let amount = match msg {
+            CosmosMsg::Bank(BankMsg::Send { amount, .. })
+            | CosmosMsg::Bank(BankMsg::Burn { amount }) => native_amount(amount),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => match denom {
+                CheckedDenom::Cw20(token) if token == contract_addr => {
+                    match from_binary::<Cw20ExecuteMsg>(msg) {
+                        Ok(Cw20ExecuteMsg::Transfer { amount, .. })
+                        | Ok(Cw20ExecuteMsg::Send { amount, .. })
+                        | Ok(Cw20ExecuteMsg::Burn { amount })
+                        | Ok(Cw20ExecuteMsg::IncreaseAllowance { amount, .. }) => amount,
+                        _ => Uint128::zero(),
+                    }
+                }
+                _ => native_amount(funds),
+            },
+            CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => native_amount(funds),
+            _ => Uint128::zero(),
+        };
+        total.checked_add(amount).map_err(StdError::overflow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coins, to_binary};
+
+    use super::*;
+
+    fn rule(name: &str, condition: RuleCondition) -> ThresholdRule {
+        ThresholdRule {
+            name: name.to_string(),
+            condition,
+            threshold: Threshold::AbsoluteCount {
+                threshold: Uint128::new(1),
+            },
+            max_voting_period: Duration::Height(10),
+        }
+    }
+
+    fn migrate(contract: &str) -> CosmosMsg {
+        WasmMsg::Migrate {
+            contract_addr: contract.to_string(),
+            new_code_id: 1,
+            msg: to_binary("").unwrap(),
+        }
+        .into()
+    }
+
+    fn send(amount: u128, denom: &str) -> CosmosMsg {
+        BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: coins(amount, denom),
+        }
+        .into()
+    }
+
+    fn cw20_transfer(token: &str, amount: u128) -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "recipient".to_string(),
+                amount: Uint128::new(amount),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_message_condition() {
+        let core_migrate = RuleCondition::Message {
+            kind: MessageKind::WasmMigrate,
+            target: Some(Addr::unchecked("core")),
+        };
+        assert!(core_migrate.matches(&[migrate("core")]).unwrap());
+        assert!(core_migrate
+            .matches(&[send(1, "ujuno"), migrate("core")])
+            .unwrap());
+        assert!(!core_migrate.matches(&[migrate("other")]).unwrap());
+        assert!(!core_migrate.matches(&[]).unwrap());
+
+        let any_migrate = RuleCondition::Message {
+            kind: MessageKind::WasmMigrate,
+            target: None,
+        };
+        assert!(any_migrate.matches(&[migrate("other")]).unwrap());
+        assert!(!any_migrate.matches(&[send(1, "ujuno")]).unwrap());
+
+        let bank = RuleCondition::Message {
+            kind: MessageKind::Bank,
+            target: Some(Addr::unchecked("recipient")),
+        };
+        assert!(bank.matches(&[send(1, "ujuno")]).unwrap());
+    }
+
+    #[test]
+    fn test_spend_condition() {
+        let native = RuleCondition::Spend {
+            denom: CheckedDenom::Native("ujuno".to_string()),
+            amount: Uint128::new(100),
+        };
+        assert!(!native.matches(&[send(100, "ujuno")]).unwrap());
+        assert!(native
+            .matches(&[send(60, "ujuno"), send(41, "ujuno")])
+            .unwrap());
+        assert!(!native.matches(&[send(101, "uatom")]).unwrap());
+
+        // Funds attached to wasm messages are spends.
+        let execute: CosmosMsg = WasmMsg::Execute {
+            contract_addr: "contract".to_string(),
+            msg: to_binary("").unwrap(),
+            funds: coins(101, "ujuno"),
+        }
+        .into();
+        assert!(native.matches(&[execute]).unwrap());
+
+        let cw20 = RuleCondition::Spend {
+            denom: CheckedDenom::Cw20(Addr::unchecked("token")),
+            amount: Uint128::new(100),
+        };
+        assert!(cw20
+            .matches(&[cw20_transfer("token", 50), cw20_transfer("token", 51)])
+            .unwrap());
+        assert!(!cw20.matches(&[cw20_transfer("other", 101)]).unwrap());
+        assert!(!cw20.matches(&[send(101, "ujuno")]).unwrap());
+    }
+
+    #[test]
+    fn test_select_rule() {
+        let rules = vec![
+            rule(
+                "upgrade",
+                RuleCondition::Message {
+                    kind: MessageKind::WasmMigrate,
+                    target: Some(Addr::unchecked("core")),
+                },
+            ),
+            rule(
+                "large spend",
+                RuleCondition::Spend {
+                    denom: CheckedDenom::Native("ujuno".to_string()),
+                    amount: Uint128::new(100),
+                },
+            ),
+        ];
+
+        // The first matching rule is selected.
+        assert_eq!(
+            select_rule(&rules, &[send(101, "ujuno"), migrate("core")]).unwrap(),
+            Some(&rules[0])
+        );
+        assert_eq!(
+            select_rule(&rules, &[send(101, "ujuno")]).unwrap(),
+            Some(&rules[1])
+        );
+        assert_eq!(select_rule(&rules, &[send(1, "ujuno")]).unwrap(), None);
+    }
+}
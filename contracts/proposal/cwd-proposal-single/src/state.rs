@@ -1,11 +1,14 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Binary, Decimal, Empty, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, KeyDeserialize, Map, MultiIndex};
 use cw_utils::Duration;
 use cwd_hooks::Hooks;
-use cwd_voting::{pre_propose::ProposalCreationPolicy, threshold::Threshold, voting::Vote};
+use cwd_voting::{
+    anti_sniping::AntiSnipingConfig, commit_reveal::CommitRevealConfig,
+    pre_propose::ProposalCreationPolicy, threshold::Threshold, veto::VetoConfig, voting::Vote,
+};
 
-use crate::proposal::SingleChoiceProposal;
+use crate::{proposal::SingleChoiceProposal, rules::ThresholdRule};
 
 /// A vote cast for a proposal.
 #[cw_serde]
@@ -14,6 +17,8 @@ pub struct Ballot {
     pub power: Uint128,
     /// The position.
     pub vote: Vote,
+    /// An optional rationale for why this vote was cast.
+    pub rationale: Option<String>,
 }
 /// The governance module's configuration.
 #[cw_serde]
@@ -49,6 +54,34 @@ pub struct Config {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// Optional configuration for an address that may veto passed
+    /// proposals before they are executed.
+    pub veto: Option<VetoConfig>,
+    /// An optional delegation registry used to count delegated
+    /// voting power.
+    pub delegation_registry: Option<Addr>,
+    /// If set, votes are committed as hashes during the voting
+    /// period and revealed during a reveal period which follows it.
+    pub commit_reveal: Option<CommitRevealConfig>,
+    /// If set, votes cast shortly before a proposal expires which
+    /// change its outcome extend its voting period.
+    pub anti_sniping: Option<AntiSnipingConfig>,
+    /// Rules which select the threshold and voting period of
+    /// proposals based on the messages they execute. Configs from
+    /// earlier versions of this module have no rules.
+    #[serde(default)]
+    pub threshold_rules: Vec<ThresholdRule>,
+    /// If set, proposals are pending for this amount of time after
+    /// they are created before they may be voted on. Voting power is
+    /// snapshotted when voting opens.
+    pub voting_delay: Option<Duration>,
+    /// If set, passed proposals which are not executed within this
+    /// amount of time after their voting period ends may no longer
+    /// be executed.
+    pub execution_window: Option<Duration>,
+    /// If set, proposals are rejected if more than this portion of
+    /// the votes cast on them are NoWithVeto votes.
+    pub no_with_veto_threshold: Option<Decimal>,
 }
 
 /// The current top level config for the module.  The "config" key was
@@ -56,8 +89,97 @@ pub struct Config {
 pub const CONFIG: Item<Config> = Item::new("config_v2");
 /// The number of proposals that have been created.
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
-pub const PROPOSALS: Map<u64, SingleChoiceProposal> = Map::new("proposals_v2");
-pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
+
+/// Secondary indexes over proposals.
+pub struct ProposalIndexes<'a> {
+    /// Proposals by their stored status. A proposal's stored status
+    /// is only updated when it is acted on, so an open proposal may
+    /// have since passed or been rejected. See
+    /// `Status::possible_stored_statuses`.
+    pub status: MultiIndex<'a, String, SingleChoiceProposal, u64>,
+    /// Proposals by their proposer.
+    pub proposer: MultiIndex<'a, Addr, SingleChoiceProposal, u64>,
+}
+
+impl<'a> IndexList<SingleChoiceProposal> for ProposalIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SingleChoiceProposal>> + '_> {
+        let v: Vec<&dyn Index<SingleChoiceProposal>> = vec![&self.status, &self.proposer];
+        Box::new(v.into_iter())
+    }
+}
+
+/// The module's proposals indexed by status and proposer.
+pub fn proposals<'a>() -> IndexedMap<'a, u64, SingleChoiceProposal, ProposalIndexes<'a>> {
+    let indexes = ProposalIndexes {
+        status: MultiIndex::new(
+            |_, proposal| proposal.status.to_string(),
+            "proposals_v2",
+            "proposals_v2__status",
+        ),
+        proposer: MultiIndex::new(
+            |_, proposal| proposal.proposer.clone(),
+            "proposals_v2",
+            "proposals_v2__proposer",
+        ),
+    };
+    IndexedMap::new("proposals_v2", indexes)
+}
+
+/// Progress re-saving proposals and ballots stored by earlier
+/// versions of this module so that they are present in their
+/// indexes.
+#[cw_serde]
+pub enum IndexProgress {
+    /// Proposals after this ID have not been indexed.
+    Proposals { start_after: Option<u64> },
+    /// Ballots after this key have not been indexed.
+    Ballots { start_after: Option<(u64, Addr)> },
+}
+
+/// Indexing progress after a migration. Removed once every proposal
+/// and ballot has been indexed.
+pub const INDEX_PROGRESS: Item<IndexProgress> = Item::new("index_progress");
+
+/// Secondary indexes over ballots.
+pub struct BallotIndexes<'a> {
+    /// Ballots by the address that cast them.
+    pub voter: MultiIndex<'a, Addr, Ballot, (u64, Addr)>,
+}
+
+impl<'a> IndexList<Ballot> for BallotIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Ballot>> + '_> {
+        let v: Vec<&dyn Index<Ballot>> = vec![&self.voter];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Ballots keyed by proposal ID and voter, indexed by voter.
+pub fn ballots<'a>() -> IndexedMap<'a, (u64, Addr), Ballot, BallotIndexes<'a>> {
+    let indexes = BallotIndexes {
+        voter: MultiIndex::new(
+            // Ballots do not store their voter so it is read from
+            // the ballot's (proposal_id, voter) key.
+            |pk, _| {
+                <(u64, Addr)>::from_slice(pk)
+                    .expect("ballot keys are (proposal_id, voter)")
+                    .1
+            },
+            "ballots",
+            "ballots__voter",
+        ),
+    };
+    IndexedMap::new("ballots", indexes)
+}
+/// Vote commitments on commit-reveal proposals which have not yet
+/// been revealed.
+pub const COMMITMENTS: Map<(u64, Addr), Binary> = Map::new("commitments");
+/// The lowest nonce that will be accepted for the next signed ballot
+/// from an address. Prevents relayed ballots from being replayed.
+pub const BALLOT_NONCES: Map<Addr, u64> = Map::new("ballot_nonces");
+/// The proposals in this module which depend on a proposal, keyed
+/// by the dependency's proposal module and ID and then the ID of the
+/// dependent proposal.
+pub const DEPENDENTS: Map<(Addr, u64, u64), Empty> = Map::new("dependents");
 /// Consumers of proposal state change hooks.
 pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
 /// Consumers of vote hooks.
@@ -117,3 +117,12 @@ pub(crate) fn cw4_voting_contract() -> Box<dyn Contract<Empty>> {
     .with_reply(cwd_voting_cw4::contract::reply);
     Box::new(contract)
 }
+
+pub(crate) fn delegation_registry_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cwd_delegation_registry::contract::execute,
+        cwd_delegation_registry::contract::instantiate,
+        cwd_delegation_registry::contract::query,
+    );
+    Box::new(contract)
+}
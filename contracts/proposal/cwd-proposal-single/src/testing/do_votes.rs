@@ -127,6 +127,14 @@ where
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        veto: None,
+        delegation_registry: None,
+        commit_reveal: None,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        voting_delay: None,
+        execution_window: None,
+        no_with_veto_threshold: None,
         pre_propose_info,
     };
 
@@ -193,6 +201,7 @@ where
                 title: "A simple text proposal".to_string(),
                 description: "This is a simple text proposal".to_string(),
                 msgs: vec![],
+                dependencies: vec![],
             },
         },
         &funds,
@@ -214,6 +223,7 @@ where
             &ExecuteMsg::Vote {
                 proposal_id: 1,
                 vote: position,
+                rationale: None,
             },
             &[],
         );
@@ -251,6 +261,7 @@ where
                             // expected voting power.
                             _ => weight,
                         },
+                        rationale: None,
                     }),
                 };
                 assert_eq!(vote, expected)
@@ -39,6 +39,10 @@ pub(crate) fn make_proposal(
                     denom,
                     amount,
                     refund_policy: _,
+                    withdrawal_refund_policy: _,
+                    veto_destination: _,
+                    funding_period: _,
+                    staking_contract: None,
                 }) => match denom {
                     CheckedDenom::Native(denom) => coins(amount.u128(), denom),
                     CheckedDenom::Cw20(addr) => {
@@ -73,6 +77,7 @@ pub(crate) fn make_proposal(
                     description: "description".to_string(),
                     msgs: msgs.clone(),
                     proposer: None,
+                    dependencies: vec![],
                 },
                 &[],
             )
@@ -86,6 +91,7 @@ pub(crate) fn make_proposal(
                         title: "title".to_string(),
                         description: "description".to_string(),
                         msgs: msgs.clone(),
+                        dependencies: vec![],
                     },
                 },
                 &funds,
@@ -130,7 +136,11 @@ pub(crate) fn vote_on_proposal(
     app.execute_contract(
         Addr::unchecked(sender),
         proposal_single.clone(),
-        &ExecuteMsg::Vote { proposal_id, vote },
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale: None,
+        },
         &[],
     )
     .unwrap();
@@ -146,7 +156,11 @@ pub(crate) fn vote_on_proposal_should_fail(
     app.execute_contract(
         Addr::unchecked(sender),
         proposal_single.clone(),
-        &ExecuteMsg::Vote { proposal_id, vote },
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale: None,
+        },
         &[],
     )
     .unwrap_err()
@@ -218,6 +232,33 @@ pub(crate) fn close_proposal(
     .unwrap();
 }
 
+pub(crate) fn veto_proposal_should_fail(
+    app: &mut App,
+    proposal_single: &Addr,
+    sender: &str,
+    proposal_id: u64,
+) -> ContractError {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_single.clone(),
+        &ExecuteMsg::Veto { proposal_id },
+        &[],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap()
+}
+
+pub(crate) fn veto_proposal(app: &mut App, proposal_single: &Addr, sender: &str, proposal_id: u64) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_single.clone(),
+        &ExecuteMsg::Veto { proposal_id },
+        &[],
+    )
+    .unwrap();
+}
+
 pub(crate) fn mint_natives(app: &mut App, receiver: &str, amount: Vec<Coin>) {
     app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
         to_address: receiver.to_string(),
@@ -37,6 +37,11 @@ pub(crate) fn get_pre_propose_info(
             msg: to_binary(&cppbps::InstantiateMsg {
                 deposit_info,
                 open_proposal_submission,
+                min_voting_power: None,
+                max_open_proposals: None,
+                submission_cooldown: None,
+                message_restrictions: None,
+                templates: vec![],
                 extension: Empty::default(),
             })
             .unwrap(),
@@ -62,10 +67,21 @@ pub(crate) fn get_default_token_dao_proposal_module_instantiate(app: &mut App) -
                 denom: cwd_voting::deposit::DepositToken::VotingModuleToken {},
                 amount: Uint128::new(10_000_000),
                 refund_policy: DepositRefundPolicy::OnlyPassed,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
             }),
             false,
         ),
         close_proposal_on_execution_failure: true,
+        veto: None,
+        delegation_registry: None,
+        commit_reveal: None,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        voting_delay: None,
+        execution_window: None,
+        no_with_veto_threshold: None,
     }
 }
 
@@ -84,6 +100,14 @@ pub(crate) fn get_default_non_token_dao_proposal_module_instantiate(
         allow_revoting: false,
         pre_propose_info: get_pre_propose_info(app, None, false),
         close_proposal_on_execution_failure: true,
+        veto: None,
+        delegation_registry: None,
+        commit_reveal: None,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        voting_delay: None,
+        execution_window: None,
+        no_with_veto_threshold: None,
     }
 }
 
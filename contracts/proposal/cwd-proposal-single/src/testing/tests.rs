@@ -6,23 +6,33 @@ use cosmwasm_std::{
 };
 use cw2::ContractVersion;
 use cw20::Cw20Coin;
-use cw_denom::CheckedDenom;
+use cw_denom::{CheckedDenom, UncheckedDenom};
 use cw_multi_test::{next_block, App, Executor};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
+use cwd_core::state::ProposalModule;
 use cwd_hooks::{HookError, HooksResponse};
 use cwd_interface::{voting::InfoResponse, Admin, ModuleInstantiateInfo};
-use cwd_testing::{ShouldExecute, TestSingleChoiceVote};
+use cwd_testing::{
+    signing::{signer_app, SignerApp, TestSigner},
+    ShouldExecute, TestSingleChoiceVote,
+};
 use cwd_voting::{
+    anti_sniping::{AntiSnipingConfig, AntiSnipingError},
+    commit_reveal::{commitment_hash, CommitRevealConfig, CommitRevealError},
+    dependency::{DependencyError, UncheckedProposalDependency},
     deposit::{CheckedDepositInfo, UncheckedDepositInfo},
+    error::VotingError,
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     proposal::MAX_PROPOSAL_SIZE,
     reply::{
         failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id,
         mask_proposal_hook_index, mask_vote_hook_index,
     },
+    signed_ballot::{SignedBallot, SignedBallotError},
     status::Status,
     threshold::{PercentageThreshold, Threshold},
-    voting::{Vote, Votes},
+    veto::{UncheckedVetoConfig, VetoConfig, VetoError},
+    voting::{SplitVote, Vote, Votes},
 };
 use cwd_voting_cw20_staked::msg::ActiveThreshold;
 
@@ -30,13 +40,18 @@ use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     proposal::SingleChoiceProposal,
-    query::{ProposalResponse, VoteInfo},
-    state::Config,
+    query::{
+        CommitmentListResponse, DependenciesResponse, DependencyInfo, DependentListResponse,
+        ProposalListResponse, ProposalResponse, VoteInfo, VoteResponse, VoterVoteInfo,
+        VoterVoteListResponse,
+    },
+    rules::{MessageKind, UncheckedRuleCondition, UncheckedThresholdRule},
+    state::{ballots, Ballot, Config, IndexProgress},
     testing::{
         contracts::{
             cw20_contract, cw20_stake_contract, cw20_staked_balances_voting_contract,
-            cw_core_contract, pre_propose_single_contract, proposal_single_contract,
-            v1_proposal_single_contract,
+            cw_core_contract, delegation_registry_contract, pre_propose_single_contract,
+            proposal_single_contract, v1_proposal_single_contract,
         },
         execute::{
             add_proposal_hook, add_proposal_hook_should_fail, add_vote_hook,
@@ -44,7 +59,8 @@ use crate::{
             execute_proposal, execute_proposal_should_fail, instantiate_cw20_base_default,
             make_proposal, mint_cw20s, mint_natives, remove_proposal_hook,
             remove_proposal_hook_should_fail, remove_vote_hook, remove_vote_hook_should_fail,
-            vote_on_proposal, vote_on_proposal_should_fail,
+            veto_proposal, veto_proposal_should_fail, vote_on_proposal,
+            vote_on_proposal_should_fail,
         },
         instantiate::{
             get_default_non_token_dao_proposal_module_instantiate,
@@ -124,6 +140,15 @@ fn test_simple_propose_staked_balances() {
         msgs: vec![],
         status: Status::Open,
         votes: Votes::zero(),
+        veto: None,
+        delegation_registry: None,
+        commit_expiration: None,
+        anti_sniping: None,
+        rule: None,
+        voting_start: None,
+        execution_window: None,
+        dependencies: vec![],
+        no_with_veto_threshold: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -139,7 +164,11 @@ fn test_simple_propose_staked_balances() {
         Some(CheckedDepositInfo {
             denom: cw_denom::CheckedDenom::Cw20(gov_token),
             amount: Uint128::new(10_000_000),
-            refund_policy: cwd_voting::deposit::DepositRefundPolicy::OnlyPassed
+            refund_policy: cwd_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         })
     );
 }
@@ -173,6 +202,15 @@ fn test_simple_proposal_cw4_voting() {
         msgs: vec![],
         status: Status::Open,
         votes: Votes::zero(),
+        veto: None,
+        delegation_registry: None,
+        commit_expiration: None,
+        anti_sniping: None,
+        rule: None,
+        voting_start: None,
+        execution_window: None,
+        dependencies: vec![],
+        no_with_veto_threshold: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -248,6 +286,9 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
             },
             amount: Uint128::new(10_000_000),
             refund_policy: cwd_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
         }),
         false,
     );
@@ -277,6 +318,15 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
         msgs: vec![],
         status: Status::Open,
         votes: Votes::zero(),
+        veto: None,
+        delegation_registry: None,
+        commit_expiration: None,
+        anti_sniping: None,
+        rule: None,
+        voting_start: None,
+        execution_window: None,
+        dependencies: vec![],
+        no_with_veto_threshold: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -292,7 +342,11 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
         Some(CheckedDepositInfo {
             denom: cw_denom::CheckedDenom::Cw20(alt_cw20),
             amount: Uint128::new(10_000_000),
-            refund_policy: cwd_voting::deposit::DepositRefundPolicy::OnlyPassed
+            refund_policy: cwd_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         })
     );
 }
@@ -527,6 +581,14 @@ fn test_update_config() {
                 allow_revoting: false,
                 dao: core_addr.to_string(),
                 close_proposal_on_execution_failure: false,
+                veto: None,
+                delegation_registry: None,
+                commit_reveal: None,
+                anti_sniping: None,
+                threshold_rules: vec![],
+                voting_delay: None,
+                execution_window: None,
+                no_with_veto_threshold: None,
             })
             .unwrap(),
             funds: vec![],
@@ -555,6 +617,14 @@ fn test_update_config() {
             allow_revoting: false,
             dao: core_addr.clone(),
             close_proposal_on_execution_failure: false,
+            veto: None,
+            delegation_registry: None,
+            commit_reveal: None,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            voting_delay: None,
+            execution_window: None,
+            no_with_veto_threshold: None,
         }
     );
 
@@ -573,6 +643,14 @@ fn test_update_config() {
                 allow_revoting: false,
                 dao: core_addr.to_string(),
                 close_proposal_on_execution_failure: false,
+                veto: None,
+                delegation_registry: None,
+                commit_reveal: None,
+                anti_sniping: None,
+                threshold_rules: vec![],
+                voting_delay: None,
+                execution_window: None,
+                no_with_veto_threshold: None,
             },
             &[],
         )
@@ -657,7 +735,17 @@ fn test_anyone_may_propose_and_proposal_listing() {
                     yes: Uint128::new(100_000_000),
                     no: Uint128::zero(),
                     abstain: Uint128::zero()
+                    no_with_veto: Uint128::zero(),
                 },
+                veto: None,
+                delegation_registry: None,
+                commit_expiration: None,
+                anti_sniping: None,
+                rule: None,
+                voting_start: None,
+                execution_window: None,
+                dependencies: vec![],
+                no_with_veto_threshold: None,
             }
         }
     )
@@ -818,6 +906,7 @@ fn test_active_threshold_absolute() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                dependencies: vec![],
             },
             &[],
         )
@@ -856,6 +945,7 @@ fn test_active_threshold_absolute() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                dependencies: vec![],
             },
             &[],
         )
@@ -899,6 +989,7 @@ fn test_active_threshold_percent() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                dependencies: vec![],
             },
             &[],
         )
@@ -938,6 +1029,7 @@ fn test_active_threshold_percent() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                dependencies: vec![],
             },
             &[],
         )
@@ -1135,6 +1227,14 @@ fn test_allow_revoting_config_changes() {
             allow_revoting: false,
             dao: core_addr.to_string(),
             close_proposal_on_execution_failure: false,
+            veto: None,
+            delegation_registry: None,
+            commit_reveal: None,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            voting_delay: None,
+            execution_window: None,
+            no_with_veto_threshold: None,
         },
         &[],
     )
@@ -1447,6 +1547,14 @@ fn test_proposal_count_initialized_to_zero() {
             allow_revoting: false,
             pre_propose_info,
             close_proposal_on_execution_failure: true,
+            veto: None,
+            delegation_registry: None,
+            commit_reveal: None,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            voting_delay: None,
+            execution_window: None,
+            no_with_veto_threshold: None,
         },
         Some(vec![
             Cw20Coin {
@@ -1513,6 +1621,83 @@ pub fn test_migrate_updates_version() {
     assert_eq!(version.contract, CONTRACT_NAME);
 }
 
+#[test]
+fn test_index_existing() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let info = cosmwasm_std::testing::mock_info("anyone", &[]);
+
+    // Ballots saved by a version of this module without indexes.
+    let unindexed: cw_storage_plus::Map<(u64, Addr), Ballot> = cw_storage_plus::Map::new("ballots");
+    for (proposal_id, voter) in [(1, "alice"), (1, "bob"), (2, "alice")] {
+        unindexed
+            .save(
+                &mut deps.storage,
+                (proposal_id, Addr::unchecked(voter)),
+                &Ballot {
+                    power: Uint128::new(1),
+                    vote: Vote::Yes,
+                    rationale: None,
+                },
+            )
+            .unwrap();
+    }
+    migrate(deps.as_mut(), env.clone(), MigrateMsg::FromCompatible {}).unwrap();
+
+    let progress = |deps: cosmwasm_std::Deps| -> Option<IndexProgress> {
+        cosmwasm_std::from_binary(
+            &crate::contract::query(deps, mock_env(), QueryMsg::IndexProgress {}).unwrap(),
+        )
+        .unwrap()
+    };
+    let votes_by = |deps: cosmwasm_std::Deps, voter: &str| {
+        ballots()
+            .idx
+            .voter
+            .prefix(Addr::unchecked(voter))
+            .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+            .count()
+    };
+    let index = |deps: cosmwasm_std::DepsMut| {
+        crate::contract::execute(
+            deps,
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::IndexExisting { limit: Some(2) },
+        )
+    };
+
+    assert_eq!(
+        progress(deps.as_ref()),
+        Some(IndexProgress::Proposals { start_after: None })
+    );
+    assert_eq!(votes_by(deps.as_ref(), "alice"), 0);
+
+    // There are no proposals so indexing moves on to ballots.
+    index(deps.as_mut()).unwrap();
+    assert_eq!(
+        progress(deps.as_ref()),
+        Some(IndexProgress::Ballots { start_after: None })
+    );
+
+    index(deps.as_mut()).unwrap();
+    assert_eq!(
+        progress(deps.as_ref()),
+        Some(IndexProgress::Ballots {
+            start_after: Some((1, Addr::unchecked("bob")))
+        })
+    );
+    assert_eq!(votes_by(deps.as_ref(), "alice"), 1);
+    assert_eq!(votes_by(deps.as_ref(), "bob"), 1);
+
+    index(deps.as_mut()).unwrap();
+    assert_eq!(progress(deps.as_ref()), None);
+    assert_eq!(votes_by(deps.as_ref(), "alice"), 2);
+
+    let err = index(deps.as_mut()).unwrap_err();
+    assert!(matches!(err, ContractError::IndexingComplete {}));
+}
+
 /// Instantiates a DAO with a v1 proposal module and then migrates it
 /// to v2.
 #[test]
@@ -1679,8 +1864,16 @@ fn test_migrate_from_v1() {
                         denom: cwd_voting::deposit::DepositToken::VotingModuleToken {},
                         amount: Uint128::new(1),
                         refund_policy: cwd_voting::deposit::DepositRefundPolicy::OnlyPassed,
+                        withdrawal_refund_policy: None,
+                        veto_destination: None,
+                        funding_period: None,
                     }),
                     open_proposal_submission: false,
+                    min_voting_power: None,
+                    max_open_proposals: None,
+                    submission_cooldown: None,
+                    message_restrictions: None,
+                    templates: vec![],
                     extension: Empty::default(),
                 })
                 .unwrap(),
@@ -1731,6 +1924,14 @@ fn test_migrate_from_v1() {
             allow_revoting: false,
             dao: core_addr.clone(),
             close_proposal_on_execution_failure: true,
+            veto: None,
+            delegation_registry: None,
+            commit_reveal: None,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            voting_delay: None,
+            execution_window: None,
+            no_with_veto_threshold: None,
         }
     );
 
@@ -1746,10 +1947,17 @@ fn test_migrate_from_v1() {
         pre_propose_config,
         cppbps::Config {
             open_proposal_submission: false,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
             deposit_info: Some(CheckedDepositInfo {
                 denom: CheckedDenom::Cw20(token_contract.clone()),
                 amount: Uint128::new(1),
                 refund_policy: cwd_voting::deposit::DepositRefundPolicy::OnlyPassed,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
+                staking_contract: None,
             })
         }
     );
@@ -1836,6 +2044,14 @@ fn test_execution_failed() {
             dao: config.dao.into_string(),
             // Disable.
             close_proposal_on_execution_failure: false,
+            veto: None,
+            delegation_registry: None,
+            commit_reveal: None,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            voting_delay: None,
+            execution_window: None,
+            no_with_veto_threshold: None,
         },
         &[],
     )
@@ -1875,13 +2091,13 @@ fn test_execution_failed() {
 #[test]
 fn test_reply_proposal_mock() {
     use crate::contract::reply;
-    use crate::state::PROPOSALS;
+    use crate::state::proposals;
 
     let mut deps = mock_dependencies();
     let env = mock_env();
 
     let m_proposal_id = mask_proposal_execution_proposal_id(1);
-    PROPOSALS
+    proposals()
         .save(
             deps.as_mut().storage,
             1,
@@ -1900,6 +2116,15 @@ fn test_reply_proposal_mock() {
                 msgs: vec![],
                 status: Status::Open,
                 votes: Votes::zero(),
+                veto: None,
+                delegation_registry: None,
+                commit_expiration: None,
+                anti_sniping: None,
+                rule: None,
+                voting_start: None,
+                execution_window: None,
+                dependencies: vec![],
+                no_with_veto_threshold: None,
             },
         )
         .unwrap();
@@ -1918,7 +2143,7 @@ fn test_reply_proposal_mock() {
         }
     );
 
-    let prop = PROPOSALS.load(deps.as_mut().storage, 1).unwrap();
+    let prop = proposals().load(deps.as_mut().storage, 1).unwrap();
     assert_eq!(prop.status, Status::ExecutionFailed);
 }
 
@@ -1939,6 +2164,7 @@ fn test_proposal_too_large() {
                 description: "a".repeat(MAX_PROPOSAL_SIZE as usize),
                 msgs: vec![],
                 proposer: None,
+                dependencies: vec![],
             },
             &[],
         )
@@ -1990,6 +2216,7 @@ fn test_proposal_creation_permissions() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                dependencies: vec![],
             },
             &[],
         )
@@ -2015,6 +2242,7 @@ fn test_proposal_creation_permissions() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                dependencies: vec![],
             },
             &[],
         )
@@ -2045,6 +2273,7 @@ fn test_proposal_creation_permissions() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: Some("ekez".to_string()),
+                dependencies: vec![],
             },
             &[],
         )
@@ -2229,26 +2458,31 @@ fn test_query_list_votes() {
                 voter: Addr::unchecked("five"),
                 vote: Vote::Yes,
                 power: Uint128::new(1)
+                rationale: None,
             },
             VoteInfo {
                 voter: Addr::unchecked("four"),
                 vote: Vote::Yes,
                 power: Uint128::new(1)
+                rationale: None,
             },
             VoteInfo {
                 voter: Addr::unchecked("one"),
                 vote: Vote::Yes,
                 power: Uint128::new(1)
+                rationale: None,
             },
             VoteInfo {
                 voter: Addr::unchecked("three"),
                 vote: Vote::No,
                 power: Uint128::new(1)
+                rationale: None,
             },
             VoteInfo {
                 voter: Addr::unchecked("two"),
                 vote: Vote::No,
                 power: Uint128::new(1)
+                rationale: None,
             }
         ]
     );
@@ -2267,11 +2501,13 @@ fn test_query_list_votes() {
                 voter: Addr::unchecked("one"),
                 vote: Vote::Yes,
                 power: Uint128::new(1)
+                rationale: None,
             },
             VoteInfo {
                 voter: Addr::unchecked("three"),
                 vote: Vote::No,
                 power: Uint128::new(1)
+                rationale: None,
             },
         ]
     );
@@ -2314,8 +2550,16 @@ fn test_update_pre_propose_module() {
                                 denom: cwd_voting::deposit::DepositToken::VotingModuleToken {},
                                 amount: Uint128::new(1),
                                 refund_policy: cwd_voting::deposit::DepositRefundPolicy::OnlyPassed,
+                                withdrawal_refund_policy: None,
+                                veto_destination: None,
+                                funding_period: None,
                             }),
                             open_proposal_submission: false,
+                            min_voting_power: None,
+                            max_open_proposals: None,
+                            submission_cooldown: None,
+                            message_restrictions: None,
+                            templates: vec![],
                             extension: Empty::default(),
                         })
                         .unwrap(),
@@ -2363,8 +2607,15 @@ fn test_update_pre_propose_module() {
                 denom: CheckedDenom::Cw20(gov_token.clone()),
                 amount: Uint128::new(1),
                 refund_policy: cwd_voting::deposit::DepositRefundPolicy::OnlyPassed,
+                withdrawal_refund_policy: None,
+                veto_destination: None,
+                funding_period: None,
+                staking_contract: None,
             }),
             open_proposal_submission: false,
+            min_voting_power: None,
+            max_open_proposals: None,
+            submission_cooldown: None,
         }
     );
 
@@ -2450,6 +2701,1989 @@ fn test_pre_propose_admin_is_dao() {
     assert_eq!(info.admin, Some(core_addr.into_string()));
 }
 
+fn setup_veto_test(early_execute: bool) -> CommonTest {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.veto = Some(UncheckedVetoConfig {
+        vetoer: "vetoer".to_string(),
+        veto_window: Duration::Time(86400), // One day.
+        early_execute,
+    });
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    CommonTest {
+        app,
+        core_addr,
+        proposal_module,
+        gov_token,
+        proposal_id,
+    }
+}
+
+#[test]
+fn test_veto() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        gov_token,
+        proposal_id,
+    } = setup_veto_test(false);
+
+    // Proposals that are not passed may not be vetoed.
+    let err = veto_proposal_should_fail(&mut app, &proposal_module, "vetoer", proposal_id);
+    assert!(matches!(err, ContractError::NotPassed {}));
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    // Passed proposals may not be executed during the veto window.
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert!(matches!(err, ContractError::VetoWindowActive {}));
+
+    // Only the vetoer may veto.
+    let err = veto_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Vetoing does not require early execution to be enabled.
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, "vetoer", proposal_id);
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    veto_proposal(&mut app, &proposal_module, "vetoer", proposal_id);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Vetoed);
+
+    // Vetoed proposals may not be executed, even after the veto
+    // window ends.
+    app.update_block(|b| b.time = b.time.plus_seconds(604800 + 86400));
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert!(matches!(err, ContractError::NotPassed {}));
+    let err = veto_proposal_should_fail(&mut app, &proposal_module, "vetoer", proposal_id);
+    assert!(matches!(err, ContractError::NotPassed {}));
+    let err = close_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+
+    // The deposit refund policy is `OnlyPassed` so the deposit goes
+    // to the DAO.
+    let balance = query_balance_cw20(&app, gov_token.as_str(), CREATOR_ADDR);
+    assert_eq!(balance, Uint128::zero());
+    let balance = query_balance_cw20(&app, gov_token.as_str(), core_addr.as_str());
+    assert_eq!(balance, Uint128::new(10_000_000));
+}
+
+#[test]
+fn test_veto_window_elapsed() {
+    let CommonTest {
+        mut app,
+        core_addr: _,
+        proposal_module,
+        gov_token,
+        proposal_id,
+    } = setup_veto_test(false);
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+
+    // The veto window runs until one day after the proposal's voting
+    // period ends.
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert!(matches!(err, ContractError::VetoWindowActive {}));
+
+    app.update_block(|b| b.time = b.time.plus_seconds(86400));
+    let err = veto_proposal_should_fail(&mut app, &proposal_module, "vetoer", proposal_id);
+    assert!(matches!(err, ContractError::VetoWindowElapsed {}));
+
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+
+    let balance = query_balance_cw20(&app, gov_token.as_str(), CREATOR_ADDR);
+    assert_eq!(balance, Uint128::new(10_000_000));
+}
+
+#[test]
+fn test_veto_early_execute() {
+    let CommonTest {
+        mut app,
+        core_addr: _,
+        proposal_module,
+        gov_token: _,
+        proposal_id,
+    } = setup_veto_test(true);
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+
+    // Only the vetoer may execute early.
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert!(matches!(err, ContractError::VetoWindowActive {}));
+
+    // The vetoer need not be a member to execute early.
+    execute_proposal(&mut app, &proposal_module, "vetoer", proposal_id);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+
+    let err = veto_proposal_should_fail(&mut app, &proposal_module, "vetoer", proposal_id);
+    assert!(matches!(err, ContractError::NotPassed {}));
+}
+
+#[test]
+fn test_veto_config_validation() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        gov_token: _,
+        proposal_id: _,
+    } = setup_test(vec![]);
+
+    let config = query_proposal_config(&app, &proposal_module);
+    assert_eq!(config.veto, None);
+
+    let update_config = |veto_window: Duration| ExecuteMsg::UpdateConfig {
+        threshold: config.threshold.clone(),
+        max_voting_period: config.max_voting_period,
+        min_voting_period: config.min_voting_period,
+        only_members_execute: config.only_members_execute,
+        allow_revoting: config.allow_revoting,
+        dao: config.dao.to_string(),
+        close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+        veto: Some(UncheckedVetoConfig {
+            vetoer: "vetoer".to_string(),
+            veto_window,
+            early_execute: false,
+        }),
+        delegation_registry: None,
+        commit_reveal: None,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        voting_delay: None,
+        execution_window: None,
+        no_with_veto_threshold: None,
+    };
+
+    // Veto window must be in the same units as the max voting
+    // period.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &update_config(Duration::Height(10)),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VetoError(VetoError::DurationUnitsConflict {})
+    ));
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &update_config(Duration::Time(0)),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VetoError(VetoError::ZeroVetoWindow {})
+    ));
+
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &update_config(Duration::Time(10)),
+        &[],
+    )
+    .unwrap();
+    let config = query_proposal_config(&app, &proposal_module);
+    assert_eq!(
+        config.veto,
+        Some(VetoConfig {
+            vetoer: Addr::unchecked("vetoer"),
+            veto_window: Duration::Time(10),
+            early_execute: false,
+        })
+    );
+}
+
+#[test]
+fn test_delegation() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    // Prevent proposals from completing early so that delegators
+    // have a chance to override their delegate.
+    instantiate.allow_revoting = true;
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "delegate".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(2),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(2),
+            },
+            Cw20Coin {
+                address: "carol".to_string(),
+                amount: Uint128::new(5),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let registry_id = app.store_code(delegation_registry_contract());
+    let registry = app
+        .instantiate_contract(
+            registry_id,
+            core_addr.clone(),
+            &cwd_delegation_registry::msg::InstantiateMsg { max_delegators: 10 },
+            &[],
+            "delegation registry",
+            None,
+        )
+        .unwrap();
+
+    let config = query_proposal_config(&app, &proposal_module);
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: config.threshold,
+            max_voting_period: config.max_voting_period,
+            min_voting_period: config.min_voting_period,
+            only_members_execute: config.only_members_execute,
+            allow_revoting: config.allow_revoting,
+            dao: config.dao.to_string(),
+            close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+            veto: config.veto,
+            delegation_registry: Some(registry.to_string()),
+            commit_reveal: None,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            voting_delay: None,
+            execution_window: None,
+            no_with_veto_threshold: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let delegate = |app: &mut App, delegator: &str| {
+        app.execute_contract(
+            Addr::unchecked(delegator),
+            registry.clone(),
+            &cwd_delegation_registry::msg::ExecuteMsg::Delegate {
+                delegate: "delegate".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    delegate(&mut app, "alice");
+    delegate(&mut app, "bob");
+    app.update_block(next_block);
+
+    let proposal_id = make_proposal(&mut app, &proposal_module, "delegate", vec![]);
+
+    // Delegations made after the proposal was created do not apply
+    // to it.
+    delegate(&mut app, "carol");
+    app.update_block(next_block);
+
+    // Bob votes before their delegate so their voting power is not
+    // counted towards the delegate's vote.
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        "bob",
+        proposal_id,
+        Vote::Abstain,
+    );
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        "delegate",
+        proposal_id,
+        Vote::Yes,
+    );
+
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(3),
+            no: Uint128::zero(),
+            abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
+        }
+    );
+
+    // Alice votes after their delegate, overriding the delegate's
+    // vote with their own.
+    vote_on_proposal(&mut app, &proposal_module, "alice", proposal_id, Vote::No);
+
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(1),
+            no: Uint128::new(2),
+            abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
+        }
+    );
+
+    let votes = query_list_votes(&app, &proposal_module, proposal_id, None, None);
+    assert_eq!(
+        votes.votes,
+        vec![
+            VoteInfo {
+                voter: Addr::unchecked("alice"),
+                vote: Vote::No,
+                power: Uint128::new(2),
+                rationale: None,
+            },
+            VoteInfo {
+                voter: Addr::unchecked("bob"),
+                vote: Vote::Abstain,
+                power: Uint128::new(2),
+                rationale: None,
+            },
+            VoteInfo {
+                voter: Addr::unchecked("delegate"),
+                vote: Vote::Yes,
+                power: Uint128::new(1),
+                rationale: None,
+            },
+        ]
+    );
+
+    // Revoting recomputes delegated power, still excluding delegators
+    // that have voted.
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        "delegate",
+        proposal_id,
+        Vote::No,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::zero(),
+            no: Uint128::new(3),
+            abstain: Uint128::new(2),
+            no_with_veto: Uint128::zero(),
+        }
+    );
+}
+
+#[test]
+fn test_split_vote() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.allow_revoting = true;
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let gov_token = query_dao_token(&app, &core_addr);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    // Portions must sum to one.
+    let err = vote_on_proposal_should_fail(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Split(SplitVote {
+            yes: Decimal::percent(60),
+            no: Decimal::zero(),
+            abstain: Decimal::percent(60),
+        }),
+    );
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::InvalidSplitVote {})
+    ));
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Split(SplitVote {
+            yes: Decimal::percent(60),
+            no: Decimal::zero(),
+            abstain: Decimal::percent(40),
+        }),
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(60_000_000),
+            no: Uint128::zero(),
+            abstain: Uint128::new(40_000_000),
+            no_with_veto: Uint128::zero(),
+        }
+    );
+
+    // Portions that don't divide voting power evenly lose no power
+    // to rounding.
+    let thirds = SplitVote {
+        yes: Decimal::from_ratio(1u128, 3u128),
+        no: Decimal::from_ratio(1u128, 3u128),
+        abstain: Decimal::one() - Decimal::from_ratio(2u128, 3u128),
+    };
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Split(thirds),
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(33_333_333),
+            no: Uint128::new(33_333_333),
+            abstain: Uint128::new(33_333_334),
+            no_with_veto: Uint128::zero(),
+        }
+    );
+
+    let err = vote_on_proposal_should_fail(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Split(thirds),
+    );
+    assert!(matches!(err, ContractError::AlreadyCast {}));
+
+    // Revoting removes the split vote exactly.
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(100_000_000),
+            no: Uint128::zero(),
+            abstain: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
+        }
+    );
+}
+
+#[test]
+fn test_vote_rationale() {
+    let CommonTest {
+        mut app,
+        core_addr: _,
+        proposal_module,
+        gov_token: _,
+        proposal_id,
+    } = setup_test(vec![]);
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote: Vote::Yes,
+            rationale: Some("to the moon".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let query_rationale = |app: &App| {
+        let vote: VoteResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::GetVote {
+                    proposal_id,
+                    voter: CREATOR_ADDR.to_string(),
+                },
+            )
+            .unwrap();
+        vote.vote.unwrap().rationale
+    };
+    assert_eq!(query_rationale(&app), Some("to the moon".to_string()));
+
+    // Rationales may be updated when revoting is disabled and after
+    // the proposal has passed.
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateRationale {
+            proposal_id,
+            rationale: Some("to mars".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_rationale(&app), Some("to mars".to_string()));
+
+    let votes = query_list_votes(&app, &proposal_module, proposal_id, None, None);
+    assert_eq!(votes.votes[0].rationale, Some("to mars".to_string()));
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateRationale {
+            proposal_id,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_rationale(&app), None);
+
+    // Addresses that have not voted have no rationale to update.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("rando"),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateRationale {
+                proposal_id,
+                rationale: Some("to the moon".to_string()),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoSuchVote { .. }));
+}
+
+#[test]
+fn test_commit_reveal() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+
+    // The reveal period must be measured in the same units as the
+    // voting period.
+    instantiate.commit_reveal = Some(CommitRevealConfig {
+        reveal_period: Duration::Height(10),
+    });
+    let proposal_id = app.store_code(proposal_single_contract());
+    let err: ContractError = app
+        .instantiate_contract(
+            proposal_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::CommitRevealError(CommitRevealError::DurationUnitsConflict {})
+    ));
+
+    instantiate.commit_reveal = Some(CommitRevealConfig {
+        reveal_period: Duration::Time(86400),
+    });
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(2),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(2),
+            },
+            Cw20Coin {
+                address: "carol".to_string(),
+                amount: Uint128::new(5),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let proposal_id = make_proposal(&mut app, &proposal_module, "carol", vec![]);
+
+    let commit = |app: &mut App, voter: &str, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::CommitVote {
+                proposal_id,
+                commitment: commitment_hash(&Addr::unchecked(voter), &vote, "salt").unwrap(),
+            },
+            &[],
+        )
+    };
+    let reveal = |app: &mut App, voter: &str, vote: Vote, salt: &str| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::RevealVote {
+                proposal_id,
+                vote,
+                salt: salt.to_string(),
+                rationale: None,
+            },
+            &[],
+        )
+    };
+    let list_commitments = |app: &App| -> Vec<Addr> {
+        let res: CommitmentListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::ListCommitments {
+                    proposal_id,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        res.commitments.into_iter().map(|c| c.voter).collect()
+    };
+
+    let err =
+        vote_on_proposal_should_fail(&mut app, &proposal_module, "carol", proposal_id, Vote::Yes);
+    assert!(matches!(err, ContractError::CommitRevealEnabled {}));
+
+    // Commitments may be replaced during the commit phase.
+    commit(&mut app, "carol", Vote::No).unwrap();
+    commit(&mut app, "carol", Vote::Yes).unwrap();
+    commit(&mut app, "alice", Vote::No).unwrap();
+    commit(&mut app, "bob", Vote::Abstain).unwrap();
+    let err: ContractError = commit(&mut app, "stranger", Vote::Yes)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    let err: ContractError = reveal(&mut app, "carol", Vote::Yes, "salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotRevealPhase { id } if id == proposal_id));
+
+    assert_eq!(
+        list_commitments(&app),
+        vec![
+            Addr::unchecked("alice"),
+            Addr::unchecked("bob"),
+            Addr::unchecked("carol")
+        ]
+    );
+
+    // Move to the reveal phase.
+    app.update_block(|block| block.time = block.time.plus_seconds(604800));
+
+    let err: ContractError = commit(&mut app, "carol", Vote::No)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotCommitPhase { id } if id == proposal_id));
+
+    let err: ContractError = reveal(&mut app, "carol", Vote::Yes, "pepper")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::CommitmentMismatch {}));
+    let err: ContractError = reveal(&mut app, "carol", Vote::No, "salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::CommitmentMismatch {}));
+
+    reveal(&mut app, "carol", Vote::Yes, "salt").unwrap();
+    reveal(&mut app, "alice", Vote::No, "salt").unwrap();
+
+    let err: ContractError = reveal(&mut app, "carol", Vote::Yes, "salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoCommitment {}));
+
+    // Carol's vote would pass the proposal but it may not pass
+    // until the reveal phase has ended.
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(5),
+            no: Uint128::new(2),
+            abstain: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
+        }
+    );
+    assert_eq!(list_commitments(&app), vec![Addr::unchecked("bob")]);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(86400));
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    let err: ContractError = reveal(&mut app, "bob", Vote::Abstain, "salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotRevealPhase { id } if id == proposal_id));
+}
+
+#[test]
+fn test_relay_votes() {
+    let mut app = signer_app();
+    let alice = TestSigner::secp256k1(1);
+    let bob = TestSigner::ed25519(2);
+    let carol = TestSigner::secp256k1(3);
+    let dave = TestSigner::secp256k1(4);
+
+    let code_id = app.store_code(proposal_single_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Time(604800),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        close_proposal_on_execution_failure: true,
+        veto: None,
+        delegation_registry: None,
+        commit_reveal: None,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        voting_delay: None,
+        execution_window: None,
+        no_with_veto_threshold: None,
+    };
+    // Carol is not a member and the relayer has no voting power.
+    let core_addr = cwd_testing::helpers::instantiate_with_cw4_groups_governance(
+        &mut app,
+        code_id,
+        to_binary(&instantiate).unwrap(),
+        Some(vec![
+            Cw20Coin {
+                address: alice.address(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: bob.address(),
+                amount: Uint128::new(2),
+            },
+            Cw20Coin {
+                address: dave.address(),
+                amount: Uint128::new(5),
+            },
+        ]),
+    );
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            &core_addr,
+            &cwd_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = modules.into_iter().next().unwrap().address;
+
+    app.execute_contract(
+        Addr::unchecked("proposer"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs: vec![],
+            proposer: None,
+            dependencies: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let chain_id = app.block_info().chain_id;
+    let relay = |app: &mut SignerApp, ballots: Vec<SignedBallot<Vote>>| {
+        app.execute_contract(
+            Addr::unchecked("relayer"),
+            proposal_module.clone(),
+            &ExecuteMsg::RelayVotes { ballots },
+            &[],
+        )
+        .map_err(|e| e.downcast::<ContractError>().unwrap())
+    };
+    let nonce = |app: &SignerApp, signer: &TestSigner| -> u64 {
+        app.wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::BallotNonce {
+                    address: signer.address(),
+                },
+            )
+            .unwrap()
+    };
+
+    let err = relay(&mut app, vec![]).unwrap_err();
+    assert!(matches!(err, ContractError::NoBallots {}));
+
+    let alice_yes = alice.sign(&chain_id, &proposal_module, 1, Vote::Yes, 0);
+    relay(
+        &mut app,
+        vec![
+            alice_yes.clone(),
+            bob.sign(&chain_id, &proposal_module, 1, Vote::No, 0),
+        ],
+    )
+    .unwrap();
+
+    let vote: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: alice.address(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vote.vote,
+        Some(VoteInfo {
+            voter: Addr::unchecked(alice.address()),
+            vote: Vote::Yes,
+            power: Uint128::new(3),
+            rationale: None,
+        })
+    );
+    assert_eq!(nonce(&app, &alice), 1);
+    assert_eq!(nonce(&app, &dave), 0);
+
+    // Ballots may not be replayed.
+    let err = relay(&mut app, vec![alice_yes]).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidNonce { next: 1 }));
+
+    // Ballots signed for another chain or which have been modified
+    // after signing are rejected.
+    let err = relay(
+        &mut app,
+        vec![dave.sign("juno-1", &proposal_module, 1, Vote::Yes, 0)],
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::SignedBallotError(SignedBallotError::InvalidSignature {})
+    ));
+    let mut tampered = dave.sign(&chain_id, &proposal_module, 1, Vote::Yes, 0);
+    tampered.vote = Vote::No;
+    let err = relay(&mut app, vec![tampered]).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::SignedBallotError(SignedBallotError::InvalidSignature {})
+    ));
+
+    // If any ballot fails, none are counted.
+    let err = relay(
+        &mut app,
+        vec![
+            dave.sign(&chain_id, &proposal_module, 1, Vote::Yes, 5),
+            carol.sign(&chain_id, &proposal_module, 1, Vote::Yes, 0),
+        ],
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+    assert_eq!(nonce(&app, &dave), 0);
+
+    // Nonces may be skipped.
+    relay(
+        &mut app,
+        vec![dave.sign(&chain_id, &proposal_module, 1, Vote::Yes, 5)],
+    )
+    .unwrap();
+    assert_eq!(nonce(&app, &dave), 6);
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(8),
+            no: Uint128::new(2),
+            abstain: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
+        }
+    );
+}
+
+#[test]
+fn test_indexed_queries() {
+    let mut app = App::default();
+    let instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(2),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(2),
+            },
+            Cw20Coin {
+                address: "carol".to_string(),
+                amount: Uint128::new(5),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let by_status = |app: &App, status: Status, start_after: Option<u64>, limit: Option<u64>| {
+        let res: ProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::ListProposalsByStatus {
+                    status,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+        res.proposals.into_iter().map(|p| p.id).collect::<Vec<_>>()
+    };
+    let by_proposer = |app: &App, proposer: &str, start_after: Option<u64>| {
+        let res: ProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::ListProposalsByProposer {
+                    proposer: proposer.to_string(),
+                    start_after,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        res.proposals.into_iter().map(|p| p.id).collect::<Vec<_>>()
+    };
+    let by_voter = |app: &App, voter: &str| {
+        let res: VoterVoteListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::ListVotesByVoter {
+                    voter: voter.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        res.votes
+    };
+
+    // Passes as soon as carol votes.
+    let passed = make_proposal(&mut app, &proposal_module, "alice", vec![]);
+    vote_on_proposal(&mut app, &proposal_module, "carol", passed, Vote::Yes);
+
+    // Will be rejected when they expire, but their stored status will
+    // remain open.
+    let unvoted = make_proposal(&mut app, &proposal_module, "bob", vec![]);
+    let no_quorum = make_proposal(&mut app, &proposal_module, "alice", vec![]);
+    vote_on_proposal(&mut app, &proposal_module, "alice", no_quorum, Vote::No);
+    vote_on_proposal(&mut app, &proposal_module, "bob", no_quorum, Vote::No);
+
+    assert_eq!(
+        by_status(&app, Status::Open, None, None),
+        vec![unvoted, no_quorum]
+    );
+    assert_eq!(by_status(&app, Status::Passed, None, None), vec![passed]);
+    assert!(by_status(&app, Status::Rejected, None, None).is_empty());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(604800));
+    let open = make_proposal(&mut app, &proposal_module, "carol", vec![]);
+
+    assert_eq!(by_status(&app, Status::Open, None, None), vec![open]);
+    assert_eq!(
+        by_status(&app, Status::Rejected, None, None),
+        vec![unvoted, no_quorum]
+    );
+    assert_eq!(
+        by_status(&app, Status::Rejected, Some(unvoted), None),
+        vec![no_quorum]
+    );
+    assert_eq!(
+        by_status(&app, Status::Rejected, None, Some(1)),
+        vec![unvoted]
+    );
+
+    execute_proposal(&mut app, &proposal_module, "carol", passed);
+    assert!(by_status(&app, Status::Passed, None, None).is_empty());
+    assert_eq!(by_status(&app, Status::Executed, None, None), vec![passed]);
+
+    assert_eq!(by_proposer(&app, "alice", None), vec![passed, no_quorum]);
+    assert_eq!(by_proposer(&app, "alice", Some(passed)), vec![no_quorum]);
+    assert_eq!(by_proposer(&app, "bob", None), vec![unvoted]);
+    assert_eq!(by_proposer(&app, "carol", None), vec![open]);
+
+    assert_eq!(
+        by_voter(&app, "alice"),
+        vec![VoterVoteInfo {
+            proposal_id: no_quorum,
+            vote: Vote::No,
+            power: Uint128::new(2),
+            rationale: None,
+        }]
+    );
+    assert_eq!(
+        by_voter(&app, "carol"),
+        vec![VoterVoteInfo {
+            proposal_id: passed,
+            vote: Vote::Yes,
+            power: Uint128::new(5),
+            rationale: None,
+        }]
+    );
+    assert!(by_voter(&app, "dave").is_empty());
+}
+
+#[test]
+fn test_anti_sniping() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.max_voting_period = Duration::Height(10);
+    instantiate.allow_revoting = true;
+    instantiate.threshold = Threshold::ThresholdQuorum {
+        threshold: PercentageThreshold::Majority {},
+        quorum: PercentageThreshold::Percent(Decimal::percent(20)),
+    };
+
+    // The window and extension must be measured in the same units as
+    // the voting period.
+    instantiate.anti_sniping = Some(AntiSnipingConfig {
+        window: Duration::Time(3),
+        extension: Duration::Height(3),
+        max_extensions: 2,
+    });
+    let proposal_id = app.store_code(proposal_single_contract());
+    let err: ContractError = app
+        .instantiate_contract(
+            proposal_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::AntiSnipingError(AntiSnipingError::DurationUnitsConflict {})
+    ));
+
+    instantiate.anti_sniping = Some(AntiSnipingConfig {
+        window: Duration::Height(3),
+        extension: Duration::Height(3),
+        max_extensions: 2,
+    });
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "carol".to_string(),
+                amount: Uint128::new(3),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let proposal_id = make_proposal(&mut app, &proposal_module, "carol", vec![]);
+    let start = app.block_info().height;
+    let advance_to = |app: &mut App, height: u64| app.update_block(|b| b.height = start + height);
+
+    // Votes before the window which change the outcome do not extend
+    // the voting period.
+    advance_to(&mut app, 1);
+    vote_on_proposal(&mut app, &proposal_module, "alice", proposal_id, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 10));
+
+    // A vote within the window which flips the outcome extends it.
+    advance_to(&mut app, 8);
+    vote_on_proposal(&mut app, &proposal_module, "bob", proposal_id, Vote::No);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 13));
+
+    // The proposal remains open after its original expiration.
+    advance_to(&mut app, 11);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Open);
+
+    // A vote moving the proposal past its threshold extends it again.
+    vote_on_proposal(&mut app, &proposal_module, "carol", proposal_id, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 16));
+    assert_eq!(proposal.anti_sniping.unwrap().extensions, 2);
+
+    // Once the maximum number of extensions have been made the
+    // voting period is not extended.
+    advance_to(&mut app, 14);
+    vote_on_proposal(&mut app, &proposal_module, "carol", proposal_id, Vote::No);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 16));
+    assert_eq!(proposal.status, Status::Open);
+
+    advance_to(&mut app, 16);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Rejected);
+}
+
+#[test]
+fn test_threshold_rules() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.max_voting_period = Duration::Height(10);
+    instantiate.threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let supermajority = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Percent(Decimal::percent(67)),
+    };
+
+    // Rule voting periods must be measured in the same units as the
+    // default voting period.
+    instantiate.threshold_rules = vec![UncheckedThresholdRule {
+        name: "upgrade".to_string(),
+        condition: UncheckedRuleCondition::Message {
+            kind: MessageKind::WasmMigrate,
+            target: None,
+        },
+        threshold: supermajority.clone(),
+        max_voting_period: Duration::Time(20),
+    }];
+    let proposal_id = app.store_code(proposal_single_contract());
+    let err: ContractError = app
+        .instantiate_contract(
+            proposal_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::RuleDurationUnitsConflict {}));
+
+    instantiate.threshold_rules = vec![
+        UncheckedThresholdRule {
+            name: "upgrade".to_string(),
+            condition: UncheckedRuleCondition::Message {
+                kind: MessageKind::WasmMigrate,
+                target: None,
+            },
+            threshold: supermajority.clone(),
+            max_voting_period: Duration::Height(20),
+        },
+        UncheckedThresholdRule {
+            name: "large spend".to_string(),
+            condition: UncheckedRuleCondition::Spend {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+                amount: Uint128::new(100_000),
+            },
+            threshold: supermajority.clone(),
+            max_voting_period: Duration::Height(20),
+        },
+    ];
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "carol".to_string(),
+                amount: Uint128::new(3),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let start = app.block_info().height;
+
+    let config = query_proposal_config(&app, &proposal_module);
+    assert_eq!(config.threshold_rules.len(), 2);
+
+    let spend = |amount: u128| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: coins(amount, "ujuno"),
+        }
+        .into()
+    };
+
+    // Proposals which meet no rule use the default threshold and
+    // voting period.
+    let small_spend = make_proposal(&mut app, &proposal_module, "alice", vec![spend(100_000)]);
+    let proposal = query_proposal(&app, &proposal_module, small_spend).proposal;
+    assert_eq!(proposal.rule, None);
+    assert_eq!(
+        proposal.threshold,
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        }
+    );
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 10));
+
+    let large_spend = make_proposal(
+        &mut app,
+        &proposal_module,
+        "alice",
+        vec![spend(60_000), spend(40_001)],
+    );
+    let proposal = query_proposal(&app, &proposal_module, large_spend).proposal;
+    assert_eq!(proposal.rule.unwrap().name, "large spend");
+    assert_eq!(proposal.threshold, supermajority);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 20));
+
+    // The first matching rule is selected.
+    let upgrade = make_proposal(
+        &mut app,
+        &proposal_module,
+        "alice",
+        vec![
+            spend(200_000),
+            WasmMsg::Migrate {
+                contract_addr: core_addr.to_string(),
+                new_code_id: 1,
+                msg: to_binary(&Empty {}).unwrap(),
+            }
+            .into(),
+        ],
+    );
+    let proposal = query_proposal(&app, &proposal_module, upgrade).proposal;
+    assert_eq!(proposal.rule.unwrap().name, "upgrade");
+
+    // Two of three members voting yes is a majority but not a
+    // supermajority.
+    for proposal_id in [small_spend, large_spend] {
+        vote_on_proposal(&mut app, &proposal_module, "alice", proposal_id, Vote::Yes);
+        vote_on_proposal(&mut app, &proposal_module, "bob", proposal_id, Vote::Yes);
+    }
+    let proposal = query_proposal(&app, &proposal_module, small_spend).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+    let proposal = query_proposal(&app, &proposal_module, large_spend).proposal;
+    assert_eq!(proposal.status, Status::Open);
+
+    vote_on_proposal(&mut app, &proposal_module, "carol", large_spend, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, large_spend).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_threshold_rules_optional() {
+    let mut app = App::default();
+    let instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    assert!(instantiate.threshold_rules.is_empty());
+
+    // Instantiate messages written before threshold rules were added
+    // still parse.
+    let encoded = String::from_utf8(cosmwasm_std::to_vec(&instantiate).unwrap())
+        .unwrap()
+        .replace(r#""threshold_rules":[],"#, "");
+    assert!(!encoded.contains("threshold_rules"));
+    let parsed: InstantiateMsg = cosmwasm_std::from_slice(encoded.as_bytes()).unwrap();
+    assert_eq!(parsed, instantiate);
+}
+
+#[test]
+fn test_voting_delay() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.max_voting_period = Duration::Height(10);
+    instantiate.threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+
+    // The voting delay must be measured in blocks so that the height
+    // voting power is snapshotted at is known when proposals are
+    // created.
+    instantiate.voting_delay = Some(Duration::Time(5));
+    let proposal_id = app.store_code(proposal_single_contract());
+    let err: ContractError = app
+        .instantiate_contract(
+            proposal_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::TimeVotingDelay {})
+    ));
+
+    // It must also be measured in the same units as the voting
+    // period.
+    let mut time_instantiate = instantiate.clone();
+    time_instantiate.max_voting_period = Duration::Time(10);
+    time_instantiate.voting_delay = Some(Duration::Height(5));
+    let err: ContractError = app
+        .instantiate_contract(
+            proposal_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &time_instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::VotingDelayUnitsConflict {})
+    ));
+
+    instantiate.voting_delay = Some(Duration::Height(5));
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(3),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let voting_module = query_voting_module(&app, &core_addr);
+    let group: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &cwd_voting_cw4::msg::QueryMsg::GroupContract {},
+        )
+        .unwrap();
+
+    let start = app.block_info().height;
+    let proposal_id = make_proposal(&mut app, &proposal_module, "alice", vec![]);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Pending);
+    assert_eq!(proposal.voting_start, Some(Expiration::AtHeight(start + 5)));
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 15));
+    assert_eq!(proposal.start_height, start + 5);
+
+    // Pending proposals may not be voted on.
+    let err =
+        vote_on_proposal_should_fail(&mut app, &proposal_module, "alice", proposal_id, Vote::Yes);
+    assert!(matches!(err, ContractError::NotOpen { .. }));
+
+    // Members who join before voting opens may vote.
+    app.execute_contract(
+        core_addr,
+        group,
+        &cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: vec![],
+            add: vec![cw4::Member {
+                addr: "carol".to_string(),
+                weight: 6,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|block| block.height = start + 7);
+    let open: ProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ListProposalsByStatus {
+                status: Status::Open,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(open.proposals.len(), 1);
+    assert_eq!(open.proposals[0].proposal.status, Status::Open);
+
+    // Voting power is snapshotted at the height voting opened.
+    vote_on_proposal(&mut app, &proposal_module, "carol", proposal_id, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.start_height, start + 5);
+    assert_eq!(proposal.total_power, Uint128::new(12));
+    assert_eq!(proposal.status, Status::Open);
+
+    vote_on_proposal(&mut app, &proposal_module, "bob", proposal_id, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_execution_window() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+
+    // The execution window must be measured in the same units as the
+    // voting period.
+    instantiate.execution_window = Some(Duration::Height(10));
+    let code_id = app.store_code(proposal_single_contract());
+    let err: ContractError = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::ExecutionWindowUnitsConflict {})
+    ));
+
+    instantiate.execution_window = Some(Duration::Time(86400)); // One day.
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+
+    // Passed proposals may be executed until one day after their
+    // voting period ends.
+    app.update_block(|b| b.time = b.time.plus_seconds(604800 + 86399));
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    app.update_block(|b| b.time = b.time.plus_seconds(1));
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::ExecutionExpired);
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert!(matches!(err, ContractError::ExecutionWindowExpired {}));
+
+    // Closing the proposal settles its deposit. The proposal passed so
+    // the deposit is returned.
+    close_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::ExecutionExpired);
+    let balance = query_balance_cw20(&app, gov_token.as_str(), CREATOR_ADDR);
+    assert_eq!(balance, Uint128::new(10_000_000));
+
+    let err = close_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+}
+
+#[test]
+fn test_proposal_dependencies() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let propose = |app: &mut App, proposal_module: &Addr, dependencies| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                dependencies,
+            },
+            &[],
+        )
+    };
+    let dependency = |proposal_module: &Addr, proposal_id| UncheckedProposalDependency {
+        proposal_module: proposal_module.to_string(),
+        proposal_id,
+    };
+
+    // Dependencies must be proposals in the DAO's proposal modules.
+    let err: ContractError = propose(
+        &mut app,
+        &proposal_module,
+        vec![dependency(&Addr::unchecked("notamodule"), 1)],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::DependencyError(DependencyError::NotProposalModule { .. })
+    ));
+    let err: ContractError = propose(
+        &mut app,
+        &proposal_module,
+        vec![dependency(&proposal_module, 1)],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::DependencyError(DependencyError::NoSuchDependency { .. })
+    ));
+
+    propose(&mut app, &proposal_module, vec![]).unwrap();
+    propose(
+        &mut app,
+        &proposal_module,
+        vec![dependency(&proposal_module, 1)],
+    )
+    .unwrap();
+
+    let dependents: DependentListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ListDependents {
+                proposal_module: proposal_module.to_string(),
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(dependents.dependents, vec![2]);
+
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 2, Vote::Yes);
+
+    let dependencies: DependenciesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ProposalDependencies { proposal_id: 2 },
+        )
+        .unwrap();
+    assert_eq!(
+        dependencies,
+        DependenciesResponse {
+            dependencies: vec![DependencyInfo {
+                proposal_module: proposal_module.clone(),
+                proposal_id: 1,
+                status: Status::Passed,
+                met: false,
+            }],
+            met: false,
+        }
+    );
+
+    // The proposal may not be executed until its dependency has been.
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, 2);
+    assert!(matches!(
+        err,
+        ContractError::DependencyNotExecuted { proposal_id: 1, .. }
+    ));
+
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1);
+    let dependencies: DependenciesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ProposalDependencies { proposal_id: 2 },
+        )
+        .unwrap();
+    assert!(dependencies.met);
+    assert_eq!(dependencies.dependencies[0].status, Status::Executed);
+
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 2);
+    let proposal = query_proposal(&app, &proposal_module, 2);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
+
+#[test]
+fn test_update_and_withdraw_proposal() {
+    use cwd_pre_propose_single as cppbps;
+
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = get_pre_propose_info(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: cwd_voting::deposit::DepositToken::VotingModuleToken {},
+            amount: Uint128::new(10_000_000),
+            refund_policy: cwd_voting::deposit::DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: Some(cwd_voting::deposit::DepositRefundPolicy::Always),
+            veto_destination: None,
+            funding_period: None,
+        }),
+        false,
+    );
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(40_000_000),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(60_000_000),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+    let pre_propose = match query_creation_policy(&app, &proposal_module) {
+        ProposalCreationPolicy::Anyone {} => panic!("expected a pre-propose module"),
+        ProposalCreationPolicy::Module { addr } => addr,
+    };
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    let update = |title: &str, dependencies| cppbps::ExecuteMsg::UpdateProposal {
+        proposal_id,
+        msg: cppbps::ProposeMessage::Propose {
+            title: title.to_string(),
+            description: "updated".to_string(),
+            msgs: vec![BankMsg::Send {
+                to_address: "ekez".to_string(),
+                amount: coins(10, "ujuno"),
+            }
+            .into()],
+            dependencies,
+        },
+    };
+
+    // Only the proposer may update the proposal.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &update("title", vec![]),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // With a pre-propose module attached, updates must be routed
+    // through it.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateProposal {
+                proposal_id,
+                title: "title".to_string(),
+                description: "updated".to_string(),
+                msgs: vec![],
+                proposer: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidProposer {}));
+
+    // Dependencies may not be updated.
+    let err: cwd_pre_propose_base::error::PreProposeError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            pre_propose.clone(),
+            &update(
+                "title",
+                vec![UncheckedProposalDependency {
+                    proposal_module: proposal_module.to_string(),
+                    proposal_id,
+                }],
+            ),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        cwd_pre_propose_base::error::PreProposeError::InvalidUpdate {}
+    );
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        pre_propose.clone(),
+        &update("updated title", vec![]),
+        &[],
+    )
+    .unwrap();
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.title, "updated title");
+    assert_eq!(proposal.proposal.description, "updated");
+    assert_eq!(proposal.proposal.msgs.len(), 1);
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    // Withdrawing the proposal refunds its deposit under its
+    // withdrawal refund policy.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        pre_propose.clone(),
+        &cppbps::ExecuteMsg::WithdrawProposal { proposal_id },
+        &[],
+    )
+    .unwrap();
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Withdrawn);
+    let balance = query_balance_cw20(&app, &gov_token, CREATOR_ADDR);
+    assert_eq!(balance, Uint128::new(10_000_000));
+
+    // Withdrawn proposals may not be withdrawn again, voted on, or
+    // closed.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            pre_propose.clone(),
+            &cppbps::ExecuteMsg::WithdrawProposal { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotOpen { .. }));
+    let err = vote_on_proposal_should_fail(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    assert!(matches!(err, ContractError::NotOpen { .. }));
+    let err = close_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+
+    // Proposals may not be updated or withdrawn once they have been
+    // voted on. The vote leaves this proposal open.
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::No,
+    );
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            pre_propose.clone(),
+            &cppbps::ExecuteMsg::WithdrawProposal { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::VotingStarted { .. }));
+}
+
+#[test]
+fn test_no_with_veto() {
+    // NoWithVeto votes may only be cast on proposals with a veto
+    // threshold.
+    let mut app = App::default();
+    let instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    let err = vote_on_proposal_should_fail(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::NoWithVeto,
+    );
+    assert!(matches!(err, ContractError::NoWithVetoDisabled {}));
+
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+
+    // The veto threshold must be greater than zero.
+    instantiate.no_with_veto_threshold = Some(Decimal::zero());
+    let code_id = app.store_code(proposal_single_contract());
+    let err: ContractError = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate,
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::InvalidNoWithVetoThreshold {})
+    ));
+
+    instantiate.no_with_veto_threshold = Some(Decimal::percent(33));
+    instantiate.pre_propose_info = get_pre_propose_info(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: cwd_voting::deposit::DepositToken::VotingModuleToken {},
+            amount: Uint128::new(10_000_000),
+            refund_policy: cwd_voting::deposit::DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: Some(cwd_voting::deposit::VetoDepositDestination::Burn {}),
+            funding_period: None,
+        }),
+        false,
+    );
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(40_000_000),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(60_000_000),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+    let pre_propose = match query_creation_policy(&app, &proposal_module) {
+        ProposalCreationPolicy::Anyone {} => panic!("expected a pre-propose module"),
+        ProposalCreationPolicy::Module { addr } => addr,
+    };
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    // NoWithVeto votes count as no votes. Once they exceed the veto
+    // threshold's portion of all voting power the proposal is
+    // rejected.
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        "ekez",
+        proposal_id,
+        Vote::NoWithVeto,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+    assert_eq!(proposal.proposal.votes.no, Uint128::new(60_000_000));
+    assert_eq!(
+        proposal.proposal.votes.no_with_veto,
+        Uint128::new(60_000_000)
+    );
+
+    // Closing the proposal sends its deposit to the veto destination
+    // even though the refund policy is `Always`.
+    let dao_balance = query_balance_cw20(&app, &gov_token, &core_addr);
+    close_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::ClosedWithVeto);
+
+    let balance = query_balance_cw20(&app, &gov_token, CREATOR_ADDR);
+    assert_eq!(balance, Uint128::zero());
+    let balance = query_balance_cw20(&app, &gov_token, &pre_propose);
+    assert_eq!(balance, Uint128::zero());
+    let balance = query_balance_cw20(&app, &gov_token, &core_addr);
+    assert_eq!(balance, dao_balance);
+}
+
 // TODO: test pre-propose module that fails on new proposal hook (ugh).
 
 // - What happens if you have proposals that can not be executed but
@@ -1,6 +1,7 @@
 //! Helper methods for migrating from v1 to v2 state. These will need
 //! to be updated when we bump our CosmWasm version for v2.
 
+use cosmwasm_std::Uint128;
 use cw_utils::{Duration, Expiration};
 use cwd_voting::{
     status::Status,
@@ -48,6 +49,7 @@ pub fn v1_votes_to_v2(v1: voting_v1::Votes) -> Votes {
         yes: v1.yes,
         no: v1.no,
         abstain: v1.abstain,
+        no_with_veto: Uint128::zero(),
     }
 }
 
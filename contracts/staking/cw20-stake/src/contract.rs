@@ -4,19 +4,20 @@ use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
     from_binary, from_slice, to_binary, to_vec, Addr, Binary, Deps, DepsMut, Empty, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128,
 };
 
 use cw20::Cw20ReceiveMsg;
 
 use crate::hooks::{stake_hook_msgs, unstake_hook_msgs};
 use crate::msg::{
-    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg,
-    ReceiveMsg, StakedBalanceAtHeightResponse, StakedValueResponse, StakerBalanceResponse,
-    TotalStakedAtHeightResponse, TotalValueResponse,
+    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListLockersResponse, ListStakersResponse,
+    LockedBalanceResponse, MigrateMsg, QueryMsg, ReceiveMsg, StakedBalanceAtHeightResponse,
+    StakedValueResponse, StakerBalanceResponse, TotalStakedAtHeightResponse, TotalValueResponse,
 };
 use crate::state::{
-    Config, BALANCE, CLAIMS, CONFIG, HOOKS, MAX_CLAIMS, STAKED_BALANCES, STAKED_TOTAL,
+    Config, BALANCE, CLAIMS, CONFIG, HOOKS, LOCKED, LOCKERS, MAX_CLAIMS, STAKED_BALANCES,
+    STAKED_TOTAL,
 };
 use crate::ContractError;
 use cw2::set_contract_version;
@@ -54,6 +55,36 @@ fn validate_duration(duration: Option<Duration>) -> Result<(), ContractError> {
     Ok(())
 }
 
+/// Gets the total amount of ADDRESS's staked balance which is locked.
+fn locked_balance(storage: &dyn Storage, address: &Addr) -> StdResult<Uint128> {
+    LOCKED
+        .prefix(address)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, amount)| amount))
+        .sum()
+}
+
+/// Removes AMOUNT from the staked balance LOCKER has locked for
+/// ADDRESS.
+fn release_lock(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    locker: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let remaining = LOCKED
+        .may_load(storage, (address, locker))?
+        .unwrap_or_default()
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InvalidUnlockAmount {})?;
+    if remaining.is_zero() {
+        LOCKED.remove(storage, (address, locker));
+    } else {
+        LOCKED.save(storage, (address, locker), &remaining)?;
+    }
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -110,6 +141,19 @@ pub fn execute(
         } => execute_update_config(info, deps, owner, manager, duration),
         ExecuteMsg::AddHook { addr } => execute_add_hook(deps, env, info, addr),
         ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, env, info, addr),
+        ExecuteMsg::AddLocker { addr } => execute_add_locker(deps, info, addr),
+        ExecuteMsg::RemoveLocker { addr } => execute_remove_locker(deps, info, addr),
+        ExecuteMsg::LockStake { address, amount } => {
+            execute_lock_stake(deps, info, address, amount)
+        }
+        ExecuteMsg::UnlockStake { address, amount } => {
+            execute_unlock_stake(deps, info, address, amount)
+        }
+        ExecuteMsg::SlashStake {
+            address,
+            amount,
+            recipient,
+        } => execute_slash_stake(deps, env, info, address, amount, recipient),
     }
 }
 
@@ -238,12 +282,16 @@ pub fn execute_unstake(
         .map_err(StdError::overflow)?
         .checked_div(staked_total)
         .map_err(StdError::divide_by_zero)?;
-    STAKED_BALANCES.update(
+    let staked = STAKED_BALANCES.update(
         deps.storage,
         &info.sender,
         env.block.height,
         |bal| -> StdResult<Uint128> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
     )?;
+    let locked = locked_balance(deps.storage, &info.sender)?;
+    if staked < locked {
+        return Err(ContractError::StakeLocked { locked });
+    }
     STAKED_TOTAL.update(
         deps.storage,
         env.block.height,
@@ -375,6 +423,148 @@ pub fn execute_remove_hook(
         .add_attribute("hook", addr))
 }
 
+pub fn execute_add_locker(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.owner != Some(info.sender.clone()) && config.manager != Some(info.sender) {
+        return Err(ContractError::Unauthorized {});
+    };
+    LOCKERS.save(deps.storage, &addr, &Empty::default())?;
+    Ok(Response::new()
+        .add_attribute("action", "add_locker")
+        .add_attribute("locker", addr))
+}
+
+pub fn execute_remove_locker(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.owner != Some(info.sender.clone()) && config.manager != Some(info.sender) {
+        return Err(ContractError::Unauthorized {});
+    };
+    LOCKERS.remove(deps.storage, &addr);
+    Ok(Response::new()
+        .add_attribute("action", "remove_locker")
+        .add_attribute("locker", addr))
+}
+
+pub fn execute_lock_stake(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if !LOCKERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let address = deps.api.addr_validate(&address)?;
+
+    let staked = STAKED_BALANCES
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    let unlocked = staked.saturating_sub(locked_balance(deps.storage, &address)?);
+    if amount > unlocked {
+        return Err(ContractError::InsufficientUnlockedStake { unlocked });
+    }
+    LOCKED.update(
+        deps.storage,
+        (&address, &info.sender),
+        |locked| -> StdResult<Uint128> { Ok(locked.unwrap_or_default().checked_add(amount)?) },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "lock_stake")
+        .add_attribute("locker", info.sender)
+        .add_attribute("address", address)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_unlock_stake(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // Lockers which have been removed may still release what they
+    // locked, so this is not restricted to registered lockers.
+    let address = deps.api.addr_validate(&address)?;
+    release_lock(deps.storage, &address, &info.sender, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unlock_stake")
+        .add_attribute("locker", info.sender)
+        .add_attribute("address", address)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_slash_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    amount: Uint128,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    release_lock(deps.storage, &address, &info.sender, amount)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let balance = BALANCE.load(deps.storage)?;
+    let staked_total = STAKED_TOTAL.load(deps.storage)?;
+    let amount_to_send = amount
+        .checked_mul(balance)
+        .map_err(StdError::overflow)?
+        .checked_div(staked_total)
+        .map_err(StdError::divide_by_zero)?;
+    STAKED_BALANCES.update(
+        deps.storage,
+        &address,
+        env.block.height,
+        |bal| -> StdResult<Uint128> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+    STAKED_TOTAL.update(
+        deps.storage,
+        env.block.height,
+        |total| -> StdResult<Uint128> {
+            // Initialized during instantiate - OK to unwrap.
+            Ok(total.unwrap().checked_sub(amount)?)
+        },
+    )?;
+    BALANCE.save(
+        deps.storage,
+        &balance
+            .checked_sub(amount_to_send)
+            .map_err(StdError::overflow)?,
+    )?;
+    let hook_msgs = unstake_hook_msgs(deps.storage, address.clone(), amount)?;
+
+    let cw_send_msg = cw20::Cw20ExecuteMsg::Transfer {
+        recipient: recipient.to_string(),
+        amount: amount_to_send,
+    };
+    let wasm_msg = cosmwasm_std::WasmMsg::Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_binary(&cw_send_msg)?,
+        funds: vec![],
+    };
+    Ok(Response::new()
+        .add_message(wasm_msg)
+        .add_submessages(hook_msgs)
+        .add_attribute("action", "slash_stake")
+        .add_attribute("locker", info.sender)
+        .add_attribute("address", address)
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -392,6 +582,8 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListStakers { start_after, limit } => {
             query_list_stakers(deps, start_after, limit)
         }
+        QueryMsg::ListLockers {} => to_binary(&query_list_lockers(deps)?),
+        QueryMsg::LockedBalance { address } => to_binary(&query_locked_balance(deps, address)?),
     }
 }
 
@@ -494,6 +686,21 @@ pub fn query_list_stakers(
     to_binary(&ListStakersResponse { stakers })
 }
 
+pub fn query_list_lockers(deps: Deps) -> StdResult<ListLockersResponse> {
+    let lockers = LOCKERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|locker| locker.map(Addr::into_string))
+        .collect::<StdResult<_>>()?;
+    Ok(ListLockersResponse { lockers })
+}
+
+pub fn query_locked_balance(deps: Deps, address: String) -> StdResult<LockedBalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(LockedBalanceResponse {
+        balance: locked_balance(deps.storage, &address)?,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     // Set contract to version to latest
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, StdError};
+use cosmwasm_std::{Addr, StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -23,4 +23,10 @@ pub enum ContractError {
     OnlyOwnerCanChangeOwner {},
     #[error("Invalid unstaking duration, unstaking duration cannot be 0")]
     InvalidUnstakingDuration {},
+    #[error("Can not unstake locked tokens. {locked} tokens are locked")]
+    StakeLocked { locked: Uint128 },
+    #[error("Not enough unlocked stake. Only {unlocked} tokens are unlocked")]
+    InsufficientUnlockedStake { unlocked: Uint128 },
+    #[error("Can only unlock or slash less than or equal to the amount you have locked")]
+    InvalidUnlockAmount {},
 }
@@ -1,8 +1,8 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Empty, Uint128};
 use cw_controllers::Claims;
 use cw_controllers::Hooks;
-use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
 
 #[cw_serde]
@@ -38,3 +38,11 @@ pub const BALANCE: Item<Uint128> = Item::new("balance");
 
 // Hooks to contracts that will receive staking and unstaking messages
 pub const HOOKS: Hooks = Hooks::new("hooks");
+
+// Contracts which may lock staked balances, generally pre-propose
+// modules which take locked stake as proposal deposits.
+pub const LOCKERS: Map<&Addr, Empty> = Map::new("lockers");
+
+// The amount of each address's staked balance locked by each locker,
+// keyed by (staker, locker).
+pub const LOCKED: Map<(&Addr, &Addr), Uint128> = Map::new("locked");
@@ -2,9 +2,9 @@ use std::borrow::BorrowMut;
 
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
 use crate::msg::{
-    ExecuteMsg, ListStakersResponse, MigrateMsg, QueryMsg, ReceiveMsg,
-    StakedBalanceAtHeightResponse, StakedValueResponse, StakerBalanceResponse,
-    TotalStakedAtHeightResponse, TotalValueResponse,
+    ExecuteMsg, ListLockersResponse, ListStakersResponse, LockedBalanceResponse, MigrateMsg,
+    QueryMsg, ReceiveMsg, StakedBalanceAtHeightResponse, StakedValueResponse,
+    StakerBalanceResponse, TotalStakedAtHeightResponse, TotalValueResponse,
 };
 use crate::state::{Config, MAX_CLAIMS};
 use crate::ContractError;
@@ -1203,6 +1203,161 @@ fn test_query_list_stakers() {
     assert_eq!(stakers, test_res)
 }
 
+#[test]
+fn test_lock_stake() {
+    let mut app = mock_app();
+    let initial_balances = vec![Cw20Coin {
+        address: ADDR1.to_string(),
+        amount: Uint128::new(200),
+    }];
+    let (staking_addr, cw20_addr) =
+        setup_test_case(&mut app, initial_balances, Some(Duration::Height(10)));
+    let locker = Addr::unchecked("locker");
+
+    let info = mock_info(ADDR1, &[]);
+    stake_tokens(&mut app, &staking_addr, &cw20_addr, info, Uint128::new(100)).unwrap();
+    // Double the value of staked balances.
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        cw20_addr.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: staking_addr.to_string(),
+            amount: Uint128::new(100),
+            msg: to_binary(&ReceiveMsg::Fund {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let lock = |app: &mut App, amount: u128| {
+        app.execute_contract(
+            locker.clone(),
+            staking_addr.clone(),
+            &ExecuteMsg::LockStake {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )
+    };
+
+    // Only registered lockers may lock stake.
+    let err: ContractError = lock(&mut app, 60).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            staking_addr.clone(),
+            &ExecuteMsg::AddLocker {
+                addr: locker.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(
+        Addr::unchecked("manager"),
+        staking_addr.clone(),
+        &ExecuteMsg::AddLocker {
+            addr: locker.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let lockers: ListLockersResponse = app
+        .wrap()
+        .query_wasm_smart(staking_addr.clone(), &QueryMsg::ListLockers {})
+        .unwrap();
+    assert_eq!(lockers.lockers, vec![locker.to_string()]);
+
+    let err: ContractError = lock(&mut app, 101).unwrap_err().downcast().unwrap();
+    assert_eq!(
+        err,
+        ContractError::InsufficientUnlockedStake {
+            unlocked: Uint128::new(100)
+        }
+    );
+    lock(&mut app, 60).unwrap();
+    let locked: LockedBalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            staking_addr.clone(),
+            &QueryMsg::LockedBalance {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(locked.balance, Uint128::new(60));
+
+    // Locked stake can't be unstaked, but the rest can.
+    let info = mock_info(ADDR1, &[]);
+    let err: ContractError =
+        unstake_tokens(&mut app, &staking_addr, info.clone(), Uint128::new(41))
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+    assert_eq!(
+        err,
+        ContractError::StakeLocked {
+            locked: Uint128::new(60)
+        }
+    );
+    unstake_tokens(&mut app, &staking_addr, info.clone(), Uint128::new(40)).unwrap();
+
+    // Slashing sends the value of the slashed stake to the recipient
+    // without waiting for the unstaking duration.
+    app.execute_contract(
+        locker.clone(),
+        staking_addr.clone(),
+        &ExecuteMsg::SlashStake {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(40),
+            recipient: "dao".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, "dao"), Uint128::new(80));
+    app.update_block(next_block);
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR1),
+        Uint128::new(20)
+    );
+    assert_eq!(query_total_staked(&app, &staking_addr), Uint128::new(20));
+    assert_eq!(query_total_value(&app, &staking_addr), Uint128::new(40));
+
+    // Lockers may only release what they locked.
+    let err: ContractError = app
+        .execute_contract(
+            locker.clone(),
+            staking_addr.clone(),
+            &ExecuteMsg::UnlockStake {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(21),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidUnlockAmount {});
+    app.execute_contract(
+        locker,
+        staking_addr.clone(),
+        &ExecuteMsg::UnlockStake {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(20),
+        },
+        &[],
+    )
+    .unwrap();
+    unstake_tokens(&mut app, &staking_addr, info, Uint128::new(20)).unwrap();
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -1,8 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, to_binary, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
-    StdResult, Uint128,
+    coins, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Response, StdResult, Storage, Uint128,
 };
 use cw2::set_contract_version;
 use cw_controllers::ClaimsResponse;
@@ -12,9 +12,12 @@ use cwd_interface::Admin;
 
 use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg, StakerBalanceResponse,
+    ExecuteMsg, InstantiateMsg, ListLockersResponse, ListStakersResponse, LockedBalanceResponse,
+    MigrateMsg, QueryMsg, StakerBalanceResponse,
+};
+use crate::state::{
+    Config, CLAIMS, CONFIG, DAO, LOCKED, LOCKERS, MAX_CLAIMS, STAKED_BALANCES, STAKED_TOTAL,
 };
-use crate::state::{Config, CLAIMS, CONFIG, DAO, MAX_CLAIMS, STAKED_BALANCES, STAKED_TOTAL};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-voting-native-staked";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -37,6 +40,35 @@ fn validate_duration(duration: Option<Duration>) -> Result<(), ContractError> {
     Ok(())
 }
 
+/// Gets the total amount of ADDRESS's stake which is locked.
+fn locked_balance(storage: &dyn Storage, address: &Addr) -> StdResult<Uint128> {
+    LOCKED
+        .prefix(address)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, amount)| amount))
+        .sum()
+}
+
+/// Removes AMOUNT from the stake LOCKER has locked for ADDRESS.
+fn release_lock(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    locker: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let remaining = LOCKED
+        .may_load(storage, (address, locker))?
+        .unwrap_or_default()
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InvalidUnlockAmount {})?;
+    if remaining.is_zero() {
+        LOCKED.remove(storage, (address, locker));
+    } else {
+        LOCKED.save(storage, (address, locker), &remaining)?;
+    }
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -105,6 +137,19 @@ pub fn execute(
             duration,
         } => execute_update_config(deps, info, owner, manager, duration),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::AddLocker { addr } => execute_add_locker(deps, info, addr),
+        ExecuteMsg::RemoveLocker { addr } => execute_remove_locker(deps, info, addr),
+        ExecuteMsg::LockStake { address, amount } => {
+            execute_lock_stake(deps, info, address, amount)
+        }
+        ExecuteMsg::UnlockStake { address, amount } => {
+            execute_unlock_stake(deps, info, address, amount)
+        }
+        ExecuteMsg::SlashStake {
+            address,
+            amount,
+            recipient,
+        } => execute_slash_stake(deps, env, info, address, amount, recipient),
     }
 }
 
@@ -142,7 +187,7 @@ pub fn execute_unstake(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
-    STAKED_BALANCES.update(
+    let balance = STAKED_BALANCES.update(
         deps.storage,
         &info.sender,
         env.block.height,
@@ -153,6 +198,10 @@ pub fn execute_unstake(
                 .map_err(|_e| ContractError::InvalidUnstakeAmount {})
         },
     )?;
+    let locked = locked_balance(deps.storage, &info.sender)?;
+    if balance < locked {
+        return Err(ContractError::StakeLocked { locked });
+    }
     STAKED_TOTAL.update(
         deps.storage,
         env.block.height,
@@ -270,6 +319,127 @@ pub fn execute_claim(
         .add_attribute("amount", release))
 }
 
+pub fn execute_add_locker(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if Some(info.sender.clone()) != config.owner && Some(info.sender) != config.manager {
+        return Err(ContractError::Unauthorized {});
+    }
+    let addr = deps.api.addr_validate(&addr)?;
+    LOCKERS.save(deps.storage, &addr, &Empty::default())?;
+    Ok(Response::new()
+        .add_attribute("action", "add_locker")
+        .add_attribute("locker", addr))
+}
+
+pub fn execute_remove_locker(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if Some(info.sender.clone()) != config.owner && Some(info.sender) != config.manager {
+        return Err(ContractError::Unauthorized {});
+    }
+    let addr = deps.api.addr_validate(&addr)?;
+    LOCKERS.remove(deps.storage, &addr);
+    Ok(Response::new()
+        .add_attribute("action", "remove_locker")
+        .add_attribute("locker", addr))
+}
+
+pub fn execute_lock_stake(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if !LOCKERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let address = deps.api.addr_validate(&address)?;
+
+    let staked = STAKED_BALANCES
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    let unlocked = staked.saturating_sub(locked_balance(deps.storage, &address)?);
+    if amount > unlocked {
+        return Err(ContractError::InsufficientUnlockedStake { unlocked });
+    }
+    LOCKED.update(
+        deps.storage,
+        (&address, &info.sender),
+        |locked| -> StdResult<Uint128> { Ok(locked.unwrap_or_default().checked_add(amount)?) },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "lock_stake")
+        .add_attribute("locker", info.sender)
+        .add_attribute("address", address)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_unlock_stake(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // Lockers which have been removed may still release what they
+    // locked, so this is not restricted to registered lockers.
+    let address = deps.api.addr_validate(&address)?;
+    release_lock(deps.storage, &address, &info.sender, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unlock_stake")
+        .add_attribute("locker", info.sender)
+        .add_attribute("address", address)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_slash_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    amount: Uint128,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    release_lock(deps.storage, &address, &info.sender, amount)?;
+
+    // Locked stake is always staked, so these can not underflow.
+    STAKED_BALANCES.update(
+        deps.storage,
+        &address,
+        env.block.height,
+        |balance| -> StdResult<Uint128> { Ok(balance.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+    STAKED_TOTAL.update(
+        deps.storage,
+        env.block.height,
+        |total| -> StdResult<Uint128> { Ok(total.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: coins(amount.u128(), config.denom),
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "slash_stake")
+        .add_attribute("locker", info.sender)
+        .add_attribute("address", address)
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -286,6 +456,8 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListStakers { start_after, limit } => {
             query_list_stakers(deps, start_after, limit)
         }
+        QueryMsg::ListLockers {} => to_binary(&query_list_lockers(deps)?),
+        QueryMsg::LockedBalance { address } => to_binary(&query_locked_balance(deps, address)?),
     }
 }
 
@@ -357,6 +529,21 @@ pub fn query_list_stakers(
     to_binary(&ListStakersResponse { stakers })
 }
 
+pub fn query_list_lockers(deps: Deps) -> StdResult<ListLockersResponse> {
+    let lockers = LOCKERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|locker| locker.map(Addr::into_string))
+        .collect::<StdResult<_>>()?;
+    Ok(ListLockersResponse { lockers })
+}
+
+pub fn query_locked_balance(deps: Deps, address: String) -> StdResult<LockedBalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(LockedBalanceResponse {
+        balance: locked_balance(deps.storage, &address)?,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     // Set contract to version to latest
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use cw_utils::PaymentError;
 use thiserror::Error;
 
@@ -27,4 +27,13 @@ pub enum ContractError {
 
     #[error("Can only unstake less than or equal to the amount you have staked")]
     InvalidUnstakeAmount {},
+
+    #[error("Can not unstake locked tokens. {locked} tokens are locked")]
+    StakeLocked { locked: Uint128 },
+
+    #[error("Not enough unlocked stake. Only {unlocked} tokens are unlocked")]
+    InsufficientUnlockedStake { unlocked: Uint128 },
+
+    #[error("Can only unlock or slash less than or equal to the amount you have locked")]
+    InvalidUnlockAmount {},
 }
@@ -28,6 +28,36 @@ pub enum ExecuteMsg {
         duration: Option<Duration>,
     },
     Claim {},
+    /// Registers a contract which may lock staked tokens. Only
+    /// callable by the owner or manager.
+    AddLocker {
+        addr: String,
+    },
+    /// Removes a locker. Stake it has already locked remains locked
+    /// until it unlocks or slashes it.
+    RemoveLocker {
+        addr: String,
+    },
+    /// Locks AMOUNT of ADDRESS's staked tokens so that they may not
+    /// be unstaked. Only callable by registered lockers.
+    LockStake {
+        address: String,
+        amount: Uint128,
+    },
+    /// Unlocks AMOUNT of the staked tokens the sender has locked for
+    /// ADDRESS.
+    UnlockStake {
+        address: String,
+        amount: Uint128,
+    },
+    /// Unstakes AMOUNT of the staked tokens the sender has locked for
+    /// ADDRESS and sends them to RECIPIENT without waiting for the
+    /// unstaking duration.
+    SlashStake {
+        address: String,
+        amount: Uint128,
+        recipient: String,
+    },
 }
 
 #[voting_query]
@@ -46,6 +76,10 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    #[returns(ListLockersResponse)]
+    ListLockers {},
+    #[returns(LockedBalanceResponse)]
+    LockedBalance { address: String },
 }
 
 #[cw_serde]
@@ -61,3 +95,13 @@ pub struct StakerBalanceResponse {
     pub address: String,
     pub balance: Uint128,
 }
+
+#[cw_serde]
+pub struct ListLockersResponse {
+    pub lockers: Vec<String>,
+}
+
+#[cw_serde]
+pub struct LockedBalanceResponse {
+    pub balance: Uint128,
+}
@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Empty, Uint128};
 use cw_controllers::Claims;
-use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
 
 #[cw_serde]
@@ -32,3 +32,11 @@ pub const STAKED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
 pub const MAX_CLAIMS: u64 = 100;
 
 pub const CLAIMS: Claims = Claims::new("claims");
+
+/// Contracts which may lock staked tokens, generally pre-propose
+/// modules which take locked stake as proposal deposits.
+pub const LOCKERS: Map<&Addr, Empty> = Map::new("lockers");
+
+/// The amount of each address's stake locked by each locker, keyed
+/// by (staker, locker).
+pub const LOCKED: Map<(&Addr, &Addr), Uint128> = Map::new("locked");
@@ -1,8 +1,10 @@
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg, StakerBalanceResponse,
+    ExecuteMsg, InstantiateMsg, ListLockersResponse, ListStakersResponse, LockedBalanceResponse,
+    MigrateMsg, QueryMsg, StakerBalanceResponse,
 };
 use crate::state::Config;
+use crate::ContractError;
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
 use cosmwasm_std::{coins, Addr, Coin, Empty, Uint128};
 use cw_controllers::ClaimsResponse;
@@ -975,6 +977,196 @@ fn test_query_list_stakers() {
     assert_eq!(stakers, ListStakersResponse { stakers: vec![] });
 }
 
+#[test]
+fn test_lock_stake() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            owner: Some(Admin::Address {
+                addr: DAO_ADDR.to_string(),
+            }),
+            manager: None,
+            denom: DENOM.to_string(),
+            unstaking_duration: None,
+        },
+    );
+    const LOCKER: &str = "locker";
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+
+    // Only registered lockers may lock stake.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(LOCKER),
+            addr.clone(),
+            &ExecuteMsg::LockStake {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(60),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+
+    // Only the owner or manager may register lockers.
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        addr.clone(),
+        &ExecuteMsg::AddLocker {
+            addr: LOCKER.to_string(),
+        },
+        &[],
+    )
+    .unwrap_err();
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::AddLocker {
+            addr: LOCKER.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let lockers: ListLockersResponse = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::ListLockers {})
+        .unwrap();
+    assert_eq!(lockers.lockers, vec![LOCKER.to_string()]);
+
+    // Can't lock more than is staked.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(LOCKER),
+            addr.clone(),
+            &ExecuteMsg::LockStake {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(101),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        ContractError::InsufficientUnlockedStake {
+            unlocked: Uint128::new(100)
+        }
+        .to_string()
+    );
+
+    app.execute_contract(
+        Addr::unchecked(LOCKER),
+        addr.clone(),
+        &ExecuteMsg::LockStake {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(60),
+        },
+        &[],
+    )
+    .unwrap();
+    let locked: LockedBalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::LockedBalance {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(locked.balance, Uint128::new(60));
+
+    // Locked stake can't be unstaked, but the rest can.
+    let err: ContractError = unstake_tokens(&mut app, addr.clone(), ADDR1, 50)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        ContractError::StakeLocked {
+            locked: Uint128::new(60)
+        }
+        .to_string()
+    );
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 40).unwrap();
+
+    // Locked stake still counts as voting power.
+    app.update_block(next_block);
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(60));
+
+    // Removed lockers may still release what they locked.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::RemoveLocker {
+            addr: LOCKER.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(LOCKER),
+        addr.clone(),
+        &ExecuteMsg::UnlockStake {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(20),
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(LOCKER),
+            addr.clone(),
+            &ExecuteMsg::SlashStake {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(41),
+                recipient: DAO_ADDR.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        ContractError::InvalidUnlockAmount {}.to_string()
+    );
+
+    // Slashed stake is sent to the recipient immediately.
+    let dao_balance = get_balance(&mut app, DAO_ADDR, DENOM);
+    app.execute_contract(
+        Addr::unchecked(LOCKER),
+        addr.clone(),
+        &ExecuteMsg::SlashStake {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(40),
+            recipient: DAO_ADDR.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        get_balance(&mut app, DAO_ADDR, DENOM),
+        dao_balance + Uint128::new(40)
+    );
+
+    app.update_block(next_block);
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(20));
+    let resp = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(resp.power, Uint128::new(20));
+
+    // Nothing is locked anymore.
+    unstake_tokens(&mut app, addr, ADDR1, 20).unwrap();
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -8,7 +8,10 @@ pub mod voting;
 pub enum ExecuteMsg {
     /// Causes the core module to execute all of MSGS in order. Only
     /// callabale by a proposal module.1
-    ExecuteProposalHook { msgs: Vec<CosmosMsg<Empty>> },
+    ExecuteProposalHook {
+        msgs: Vec<CosmosMsg<Empty>>,
+        proposal_id: Option<u64>,
+    },
 }
 
 /// Information about the CosmWasm level admin of a contract. Used in
@@ -1,5 +1,6 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use cw_denom::DenomError;
+use cw_utils::Expiration;
 use thiserror::Error;
 
 use cwd_voting::{deposit::DepositError, status::Status};
@@ -30,6 +31,56 @@ pub enum PreProposeError {
     #[error("Nothing to withdraw")]
     NothingToWithdraw {},
 
-    #[error("Proposal status ({status}) not closed or executed")]
+    #[error(
+        "Proposal status ({status}) not closed, closed with veto, executed, vetoed, execution expired, or withdrawn"
+    )]
     NotClosedOrExecuted { status: Status },
+
+    #[error("Only the title, description, and contents of a proposal may be updated")]
+    InvalidUpdate {},
+
+    #[error("Drafts are not enabled. The deposit must have a funding period")]
+    DraftsDisabled {},
+
+    #[error("No draft with ID ({id})")]
+    NoSuchDraft { id: u64 },
+
+    #[error("Draft ({id}) has expired")]
+    DraftExpired { id: u64 },
+
+    #[error("Draft ({id}) has not expired")]
+    DraftNotExpired { id: u64 },
+
+    #[error("Contributions must be non-zero")]
+    ZeroContribution {},
+
+    #[error("Contribution exceeds the remainder of the deposit ({remaining})")]
+    Overfunded { remaining: Uint128 },
+
+    #[error("Message ({index}) is not allowed by this module's message restrictions")]
+    MessageNotAllowed { index: usize },
+
+    #[error("Message ({index}) migrates a contract or changes its admin, which this module does not allow")]
+    AdminMessage { index: usize },
+
+    #[error("Proposals may not send more than ({cap}) ({denom}) in total")]
+    BankSendOverCap { denom: String, cap: Uint128 },
+
+    #[error("No template named ({name})")]
+    NoSuchTemplate { name: String },
+
+    #[error("No value for template parameter ({name})")]
+    MissingTemplateParam { name: String },
+
+    #[error("Template ({name}) does not render to a list of messages")]
+    InvalidTemplate { name: String },
+
+    #[error("Proposers must have at least ({min}) voting power, but have ({power})")]
+    InsufficientVotingPower { min: Uint128, power: Uint128 },
+
+    #[error("Proposers may not have more than ({max}) open proposals")]
+    TooManyOpenProposals { max: u64 },
+
+    #[error("Proposer may not submit another proposal until ({expiration})")]
+    SubmissionCooldown { expiration: Expiration },
 }
@@ -1,31 +1,83 @@
-use cosmwasm_schema::schemars::JsonSchema;
+use cosmwasm_schema::{cw_serde, schemars::JsonSchema};
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, WasmMsg,
+    to_binary, Addr, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdResult, Storage, Uint128, WasmMsg,
 };
+use cw_storage_plus::Map;
+use cw_utils::Duration;
 
 use cw2::set_contract_version;
 
 use cw_denom::UncheckedDenom;
 use cwd_interface::voting::{Query as CwCoreQuery, VotingPowerAtHeightResponse};
 use cwd_voting::{
-    deposit::{DepositRefundPolicy, UncheckedDepositInfo},
+    deposit::{CheckedDepositInfo, DepositRefundPolicy, UncheckedDepositInfo},
     status::Status,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::PreProposeError,
-    msg::{DepositInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg},
-    state::{Config, PreProposeContract},
+    msg::{Contribution, DepositInfoResponse, DraftResponse, ExecuteMsg, InstantiateMsg, QueryMsg},
+    restrictions::{ProposalMessages, Template, TemplateParam, UncheckedMessageRestrictions},
+    state::{Config, Draft, PreProposeContract},
 };
 
 const CONTRACT_NAME: &str = "crates.io::cwd-pre-propose-base";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Messages sent to the proposal module on behalf of proposers which
+/// are the same for all proposal modules.
+#[cw_serde]
+enum ProposalModuleExecuteMsg {
+    WithdrawProposal {
+        proposal_id: u64,
+        proposer: Option<String>,
+    },
+}
+
+/// Queries of the proposal module which are the same for all proposal
+/// modules.
+#[cw_serde]
+enum ProposalModuleQueryMsg {
+    Proposal { proposal_id: u64 },
+}
+
+/// The part of a proposal module's proposal query response which is
+/// the same for all proposal modules.
+#[derive(Deserialize)]
+struct ProposalStatusResponse {
+    proposal: ProposalStatus,
+}
+
+#[derive(Deserialize)]
+struct ProposalStatus {
+    status: Status,
+}
+
+/// Lists the contributions stored in CONTRIBUTIONS for the draft or
+/// proposal identified by ID.
+fn list_contributions(
+    storage: &dyn Storage,
+    contributions: &Map<'static, (u64, Addr), Uint128>,
+    id: u64,
+) -> StdResult<Vec<Contribution>> {
+    contributions
+        .prefix(id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(contributor, amount)| Contribution {
+                contributor,
+                amount,
+            })
+        })
+        .collect()
+}
+
 impl<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage>
     PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage>
 where
-    ProposalMessage: Serialize,
+    ProposalMessage: Serialize + ProposalMessages,
     QueryExt: JsonSchema,
 {
     pub fn instantiate(
@@ -58,10 +110,22 @@ where
         let config = Config {
             deposit_info,
             open_proposal_submission: msg.open_proposal_submission,
+            min_voting_power: msg.min_voting_power,
+            max_open_proposals: msg.max_open_proposals,
+            submission_cooldown: msg.submission_cooldown,
         };
 
         self.config.save(deps.storage, &config)?;
 
+        if let Some(restrictions) = msg.message_restrictions {
+            self.message_restrictions
+                .save(deps.storage, &restrictions.into_checked(deps.as_ref())?)?;
+        }
+        for template in msg.templates {
+            self.templates
+                .save(deps.storage, template.name.clone(), &template)?;
+        }
+
         Ok(Response::default()
             .add_attribute("method", "instantiate")
             .add_attribute("proposal_module", info.sender.into_string())
@@ -81,11 +145,43 @@ where
         msg: ExecuteMsg<ProposalMessage, ExecuteExt>,
     ) -> Result<Response, PreProposeError> {
         match msg {
-            ExecuteMsg::Propose { msg } => self.execute_propose(deps.as_ref(), env, info, msg),
+            ExecuteMsg::Propose { msg } => self.execute_propose(deps, env, info, msg),
+            ExecuteMsg::ProposeDraft { msg, amount } => {
+                self.execute_propose_draft(deps, env, info, msg, amount)
+            }
+            ExecuteMsg::FundDraft { draft_id, amount } => {
+                self.execute_fund_draft(deps, env, info, draft_id, amount)
+            }
+            ExecuteMsg::ExpireDraft { draft_id } => {
+                self.execute_expire_draft(deps, env, info, draft_id)
+            }
+            ExecuteMsg::UpdateProposal { proposal_id, msg } => {
+                self.execute_update_proposal(deps.as_ref(), info, proposal_id, msg)
+            }
+            ExecuteMsg::WithdrawProposal { proposal_id } => {
+                self.execute_withdraw_proposal(deps.as_ref(), info, proposal_id)
+            }
             ExecuteMsg::UpdateConfig {
                 deposit_info,
                 open_proposal_submission,
-            } => self.execute_update_config(deps, info, deposit_info, open_proposal_submission),
+                min_voting_power,
+                max_open_proposals,
+                submission_cooldown,
+            } => self.execute_update_config(
+                deps,
+                info,
+                deposit_info,
+                open_proposal_submission,
+                min_voting_power,
+                max_open_proposals,
+                submission_cooldown,
+            ),
+            ExecuteMsg::UpdateMessageRestrictions { restrictions } => {
+                self.execute_update_message_restrictions(deps, info, restrictions)
+            }
+            ExecuteMsg::UpdateTemplates { to_add, to_remove } => {
+                self.execute_update_templates(deps, info, to_add, to_remove)
+            }
             ExecuteMsg::Withdraw { denom } => {
                 self.execute_withdraw(deps.as_ref(), env, info, denom)
             }
@@ -97,7 +193,7 @@ where
             ExecuteMsg::ProposalCompletedHook {
                 proposal_id,
                 new_status,
-            } => self.execute_proposal_completed_hook(deps.as_ref(), info, proposal_id, new_status),
+            } => self.execute_proposal_completed_hook(deps, env, info, proposal_id, new_status),
         }
     }
 
@@ -111,34 +207,47 @@ where
                 to_binary(&DepositInfoResponse {
                     deposit_info,
                     proposer,
+                    contributions: list_contributions(
+                        deps.storage,
+                        &self.contributions,
+                        proposal_id,
+                    )?,
                 })
             }
+            QueryMsg::Draft { draft_id } => to_binary(&DraftResponse {
+                id: draft_id,
+                draft: self.drafts.load(deps.storage, draft_id)?,
+                contributions: list_contributions(
+                    deps.storage,
+                    &self.draft_contributions,
+                    draft_id,
+                )?,
+            }),
+            QueryMsg::MessageRestrictions {} => {
+                to_binary(&self.message_restrictions.may_load(deps.storage)?)
+            }
+            QueryMsg::Templates {} => to_binary(
+                &self
+                    .templates
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .map(|item| item.map(|(_, template)| template))
+                    .collect::<StdResult<Vec<_>>>()?,
+            ),
             QueryMsg::QueryExtension { .. } => Ok(Binary::default()),
         }
     }
 
     pub fn execute_propose(
         &self,
-        deps: Deps,
+        mut deps: DepsMut,
         env: Env,
         info: MessageInfo,
         msg: ProposalMessage,
     ) -> Result<Response, PreProposeError> {
         let config = self.config.load(deps.storage)?;
-
-        if !config.open_proposal_submission {
-            let dao = self.dao.load(deps.storage)?;
-            let voting_power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
-                dao.into_string(),
-                &CwCoreQuery::VotingPowerAtHeight {
-                    address: info.sender.to_string(),
-                    height: None,
-                },
-            )?;
-            if voting_power.power.is_zero() {
-                return Err(PreProposeError::NotMember {});
-            }
-        }
+        self.check_can_propose(deps.branch(), &env.block, &config, &info.sender)?;
+        self.check_message_restrictions(deps.storage, msg.cosmos_msgs())?;
+        self.start_cooldown(deps.storage, &env.block, &config, &info.sender)?;
 
         let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
             deposit_info.check_native_deposit_paid(&info)?;
@@ -162,12 +271,166 @@ where
             .add_message(propose_messsage))
     }
 
+    pub fn execute_propose_draft(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ProposalMessage,
+        amount: Uint128,
+    ) -> Result<Response, PreProposeError> {
+        let config = self.config.load(deps.storage)?;
+        self.check_can_propose(deps.branch(), &env.block, &config, &info.sender)?;
+        self.check_message_restrictions(deps.storage, msg.cosmos_msgs())?;
+        self.start_cooldown(deps.storage, &env.block, &config, &info.sender)?;
+
+        let deposit_info = config
+            .deposit_info
+            .ok_or(PreProposeError::DraftsDisabled {})?;
+        let funding_period = deposit_info
+            .funding_period
+            .ok_or(PreProposeError::DraftsDisabled {})?;
+
+        let id = self.draft_count.may_load(deps.storage)?.unwrap_or_default() + 1;
+        self.draft_count.save(deps.storage, &id)?;
+        self.add_pending_submission(deps.storage, &info.sender)?;
+        let draft = Draft {
+            proposer: info.sender.clone(),
+            msg: to_binary(&msg)?,
+            deposit_info,
+            funded: Uint128::zero(),
+            expiration: funding_period.after(&env.block),
+        };
+
+        Ok(self
+            .contribute(deps, &env, &info, id, draft, amount)?
+            .add_attribute("method", "execute_propose_draft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("draft_id", id.to_string()))
+    }
+
+    pub fn execute_fund_draft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        draft_id: u64,
+        amount: Uint128,
+    ) -> Result<Response, PreProposeError> {
+        let draft = self
+            .drafts
+            .may_load(deps.storage, draft_id)?
+            .ok_or(PreProposeError::NoSuchDraft { id: draft_id })?;
+        if draft.expiration.is_expired(&env.block) {
+            return Err(PreProposeError::DraftExpired { id: draft_id });
+        }
+
+        Ok(self
+            .contribute(deps, &env, &info, draft_id, draft, amount)?
+            .add_attribute("method", "execute_fund_draft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("draft_id", draft_id.to_string()))
+    }
+
+    pub fn execute_expire_draft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        draft_id: u64,
+    ) -> Result<Response, PreProposeError> {
+        let draft = self
+            .drafts
+            .may_load(deps.storage, draft_id)?
+            .ok_or(PreProposeError::NoSuchDraft { id: draft_id })?;
+        if !draft.expiration.is_expired(&env.block) {
+            return Err(PreProposeError::DraftNotExpired { id: draft_id });
+        }
+
+        let mut refund_messages = vec![];
+        for Contribution {
+            contributor,
+            amount,
+        } in list_contributions(deps.storage, &self.draft_contributions, draft_id)?
+        {
+            let contribution = CheckedDepositInfo {
+                amount,
+                ..draft.deposit_info.clone()
+            };
+            refund_messages.extend(contribution.get_return_deposit_message(&contributor)?);
+            self.draft_contributions
+                .remove(deps.storage, (draft_id, contributor));
+        }
+        self.drafts.remove(deps.storage, draft_id);
+        self.remove_pending_submission(deps.storage, &draft.proposer)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "execute_expire_draft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("draft_id", draft_id.to_string())
+            .add_messages(refund_messages))
+    }
+
+    pub fn execute_update_proposal(
+        &self,
+        deps: Deps,
+        info: MessageInfo,
+        proposal_id: u64,
+        msg: ProposalMessage,
+    ) -> Result<Response, PreProposeError> {
+        self.check_message_restrictions(deps.storage, msg.cosmos_msgs())?;
+
+        // The proposal module checks that the proposer in MSG, which
+        // is the message sender, created the proposal.
+        let proposal_module = self.proposal_module.load(deps.storage)?;
+        let update_message = WasmMsg::Execute {
+            contract_addr: proposal_module.into_string(),
+            msg: to_binary(&msg)?,
+            funds: vec![],
+        };
+
+        Ok(Response::default()
+            .add_attribute("method", "execute_update_proposal")
+            .add_attribute("sender", info.sender)
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_message(update_message))
+    }
+
+    pub fn execute_withdraw_proposal(
+        &self,
+        deps: Deps,
+        info: MessageInfo,
+        proposal_id: u64,
+    ) -> Result<Response, PreProposeError> {
+        // Deposits are handled when the proposal module fires the
+        // proposal completed hook for the withdrawn proposal.
+        let proposal_module = self.proposal_module.load(deps.storage)?;
+        let withdraw_message = WasmMsg::Execute {
+            contract_addr: proposal_module.into_string(),
+            msg: to_binary(&ProposalModuleExecuteMsg::WithdrawProposal {
+                proposal_id,
+                proposer: Some(info.sender.to_string()),
+            })?,
+            funds: vec![],
+        };
+
+        Ok(Response::default()
+            .add_attribute("method", "execute_withdraw_proposal")
+            .add_attribute("sender", info.sender)
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_message(withdraw_message))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn execute_update_config(
         &self,
         deps: DepsMut,
         info: MessageInfo,
         deposit_info: Option<UncheckedDepositInfo>,
         open_proposal_submission: bool,
+        min_voting_power: Option<Uint128>,
+        max_open_proposals: Option<u64>,
+        submission_cooldown: Option<Duration>,
     ) -> Result<Response, PreProposeError> {
         let dao = self.dao.load(deps.storage)?;
         if info.sender != dao {
@@ -181,6 +444,9 @@ where
                 &Config {
                     deposit_info,
                     open_proposal_submission,
+                    min_voting_power,
+                    max_open_proposals,
+                    submission_cooldown,
                 },
             )?;
 
@@ -190,6 +456,54 @@ where
         }
     }
 
+    pub fn execute_update_message_restrictions(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        restrictions: Option<UncheckedMessageRestrictions>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        match restrictions {
+            Some(restrictions) => self
+                .message_restrictions
+                .save(deps.storage, &restrictions.into_checked(deps.as_ref())?)?,
+            None => self.message_restrictions.remove(deps.storage),
+        }
+
+        Ok(Response::default()
+            .add_attribute("method", "update_message_restrictions")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn execute_update_templates(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        to_add: Vec<Template>,
+        to_remove: Vec<String>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        for template in to_add {
+            self.templates
+                .save(deps.storage, template.name.clone(), &template)?;
+        }
+        for name in to_remove {
+            self.templates.remove(deps.storage, name);
+        }
+
+        Ok(Response::default()
+            .add_attribute("method", "update_templates")
+            .add_attribute("sender", info.sender))
+    }
+
     pub fn execute_withdraw(
         &self,
         deps: Deps,
@@ -229,7 +543,8 @@ where
 
     pub fn execute_proposal_completed_hook(
         &self,
-        deps: Deps,
+        deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         id: u64,
         new_status: Status,
@@ -240,26 +555,91 @@ where
         }
 
         // These are the only proposal statuses we handle deposits for.
-        if new_status != Status::Closed && new_status != Status::Executed {
+        if new_status != Status::Closed
+            && new_status != Status::Executed
+            && new_status != Status::Vetoed
+            && new_status != Status::ExecutionExpired
+            && new_status != Status::Withdrawn
+            && new_status != Status::ClosedWithVeto
+        {
             return Err(PreProposeError::NotClosedOrExecuted { status: new_status });
         }
 
         match self.deposits.may_load(deps.storage, id)? {
             Some((deposit_info, proposer)) => {
+                self.open_proposals
+                    .remove(deps.storage, (proposer.clone(), id));
+
                 let messages = if let Some(ref deposit_info) = deposit_info {
-                    // Refund can be issued if proposal if it is going to
-                    // closed or executed.
-                    let should_refund_to_proposer = (new_status == Status::Closed
-                        && deposit_info.refund_policy == DepositRefundPolicy::Always)
-                        || (new_status == Status::Executed
-                            && deposit_info.refund_policy != DepositRefundPolicy::Never);
-
-                    if should_refund_to_proposer {
-                        deposit_info.get_return_deposit_message(&proposer)?
+                    // Crowdfunded deposits are made up of their
+                    // contributions. Staked deposits are locked per
+                    // depositor, so each part is handled separately.
+                    let contributions = list_contributions(deps.storage, &self.contributions, id)?;
+                    let parts = if contributions.is_empty() {
+                        vec![(proposer.clone(), deposit_info.clone())]
                     } else {
-                        // If the proposer doesn't get the deposit, the DAO does.
-                        let dao = self.dao.load(deps.storage)?;
-                        deposit_info.get_return_deposit_message(&dao)?
+                        contributions
+                            .into_iter()
+                            .map(|c| {
+                                (
+                                    c.contributor,
+                                    CheckedDepositInfo {
+                                        amount: c.amount,
+                                        ..deposit_info.clone()
+                                    },
+                                )
+                            })
+                            .collect()
+                    };
+                    let dao = self.dao.load(deps.storage)?;
+
+                    if new_status == Status::ClosedWithVeto {
+                        // Deposits of proposals rejected by NoWithVeto
+                        // votes are never refunded.
+                        parts
+                            .iter()
+                            .map(|(depositor, part)| {
+                                part.get_veto_deposit_messages(
+                                    depositor,
+                                    &dao,
+                                    &env.contract.address,
+                                )
+                            })
+                            .collect::<StdResult<Vec<_>>>()?
+                            .concat()
+                    } else {
+                        // Refund can be issued if proposal if it is going to
+                        // closed or executed. Vetoed proposals are treated
+                        // like closed ones as they will never be executed,
+                        // and proposals whose execution window expired
+                        // like executed ones as they passed. Withdrawn
+                        // proposals are treated like closed ones under
+                        // their withdrawal refund policy.
+                        let should_refund_to_proposer = ((new_status == Status::Closed
+                            || new_status == Status::Vetoed)
+                            && deposit_info.refund_policy == DepositRefundPolicy::Always)
+                            || ((new_status == Status::Executed
+                                || new_status == Status::ExecutionExpired)
+                                && deposit_info.refund_policy != DepositRefundPolicy::Never)
+                            || (new_status == Status::Withdrawn
+                                && deposit_info.withdrawal_refund_policy()
+                                    == DepositRefundPolicy::Always);
+
+                        // Contributions sum to the deposit, so
+                        // returning each of them refunds contributors
+                        // pro rata.
+                        parts
+                            .iter()
+                            .map(|(depositor, part)| {
+                                if should_refund_to_proposer {
+                                    part.get_return_deposit_message(depositor)
+                                } else {
+                                    // If the proposer doesn't get the deposit, the DAO does.
+                                    part.get_forfeit_deposit_messages(depositor, &dao)
+                                }
+                            })
+                            .collect::<StdResult<Vec<_>>>()?
+                            .concat()
                     }
                 } else {
                     // No deposit info for this proposal. Nothing to do.
@@ -303,12 +683,253 @@ where
         // about this though as the only way to be able to update our
         // config is to have root on the code module and if someone
         // has that we're totally screwed anyhow.
-        let config = self.config.load(deps.storage)?;
+        //
+        // Proposals created from drafts use the deposit configured
+        // when the draft was created, and their contributions are
+        // moved over to the proposal.
+        let deposit_info = match self.pending_draft.may_load(deps.storage)? {
+            Some(draft_id) => {
+                self.pending_draft.remove(deps.storage);
+                let draft = self.drafts.load(deps.storage, draft_id)?;
+                for Contribution {
+                    contributor,
+                    amount,
+                } in list_contributions(deps.storage, &self.draft_contributions, draft_id)?
+                {
+                    self.draft_contributions
+                        .remove(deps.storage, (draft_id, contributor.clone()));
+                    self.contributions
+                        .save(deps.storage, (id, contributor), &amount)?;
+                }
+                self.drafts.remove(deps.storage, draft_id);
+                self.remove_pending_submission(deps.storage, &draft.proposer)?;
+                Some(draft.deposit_info)
+            }
+            None => self.config.load(deps.storage)?.deposit_info,
+        };
+        self.open_proposals
+            .save(deps.storage, (proposer.clone(), id), &Empty {})?;
         self.deposits
-            .save(deps.storage, id, &(config.deposit_info, proposer))?;
+            .save(deps.storage, id, &(deposit_info, proposer))?;
 
         Ok(Response::default()
             .add_attribute("method", "execute_new_proposal_hook")
             .add_attribute("proposal_id", id.to_string()))
     }
+
+    /// Returns an error if SENDER may not create proposals.
+    pub fn check_can_propose(
+        &self,
+        mut deps: DepsMut,
+        block: &BlockInfo,
+        config: &Config,
+        sender: &Addr,
+    ) -> Result<(), PreProposeError> {
+        if !config.open_proposal_submission || config.min_voting_power.is_some() {
+            let dao = self.dao.load(deps.storage)?;
+            let voting_power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+                dao.into_string(),
+                &CwCoreQuery::VotingPowerAtHeight {
+                    address: sender.to_string(),
+                    height: None,
+                },
+            )?;
+            if !config.open_proposal_submission && voting_power.power.is_zero() {
+                return Err(PreProposeError::NotMember {});
+            }
+            if let Some(min) = config.min_voting_power {
+                if voting_power.power < min {
+                    return Err(PreProposeError::InsufficientVotingPower {
+                        min,
+                        power: voting_power.power,
+                    });
+                }
+            }
+        }
+
+        if let Some(max) = config.max_open_proposals {
+            let open = self.count_open_proposals(deps.branch(), sender)?;
+            if open >= max {
+                return Err(PreProposeError::TooManyOpenProposals { max });
+            }
+        }
+
+        if let Some(expiration) = self.cooldowns.may_load(deps.storage, sender.clone())? {
+            if !expiration.is_expired(block) {
+                return Err(PreProposeError::SubmissionCooldown { expiration });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of open proposals PROPOSER has, counting
+    /// submissions which have not yet become proposals.
+    ///
+    /// Proposals which were rejected or passed stay in
+    /// `open_proposals` until they are closed or executed, which may
+    /// never happen, so the proposal module is asked for the status of
+    /// each proposal and those which are no longer open or pending are
+    /// removed.
+    pub fn count_open_proposals(
+        &self,
+        deps: DepsMut,
+        proposer: &Addr,
+    ) -> Result<u64, PreProposeError> {
+        let proposal_module = self.proposal_module.load(deps.storage)?;
+        let ids = self
+            .open_proposals
+            .prefix(proposer.clone())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<u64>>>()?;
+
+        let mut open = self
+            .pending_submissions
+            .may_load(deps.storage, proposer.clone())?
+            .unwrap_or_default();
+        for id in ids {
+            let ProposalStatusResponse { proposal } = deps.querier.query_wasm_smart(
+                &proposal_module,
+                &ProposalModuleQueryMsg::Proposal { proposal_id: id },
+            )?;
+            if proposal.status == Status::Open || proposal.status == Status::Pending {
+                open += 1;
+            } else {
+                self.open_proposals
+                    .remove(deps.storage, (proposer.clone(), id));
+            }
+        }
+        Ok(open)
+    }
+
+    /// Records a submission by PROPOSER which will become a proposal
+    /// later, for example a draft. It counts towards PROPOSER's open
+    /// proposals until it is removed with
+    /// `remove_pending_submission`.
+    pub fn add_pending_submission(
+        &self,
+        storage: &mut dyn Storage,
+        proposer: &Addr,
+    ) -> StdResult<()> {
+        self.pending_submissions
+            .update(storage, proposer.clone(), |pending| -> StdResult<_> {
+                Ok(pending.unwrap_or_default() + 1)
+            })?;
+        Ok(())
+    }
+
+    /// Removes a submission recorded by `add_pending_submission`,
+    /// either because it became a proposal or because it was
+    /// discarded.
+    pub fn remove_pending_submission(
+        &self,
+        storage: &mut dyn Storage,
+        proposer: &Addr,
+    ) -> StdResult<()> {
+        self.pending_submissions
+            .update(storage, proposer.clone(), |pending| -> StdResult<_> {
+                Ok(pending.unwrap_or_default().saturating_sub(1))
+            })?;
+        Ok(())
+    }
+
+    /// Starts the submission cooldown for SENDER, if the module has
+    /// one. Called when SENDER submits a proposal.
+    pub fn start_cooldown(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        config: &Config,
+        sender: &Addr,
+    ) -> StdResult<()> {
+        match config.submission_cooldown {
+            Some(cooldown) => self
+                .cooldowns
+                .save(storage, sender.clone(), &cooldown.after(block)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns an error if MSGS are not allowed by this module's
+    /// message restrictions.
+    pub fn check_message_restrictions(
+        &self,
+        storage: &dyn Storage,
+        msg_sets: Vec<Vec<&CosmosMsg>>,
+    ) -> Result<(), PreProposeError> {
+        match self.message_restrictions.may_load(storage)? {
+            Some(restrictions) => restrictions.check(&msg_sets),
+            None => Ok(()),
+        }
+    }
+
+    /// Fills in the template named NAME with PARAMS.
+    pub fn render_template(
+        &self,
+        storage: &dyn Storage,
+        name: &str,
+        params: &[TemplateParam],
+    ) -> Result<Vec<CosmosMsg>, PreProposeError> {
+        self.templates
+            .may_load(storage, name.to_string())?
+            .ok_or_else(|| PreProposeError::NoSuchTemplate {
+                name: name.to_string(),
+            })?
+            .render(params)
+    }
+
+    /// Takes AMOUNT from the message sender towards the deposit of
+    /// DRAFT and saves it. If this fully funds the deposit, the
+    /// draft's proposal is created and the proposal created hook
+    /// picks up its deposit and contributions.
+    fn contribute(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        id: u64,
+        mut draft: Draft,
+        amount: Uint128,
+    ) -> Result<Response, PreProposeError> {
+        if amount.is_zero() {
+            return Err(PreProposeError::ZeroContribution {});
+        }
+        let remaining = draft.deposit_info.amount - draft.funded;
+        if amount > remaining {
+            return Err(PreProposeError::Overfunded { remaining });
+        }
+
+        // Contributions are taken the same way as whole deposits.
+        let contribution = CheckedDepositInfo {
+            amount,
+            ..draft.deposit_info.clone()
+        };
+        contribution.check_native_deposit_paid(info)?;
+        let take_messages =
+            contribution.get_take_deposit_messages(&info.sender, &env.contract.address)?;
+
+        self.draft_contributions.update(
+            deps.storage,
+            (id, info.sender.clone()),
+            |contributed| -> StdResult<_> { Ok(contributed.unwrap_or_default() + amount) },
+        )?;
+        draft.funded += amount;
+        self.drafts.save(deps.storage, id, &draft)?;
+
+        let response = Response::default()
+            .add_attribute("amount", amount)
+            .add_attribute("funded", draft.funded)
+            .add_messages(take_messages);
+        if draft.funded == draft.deposit_info.amount {
+            self.pending_draft.save(deps.storage, &id)?;
+            let proposal_module = self.proposal_module.load(deps.storage)?;
+            Ok(response.add_message(WasmMsg::Execute {
+                contract_addr: proposal_module.into_string(),
+                msg: draft.msg,
+                funds: vec![],
+            }))
+        } else {
+            Ok(response)
+        }
+    }
 }
@@ -1,10 +1,14 @@
 use cosmwasm_schema::{cw_serde, schemars::JsonSchema, QueryResponses};
+use cosmwasm_std::{Addr, Uint128};
 use cw_denom::UncheckedDenom;
+use cw_utils::Duration;
 use cwd_voting::{
     deposit::{CheckedDepositInfo, UncheckedDepositInfo},
     status::Status,
 };
 
+use crate::restrictions::{Template, UncheckedMessageRestrictions};
+
 #[cw_serde]
 pub struct InstantiateMsg<InstantiateExt> {
     /// Information about the deposit requirements for this
@@ -14,6 +18,27 @@ pub struct InstantiateMsg<InstantiateExt> {
     /// proposals in the DAO. Otherwise, any address may create a
     /// proposal so long as they pay the deposit.
     pub open_proposal_submission: bool,
+    /// The minimum voting power an address must have to create a
+    /// proposal. None if there is no minimum.
+    #[serde(default)]
+    pub min_voting_power: Option<Uint128>,
+    /// The maximum number of open proposals a proposer may have at
+    /// once, counting drafts and proposals awaiting approval. Proposals
+    /// count while they are open or pending. None if there is no
+    /// maximum.
+    #[serde(default)]
+    pub max_open_proposals: Option<u64>,
+    /// How long a proposer must wait after submitting a proposal
+    /// before submitting another. None if there is no cooldown.
+    #[serde(default)]
+    pub submission_cooldown: Option<Duration>,
+    /// Restrictions on the messages proposals may contain. None if
+    /// proposals may contain any messages.
+    #[serde(default)]
+    pub message_restrictions: Option<UncheckedMessageRestrictions>,
+    /// Templates proposers may fill in to create proposals.
+    #[serde(default)]
+    pub templates: Vec<Template>,
     /// Extension for instantiation. The default implementation will
     /// do nothing with this data.
     pub extension: InstantiateExt,
@@ -25,6 +50,45 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
     /// serialized and used as the proposal creation message.
     Propose { msg: ProposalMessage },
 
+    /// Creates a draft of a proposal whose deposit may be funded by
+    /// other addresses. The message sender contributes AMOUNT, which
+    /// must be non-zero and no more than the deposit. For native
+    /// deposits AMOUNT must be sent with the message. MSG is
+    /// serialized and used as the proposal creation message once the
+    /// deposit is fully funded. Only allowed if the deposit has a
+    /// funding period.
+    ProposeDraft {
+        msg: ProposalMessage,
+        amount: Uint128,
+    },
+
+    /// Contributes AMOUNT to the deposit of a draft which has not
+    /// expired. AMOUNT must be non-zero and no more than the
+    /// remainder of the deposit. If this fully funds the deposit the
+    /// draft's proposal is created.
+    FundDraft { draft_id: u64, amount: Uint128 },
+
+    /// Refunds every contribution to a draft which expired before its
+    /// deposit was fully funded, and removes the draft. Callable by
+    /// any address.
+    ExpireDraft { draft_id: u64 },
+
+    /// Updates a proposal on behalf of the message sender. MSG will
+    /// be serialized and used as the proposal update message. The
+    /// proposal module only allows the proposal's proposer to update
+    /// it, and only before it has been voted on.
+    UpdateProposal {
+        proposal_id: u64,
+        msg: ProposalMessage,
+    },
+
+    /// Withdraws a proposal on behalf of the message sender. The
+    /// proposal module only allows the proposal's proposer to
+    /// withdraw it, and only before it has been voted on. Its
+    /// deposit is refunded according to the withdrawal refund policy
+    /// of the deposit it was created with.
+    WithdrawProposal { proposal_id: u64 },
+
     /// Updates the configuration of this module. This will completely
     /// override the existing configuration. This new configuration
     /// will only apply to proposals created after the config is
@@ -32,6 +96,28 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
     UpdateConfig {
         deposit_info: Option<UncheckedDepositInfo>,
         open_proposal_submission: bool,
+        #[serde(default)]
+        min_voting_power: Option<Uint128>,
+        #[serde(default)]
+        max_open_proposals: Option<u64>,
+        #[serde(default)]
+        submission_cooldown: Option<Duration>,
+    },
+
+    /// Updates the restrictions on the messages proposals may
+    /// contain. None if proposals may contain any messages. Like
+    /// config updates, this only applies to proposals created or
+    /// updated after it. Only the DAO may execute this message.
+    UpdateMessageRestrictions {
+        restrictions: Option<UncheckedMessageRestrictions>,
+    },
+
+    /// Adds the templates in TO_ADD, replacing any templates with the
+    /// same names, and removes the templates named in TO_REMOVE. Only
+    /// the DAO may execute this message.
+    UpdateTemplates {
+        to_add: Vec<Template>,
+        to_remove: Vec<String>,
     },
 
     /// Withdraws funds inside of this contract to the message
@@ -77,7 +163,8 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
     ProposalCreatedHook { proposal_id: u64, proposer: String },
 
     /// Handles proposal hook fired by the associated proposal
-    /// module when a proposal is completed (ie executed or rejected).
+    /// module when a proposal is completed (ie executed, rejected, or
+    /// withdrawn).
     /// By default, the base contract will return deposits
     /// proposals, when they are closed.
     /// when proposals are executed, or, if it is refunding failed
@@ -108,6 +195,18 @@ where
     /// PROPOSAL_ID.
     #[returns(DepositInfoResponse)]
     DepositInfo { proposal_id: u64 },
+    /// Gets the draft identified by DRAFT_ID and its contributions.
+    /// Drafts are removed once their proposal is created or they
+    /// expire and are refunded.
+    #[returns(DraftResponse)]
+    Draft { draft_id: u64 },
+    /// Gets the restrictions on the messages proposals may contain.
+    #[returns(Option<crate::restrictions::MessageRestrictions>)]
+    MessageRestrictions {},
+    /// Lists the templates proposers may fill in to create
+    /// proposals.
+    #[returns(Vec<crate::restrictions::Template>)]
+    Templates {},
     /// Extension for queries. The default implementation will do
     /// nothing if queried for will return `Binary::default()`.
     #[returns(cosmwasm_std::Binary)]
@@ -120,4 +219,20 @@ pub struct DepositInfoResponse {
     pub deposit_info: Option<CheckedDepositInfo>,
     /// The address that created the proposal.
     pub proposer: cosmwasm_std::Addr,
+    /// The contributions which funded the deposit if the proposal was
+    /// created from a draft. Empty otherwise.
+    pub contributions: Vec<Contribution>,
+}
+
+#[cw_serde]
+pub struct Contribution {
+    pub contributor: Addr,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct DraftResponse {
+    pub id: u64,
+    pub draft: crate::state::Draft,
+    pub contributions: Vec<Contribution>,
 }
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_binary, from_slice, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, StdError, Uint128,
+    WasmMsg,
+};
+use serde::de::IgnoredAny;
+
+use crate::error::PreProposeError;
+
+/// Implemented by proposal messages so that the messages a proposal
+/// would execute may be checked against a module's message
+/// restrictions.
+pub trait ProposalMessages {
+    /// Returns every message the proposal may execute, grouped into
+    /// the sets of messages which are executed together. For example,
+    /// one set for a single choice proposal and one set per choice for
+    /// a multiple choice proposal.
+    fn cosmos_msgs(&self) -> Vec<Vec<&CosmosMsg>>;
+}
+
+/// A contract and the name of an execute message variant proposals
+/// may send to it.
+#[cw_serde]
+pub struct UncheckedAllowedExecute {
+    pub contract: String,
+    /// The name of the top level variant of the execute message. For
+    /// example, `transfer` for `{"transfer": {...}}`.
+    pub variant: String,
+}
+
+#[cw_serde]
+pub struct AllowedExecute {
+    pub contract: Addr,
+    pub variant: String,
+}
+
+/// Restrictions on the messages proposals created by a module may
+/// contain. Messages must be one of:
+///
+/// 1. An execute message sent to an allowed contract with an allowed
+///    variant.
+/// 2. A bank send.
+///
+/// The total of each denom sent by the bank sends and execute message
+/// funds of a set of messages which are executed together may not
+/// exceed that denom's bank send cap. Migrating contracts and updating their admins is never
+/// allowed.
+#[cw_serde]
+pub struct UncheckedMessageRestrictions {
+    pub allowed_executes: Vec<UncheckedAllowedExecute>,
+    /// The most of each denom a proposal may send, summed across all
+    /// of the messages which are executed together. Sending exactly the cap is allowed. Denoms
+    /// which are not listed may not be sent.
+    pub bank_send_caps: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct MessageRestrictions {
+    pub allowed_executes: Vec<AllowedExecute>,
+    pub bank_send_caps: Vec<Coin>,
+}
+
+/// A named list of messages with parameter slots that proposers fill
+/// in.
+#[cw_serde]
+pub struct Template {
+    /// The name proposers use to refer to this template.
+    pub name: String,
+    /// A JSON list of `CosmosMsg`s. Each `{{NAME}}` slot is replaced
+    /// with the value of the parameter named NAME. Values are escaped
+    /// as JSON string contents, so slots must appear inside of
+    /// strings. For example, `"amount": "{{amount}}"`.
+    pub msgs: String,
+}
+
+#[cw_serde]
+pub struct TemplateParam {
+    pub name: String,
+    pub value: String,
+}
+
+impl UncheckedMessageRestrictions {
+    pub fn into_checked(self, deps: Deps) -> Result<MessageRestrictions, PreProposeError> {
+        let allowed_executes = self
+            .allowed_executes
+            .into_iter()
+            .map(|UncheckedAllowedExecute { contract, variant }| {
+                Ok(AllowedExecute {
+                    contract: deps.api.addr_validate(&contract)?,
+                    variant,
+                })
+            })
+            .collect::<Result<Vec<_>, PreProposeError>>()?;
+        Ok(MessageRestrictions {
+            allowed_executes,
+            bank_send_caps: self.bank_send_caps,
+        })
+    }
+}
+
+impl MessageRestrictions {
+    /// Returns an error if any of the messages in MSG_SETS are not
+    /// allowed. Message indexes count across all of the sets.
+    pub fn check(&self, msg_sets: &[Vec<&CosmosMsg>]) -> Result<(), PreProposeError> {
+        let mut offset = 0;
+        for msgs in msg_sets {
+            self.check_set(msgs, offset)?;
+            offset += msgs.len();
+        }
+        Ok(())
+    }
+
+    fn check_set(&self, msgs: &[&CosmosMsg], offset: usize) -> Result<(), PreProposeError> {
+        // Caps apply to the set as a whole so that they may not be
+        // avoided by splitting a send across many messages.
+        let mut sent: BTreeMap<String, Uint128> = BTreeMap::new();
+        let mut add_sent = |amount: &[Coin]| -> Result<(), PreProposeError> {
+            for coin in amount {
+                let total = sent.entry(coin.denom.clone()).or_default();
+                *total = total.checked_add(coin.amount).map_err(StdError::overflow)?;
+            }
+            Ok(())
+        };
+        for (index, msg) in msgs.iter().enumerate() {
+            let index = index + offset;
+            match msg {
+                CosmosMsg::Wasm(WasmMsg::Migrate { .. })
+                | CosmosMsg::Wasm(WasmMsg::UpdateAdmin { .. })
+                | CosmosMsg::Wasm(WasmMsg::ClearAdmin { .. }) => {
+                    return Err(PreProposeError::AdminMessage { index })
+                }
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr,
+                    msg,
+                    funds,
+                }) => {
+                    let variant =
+                        execute_variant(msg).ok_or(PreProposeError::MessageNotAllowed { index })?;
+                    if !self.allowed_executes.iter().any(|a| {
+                        a.contract.as_str() == contract_addr.as_str() && a.variant == variant
+                    }) {
+                        return Err(PreProposeError::MessageNotAllowed { index });
+                    }
+                    add_sent(funds)?;
+                }
+                CosmosMsg::Bank(BankMsg::Send { amount, .. }) => add_sent(amount)?,
+                _ => return Err(PreProposeError::MessageNotAllowed { index }),
+            }
+        }
+
+        for (denom, total) in sent {
+            let cap = self
+                .bank_send_caps
+                .iter()
+                .find(|cap| cap.denom == denom)
+                .map(|cap| cap.amount)
+                .unwrap_or_default();
+            if total > cap {
+                return Err(PreProposeError::BankSendOverCap { denom, cap });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Gets the name of the top level variant of an execute message, or
+/// `None` if the message is not a JSON object with a single key.
+fn execute_variant(msg: &Binary) -> Option<String> {
+    let object: BTreeMap<String, IgnoredAny> = from_binary(msg).ok()?;
+    if object.len() != 1 {
+        return None;
+    }
+    object.into_keys().next()
+}
+
+impl Template {
+    /// Fills in the template's slots with PARAMS and parses the
+    /// result.
+    pub fn render(&self, params: &[TemplateParam]) -> Result<Vec<CosmosMsg>, PreProposeError> {
+        let mut rendered = String::with_capacity(self.msgs.len());
+        let mut rest = self.msgs.as_str();
+        while let Some(start) = rest.find("{{") {
+            rendered.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find("}}")
+                .ok_or_else(|| PreProposeError::InvalidTemplate {
+                    name: self.name.clone(),
+                })?
+                + start;
+            let slot = &rest[start + 2..end];
+            let param = params.iter().find(|p| p.name == slot).ok_or_else(|| {
+                PreProposeError::MissingTemplateParam {
+                    name: slot.to_string(),
+                }
+            })?;
+            rendered.push_str(&escape_json_string(&param.value));
+            rest = &rest[end + 2..];
+        }
+        rendered.push_str(rest);
+
+        from_slice(rendered.as_bytes()).map_err(|_| PreProposeError::InvalidTemplate {
+            name: self.name.clone(),
+        })
+    }
+}
+
+fn escape_json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,11 +1,14 @@
 use std::marker::PhantomData;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary, Empty, Uint128};
 use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
 
 use cwd_voting::deposit::CheckedDepositInfo;
 
+use crate::restrictions::{MessageRestrictions, Template};
+
 #[cw_serde]
 pub struct Config {
     /// Information about the deposit required to create a
@@ -15,6 +18,40 @@ pub struct Config {
     /// proposals in the DAO. Otherwise, any address may create a
     /// proposal so long as they pay the deposit.
     pub open_proposal_submission: bool,
+    /// The minimum voting power an address must have to create a
+    /// proposal. None if there is no minimum.
+    #[serde(default)]
+    pub min_voting_power: Option<Uint128>,
+    /// The maximum number of open proposals a proposer may have at
+    /// once, counting drafts and proposals awaiting approval. Proposals
+    /// count while they are open or pending. None if there is no
+    /// maximum.
+    #[serde(default)]
+    pub max_open_proposals: Option<u64>,
+    /// How long a proposer must wait after submitting a proposal
+    /// before submitting another. None if there is no cooldown.
+    #[serde(default)]
+    pub submission_cooldown: Option<Duration>,
+}
+
+/// A proposal whose deposit is being funded by contributions. The
+/// proposal is created once its deposit is fully funded.
+#[cw_serde]
+pub struct Draft {
+    /// The address that created the draft. The proposal is created
+    /// on its behalf.
+    pub proposer: Addr,
+    /// The message sent to the proposal module to create the
+    /// proposal.
+    pub msg: Binary,
+    /// The deposit required to create the proposal. This is the
+    /// deposit configured when the draft was created.
+    pub deposit_info: CheckedDepositInfo,
+    /// The amount of the deposit which has been contributed.
+    pub funded: Uint128,
+    /// The time after which the draft may no longer be funded and
+    /// its contributions may be refunded.
+    pub expiration: Expiration,
 }
 
 pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage> {
@@ -27,6 +64,40 @@ pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposalMess
     pub config: Item<'static, Config>,
     /// Map between proposal IDs and (deposit, proposer) pairs.
     pub deposits: Map<'static, u64, (Option<CheckedDepositInfo>, Addr)>,
+    /// The number of drafts that have been created.
+    pub draft_count: Item<'static, u64>,
+    /// Map between draft IDs and drafts which have not been fully
+    /// funded or expired.
+    pub drafts: Map<'static, u64, Draft>,
+    /// Map between (draft ID, contributor) pairs and the amount the
+    /// contributor has contributed to the draft's deposit.
+    pub draft_contributions: Map<'static, (u64, Addr), Uint128>,
+    /// Map between (proposal ID, contributor) pairs and the amount
+    /// the contributor contributed to the deposit of a proposal
+    /// created from a draft.
+    pub contributions: Map<'static, (u64, Addr), Uint128>,
+    /// The draft whose proposal is being created. Set when a draft is
+    /// fully funded and removed by the proposal created hook.
+    pub pending_draft: Item<'static, u64>,
+    /// Restrictions on the messages proposals may contain. If unset,
+    /// proposals may contain any messages.
+    pub message_restrictions: Item<'static, MessageRestrictions>,
+    /// Map between template names and templates proposers may fill
+    /// in to create proposals.
+    pub templates: Map<'static, String, Template>,
+    /// Map between proposers and the IDs of their proposals which
+    /// were open or pending when last checked. Proposals are added by
+    /// the proposal created hook and removed by the proposal completed
+    /// hook, or when a proposer's open proposals are counted and they
+    /// are found to no longer be open.
+    pub open_proposals: Map<'static, (Addr, u64), Empty>,
+    /// Map between proposers and the number of their submissions which
+    /// have not yet become proposals, for example drafts. These count
+    /// towards their open proposals.
+    pub pending_submissions: Map<'static, Addr, u64>,
+    /// Map between proposers and the time after which they may submit
+    /// another proposal.
+    pub cooldowns: Map<'static, Addr, Expiration>,
 
     // These types are used in associated functions, but not
     // assocaited data. To stop the compiler complaining about unused
@@ -40,17 +111,38 @@ pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposalMess
 impl<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage>
     PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage>
 {
+    #[allow(clippy::too_many_arguments)]
     const fn new(
         proposal_key: &'static str,
         dao_key: &'static str,
         config_key: &'static str,
         deposits_key: &'static str,
+        draft_count_key: &'static str,
+        drafts_key: &'static str,
+        draft_contributions_key: &'static str,
+        contributions_key: &'static str,
+        pending_draft_key: &'static str,
+        message_restrictions_key: &'static str,
+        templates_key: &'static str,
+        open_proposals_key: &'static str,
+        pending_submissions_key: &'static str,
+        cooldowns_key: &'static str,
     ) -> Self {
         Self {
             proposal_module: Item::new(proposal_key),
             dao: Item::new(dao_key),
             config: Item::new(config_key),
             deposits: Map::new(deposits_key),
+            draft_count: Item::new(draft_count_key),
+            drafts: Map::new(drafts_key),
+            draft_contributions: Map::new(draft_contributions_key),
+            contributions: Map::new(contributions_key),
+            pending_draft: Item::new(pending_draft_key),
+            message_restrictions: Item::new(message_restrictions_key),
+            templates: Map::new(templates_key),
+            open_proposals: Map::new(open_proposals_key),
+            pending_submissions: Map::new(pending_submissions_key),
+            cooldowns: Map::new(cooldowns_key),
             execute_type: PhantomData,
             instantiate_type: PhantomData,
             query_type: PhantomData,
@@ -66,6 +158,21 @@ impl<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage> Default
         // Call into constant function here. Presumably, the compiler
         // is clever enough to inline this. This gives us
         // "more-or-less" constant evaluation for our default method.
-        Self::new("proposal_module", "dao", "config", "deposits")
+        Self::new(
+            "proposal_module",
+            "dao",
+            "config",
+            "deposits",
+            "draft_count",
+            "drafts",
+            "draft_contributions",
+            "contributions",
+            "pending_draft",
+            "message_restrictions",
+            "templates",
+            "open_proposals",
+            "pending_submissions",
+            "cooldowns",
+        )
     }
 }
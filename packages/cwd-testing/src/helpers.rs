@@ -1,6 +1,6 @@
-use cosmwasm_std::{to_binary, Addr, Binary, Empty, Uint128};
+use cosmwasm_std::{to_binary, Addr, Api, Binary, Empty, Uint128};
 use cw20::Cw20Coin;
-use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_multi_test::{App, BankKeeper, Contract, ContractWrapper, Executor};
 use cw_utils::Duration;
 use cwd_interface::{Admin, ModuleInstantiateInfo};
 use cwd_voting_cw20_staked::msg::ActiveThreshold;
@@ -284,8 +284,10 @@ pub fn instantiate_with_staking_active_threshold(
     .unwrap()
 }
 
-pub fn instantiate_with_cw4_groups_governance(
-    app: &mut App,
+/// Generic over the app's API so that it may be used with apps like
+/// `signing::SignerApp`.
+pub fn instantiate_with_cw4_groups_governance<A: Api>(
+    app: &mut App<BankKeeper, A>,
     core_code_id: u64,
     proposal_module_instantiate: Binary,
     initial_weights: Option<Vec<Cw20Coin>>,
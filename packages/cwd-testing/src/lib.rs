@@ -7,5 +7,8 @@ pub mod helpers;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod contracts;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod signing;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub use tests::*;
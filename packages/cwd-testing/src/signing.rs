@@ -0,0 +1,177 @@
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{
+    testing::MockApi, Addr, Api, Binary, CanonicalAddr, RecoverPubkeyError, StdError, StdResult,
+    VerificationError,
+};
+use cw_multi_test::{App, AppBuilder, BankKeeper};
+use cwd_voting::signed_ballot::{BallotPubKey, SignedBallot};
+
+/// The prefix of addresses derived from public keys by `SignerApi`.
+const SIGNER_PREFIX: &str = "signer";
+
+/// An app whose API can humanize addresses derived from public keys.
+pub type SignerApp = App<BankKeeper, SignerApi>;
+
+/// A mock API which behaves like `MockApi` except that 20 byte
+/// canonical addresses, like those derived from public keys, are
+/// humanized as "signer" followed by their hex encoding. `MockApi`
+/// can not humanize addresses it did not canonicalize so this is
+/// needed to test signed ballots.
+#[derive(Default)]
+pub struct SignerApi(MockApi);
+
+impl Api for SignerApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        let canonical = self.addr_canonicalize(human)?;
+        let normalized = self.addr_humanize(&canonical)?;
+        if human != normalized {
+            return Err(StdError::generic_err(
+                "Invalid input: address not normalized",
+            ));
+        }
+        Ok(Addr::unchecked(human))
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        match human
+            .strip_prefix(SIGNER_PREFIX)
+            .and_then(|hex| hex::decode(hex).ok())
+        {
+            Some(bytes) if bytes.len() == 20 => Ok(bytes.into()),
+            _ => self.0.addr_canonicalize(human),
+        }
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        if canonical.len() == 20 {
+            Ok(Addr::unchecked(format!(
+                "{}{}",
+                SIGNER_PREFIX,
+                hex::encode(canonical.as_slice())
+            )))
+        } else {
+            self.0.addr_humanize(canonical)
+        }
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.0
+            .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.0
+            .ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.0.debug(message)
+    }
+}
+
+/// Creates an app which uses `SignerApi`.
+pub fn signer_app() -> SignerApp {
+    AppBuilder::new()
+        .with_api(SignerApi::default())
+        .build(|_, _, _| {})
+}
+
+/// A voter who signs ballots off chain.
+pub enum TestSigner {
+    Secp256k1(k256::ecdsa::SigningKey),
+    Ed25519(ed25519_zebra::SigningKey),
+}
+
+impl TestSigner {
+    /// Creates a secp256k1 signer whose secret key is SEED repeated.
+    pub fn secp256k1(seed: u8) -> Self {
+        Self::Secp256k1(k256::ecdsa::SigningKey::from_bytes(&[seed; 32]).unwrap())
+    }
+
+    /// Creates an ed25519 signer whose secret key is SEED repeated.
+    pub fn ed25519(seed: u8) -> Self {
+        Self::Ed25519(ed25519_zebra::SigningKey::from([seed; 32]))
+    }
+
+    pub fn pub_key(&self) -> BallotPubKey {
+        match self {
+            TestSigner::Secp256k1(key) => BallotPubKey::Secp256k1(Binary::from(
+                key.verifying_key().to_encoded_point(true).as_bytes(),
+            )),
+            TestSigner::Ed25519(key) => BallotPubKey::Ed25519(Binary::from(
+                <[u8; 32]>::from(ed25519_zebra::VerificationKey::from(key)).as_slice(),
+            )),
+        }
+    }
+
+    /// The signer's address as humanized by `SignerApi`.
+    pub fn address(&self) -> String {
+        let canonical = self.pub_key().canonical_address().unwrap();
+        SignerApi::default()
+            .addr_humanize(&canonical)
+            .unwrap()
+            .into_string()
+    }
+
+    /// Signs a ballot for submission to PROPOSAL_MODULE on the chain
+    /// with CHAIN_ID.
+    pub fn sign<V: Serialize + Clone>(
+        &self,
+        chain_id: &str,
+        proposal_module: &Addr,
+        proposal_id: u64,
+        vote: V,
+        nonce: u64,
+    ) -> SignedBallot<V> {
+        let mut ballot = SignedBallot {
+            proposal_id,
+            vote,
+            nonce,
+            pub_key: self.pub_key(),
+            signature: Binary::default(),
+        };
+        let sign_bytes = ballot
+            .sign_doc(chain_id, proposal_module)
+            .sign_bytes()
+            .unwrap();
+        ballot.signature = match self {
+            TestSigner::Secp256k1(key) => {
+                use k256::ecdsa::signature::Signer;
+                let signature: k256::ecdsa::Signature = key.sign(&sign_bytes);
+                Binary::from(signature.as_ref())
+            }
+            TestSigner::Ed25519(key) => {
+                Binary::from(<[u8; 64]>::from(key.sign(&sign_bytes)).as_slice())
+            }
+        };
+        ballot
+    }
+}
@@ -0,0 +1,158 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{BlockInfo, StdResult};
+use cw_utils::{Duration, Expiration};
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AntiSnipingError {
+    #[error("The anti-sniping window must be non-zero")]
+    ZeroWindow {},
+
+    #[error("The anti-sniping extension must be non-zero")]
+    ZeroExtension {},
+
+    #[error("The anti-sniping window and extension must have the same units (height or time) as max_voting_period")]
+    DurationUnitsConflict {},
+}
+
+/// Configuration for extending the voting period of proposals whose
+/// outcome is changed by a vote cast shortly before they expire. This
+/// gives voters time to respond to votes cast in the final moments of
+/// a proposal's voting period.
+#[cw_serde]
+pub struct AntiSnipingConfig {
+    /// Votes cast within this amount of time before a proposal
+    /// expires which change its outcome extend its voting period.
+    pub window: Duration,
+    /// The amount of time a proposal's voting period is extended by.
+    pub extension: Duration,
+    /// The maximum number of times a proposal's voting period may be
+    /// extended.
+    pub max_extensions: u64,
+}
+
+/// The anti-sniping configuration of a proposal and the number of
+/// times its voting period has been extended.
+#[cw_serde]
+pub struct AntiSnipingState {
+    pub config: AntiSnipingConfig,
+    pub extensions: u64,
+}
+
+impl AntiSnipingConfig {
+    /// Validates that the window and extension are non-zero and
+    /// measured in the same units as MAX_VOTING_PERIOD.
+    pub fn validate(&self, max_voting_period: &Duration) -> Result<(), AntiSnipingError> {
+        if matches!(self.window, Duration::Height(0) | Duration::Time(0)) {
+            return Err(AntiSnipingError::ZeroWindow {});
+        }
+        if matches!(self.extension, Duration::Height(0) | Duration::Time(0)) {
+            return Err(AntiSnipingError::ZeroExtension {});
+        }
+        match (self.window, self.extension, max_voting_period) {
+            (Duration::Height(_), Duration::Height(_), Duration::Height(_))
+            | (Duration::Time(_), Duration::Time(_), Duration::Time(_)) => Ok(()),
+            _ => Err(AntiSnipingError::DurationUnitsConflict {}),
+        }
+    }
+
+    /// Returns true if BLOCK is within the window before EXPIRATION.
+    pub fn is_in_window(&self, expiration: &Expiration, block: &BlockInfo) -> bool {
+        !expiration.is_expired(block) && *expiration <= self.window.after(block)
+    }
+}
+
+impl AntiSnipingState {
+    pub fn new(config: AntiSnipingConfig) -> Self {
+        Self {
+            config,
+            extensions: 0,
+        }
+    }
+
+    /// Called when a vote cast at BLOCK changes the outcome of a
+    /// proposal which expires at EXPIRATION. If the vote was cast
+    /// within the window before expiration and the proposal has
+    /// extensions remaining, records the extension and returns the
+    /// proposal's new expiration.
+    pub fn extend(
+        &mut self,
+        expiration: Expiration,
+        block: &BlockInfo,
+    ) -> StdResult<Option<Expiration>> {
+        if self.extensions >= self.config.max_extensions
+            || !self.config.is_in_window(&expiration, block)
+        {
+            return Ok(None);
+        }
+        self.extensions += 1;
+        (expiration + self.config.extension).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+
+    use super::*;
+
+    fn config(window: Duration, extension: Duration) -> AntiSnipingConfig {
+        AntiSnipingConfig {
+            window,
+            extension,
+            max_extensions: 2,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(
+            config(Duration::Height(0), Duration::Height(5)).validate(&Duration::Height(10)),
+            Err(AntiSnipingError::ZeroWindow {})
+        );
+        assert_eq!(
+            config(Duration::Height(5), Duration::Height(0)).validate(&Duration::Height(10)),
+            Err(AntiSnipingError::ZeroExtension {})
+        );
+        assert_eq!(
+            config(Duration::Height(5), Duration::Time(5)).validate(&Duration::Height(10)),
+            Err(AntiSnipingError::DurationUnitsConflict {})
+        );
+        assert_eq!(
+            config(Duration::Time(5), Duration::Time(5)).validate(&Duration::Height(10)),
+            Err(AntiSnipingError::DurationUnitsConflict {})
+        );
+        assert_eq!(
+            config(Duration::Time(5), Duration::Time(5)).validate(&Duration::Time(10)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut block = mock_env().block;
+        let expiration = Expiration::AtHeight(block.height + 10);
+        let mut state = AntiSnipingState::new(config(Duration::Height(5), Duration::Height(3)));
+
+        // Votes before the window do not extend the voting period.
+        assert_eq!(state.extend(expiration, &block).unwrap(), None);
+
+        block.height += 5;
+        let expiration = state.extend(expiration, &block).unwrap().unwrap();
+        assert_eq!(expiration, Expiration::AtHeight(block.height + 8));
+
+        block.height += 7;
+        let expiration = state.extend(expiration, &block).unwrap().unwrap();
+        assert_eq!(expiration, Expiration::AtHeight(block.height + 4));
+        assert_eq!(state.extensions, 2);
+
+        // No extensions remain.
+        assert_eq!(state.extend(expiration, &block).unwrap(), None);
+
+        // Expired proposals are never extended.
+        let mut state = AntiSnipingState::new(config(Duration::Height(5), Duration::Height(3)));
+        block.height += 4;
+        assert_eq!(state.extend(expiration, &block).unwrap(), None);
+    }
+}
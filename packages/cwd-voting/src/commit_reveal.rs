@@ -0,0 +1,111 @@
+use cosmwasm_schema::{cw_serde, serde::Serialize};
+use cosmwasm_std::{to_vec, Addr, Binary, StdResult};
+use cw_utils::{Duration, Expiration};
+use sha2::{Digest, Sha256};
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CommitRevealError {
+    #[error("The reveal period must be non-zero")]
+    ZeroRevealPeriod {},
+
+    #[error("The reveal period and max_voting_period must have the same units (height or time)")]
+    DurationUnitsConflict {},
+}
+
+/// Configuration for commit-reveal voting. When enabled, voters first
+/// commit to a hash of their vote during the proposal's voting period
+/// and then reveal it during a reveal period which follows. Only
+/// revealed votes are counted, so running tallies remain hidden until
+/// the voting period ends.
+#[cw_serde]
+pub struct CommitRevealConfig {
+    /// The amount of time after a proposal's commit phase during
+    /// which votes may be revealed. Must have the same units as the
+    /// proposal module's `max_voting_period`.
+    pub reveal_period: Duration,
+}
+
+impl CommitRevealConfig {
+    /// Validates that the reveal period is non-zero and measured in
+    /// the same units as MAX_VOTING_PERIOD.
+    pub fn validate(&self, max_voting_period: &Duration) -> Result<(), CommitRevealError> {
+        match (self.reveal_period, max_voting_period) {
+            (Duration::Height(0), _) | (Duration::Time(0), _) => {
+                Err(CommitRevealError::ZeroRevealPeriod {})
+            }
+            (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_)) => {
+                Ok(())
+            }
+            _ => Err(CommitRevealError::DurationUnitsConflict {}),
+        }
+    }
+
+    /// Computes when the reveal phase of a proposal whose commit
+    /// phase ends at COMMIT_EXPIRATION ends.
+    pub fn reveal_end(&self, commit_expiration: Expiration) -> StdResult<Expiration> {
+        commit_expiration + self.reveal_period
+    }
+}
+
+/// Computes the commitment for VOTER voting VOTE with SALT. This is
+/// the SHA-256 hash of the voter's address, the JSON encoding of the
+/// vote and the salt concatenated together. Including the voter's
+/// address prevents other voters from copying a commitment and
+/// revealing the same vote once it has been revealed.
+pub fn commitment_hash<T: Serialize>(voter: &Addr, vote: &T, salt: &str) -> StdResult<Binary> {
+    let mut hasher = Sha256::new();
+    hasher.update(voter.as_bytes());
+    hasher.update(to_vec(vote)?);
+    hasher.update(salt.as_bytes());
+    Ok(Binary::from(hasher.finalize().as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voting::Vote;
+
+    #[test]
+    fn test_validate() {
+        let config = |reveal_period| CommitRevealConfig { reveal_period };
+
+        assert_eq!(
+            config(Duration::Height(0)).validate(&Duration::Height(10)),
+            Err(CommitRevealError::ZeroRevealPeriod {})
+        );
+        assert_eq!(
+            config(Duration::Time(10)).validate(&Duration::Height(10)),
+            Err(CommitRevealError::DurationUnitsConflict {})
+        );
+        assert_eq!(
+            config(Duration::Height(5)).validate(&Duration::Height(10)),
+            Ok(())
+        );
+        assert_eq!(
+            config(Duration::Height(5))
+                .reveal_end(Expiration::AtHeight(10))
+                .unwrap(),
+            Expiration::AtHeight(15)
+        );
+    }
+
+    #[test]
+    fn test_commitment_hash() {
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        let yes = commitment_hash(&alice, &Vote::Yes, "salt").unwrap();
+        assert_eq!(yes, commitment_hash(&alice, &Vote::Yes, "salt").unwrap());
+        assert_ne!(yes, commitment_hash(&alice, &Vote::No, "salt").unwrap());
+        assert_ne!(yes, commitment_hash(&alice, &Vote::Yes, "pepper").unwrap());
+        assert_ne!(yes, commitment_hash(&bob, &Vote::Yes, "salt").unwrap());
+
+        // The hash is over the JSON encoding of the vote so that it
+        // may be computed off chain.
+        let mut hasher = Sha256::new();
+        hasher.update(b"alice\"yes\"salt");
+        assert_eq!(yes, Binary::from(hasher.finalize().as_slice()));
+    }
+}
@@ -0,0 +1,98 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Deps, StdResult, Uint128};
+
+use crate::voting::get_voting_power;
+
+/// Queries that a delegation registry must support in order to be
+/// used by a proposal module. Delegation registries may support
+/// additional queries, but these must be present with these response
+/// types.
+#[cw_serde]
+pub enum Query {
+    /// Returns the address that DELEGATOR had delegated their voting
+    /// power to at HEIGHT. A height of None returns the current
+    /// delegate.
+    Delegate {
+        delegator: String,
+        height: Option<u64>,
+    },
+    /// Returns the addresses that had delegated their voting power to
+    /// DELEGATE at HEIGHT. A height of None returns the current
+    /// delegators.
+    Delegators {
+        delegate: String,
+        height: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub struct DelegateResponse {
+    pub delegate: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct DelegatorsResponse {
+    pub delegators: Vec<Addr>,
+}
+
+/// Voting power that has been delegated to an address by a single
+/// delegator.
+#[cw_serde]
+pub struct Delegation {
+    pub delegator: Addr,
+    pub power: Uint128,
+}
+
+/// Gets the address that DELEGATOR had delegated their voting power
+/// to at HEIGHT according to REGISTRY. A height of None will query
+/// for the current block height.
+pub fn get_delegate(
+    deps: Deps,
+    registry: &Addr,
+    delegator: &Addr,
+    height: Option<u64>,
+) -> StdResult<Option<Addr>> {
+    let response: DelegateResponse = deps.querier.query_wasm_smart(
+        registry,
+        &Query::Delegate {
+            delegator: delegator.to_string(),
+            height,
+        },
+    )?;
+    Ok(response.delegate)
+}
+
+/// Gets the voting power delegated to DELEGATE at HEIGHT according to
+/// REGISTRY. Voting power is queried from DAO's voting module at the
+/// same height the delegations are loaded at. Delegators with no
+/// voting power at HEIGHT are omitted.
+///
+/// Delegation is not transitive. If A delegates to B and B delegates
+/// to C, C receives B's voting power but not A's.
+///
+/// This makes one voting power query per delegator. Delegation
+/// registries should bound the number of delegators that an address
+/// may have to avoid votes running out of gas.
+pub fn get_delegations(
+    deps: Deps,
+    registry: &Addr,
+    dao: &Addr,
+    delegate: &Addr,
+    height: Option<u64>,
+) -> StdResult<Vec<Delegation>> {
+    let response: DelegatorsResponse = deps.querier.query_wasm_smart(
+        registry,
+        &Query::Delegators {
+            delegate: delegate.to_string(),
+            height,
+        },
+    )?;
+    let mut delegations = vec![];
+    for delegator in response.delegators {
+        let power = get_voting_power(deps, delegator.clone(), dao.clone(), height)?;
+        if !power.is_zero() {
+            delegations.push(Delegation { delegator, power });
+        }
+    }
+    Ok(delegations)
+}
@@ -0,0 +1,105 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Deps, StdError};
+use serde::Deserialize;
+
+use thiserror::Error;
+
+use crate::status::Status;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DependencyError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error("({address}) is not a proposal module of this DAO")]
+    NotProposalModule { address: Addr },
+
+    #[error("proposal {proposal_id} does not exist in ({proposal_module})")]
+    NoSuchDependency {
+        proposal_module: Addr,
+        proposal_id: u64,
+    },
+}
+
+/// A proposal that must be executed before the proposal which
+/// depends on it may be executed. The proposal may have been created
+/// in any of the DAO's proposal modules.
+#[cw_serde]
+pub struct UncheckedProposalDependency {
+    /// The address of the proposal module the proposal was created
+    /// in.
+    pub proposal_module: String,
+    /// The ID of the proposal in that module.
+    pub proposal_id: u64,
+}
+
+/// A validated `UncheckedProposalDependency`.
+#[cw_serde]
+pub struct ProposalDependency {
+    pub proposal_module: Addr,
+    pub proposal_id: u64,
+}
+
+/// The query all of a DAO's proposal modules respond to with the
+/// proposal's current status.
+#[cw_serde]
+enum ProposalQuery {
+    Proposal { proposal_id: u64 },
+}
+
+/// The parts of a proposal module's `Proposal` query response needed
+/// to check dependencies. Other fields are ignored so that this works
+/// for any proposal module.
+#[derive(Deserialize)]
+struct ProposalStatusResponse {
+    proposal: ProposalStatus,
+}
+
+#[derive(Deserialize)]
+struct ProposalStatus {
+    status: Status,
+}
+
+impl UncheckedProposalDependency {
+    /// Validates that the dependency's proposal module is a proposal
+    /// module of DAO. Does not check that the proposal exists.
+    pub fn into_checked(
+        self,
+        deps: Deps,
+        dao: &Addr,
+    ) -> Result<ProposalDependency, DependencyError> {
+        let proposal_module = deps.api.addr_validate(&self.proposal_module)?;
+        if cwd_core::state::PROPOSAL_MODULES
+            .query(&deps.querier, dao.clone(), proposal_module.clone())?
+            .is_none()
+        {
+            return Err(DependencyError::NotProposalModule {
+                address: proposal_module,
+            });
+        }
+        Ok(ProposalDependency {
+            proposal_module,
+            proposal_id: self.proposal_id,
+        })
+    }
+}
+
+impl ProposalDependency {
+    /// Queries the proposal's current status from its proposal
+    /// module.
+    pub fn query_status(&self, deps: Deps) -> Result<Status, DependencyError> {
+        let response: ProposalStatusResponse = deps
+            .querier
+            .query_wasm_smart(
+                &self.proposal_module,
+                &ProposalQuery::Proposal {
+                    proposal_id: self.proposal_id,
+                },
+            )
+            .map_err(|_| DependencyError::NoSuchDependency {
+                proposal_module: self.proposal_module.clone(),
+                proposal_id: self.proposal_id,
+            })?;
+        Ok(response.proposal.status)
+    }
+}
@@ -1,8 +1,10 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    to_binary, Addr, CosmosMsg, Deps, MessageInfo, StdError, StdResult, Uint128, WasmMsg,
+    coins, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, MessageInfo, StdError, StdResult,
+    Uint128, WasmMsg,
 };
-use cw_utils::{must_pay, PaymentError};
+use cw_utils::{must_pay, nonpayable, Duration, PaymentError};
+use serde::Deserialize;
 
 use thiserror::Error;
 
@@ -25,6 +27,18 @@ pub enum DepositError {
 
     #[error("invalid deposit amount. got ({actual}), expected ({expected})")]
     InvalidDeposit { actual: Uint128, expected: Uint128 },
+
+    #[error("cw20 deposits may not be sent to the community pool")]
+    Cw20CommunityPool,
+
+    #[error("invalid zero funding period. set the funding period to `None` to disable drafts")]
+    ZeroFundingPeriod,
+
+    #[error("staking contract ({contract}) has neither a token address nor a denom")]
+    InvalidStakingContract { contract: Addr },
+
+    #[error("staked deposits may only be sent to the DAO when vetoed")]
+    StakedVetoDestination,
 }
 
 /// Information about the token to use for proposal deposits.
@@ -39,6 +53,49 @@ pub enum DepositToken {
     /// `cwd_macros::token_query`. Failing to implement that
     /// and using this option will cause instantiation to fail.
     VotingModuleToken {},
+    /// Lock the proposer's tokens staked in a staking contract
+    /// instead of transferring a deposit. Locked tokens can not be
+    /// unstaked until the proposal completes, at which point they are
+    /// unlocked or slashed according to the refund policy. The
+    /// staking contract must be a `cw20-stake` or
+    /// `cwd-voting-native-staked` contract which has registered the
+    /// pre-propose module as a locker. For `cw20-stake`, the deposit
+    /// amount is in units of staked balance.
+    StakedToken { staking_contract: String },
+}
+
+/// Messages staking contracts accept from the contracts they have
+/// registered as lockers. Implemented by `cw20-stake` and
+/// `cwd-voting-native-staked`.
+#[cw_serde]
+pub enum StakeLockMsg {
+    /// Locks AMOUNT of ADDRESS's stake so that it may not be
+    /// unstaked.
+    LockStake { address: String, amount: Uint128 },
+    /// Unlocks AMOUNT of the stake the sender has locked for ADDRESS.
+    UnlockStake { address: String, amount: Uint128 },
+    /// Unstakes AMOUNT of the stake the sender has locked for ADDRESS
+    /// and sends it to RECIPIENT.
+    SlashStake {
+        address: String,
+        amount: Uint128,
+        recipient: String,
+    },
+}
+
+/// The `GetConfig {}` query of the supported staking contracts.
+#[cw_serde]
+enum StakingQueryMsg {
+    GetConfig {},
+}
+
+/// The parts of the supported staking contracts' configs needed to
+/// find their token. `cw20-stake` has a token address and
+/// `cwd-voting-native-staked` has a denom.
+#[derive(Deserialize)]
+struct StakingConfig {
+    token_address: Option<String>,
+    denom: Option<String>,
 }
 
 /// Information about the deposit required to create a proposal.
@@ -51,6 +108,21 @@ pub struct UncheckedDepositInfo {
     pub amount: Uint128,
     /// The policy used for refunding deposits on proposal completion.
     pub refund_policy: DepositRefundPolicy,
+    /// The policy used for refunding the deposits of proposals which
+    /// are withdrawn by their proposer. Withdrawn proposals are
+    /// treated as closed ones, so their deposits are only refunded
+    /// under `Always`. If `None`, `refund_policy` is used.
+    pub withdrawal_refund_policy: Option<DepositRefundPolicy>,
+    /// Where the deposits of proposals rejected by NoWithVeto votes
+    /// are sent. These deposits are never refunded. If `None`, they
+    /// are sent to the DAO.
+    pub veto_destination: Option<VetoDepositDestination>,
+    /// If set, proposers may submit drafts with less than the full
+    /// deposit which other addresses may top up. Proposals are
+    /// created once their deposit is fully funded. Drafts which are
+    /// not fully funded within this amount of time expire and their
+    /// contributions are refunded. If `None`, drafts are disabled.
+    pub funding_period: Option<Duration>,
 }
 
 #[cw_serde]
@@ -63,6 +135,19 @@ pub enum DepositRefundPolicy {
     Never,
 }
 
+/// Where the deposit of a proposal rejected by NoWithVeto votes is
+/// sent.
+#[cw_serde]
+pub enum VetoDepositDestination {
+    /// The deposit is burned.
+    Burn {},
+    /// The deposit is sent to the chain's community pool. Only
+    /// native deposits may be sent to the community pool.
+    CommunityPool {},
+    /// The deposit is sent to the DAO.
+    Dao {},
+}
+
 /// Counterpart to the `DepositInfo` struct which has been
 /// processed. This type should never be constructed literally and
 /// should always by built by calling `into_checked` on a
@@ -79,6 +164,19 @@ pub struct CheckedDepositInfo {
     pub amount: Uint128,
     /// The policy used for refunding proposal deposits.
     pub refund_policy: DepositRefundPolicy,
+    /// The policy used for refunding the deposits of withdrawn
+    /// proposals, if it differs from `refund_policy`.
+    pub withdrawal_refund_policy: Option<DepositRefundPolicy>,
+    /// Where the deposits of proposals rejected by NoWithVeto votes
+    /// are sent. `None` is the DAO.
+    pub veto_destination: Option<VetoDepositDestination>,
+    /// The amount of time drafts have to fund their deposit, if
+    /// drafts are enabled.
+    pub funding_period: Option<Duration>,
+    /// If set, deposits are stake locked in this staking contract
+    /// rather than tokens transferred to the module. `denom` is the
+    /// staked token.
+    pub staking_contract: Option<Addr>,
 }
 
 impl UncheckedDepositInfo {
@@ -88,6 +186,9 @@ impl UncheckedDepositInfo {
             denom,
             amount,
             refund_policy,
+            withdrawal_refund_policy,
+            veto_destination,
+            funding_period,
         } = self;
         // Check that the deposit is non-zero. Modules should make
         // deposit information optional and consumers should provide
@@ -95,7 +196,14 @@ impl UncheckedDepositInfo {
         if amount.is_zero() {
             return Err(DepositError::ZeroDeposit);
         }
+        if matches!(
+            funding_period,
+            Some(Duration::Height(0)) | Some(Duration::Time(0))
+        ) {
+            return Err(DepositError::ZeroFundingPeriod);
+        }
 
+        let mut staking_contract = None;
         let denom = match denom {
             DepositToken::Token { denom } => denom.into_checked(deps),
             DepositToken::VotingModuleToken {} => {
@@ -114,18 +222,69 @@ impl UncheckedDepositInfo {
                 // query.
                 UncheckedDenom::Cw20(token_addr.into_string()).into_checked(deps)
             }
+            DepositToken::StakedToken {
+                staking_contract: contract,
+            } => {
+                let contract = deps.api.addr_validate(&contract)?;
+                let config: StakingConfig = deps
+                    .querier
+                    .query_wasm_smart(contract.clone(), &StakingQueryMsg::GetConfig {})?;
+                let denom = match config {
+                    StakingConfig {
+                        token_address: Some(token_address),
+                        ..
+                    } => UncheckedDenom::Cw20(token_address),
+                    StakingConfig {
+                        denom: Some(denom), ..
+                    } => UncheckedDenom::Native(denom),
+                    _ => return Err(DepositError::InvalidStakingContract { contract }),
+                };
+                staking_contract = Some(contract);
+                denom.into_checked(deps)
+            }
         }?;
 
+        // Slashed stake is sent directly to its recipient, so there
+        // is no way to burn it or send it to the community pool.
+        if staking_contract.is_some()
+            && !matches!(
+                veto_destination,
+                Some(VetoDepositDestination::Dao {}) | None
+            )
+        {
+            return Err(DepositError::StakedVetoDestination);
+        }
+
+        // There is no way to send cw20 tokens to the community pool.
+        if matches!(
+            (&denom, &veto_destination),
+            (
+                CheckedDenom::Cw20(_),
+                Some(VetoDepositDestination::CommunityPool {})
+            )
+        ) {
+            return Err(DepositError::Cw20CommunityPool);
+        }
+
         Ok(CheckedDepositInfo {
             denom,
             amount,
             refund_policy,
+            withdrawal_refund_policy,
+            veto_destination,
+            funding_period,
+            staking_contract,
         })
     }
 }
 
 impl CheckedDepositInfo {
     pub fn check_native_deposit_paid(&self, info: &MessageInfo) -> Result<(), DepositError> {
+        if self.staking_contract.is_some() {
+            // Staked deposits are locked, not paid.
+            nonpayable(info)?;
+            return Ok(());
+        }
         if let Self {
             amount,
             denom: CheckedDenom::Native(denom),
@@ -156,6 +315,15 @@ impl CheckedDepositInfo {
         depositor: &Addr,
         contract: &Addr,
     ) -> StdResult<Vec<CosmosMsg>> {
+        if let Some(ref staking_contract) = self.staking_contract {
+            return self.get_stake_lock_messages(
+                staking_contract,
+                StakeLockMsg::LockStake {
+                    address: depositor.to_string(),
+                    amount: self.amount,
+                },
+            );
+        }
         let take_deposit_msg: Vec<CosmosMsg> = if let Self {
             amount,
             denom: CheckedDenom::Cw20(address),
@@ -186,7 +354,24 @@ impl CheckedDepositInfo {
         Ok(take_deposit_msg)
     }
 
+    /// Returns the policy used for refunding the deposit of a
+    /// withdrawn proposal.
+    pub fn withdrawal_refund_policy(&self) -> DepositRefundPolicy {
+        self.withdrawal_refund_policy
+            .clone()
+            .unwrap_or_else(|| self.refund_policy.clone())
+    }
+
     pub fn get_return_deposit_message(&self, depositor: &Addr) -> StdResult<Vec<CosmosMsg>> {
+        if let Some(ref staking_contract) = self.staking_contract {
+            return self.get_stake_lock_messages(
+                staking_contract,
+                StakeLockMsg::UnlockStake {
+                    address: depositor.to_string(),
+                    amount: self.amount,
+                },
+            );
+        }
         // Should get caught in `into_checked()`, but to be pedantic.
         if self.amount.is_zero() {
             return Ok(vec![]);
@@ -194,6 +379,121 @@ impl CheckedDepositInfo {
         let message = self.denom.get_transfer_to_message(depositor, self.amount)?;
         Ok(vec![message])
     }
+
+    /// Returns messages sending the deposit of DEPOSITOR to the DAO
+    /// when it is not refunded.
+    pub fn get_forfeit_deposit_messages(
+        &self,
+        depositor: &Addr,
+        dao: &Addr,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        match self.staking_contract {
+            Some(ref staking_contract) => self.get_stake_lock_messages(
+                staking_contract,
+                StakeLockMsg::SlashStake {
+                    address: depositor.to_string(),
+                    amount: self.amount,
+                    recipient: dao.to_string(),
+                },
+            ),
+            None => self.get_return_deposit_message(dao),
+        }
+    }
+
+    /// Returns messages sending the deposit of DEPOSITOR for a
+    /// proposal rejected by NoWithVeto votes to its veto
+    /// destination. CONTRACT is the address holding the deposit.
+    pub fn get_veto_deposit_messages(
+        &self,
+        depositor: &Addr,
+        dao: &Addr,
+        contract: &Addr,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        if self.staking_contract.is_some() {
+            // `into_checked()` only allows staked deposits to be sent
+            // to the DAO.
+            return self.get_forfeit_deposit_messages(depositor, dao);
+        }
+        if self.amount.is_zero() {
+            return Ok(vec![]);
+        }
+        let message = match (&self.veto_destination, &self.denom) {
+            (Some(VetoDepositDestination::Burn {}), CheckedDenom::Native(denom)) => BankMsg::Burn {
+                amount: coins(self.amount.u128(), denom),
+            }
+            .into(),
+            (Some(VetoDepositDestination::Burn {}), CheckedDenom::Cw20(address)) => {
+                WasmMsg::Execute {
+                    contract_addr: address.to_string(),
+                    msg: to_binary(&cw20::Cw20ExecuteMsg::Burn {
+                        amount: self.amount,
+                    })?,
+                    funds: vec![],
+                }
+                .into()
+            }
+            (Some(VetoDepositDestination::CommunityPool {}), CheckedDenom::Native(denom)) => {
+                CosmosMsg::Stargate {
+                    type_url: "/cosmos.distribution.v1beta1.MsgFundCommunityPool".to_string(),
+                    value: encode_fund_community_pool(denom, self.amount, contract),
+                }
+            }
+            // Caught in `into_checked()`.
+            (Some(VetoDepositDestination::CommunityPool {}), CheckedDenom::Cw20(_)) => {
+                return Err(StdError::generic_err(
+                    DepositError::Cw20CommunityPool.to_string(),
+                ))
+            }
+            (Some(VetoDepositDestination::Dao {}) | None, _) => {
+                self.denom.get_transfer_to_message(dao, self.amount)?
+            }
+        };
+        Ok(vec![message])
+    }
+
+    fn get_stake_lock_messages(
+        &self,
+        staking_contract: &Addr,
+        msg: StakeLockMsg,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        if self.amount.is_zero() {
+            return Ok(vec![]);
+        }
+        Ok(vec![WasmMsg::Execute {
+            contract_addr: staking_contract.to_string(),
+            msg: to_binary(&msg)?,
+            funds: vec![],
+        }
+        .into()])
+    }
+}
+
+/// Encodes a `cosmos.distribution.v1beta1.MsgFundCommunityPool`
+/// which sends AMOUNT of DENOM from DEPOSITOR to the community
+/// pool. The message has a repeated `Coin` field, `amount`, and a
+/// string field, `depositor`. `Coin`s have the string fields `denom`
+/// and `amount`.
+fn encode_fund_community_pool(denom: &str, amount: Uint128, depositor: &Addr) -> Binary {
+    let mut coin = vec![];
+    encode_bytes_field(&mut coin, 1, denom.as_bytes());
+    encode_bytes_field(&mut coin, 2, amount.to_string().as_bytes());
+
+    let mut msg = vec![];
+    encode_bytes_field(&mut msg, 1, &coin);
+    encode_bytes_field(&mut msg, 2, depositor.as_bytes());
+    Binary(msg)
+}
+
+/// Appends a length delimited protobuf field to BUF.
+fn encode_bytes_field(buf: &mut Vec<u8>, field: u8, bytes: &[u8]) {
+    buf.push((field << 3) | 2);
+    let mut len = bytes.len();
+    while len >= 0x80 {
+        buf.push(((len & 0x7f) as u8) | 0x80);
+        len >>= 7;
+    }
+    buf.push(len as u8);
+    buf.extend_from_slice(bytes);
 }
 
 #[cfg(test)]
@@ -212,6 +512,10 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         };
         deposit_info.check_native_deposit_paid(&info).unwrap();
 
@@ -234,6 +538,10 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         };
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
         assert_eq!(
@@ -252,6 +560,10 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         };
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
         assert_eq!(
@@ -272,6 +584,10 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         };
 
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
@@ -285,6 +601,10 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         };
         let err = deposit_info.check_native_deposit_paid(&info).unwrap_err();
         assert_eq!(err, DepositError::Payment(PaymentError::NoFunds {}));
@@ -297,6 +617,10 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         };
         let messages = deposit_info
             .get_take_deposit_messages(&Addr::unchecked("ekez"), &Addr::unchecked(CW20))
@@ -337,6 +661,10 @@ pub mod tests {
             denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         };
         let messages = deposit_info
             .get_return_deposit_message(&Addr::unchecked("ekez"))
@@ -363,6 +691,10 @@ pub mod tests {
             denom: CheckedDenom::Cw20(Addr::unchecked(CW20)),
             amount: Uint128::new(10),
             refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
         };
         let messages = deposit_info
             .get_return_deposit_message(&Addr::unchecked("ekez"))
@@ -387,4 +719,151 @@ pub mod tests {
             .unwrap();
         assert_eq!(messages, vec![]);
     }
+
+    #[test]
+    fn test_get_veto_deposit_messages() {
+        let depositor = Addr::unchecked("ekez");
+        let dao = Addr::unchecked("dao");
+        let contract = Addr::unchecked("contract");
+        let mut deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: None,
+        };
+
+        // Deposits are sent to the DAO by default.
+        let messages = deposit_info
+            .get_veto_deposit_messages(&depositor, &dao, &contract)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dao".to_string(),
+                amount: coins(10, NATIVE_DENOM)
+            })]
+        );
+
+        deposit_info.veto_destination = Some(VetoDepositDestination::Burn {});
+        let messages = deposit_info
+            .get_veto_deposit_messages(&depositor, &dao, &contract)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Bank(BankMsg::Burn {
+                amount: coins(10, NATIVE_DENOM)
+            })]
+        );
+
+        deposit_info.veto_destination = Some(VetoDepositDestination::CommunityPool {});
+        let messages = deposit_info
+            .get_veto_deposit_messages(&depositor, &dao, &contract)
+            .unwrap();
+        let mut expected = vec![0x0a, 11, 0x0a, 5];
+        expected.extend_from_slice(b"uekez");
+        expected.extend_from_slice(&[0x12, 2]);
+        expected.extend_from_slice(b"10");
+        expected.extend_from_slice(&[0x12, 8]);
+        expected.extend_from_slice(b"contract");
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Stargate {
+                type_url: "/cosmos.distribution.v1beta1.MsgFundCommunityPool".to_string(),
+                value: Binary(expected),
+            }]
+        );
+
+        deposit_info.denom = CheckedDenom::Cw20(Addr::unchecked(CW20));
+        deposit_info.veto_destination = Some(VetoDepositDestination::Burn {});
+        let messages = deposit_info
+            .get_veto_deposit_messages(&depositor, &dao, &contract)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: CW20.to_string(),
+                msg: to_binary(&cw20::Cw20ExecuteMsg::Burn {
+                    amount: Uint128::new(10)
+                })
+                .unwrap(),
+                funds: vec![]
+            })]
+        );
+
+        // Don't fire a message if there is nothing to send!
+        deposit_info.amount = Uint128::zero();
+        let messages = deposit_info
+            .get_veto_deposit_messages(&depositor, &dao, &contract)
+            .unwrap();
+        assert_eq!(messages, vec![]);
+    }
+
+    #[test]
+    fn test_stake_lock_messages() {
+        let depositor = Addr::unchecked("ekez");
+        let dao = Addr::unchecked("dao");
+        let contract = Addr::unchecked("contract");
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+            withdrawal_refund_policy: None,
+            veto_destination: None,
+            funding_period: None,
+            staking_contract: Some(Addr::unchecked("staking")),
+        };
+        let stake_lock_message = |msg: StakeLockMsg| {
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking".to_string(),
+                msg: to_binary(&msg).unwrap(),
+                funds: vec![],
+            })]
+        };
+
+        // Staked deposits are locked, not paid.
+        deposit_info
+            .check_native_deposit_paid(&mock_info("ekez", &[]))
+            .unwrap();
+        let err = deposit_info
+            .check_native_deposit_paid(&mock_info("ekez", &coins(10, NATIVE_DENOM)))
+            .unwrap_err();
+        assert_eq!(err, DepositError::Payment(PaymentError::NonPayable {}));
+
+        let messages = deposit_info
+            .get_take_deposit_messages(&depositor, &contract)
+            .unwrap();
+        assert_eq!(
+            messages,
+            stake_lock_message(StakeLockMsg::LockStake {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10)
+            })
+        );
+
+        let messages = deposit_info.get_return_deposit_message(&depositor).unwrap();
+        assert_eq!(
+            messages,
+            stake_lock_message(StakeLockMsg::UnlockStake {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10)
+            })
+        );
+
+        let slash = stake_lock_message(StakeLockMsg::SlashStake {
+            address: "ekez".to_string(),
+            amount: Uint128::new(10),
+            recipient: "dao".to_string(),
+        });
+        let messages = deposit_info
+            .get_forfeit_deposit_messages(&depositor, &dao)
+            .unwrap();
+        assert_eq!(messages, slash);
+        let messages = deposit_info
+            .get_veto_deposit_messages(&depositor, &dao, &contract)
+            .unwrap();
+        assert_eq!(messages, slash);
+    }
 }
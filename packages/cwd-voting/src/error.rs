@@ -11,4 +11,22 @@ pub enum VotingError {
 
     #[error("Min voting period must be less than or equal to max voting period")]
     InvalidMinVotingPeriod {},
+
+    #[error("voting_delay and max_voting_period must have the same units (height or time)")]
+    VotingDelayUnitsConflict {},
+
+    #[error("voting_delay must be measured in blocks (height)")]
+    TimeVotingDelay {},
+
+    #[error("execution_window and max_voting_period must have the same units (height or time)")]
+    ExecutionWindowUnitsConflict {},
+
+    #[error("the execution window must be non-zero")]
+    ZeroExecutionWindow {},
+
+    #[error("the no with veto threshold must be greater than zero and at most one")]
+    InvalidNoWithVetoThreshold {},
+
+    #[error("split vote portions must sum to one")]
+    InvalidSplitVote {},
 }
@@ -1,9 +1,15 @@
+pub mod anti_sniping;
+pub mod commit_reveal;
+pub mod delegation;
+pub mod dependency;
 pub mod deposit;
 pub mod error;
 pub mod multiple_choice;
 pub mod pre_propose;
 pub mod proposal;
 pub mod reply;
+pub mod signed_ballot;
 pub mod status;
 pub mod threshold;
+pub mod veto;
 pub mod voting;
@@ -1,5 +1,6 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{CosmosMsg, Empty, StdError, StdResult, Uint128};
+use serde::Deserialize;
 
 use crate::threshold::{validate_quorum, PercentageThreshold, ThresholdError};
 
@@ -7,22 +8,38 @@ use crate::threshold::{validate_quorum, PercentageThreshold, ThresholdError};
 pub const MAX_NUM_CHOICES: u32 = 10;
 const NONE_OPTION_DESCRIPTION: &str = "None of the above";
 
-/// Determines how many choices may be selected.
+/// Determines how many choices may be selected and how votes are
+/// tallied.
 #[cw_serde]
 pub enum VotingStrategy {
+    /// Voters select one option. The option with the most voting
+    /// power wins.
     SingleChoice { quorum: PercentageThreshold },
+    /// Voters approve any number of options and each approved option
+    /// receives the voter's full voting power. The option with the
+    /// most approving voting power wins.
+    Approval { quorum: PercentageThreshold },
+    /// Voters rank options in order of preference and the winner is
+    /// determined by instant runoff. Options a voter does not rank
+    /// are treated as less preferred than all ranked options.
+    RankedChoice { quorum: PercentageThreshold },
+    /// Voters spread their voting power, as credits, across options.
+    /// Each option receives the square root of the credits allocated
+    /// to it. The option with the highest total wins.
+    Quadratic { quorum: PercentageThreshold },
 }
 
 impl VotingStrategy {
     pub fn validate(&self) -> Result<(), ThresholdError> {
-        match self {
-            VotingStrategy::SingleChoice { quorum } => validate_quorum(quorum),
-        }
+        validate_quorum(&self.get_quorum())
     }
 
     pub fn get_quorum(&self) -> PercentageThreshold {
         match self {
-            VotingStrategy::SingleChoice { quorum } => *quorum,
+            VotingStrategy::SingleChoice { quorum }
+            | VotingStrategy::Approval { quorum }
+            | VotingStrategy::RankedChoice { quorum }
+            | VotingStrategy::Quadratic { quorum } => *quorum,
         }
     }
 }
@@ -41,6 +58,172 @@ impl std::fmt::Display for MultipleChoiceVote {
     }
 }
 
+/// Credits allocated to an option in a quadratic ballot.
+#[cw_serde]
+pub struct QuadraticAllocation {
+    pub option_id: u32,
+    pub credits: Uint128,
+}
+
+/// A ballot cast on a multiple choice proposal. The ballot type must
+/// match the proposal's voting strategy. For compatibility with
+/// ballots and vote messages from before ballot types were added, a
+/// bare `MultipleChoiceVote` is read as a `Single` ballot.
+#[cw_serde]
+#[serde(from = "BallotEncoding")]
+pub enum MultipleChoiceBallot {
+    /// Selects a single option. Used with `SingleChoice`.
+    Single(MultipleChoiceVote),
+    /// Approves each of the listed options. Used with `Approval`.
+    Approval { option_ids: Vec<u32> },
+    /// Ranks options from most to least preferred. Used with
+    /// `RankedChoice`.
+    Ranked { option_ids: Vec<u32> },
+    /// Allocates credits to options. The total credits allocated may
+    /// not exceed the voter's voting power. Used with `Quadratic`.
+    Quadratic {
+        allocations: Vec<QuadraticAllocation>,
+    },
+}
+
+/// The encodings a `MultipleChoiceBallot` may be read from.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BallotEncoding {
+    Legacy(MultipleChoiceVote),
+    Tagged(TaggedBallot),
+}
+
+/// Mirror of `MultipleChoiceBallot` used to read its tagged encoding.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+enum TaggedBallot {
+    Single(MultipleChoiceVote),
+    Approval {
+        option_ids: Vec<u32>,
+    },
+    Ranked {
+        option_ids: Vec<u32>,
+    },
+    Quadratic {
+        allocations: Vec<QuadraticAllocation>,
+    },
+}
+
+impl From<BallotEncoding> for MultipleChoiceBallot {
+    fn from(encoding: BallotEncoding) -> Self {
+        match encoding {
+            BallotEncoding::Legacy(vote) => MultipleChoiceBallot::Single(vote),
+            BallotEncoding::Tagged(TaggedBallot::Single(vote)) => {
+                MultipleChoiceBallot::Single(vote)
+            }
+            BallotEncoding::Tagged(TaggedBallot::Approval { option_ids }) => {
+                MultipleChoiceBallot::Approval { option_ids }
+            }
+            BallotEncoding::Tagged(TaggedBallot::Ranked { option_ids }) => {
+                MultipleChoiceBallot::Ranked { option_ids }
+            }
+            BallotEncoding::Tagged(TaggedBallot::Quadratic { allocations }) => {
+                MultipleChoiceBallot::Quadratic { allocations }
+            }
+        }
+    }
+}
+
+impl MultipleChoiceBallot {
+    /// Returns true if this ballot may be cast on a proposal using
+    /// STRATEGY.
+    pub fn matches_strategy(&self, strategy: &VotingStrategy) -> bool {
+        matches!(
+            (self, strategy),
+            (
+                MultipleChoiceBallot::Single(_),
+                VotingStrategy::SingleChoice { .. }
+            ) | (
+                MultipleChoiceBallot::Approval { .. },
+                VotingStrategy::Approval { .. }
+            ) | (
+                MultipleChoiceBallot::Ranked { .. },
+                VotingStrategy::RankedChoice { .. }
+            ) | (
+                MultipleChoiceBallot::Quadratic { .. },
+                VotingStrategy::Quadratic { .. }
+            )
+        )
+    }
+
+    /// The option IDs referenced by this ballot, in the order they
+    /// appear.
+    pub fn option_ids(&self) -> Vec<u32> {
+        match self {
+            MultipleChoiceBallot::Single(vote) => vec![vote.option_id],
+            MultipleChoiceBallot::Approval { option_ids }
+            | MultipleChoiceBallot::Ranked { option_ids } => option_ids.clone(),
+            MultipleChoiceBallot::Quadratic { allocations } => {
+                allocations.iter().map(|a| a.option_id).collect()
+            }
+        }
+    }
+}
+
+impl From<MultipleChoiceVote> for MultipleChoiceBallot {
+    fn from(vote: MultipleChoiceVote) -> Self {
+        MultipleChoiceBallot::Single(vote)
+    }
+}
+
+impl std::fmt::Display for MultipleChoiceBallot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |ids: &[u32], sep: &str| {
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+        match self {
+            MultipleChoiceBallot::Single(vote) => write!(f, "{}", vote),
+            MultipleChoiceBallot::Approval { option_ids } => write!(f, "{}", join(option_ids, ",")),
+            MultipleChoiceBallot::Ranked { option_ids } => write!(f, "{}", join(option_ids, ">")),
+            MultipleChoiceBallot::Quadratic { allocations } => write!(
+                f,
+                "{}",
+                allocations
+                    .iter()
+                    .map(|a| format!("{}:{}", a.option_id, a.credits))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+/// Voting power behind a ranking on a ranked choice proposal.
+/// Ballots with identical rankings share an entry.
+#[cw_serde]
+pub struct Ranking {
+    pub option_ids: Vec<u32>,
+    pub power: Uint128,
+}
+
+/// The weight an option receives from CREDITS allocated to it in a
+/// quadratic ballot. This is the integer square root of CREDITS,
+/// rounded down.
+pub fn quadratic_weight(credits: Uint128) -> Uint128 {
+    let n = credits.u128();
+    if n < 2 {
+        return credits;
+    }
+    // Newton's method. The initial guess, ceil(n / 2), is at least the
+    // square root so the sequence decreases until it converges.
+    let mut x = n;
+    let mut y = n / 2 + n % 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    Uint128::new(x)
+}
+
 // Holds the vote weights for each option
 #[cw_serde]
 pub struct MultipleChoiceVotes {
@@ -168,6 +351,7 @@ impl MultipleChoiceOptions {
 #[cfg(test)]
 mod test {
     use super::*;
+    use cosmwasm_std::{from_slice, to_vec};
 
     #[test]
     fn test_display_multiple_choice_vote() {
@@ -175,6 +359,104 @@ mod test {
         assert_eq!("0", vote.to_string())
     }
 
+    #[test]
+    fn test_display_multiple_choice_ballot() {
+        let ballot: MultipleChoiceBallot = MultipleChoiceVote { option_id: 1 }.into();
+        assert_eq!("1", ballot.to_string());
+        let ballot = MultipleChoiceBallot::Approval {
+            option_ids: vec![0, 2],
+        };
+        assert_eq!("0,2", ballot.to_string());
+        let ballot = MultipleChoiceBallot::Ranked {
+            option_ids: vec![2, 0, 1],
+        };
+        assert_eq!("2>0>1", ballot.to_string());
+        let ballot = MultipleChoiceBallot::Quadratic {
+            allocations: vec![
+                QuadraticAllocation {
+                    option_id: 0,
+                    credits: Uint128::new(9),
+                },
+                QuadraticAllocation {
+                    option_id: 1,
+                    credits: Uint128::new(16),
+                },
+            ],
+        };
+        assert_eq!("0:9,1:16", ballot.to_string());
+    }
+
+    #[test]
+    fn test_ballot_matches_strategy() {
+        let quorum = PercentageThreshold::Majority {};
+        let ballot = MultipleChoiceBallot::Ranked {
+            option_ids: vec![0],
+        };
+        assert!(ballot.matches_strategy(&VotingStrategy::RankedChoice { quorum }));
+        assert!(!ballot.matches_strategy(&VotingStrategy::Approval { quorum }));
+        assert!(!ballot.matches_strategy(&VotingStrategy::SingleChoice { quorum }));
+    }
+
+    #[test]
+    fn test_ballot_encoding() {
+        // Ballots cast before ballot types were added.
+        let ballot: MultipleChoiceBallot = from_slice(br#"{"option_id":2}"#).unwrap();
+        assert_eq!(
+            ballot,
+            MultipleChoiceBallot::Single(MultipleChoiceVote { option_id: 2 })
+        );
+
+        for ballot in [
+            MultipleChoiceBallot::Single(MultipleChoiceVote { option_id: 2 }),
+            MultipleChoiceBallot::Approval {
+                option_ids: vec![0, 1],
+            },
+            MultipleChoiceBallot::Ranked {
+                option_ids: vec![1, 0],
+            },
+            MultipleChoiceBallot::Quadratic {
+                allocations: vec![QuadraticAllocation {
+                    option_id: 1,
+                    credits: Uint128::new(4),
+                }],
+            },
+        ] {
+            let encoded = to_vec(&ballot).unwrap();
+            assert_eq!(
+                from_slice::<MultipleChoiceBallot>(&encoded).unwrap(),
+                ballot
+            );
+        }
+        assert_eq!(
+            to_vec(&MultipleChoiceBallot::Ranked {
+                option_ids: vec![1, 0]
+            })
+            .unwrap(),
+            br#"{"ranked":{"option_ids":[1,0]}}"#.to_vec()
+        );
+
+        from_slice::<MultipleChoiceBallot>(br#"{"option_id":2,"extra":1}"#).unwrap_err();
+    }
+
+    #[test]
+    fn test_quadratic_weight() {
+        assert_eq!(quadratic_weight(Uint128::zero()), Uint128::zero());
+        assert_eq!(quadratic_weight(Uint128::new(1)), Uint128::new(1));
+        assert_eq!(quadratic_weight(Uint128::new(2)), Uint128::new(1));
+        assert_eq!(quadratic_weight(Uint128::new(3)), Uint128::new(1));
+        assert_eq!(quadratic_weight(Uint128::new(4)), Uint128::new(2));
+        assert_eq!(quadratic_weight(Uint128::new(99)), Uint128::new(9));
+        assert_eq!(quadratic_weight(Uint128::new(100)), Uint128::new(10));
+        assert_eq!(
+            quadratic_weight(Uint128::new(1_000_000_000_000)),
+            Uint128::new(1_000_000)
+        );
+        assert_eq!(
+            quadratic_weight(Uint128::MAX),
+            Uint128::new(u64::MAX as u128)
+        );
+    }
+
     #[test]
     fn test_multiple_choice_votes() {
         let mut votes = MultipleChoiceVotes {
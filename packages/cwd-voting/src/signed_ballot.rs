@@ -0,0 +1,205 @@
+use cosmwasm_schema::{cw_serde, serde::Serialize};
+use cosmwasm_std::{to_vec, Addr, Api, Binary, CanonicalAddr, StdError, StdResult};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SignedBallotError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error("Invalid public key. secp256k1 keys must be 33 byte compressed keys and ed25519 keys must be 32 bytes")]
+    InvalidPubKey {},
+
+    #[error("Invalid ballot signature")]
+    InvalidSignature {},
+}
+
+/// The public key of a voter who signed a ballot. The voter's address
+/// is derived from their public key in the same way the Cosmos SDK
+/// derives account addresses.
+#[cw_serde]
+pub enum BallotPubKey {
+    /// A 33 byte compressed secp256k1 public key. Signatures are
+    /// over the SHA-256 hash of the ballot's sign bytes.
+    Secp256k1(Binary),
+    /// A 32 byte ed25519 public key. Signatures are over the
+    /// ballot's sign bytes.
+    Ed25519(Binary),
+}
+
+/// A ballot signed by a voter off chain so that it may be submitted
+/// by a relayer who pays for gas on their behalf.
+#[cw_serde]
+pub struct SignedBallot<V> {
+    /// The ID of the proposal being voted on.
+    pub proposal_id: u64,
+    /// The voter's position on the proposal.
+    pub vote: V,
+    /// The voter's ballot nonce. This must be at least the next nonce
+    /// for the voter reported by the proposal module and prevents the
+    /// ballot from being replayed.
+    pub nonce: u64,
+    /// The voter's public key.
+    pub pub_key: BallotPubKey,
+    /// The voter's signature over the ballot's sign bytes.
+    pub signature: Binary,
+}
+
+/// The message voters sign. Its sign bytes are its JSON encoding.
+/// The chain ID and proposal module address bind the signature to a
+/// single proposal module so that it may not be replayed elsewhere.
+#[cw_serde]
+pub struct BallotSignDoc<V> {
+    pub chain_id: String,
+    pub proposal_module: String,
+    pub proposal_id: u64,
+    pub vote: V,
+    pub nonce: u64,
+}
+
+impl<V: Serialize> BallotSignDoc<V> {
+    /// Returns the bytes that voters sign.
+    pub fn sign_bytes(&self) -> StdResult<Vec<u8>> {
+        to_vec(self)
+    }
+}
+
+impl BallotPubKey {
+    /// Derives the canonical address of the key's owner. For
+    /// secp256k1 keys this is RIPEMD-160(SHA-256(key)) and for
+    /// ed25519 keys this is the first 20 bytes of SHA-256(key).
+    pub fn canonical_address(&self) -> Result<CanonicalAddr, SignedBallotError> {
+        match self {
+            BallotPubKey::Secp256k1(key) => {
+                if key.len() != 33 {
+                    return Err(SignedBallotError::InvalidPubKey {});
+                }
+                let hash = Ripemd160::digest(Sha256::digest(key.as_slice()));
+                Ok(CanonicalAddr::from(hash.as_slice()))
+            }
+            BallotPubKey::Ed25519(key) => {
+                if key.len() != 32 {
+                    return Err(SignedBallotError::InvalidPubKey {});
+                }
+                let hash = Sha256::digest(key.as_slice());
+                Ok(CanonicalAddr::from(&hash[..20]))
+            }
+        }
+    }
+
+    /// Verifies that SIGNATURE is this key's signature over
+    /// SIGN_BYTES.
+    pub fn verify(
+        &self,
+        api: &dyn Api,
+        sign_bytes: &[u8],
+        signature: &[u8],
+    ) -> Result<(), SignedBallotError> {
+        let valid = match self {
+            BallotPubKey::Secp256k1(key) => {
+                api.secp256k1_verify(&Sha256::digest(sign_bytes), signature, key.as_slice())
+            }
+            BallotPubKey::Ed25519(key) => api.ed25519_verify(sign_bytes, signature, key.as_slice()),
+        };
+        match valid {
+            Ok(true) => Ok(()),
+            _ => Err(SignedBallotError::InvalidSignature {}),
+        }
+    }
+}
+
+impl<V: Serialize + Clone> SignedBallot<V> {
+    /// The document the voter signed when submitted to
+    /// PROPOSAL_MODULE on the chain with CHAIN_ID.
+    pub fn sign_doc(&self, chain_id: &str, proposal_module: &Addr) -> BallotSignDoc<V> {
+        BallotSignDoc {
+            chain_id: chain_id.to_string(),
+            proposal_module: proposal_module.to_string(),
+            proposal_id: self.proposal_id,
+            vote: self.vote.clone(),
+            nonce: self.nonce,
+        }
+    }
+
+    /// Verifies the ballot's signature and returns the address of the
+    /// voter who signed it.
+    pub fn verify(
+        &self,
+        api: &dyn Api,
+        chain_id: &str,
+        proposal_module: &Addr,
+    ) -> Result<Addr, SignedBallotError> {
+        let canonical = self.pub_key.canonical_address()?;
+        let sign_bytes = self.sign_doc(chain_id, proposal_module).sign_bytes()?;
+        self.pub_key
+            .verify(api, &sign_bytes, self.signature.as_slice())?;
+        Ok(api.addr_humanize(&canonical)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockApi;
+
+    use super::*;
+    use crate::voting::Vote;
+
+    #[test]
+    fn test_canonical_address() {
+        let secp = BallotPubKey::Secp256k1(Binary::from([2u8; 33].as_slice()));
+        let ed = BallotPubKey::Ed25519(Binary::from([2u8; 32].as_slice()));
+        assert_eq!(secp.canonical_address().unwrap().len(), 20);
+        assert_eq!(ed.canonical_address().unwrap().len(), 20);
+        assert_ne!(
+            secp.canonical_address().unwrap(),
+            ed.canonical_address().unwrap()
+        );
+
+        // Uncompressed secp256k1 keys would derive a different
+        // address than the voter's account so they are rejected.
+        assert_eq!(
+            BallotPubKey::Secp256k1(Binary::from([4u8; 65].as_slice())).canonical_address(),
+            Err(SignedBallotError::InvalidPubKey {})
+        );
+        assert_eq!(
+            BallotPubKey::Ed25519(Binary::from([2u8; 33].as_slice())).canonical_address(),
+            Err(SignedBallotError::InvalidPubKey {})
+        );
+    }
+
+    #[test]
+    fn test_sign_bytes() {
+        let ballot = SignedBallot {
+            proposal_id: 1,
+            vote: Vote::Yes,
+            nonce: 7,
+            pub_key: BallotPubKey::Ed25519(Binary::default()),
+            signature: Binary::default(),
+        };
+        let doc = ballot.sign_doc("juno-1", &Addr::unchecked("proposal"));
+        assert_eq!(
+            String::from_utf8(doc.sign_bytes().unwrap()).unwrap(),
+            r#"{"chain_id":"juno-1","proposal_module":"proposal","proposal_id":1,"vote":"yes","nonce":7}"#
+        );
+    }
+
+    #[test]
+    fn test_invalid_signature() {
+        let api = MockApi::default();
+        let key = BallotPubKey::Ed25519(Binary::from([2u8; 32].as_slice()));
+        assert_eq!(
+            key.verify(&api, b"ballot", &[0u8; 64]),
+            Err(SignedBallotError::InvalidSignature {})
+        );
+        // Malformed signatures are invalid signatures, not errors
+        // which abort verification.
+        let key = BallotPubKey::Secp256k1(Binary::from([2u8; 33].as_slice()));
+        assert_eq!(
+            key.verify(&api, b"ballot", &[0u8; 3]),
+            Err(SignedBallotError::InvalidSignature {})
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::StdError;
 
 #[cw_serde]
 #[derive(Copy)]
@@ -17,6 +18,22 @@ pub enum Status {
     Closed,
     /// The proposal's execution failed.
     ExecutionFailed,
+    /// The proposal passed but was vetoed before it could be
+    /// executed.
+    Vetoed,
+    /// The proposal has been created but its voting delay has not
+    /// yet elapsed. It may not be voted on until it opens.
+    Pending,
+    /// The proposal passed but was not executed within its execution
+    /// window. It may no longer be executed.
+    ExecutionExpired,
+    /// The proposal was withdrawn by its proposer before voting on
+    /// it began.
+    Withdrawn,
+    /// The proposal was rejected because its NoWithVeto votes
+    /// exceeded its veto threshold and has been closed. Its deposit,
+    /// if any, is not refunded.
+    ClosedWithVeto,
 }
 
 impl std::fmt::Display for Status {
@@ -28,6 +45,83 @@ impl std::fmt::Display for Status {
             Status::Executed => write!(f, "executed"),
             Status::Closed => write!(f, "closed"),
             Status::ExecutionFailed => write!(f, "execution_failed"),
+            Status::Vetoed => write!(f, "vetoed"),
+            Status::Pending => write!(f, "pending"),
+            Status::ExecutionExpired => write!(f, "execution_expired"),
+            Status::Withdrawn => write!(f, "withdrawn"),
+            Status::ClosedWithVeto => write!(f, "closed_with_veto"),
         }
     }
 }
+
+impl std::str::FromStr for Status {
+    type Err = StdError;
+
+    /// Parses a status from its `Display` form, as sent in proposal
+    /// status changed hooks.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(Status::Open),
+            "rejected" => Ok(Status::Rejected),
+            "passed" => Ok(Status::Passed),
+            "executed" => Ok(Status::Executed),
+            "closed" => Ok(Status::Closed),
+            "execution_failed" => Ok(Status::ExecutionFailed),
+            "vetoed" => Ok(Status::Vetoed),
+            "pending" => Ok(Status::Pending),
+            "execution_expired" => Ok(Status::ExecutionExpired),
+            "withdrawn" => Ok(Status::Withdrawn),
+            "closed_with_veto" => Ok(Status::ClosedWithVeto),
+            _ => Err(StdError::parse_err(
+                "Status",
+                format!("unknown status ({s})"),
+            )),
+        }
+    }
+}
+
+impl Status {
+    /// Returns the statuses a proposal may have in storage while its
+    /// current status is SELF. Proposal modules only save a
+    /// proposal's status when it is acted on, so a proposal stored as
+    /// open may have since passed or been rejected, and a proposal
+    /// stored as pending may have since opened, passed, or been
+    /// rejected. A passed proposal's execution window may also end
+    /// before it is acted on. All other status changes are saved when
+    /// they happen.
+    pub fn possible_stored_statuses(self) -> Vec<Status> {
+        match self {
+            Status::Passed | Status::Rejected => vec![self, Status::Open, Status::Pending],
+            Status::Open => vec![self, Status::Pending],
+            Status::ExecutionExpired => {
+                vec![self, Status::Passed, Status::Open, Status::Pending]
+            }
+            _ => vec![self],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        for status in [
+            Status::Open,
+            Status::Rejected,
+            Status::Passed,
+            Status::Executed,
+            Status::Closed,
+            Status::ExecutionFailed,
+            Status::Vetoed,
+            Status::Pending,
+            Status::ExecutionExpired,
+            Status::Withdrawn,
+            Status::ClosedWithVeto,
+        ] {
+            assert_eq!(status.to_string().parse::<Status>().unwrap(), status);
+        }
+        "Open".parse::<Status>().unwrap_err();
+    }
+}
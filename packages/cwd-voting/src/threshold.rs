@@ -10,6 +10,12 @@ pub enum ThresholdError {
 
     #[error("Not possible to reach required (passing) threshold")]
     UnreachableThreshold {},
+
+    #[error("Adaptive quorums must be less than 100%")]
+    AdaptiveQuorumTooHigh {},
+
+    #[error("Adaptive thresholds may not increase as turnout rises")]
+    IncreasingAdaptiveThreshold {},
 }
 
 /// A percentage of voting power that must vote yes for a proposal to
@@ -66,6 +72,20 @@ pub enum Threshold {
     /// An absolute number of votes needed for something to cross the
     /// threshold. Useful for multisig style voting.
     AbsoluteCount { threshold: Uint128 },
+
+    /// A passing threshold which relaxes as turnout rises. At least
+    /// `quorum` of the total weight must participate for the vote to
+    /// be considered at all. When turnout is exactly `quorum`,
+    /// `threshold_at_quorum` of the yes and no votes must be yes. The
+    /// required percentage then falls linearly to
+    /// `threshold_at_full_turnout` as turnout rises to 100%. This
+    /// requires a super-majority to pass proposals at low turnout
+    /// without needing a quorum that is hard to reach.
+    AdaptiveQuorum {
+        quorum: Decimal,
+        threshold_at_quorum: Decimal,
+        threshold_at_full_turnout: Decimal,
+    },
 }
 
 /// Asserts that the 0.0 < percent <= 1.0
@@ -103,6 +123,8 @@ impl Threshold {
     /// - Quorums must never be over 100%.
     /// - Passing thresholds must never be over 100%, nor be 0%.
     /// - Absolute count thresholds must be non-zero.
+    /// - Adaptive quorums must be under 100% and their thresholds
+    ///   must not increase with turnout.
     pub fn validate(&self) -> Result<(), ThresholdError> {
         match self {
             Threshold::AbsolutePercentage {
@@ -119,6 +141,21 @@ impl Threshold {
                     Ok(())
                 }
             }
+            Threshold::AdaptiveQuorum {
+                quorum,
+                threshold_at_quorum,
+                threshold_at_full_turnout,
+            } => {
+                validate_percentage(&PercentageThreshold::Percent(*threshold_at_quorum))?;
+                validate_percentage(&PercentageThreshold::Percent(*threshold_at_full_turnout))?;
+                if *quorum >= Decimal::one() {
+                    Err(ThresholdError::AdaptiveQuorumTooHigh {})
+                } else if threshold_at_full_turnout > threshold_at_quorum {
+                    Err(ThresholdError::IncreasingAdaptiveThreshold {})
+                } else {
+                    Ok(())
+                }
+            }
         }
     }
 }
@@ -180,4 +217,33 @@ mod tests {
             ThresholdError::UnreachableThreshold {}
         );
     }
+
+    #[test]
+    fn test_adaptive_quorum_validation() {
+        let adaptive =
+            |quorum, threshold_at_quorum, threshold_at_full_turnout| Threshold::AdaptiveQuorum {
+                quorum: Decimal::percent(quorum),
+                threshold_at_quorum: Decimal::percent(threshold_at_quorum),
+                threshold_at_full_turnout: Decimal::percent(threshold_at_full_turnout),
+            };
+
+        adaptive(20, 67, 50).validate().unwrap();
+        adaptive(0, 50, 50).validate().unwrap();
+        assert_eq!(
+            adaptive(20, 67, 0).validate().unwrap_err(),
+            ThresholdError::ZeroThreshold {}
+        );
+        assert_eq!(
+            adaptive(20, 101, 50).validate().unwrap_err(),
+            ThresholdError::UnreachableThreshold {}
+        );
+        assert_eq!(
+            adaptive(100, 67, 50).validate().unwrap_err(),
+            ThresholdError::AdaptiveQuorumTooHigh {}
+        );
+        assert_eq!(
+            adaptive(20, 50, 67).validate().unwrap_err(),
+            ThresholdError::IncreasingAdaptiveThreshold {}
+        );
+    }
 }
@@ -0,0 +1,169 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Deps, StdError, StdResult};
+use cw_utils::{Duration, Expiration};
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum VetoError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error("The veto window must be non-zero")]
+    ZeroVetoWindow {},
+
+    #[error("The veto window and max_voting_period must have the same units (height or time)")]
+    DurationUnitsConflict {},
+}
+
+/// Configuration for an address that may veto proposals which have
+/// passed. This allows a trusted party, for example a council
+/// subDAO, to guard against governance attacks without having admin
+/// power over the DAO.
+#[cw_serde]
+pub struct UncheckedVetoConfig {
+    /// The address that may veto passed proposals.
+    pub vetoer: String,
+    /// The amount of time after a proposal's voting period ends
+    /// during which it may be vetoed if it has passed. Proposals may
+    /// be vetoed as soon as they pass, including before their voting
+    /// period ends, but may not be executed until this window has
+    /// ended unless `early_execute` is set. Must have the same units
+    /// as the proposal module's `max_voting_period`.
+    pub veto_window: Duration,
+    /// If true, the vetoer may execute a passed proposal before its
+    /// veto window has ended. Otherwise, no one may execute a
+    /// proposal until its veto window has ended.
+    pub early_execute: bool,
+}
+
+/// Counterpart to `UncheckedVetoConfig` whose vetoer has been
+/// validated. Built by calling `into_checked` on an
+/// `UncheckedVetoConfig`.
+#[cw_serde]
+pub struct VetoConfig {
+    pub vetoer: Addr,
+    pub veto_window: Duration,
+    pub early_execute: bool,
+}
+
+impl UncheckedVetoConfig {
+    /// Validates the vetoer's address and that the veto window is
+    /// non-zero and measured in the same units as MAX_VOTING_PERIOD.
+    pub fn into_checked(
+        self,
+        deps: &Deps,
+        max_voting_period: &Duration,
+    ) -> Result<VetoConfig, VetoError> {
+        let vetoer = deps.api.addr_validate(&self.vetoer)?;
+        match (self.veto_window, max_voting_period) {
+            (Duration::Height(0), _) | (Duration::Time(0), _) => {
+                return Err(VetoError::ZeroVetoWindow {})
+            }
+            (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_)) => {
+            }
+            _ => return Err(VetoError::DurationUnitsConflict {}),
+        }
+        Ok(VetoConfig {
+            vetoer,
+            veto_window: self.veto_window,
+            early_execute: self.early_execute,
+        })
+    }
+}
+
+impl VetoConfig {
+    /// Returns true if ADDR is the vetoer.
+    pub fn is_vetoer(&self, addr: &Addr) -> bool {
+        *addr == self.vetoer
+    }
+
+    /// Computes when the veto window of a proposal whose voting
+    /// period ends at EXPIRATION ends. Errors if EXPIRATION is never
+    /// or measured in different units than the veto window.
+    pub fn window_end(&self, expiration: Expiration) -> StdResult<Expiration> {
+        expiration + self.veto_window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    use super::*;
+
+    fn veto_config(veto_window: Duration) -> UncheckedVetoConfig {
+        UncheckedVetoConfig {
+            vetoer: "vetoer".to_string(),
+            veto_window,
+            early_execute: false,
+        }
+    }
+
+    fn checked_veto_config(veto_window: Duration) -> VetoConfig {
+        VetoConfig {
+            vetoer: Addr::unchecked("vetoer"),
+            veto_window,
+            early_execute: false,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let deps = mock_dependencies();
+        let deps = deps.as_ref();
+
+        let checked = veto_config(Duration::Height(10))
+            .into_checked(&deps, &Duration::Height(100))
+            .unwrap();
+        assert!(checked.is_vetoer(&Addr::unchecked("vetoer")));
+        veto_config(Duration::Time(10))
+            .into_checked(&deps, &Duration::Time(100))
+            .unwrap();
+
+        let err = veto_config(Duration::Height(0))
+            .into_checked(&deps, &Duration::Height(100))
+            .unwrap_err();
+        assert_eq!(err, VetoError::ZeroVetoWindow {});
+
+        let err = veto_config(Duration::Time(10))
+            .into_checked(&deps, &Duration::Height(100))
+            .unwrap_err();
+        assert_eq!(err, VetoError::DurationUnitsConflict {});
+
+        let err = veto_config(Duration::Height(10))
+            .into_checked(&deps, &Duration::Time(100))
+            .unwrap_err();
+        assert_eq!(err, VetoError::DurationUnitsConflict {});
+
+        // Vetoers must be valid addresses.
+        let mut config = veto_config(Duration::Height(10));
+        config.vetoer = "Vetoer".to_string();
+        let err = config
+            .into_checked(&deps, &Duration::Height(100))
+            .unwrap_err();
+        assert!(matches!(err, VetoError::Std(_)));
+    }
+
+    #[test]
+    fn test_window_end() {
+        let env = mock_env();
+
+        let end = checked_veto_config(Duration::Height(10))
+            .window_end(Expiration::AtHeight(env.block.height + 5))
+            .unwrap();
+        assert_eq!(end, Expiration::AtHeight(env.block.height + 15));
+
+        let end = checked_veto_config(Duration::Time(10))
+            .window_end(Expiration::AtTime(env.block.time))
+            .unwrap();
+        assert_eq!(end, Expiration::AtTime(env.block.time.plus_seconds(10)));
+
+        checked_veto_config(Duration::Time(10))
+            .window_end(Expiration::AtHeight(env.block.height))
+            .unwrap_err();
+        checked_veto_config(Duration::Height(10))
+            .window_end(Expiration::Never {})
+            .unwrap_err();
+    }
+}
@@ -1,9 +1,9 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Deps, StdError, StdResult, Uint128, Uint256};
+use cosmwasm_std::{Addr, Decimal, Deps, StdError, StdResult, Uint128, Uint256, Uint512};
 use cw_utils::Duration;
 use cwd_interface::voting;
 
-use crate::threshold::PercentageThreshold;
+use crate::{error::VotingError, threshold::PercentageThreshold};
 
 // We multiply by this when calculating needed_votes in order to round
 // up properly.
@@ -14,6 +14,9 @@ pub struct Votes {
     pub yes: Uint128,
     pub no: Uint128,
     pub abstain: Uint128,
+    /// The portion of `no` votes that were cast as `NoWithVeto`.
+    #[serde(default)]
+    pub no_with_veto: Uint128,
 }
 
 #[cw_serde]
@@ -27,6 +30,22 @@ pub enum Vote {
     /// Marks participation but does not count towards the ratio of
     /// support / opposed.
     Abstain,
+    /// Divides the voter's voting power between positions.
+    Split(SplitVote),
+    /// Marks opposition to the proposal and counts as a no vote. If
+    /// enough voting power votes this way the proposal is rejected
+    /// and its deposit is not refunded.
+    NoWithVeto,
+}
+
+/// The portion of a voter's voting power allocated to each position
+/// in a split vote. Portions must sum to one.
+#[cw_serde]
+#[derive(Copy)]
+pub struct SplitVote {
+    pub yes: Decimal,
+    pub no: Decimal,
+    pub abstain: Decimal,
 }
 
 #[cw_serde]
@@ -172,6 +191,106 @@ pub fn does_vote_count_fail(
     }
 }
 
+/// Determines if YES_VOTES meet an adaptive quorum threshold. TURNOUT
+/// is all voting power that has voted, including abstentions, OPTIONS
+/// is the yes and no votes, and TOTAL_POWER is all voting power. See
+/// `Threshold::AdaptiveQuorum`.
+///
+/// Turnout must be at least `total_power * quorum`. The passing
+/// threshold is then
+///
+/// ```text
+/// r = a - (a - b) * (turnout / total_power - q) / (1 - q)
+/// ```
+///
+/// where `q` is the quorum, `a` the threshold at quorum, and `b` the
+/// threshold at full turnout, and the proposal passes if
+/// `yes_votes >= options * r`. Unlike `compare_vote_count` this
+/// comparison is exact. Multiplying out the denominators and writing
+/// each decimal as its atomics over `S = 10^18` gives
+///
+/// ```text
+/// yes * (S - Q) * P * S + (A - B) * (S * V - Q * P) * O >= A * O * (S - Q) * P
+/// ```
+///
+/// which never exceeds 512 bits for 128 bit vote counts. `quorum`
+/// must be less than one.
+pub fn does_adaptive_vote_count_pass(
+    yes_votes: Uint128,
+    options: Uint128,
+    turnout: Uint128,
+    total_power: Uint128,
+    quorum: Decimal,
+    threshold_at_quorum: Decimal,
+    threshold_at_full_turnout: Decimal,
+) -> bool {
+    // Don't pass proposals if all the votes are abstain.
+    if options.is_zero() {
+        return false;
+    }
+    let scale = Uint512::from(Decimal::one().atomics().u128());
+    let [yes, options, turnout, total_power, q, a, b] = [
+        yes_votes,
+        options,
+        turnout,
+        total_power,
+        quorum.atomics(),
+        threshold_at_quorum.atomics(),
+        threshold_at_full_turnout.atomics(),
+    ]
+    .map(|n| Uint512::from(n.u128()));
+
+    let scaled_turnout = scale * turnout;
+    let scaled_quorum = q * total_power;
+    if scaled_turnout < scaled_quorum {
+        return false;
+    }
+    let lhs = yes * (scale - q) * total_power * scale
+        + (a - b) * (scaled_turnout - scaled_quorum) * options;
+    let rhs = a * options * (scale - q) * total_power;
+    lhs >= rhs
+}
+
+impl Vote {
+    /// Validates that split votes allocate exactly all of a voter's
+    /// voting power.
+    pub fn validate(&self) -> Result<(), VotingError> {
+        match self {
+            Vote::Split(split) => split.validate(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl SplitVote {
+    /// Validates that the portions sum to one.
+    pub fn validate(&self) -> Result<(), VotingError> {
+        // Checking each portion first prevents overflow when summing.
+        if [self.yes, self.no, self.abstain]
+            .iter()
+            .any(|portion| *portion > Decimal::one())
+            || self.yes + self.no + self.abstain != Decimal::one()
+        {
+            return Err(VotingError::InvalidSplitVote {});
+        }
+        Ok(())
+    }
+
+    /// Divides POWER between positions. Rounding is done on the
+    /// running total of portions so the parts always sum to POWER
+    /// and positions with a zero portion receive no power.
+    pub fn divide(&self, power: Uint128) -> Votes {
+        let yes = power * self.yes;
+        let no = power * (self.yes + self.no) - yes;
+        Votes {
+            yes,
+            no,
+            abstain: power - yes - no,
+            no_with_veto: Uint128::zero(),
+        }
+    }
+}
+
 impl Votes {
     /// Constructs an zero'd out votes struct.
     pub fn zero() -> Self {
@@ -179,6 +298,7 @@ impl Votes {
             yes: Uint128::zero(),
             no: Uint128::zero(),
             abstain: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
         }
     }
 
@@ -190,6 +310,7 @@ impl Votes {
             yes,
             no: Uint128::zero(),
             abstain: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
         }
     }
 
@@ -199,6 +320,16 @@ impl Votes {
             Vote::Yes => self.yes += power,
             Vote::No => self.no += power,
             Vote::Abstain => self.abstain += power,
+            Vote::NoWithVeto => {
+                self.no += power;
+                self.no_with_veto += power;
+            }
+            Vote::Split(split) => {
+                let parts = split.divide(power);
+                self.yes += parts.yes;
+                self.no += parts.no;
+                self.abstain += parts.abstain;
+            }
         }
     }
 
@@ -210,6 +341,16 @@ impl Votes {
             Vote::Yes => self.yes -= power,
             Vote::No => self.no -= power,
             Vote::Abstain => self.abstain -= power,
+            Vote::NoWithVeto => {
+                self.no -= power;
+                self.no_with_veto -= power;
+            }
+            Vote::Split(split) => {
+                let parts = split.divide(power);
+                self.yes -= parts.yes;
+                self.no -= parts.no;
+                self.abstain -= parts.abstain;
+            }
         }
     }
 
@@ -231,6 +372,8 @@ impl std::fmt::Display for Vote {
             Vote::Yes => write!(f, "yes"),
             Vote::No => write!(f, "no"),
             Vote::Abstain => write!(f, "abstain"),
+            Vote::Split(_) => write!(f, "split"),
+            Vote::NoWithVeto => write!(f, "no_with_veto"),
         }
     }
 }
@@ -284,6 +427,54 @@ pub fn validate_voting_period(
     Ok((min, max))
 }
 
+/// Validates that the voting delay is measured in blocks, and so in
+/// the same units as the max voting period. Voting power is
+/// snapshotted at the height a proposal's voting delay ends, which is
+/// only known when the proposal is created if the delay is measured
+/// in blocks. A proposal's voting period begins once its voting delay
+/// has elapsed so the two must be added together.
+pub fn validate_voting_delay(
+    delay: Option<Duration>,
+    max: &Duration,
+) -> Result<Option<Duration>, crate::error::VotingError> {
+    match (delay, max) {
+        (None, _) | (Some(Duration::Height(_)), Duration::Height(_)) => Ok(delay),
+        (Some(Duration::Time(_)), _) => Err(crate::error::VotingError::TimeVotingDelay {}),
+        _ => Err(crate::error::VotingError::VotingDelayUnitsConflict {}),
+    }
+}
+
+/// Validates that the execution window is non-zero and measured in
+/// the same units as the max voting period. A proposal's execution
+/// window begins once its voting period has ended.
+pub fn validate_execution_window(
+    window: Option<Duration>,
+    max: &Duration,
+) -> Result<Option<Duration>, crate::error::VotingError> {
+    match (window, max) {
+        (Some(Duration::Height(0)), _) | (Some(Duration::Time(0)), _) => {
+            Err(crate::error::VotingError::ZeroExecutionWindow {})
+        }
+        (None, _)
+        | (Some(Duration::Height(_)), Duration::Height(_))
+        | (Some(Duration::Time(_)), Duration::Time(_)) => Ok(window),
+        _ => Err(crate::error::VotingError::ExecutionWindowUnitsConflict {}),
+    }
+}
+
+/// Validates that the NoWithVeto threshold, if any, is greater than
+/// zero and at most one.
+pub fn validate_no_with_veto_threshold(
+    threshold: Option<Decimal>,
+) -> Result<Option<Decimal>, crate::error::VotingError> {
+    match threshold {
+        Some(threshold) if threshold.is_zero() || threshold > Decimal::one() => {
+            Err(crate::error::VotingError::InvalidNoWithVetoThreshold {})
+        }
+        _ => Ok(threshold),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -301,6 +492,83 @@ mod test {
         assert_eq!(votes.abstain, Uint128::new(40));
     }
 
+    #[test]
+    fn no_with_veto_votes() {
+        let mut votes = Votes::zero();
+        votes.add_vote(Vote::No, Uint128::new(10));
+        votes.add_vote(Vote::NoWithVeto, Uint128::new(20));
+
+        assert_eq!(votes.total(), Uint128::new(30));
+        assert_eq!(votes.no, Uint128::new(30));
+        assert_eq!(votes.no_with_veto, Uint128::new(20));
+
+        votes.remove_vote(Vote::NoWithVeto, Uint128::new(20));
+        assert_eq!(votes, Votes::zero());
+    }
+
+    #[test]
+    fn split_votes() {
+        let split = SplitVote {
+            yes: Decimal::percent(60),
+            no: Decimal::zero(),
+            abstain: Decimal::percent(40),
+        };
+        assert_eq!(Vote::Split(split).validate(), Ok(()));
+        assert_eq!(
+            split.divide(Uint128::new(10)),
+            Votes {
+                yes: Uint128::new(6),
+                no: Uint128::zero(),
+                abstain: Uint128::new(4),
+                no_with_veto: Uint128::zero(),
+            }
+        );
+
+        // Rounding never loses power or gives power to a position
+        // with a zero portion.
+        let split = SplitVote {
+            yes: Decimal::percent(50),
+            no: Decimal::percent(50),
+            abstain: Decimal::zero(),
+        };
+        assert_eq!(
+            split.divide(Uint128::new(3)),
+            Votes {
+                yes: Uint128::new(1),
+                no: Uint128::new(2),
+                abstain: Uint128::zero(),
+                no_with_veto: Uint128::zero(),
+            }
+        );
+        let split = SplitVote {
+            yes: Decimal::from_ratio(1u128, 3u128),
+            no: Decimal::from_ratio(1u128, 3u128),
+            abstain: Decimal::one() - Decimal::from_ratio(2u128, 3u128),
+        };
+        assert_eq!(split.divide(Uint128::new(7)).total(), Uint128::new(7));
+
+        let mut votes = Votes::with_yes(Uint128::new(5));
+        votes.add_vote(Vote::Split(split), Uint128::new(7));
+        votes.remove_vote(Vote::Split(split), Uint128::new(7));
+        assert_eq!(votes, Votes::with_yes(Uint128::new(5)));
+
+        let invalid = SplitVote {
+            yes: Decimal::percent(60),
+            no: Decimal::percent(60),
+            abstain: Decimal::zero(),
+        };
+        assert_eq!(
+            Vote::Split(invalid).validate(),
+            Err(VotingError::InvalidSplitVote {})
+        );
+        let invalid = SplitVote {
+            yes: Decimal::percent(50),
+            no: Decimal::percent(20),
+            abstain: Decimal::percent(20),
+        };
+        assert_eq!(invalid.validate(), Err(VotingError::InvalidSplitVote {}));
+    }
+
     #[test]
     fn vote_comparisons() {
         assert!(!compare_vote_count(
@@ -498,6 +766,118 @@ mod test {
         ))
     }
 
+    #[test]
+    fn adaptive_vote_counts() {
+        // 20% quorum, 67% threshold at quorum relaxing to 50% at full
+        // turnout.
+        let passes = |yes: u128, options: u128, turnout: u128| {
+            does_adaptive_vote_count_pass(
+                Uint128::new(yes),
+                Uint128::new(options),
+                Uint128::new(turnout),
+                Uint128::new(1000),
+                Decimal::percent(20),
+                Decimal::percent(67),
+                Decimal::percent(50),
+            )
+        };
+
+        // At quorum 67% is required.
+        assert!(passes(134, 200, 200));
+        assert!(!passes(133, 200, 200));
+
+        // Below quorum nothing passes.
+        assert!(!passes(199, 199, 199));
+
+        // At full turnout 50% is required.
+        assert!(passes(500, 1000, 1000));
+        assert!(!passes(499, 1000, 1000));
+
+        // Halfway between quorum and full turnout 58.5% is required.
+        assert!(passes(351, 600, 600));
+        assert!(!passes(350, 600, 600));
+
+        // Abstentions count towards turnout but not options. 58.5%
+        // of 500 is 292.5.
+        assert!(passes(293, 500, 600));
+        assert!(!passes(292, 500, 600));
+
+        // All abstain.
+        assert!(!passes(0, 0, 1000));
+    }
+
+    #[test]
+    fn tricky_adaptive_vote_counts() {
+        // With a constant threshold the comparison matches
+        // `compare_vote_count`. 7 * (7/13) is 6.999... after rounding.
+        let threshold = Decimal::from_ratio(7u64, 13u64);
+        assert!(does_adaptive_vote_count_pass(
+            Uint128::new(7),
+            Uint128::new(13),
+            Uint128::new(13),
+            Uint128::new(13),
+            Decimal::zero(),
+            threshold,
+            threshold,
+        ));
+        assert!(!does_adaptive_vote_count_pass(
+            Uint128::new(6),
+            Uint128::new(13),
+            Uint128::new(13),
+            Uint128::new(13),
+            Decimal::zero(),
+            threshold,
+            threshold,
+        ));
+
+        // The quorum comparison is exact. One third of 3 * 10^20 is
+        // 10^20, but a one third decimal is slightly less than one
+        // third so turnout 100 below 10^20 meets quorum and 101
+        // below does not.
+        let total = Uint128::new(3 * 10u128.pow(20));
+        let third = Decimal::from_ratio(1u64, 3u64);
+        let turnout = Uint128::new(10u128.pow(20) - 100);
+        assert!(does_adaptive_vote_count_pass(
+            turnout,
+            turnout,
+            turnout,
+            total,
+            third,
+            Decimal::one(),
+            Decimal::percent(50),
+        ));
+        let turnout = Uint128::new(10u128.pow(20) - 101);
+        assert!(!does_adaptive_vote_count_pass(
+            turnout,
+            turnout,
+            turnout,
+            total,
+            third,
+            Decimal::one(),
+            Decimal::percent(50),
+        ));
+
+        // Votes at the limit of Uint128 do not overflow.
+        assert!(does_adaptive_vote_count_pass(
+            Uint128::MAX,
+            Uint128::MAX,
+            Uint128::MAX,
+            Uint128::MAX,
+            Decimal::percent(99),
+            Decimal::one(),
+            Decimal::one(),
+        ));
+        assert!(!does_adaptive_vote_count_pass(
+            Uint128::MAX - Uint128::new(1),
+            Uint128::MAX,
+            Uint128::MAX,
+            Uint128::MAX,
+            Decimal::percent(99),
+            Decimal::one(),
+            Decimal::one(),
+        ));
+    }
+
     #[test]
     fn test_display_multiple_choice_vote() {
         let vote = MultipleChoiceVote { option_id: 0 };
@@ -148,6 +148,14 @@ fn test_counters() {
         allow_revoting: false,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
         close_proposal_on_execution_failure: true,
+        veto: None,
+        delegation_registry: None,
+        commit_reveal: None,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        voting_delay: None,
+        execution_window: None,
+        no_with_veto_threshold: None,
     };
 
     let governance_addr =
@@ -252,6 +260,7 @@ fn test_counters() {
             description: "This is a simple text proposal".to_string(),
             msgs: vec![],
             proposer: None,
+            dependencies: vec![],
         },
         &[],
     )
@@ -285,6 +294,7 @@ fn test_counters() {
         &cwd_proposal_single::msg::ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
@@ -351,6 +361,7 @@ fn test_counters() {
             description: "This is a simple text proposal 2nd".to_string(),
             msgs: vec![],
             proposer: None,
+            dependencies: vec![],
         },
         &[],
     )
@@ -404,6 +415,7 @@ fn test_counters() {
         &cwd_proposal_single::msg::ExecuteMsg::Vote {
             proposal_id: 2,
             vote: Vote::Yes,
+            rationale: None,
         },
         &[],
     )
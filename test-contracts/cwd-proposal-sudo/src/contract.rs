@@ -59,7 +59,10 @@ pub fn execute_execute(
 
     let msg = WasmMsg::Execute {
         contract_addr: dao.to_string(),
-        msg: to_binary(&cwd_interface::ExecuteMsg::ExecuteProposalHook { msgs })?,
+        msg: to_binary(&cwd_interface::ExecuteMsg::ExecuteProposalHook {
+            msgs,
+            proposal_id: None,
+        })?,
         funds: vec![],
     };
 